@@ -215,11 +215,12 @@ fn add_item_if_package_changed<'a>(
     match filter {
         Filter::None => history.push(item),
         Filter::Fast(comp) => {
-            let current = git::objs::TreeRefIter::from_bytes(&data_by_tree_id[&item.tree_id])
-                .filter_map(Result::ok)
-                .find(|e| e.filename == comp);
+            let current =
+                git::objs::TreeRefIter::from_bytes_with_hash(&data_by_tree_id[&item.tree_id], item.tree_id.kind())
+                    .filter_map(Result::ok)
+                    .find(|e| e.filename == comp);
             let parent = item.parent_tree_id.and_then(|parent| {
-                git::objs::TreeRefIter::from_bytes(&data_by_tree_id[&parent])
+                git::objs::TreeRefIter::from_bytes_with_hash(&data_by_tree_id[&parent], parent.kind())
                     .filter_map(Result::ok)
                     .find(|e| e.filename == comp)
             });
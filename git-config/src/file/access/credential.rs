@@ -0,0 +1,89 @@
+use std::convert::TryFrom;
+
+use bstr::{BStr, ByteSlice};
+
+use crate::{file, CredentialConfig, File};
+
+/// Credentials
+impl<'event> File<'event> {
+    /// Return the credential configuration that applies to `url`, or `None` if no `[credential]` section matches it.
+    ///
+    /// `[credential "<url>"]` sections match if their scheme, host, port and user name are the same as the one of `url`,
+    /// with `*` being allowed as glob in each component of the host. Their path has to be a prefix of the one of `url`,
+    /// ending at a `/`. Urls which can't be parsed never match, while `[credential]` sections without url always match.
+    ///
+    /// Values of more specific sections override those of less specific ones, with sections having a longer path being
+    /// more specific than those with a shorter one, and sections without url being the least specific.
+    /// Among equally specific sections the last one wins. An empty `helper` or `username` resets the value.
+    /// Invalid `useHttpPath` values are ignored.
+    pub fn credential_for_url(&self, url: &git_url::Url) -> Option<CredentialConfig> {
+        let mut sections: Vec<_> = self
+            .sections_by_name("credential")?
+            .filter_map(|section| match section.header().subsection_name() {
+                Some(pattern) => url_specificity(pattern, url).map(|specificity| (specificity + 1, section)),
+                None => Some((0, section)),
+            })
+            .collect();
+        if sections.is_empty() {
+            return None;
+        }
+        sections.sort_by_key(|(specificity, _)| *specificity);
+
+        let mut config = CredentialConfig::default();
+        for (_, section) in sections {
+            apply(section, &mut config);
+        }
+        Some(config)
+    }
+}
+
+fn apply(section: &file::Section<'_>, config: &mut CredentialConfig) {
+    if let Some(username) = section.value("username") {
+        config.username = (!username.trim().is_empty()).then(|| username.into_owned());
+    }
+    if let Some(helper) = section.values("helper").into_iter().last() {
+        config.helper = (!helper.trim().is_empty()).then(|| helper.into_owned());
+    }
+    if let Some(toggle) = section
+        .value("useHttpPath")
+        .and_then(|value| crate::Boolean::try_from(value).ok())
+    {
+        config.use_http_path = toggle.0;
+    }
+}
+
+/// Return the length of the path of `pattern` if it matches `url`, or `None` if it doesn't match or isn't a valid url.
+fn url_specificity(pattern: &BStr, url: &git_url::Url) -> Option<usize> {
+    let pattern = git_url::parse(pattern).ok()?;
+    if pattern.scheme != url.scheme || !host_matches(pattern.host(), url.host()) {
+        return None;
+    }
+    let is_http = matches!(pattern.scheme, git_url::Scheme::Https | git_url::Scheme::Http);
+    let ports_match = if is_http {
+        pattern.port_or_default() == url.port_or_default()
+    } else {
+        pattern.port == url.port
+    };
+    if !ports_match || (pattern.user().is_some() && pattern.user() != url.user()) {
+        return None;
+    }
+    let prefix = pattern.path.trim_end_with(|c| c == '/');
+    let path = url.path.as_bstr();
+    let path_matches = path.starts_with(prefix) && matches!(path.get(prefix.len()), None | Some(b'/'));
+    path_matches.then(|| prefix.len())
+}
+
+fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
+    match (pattern, host) {
+        (Some(pattern), Some(host)) => {
+            let patterns = pattern.split('.');
+            let components = host.split('.');
+            patterns.clone().count() == components.clone().count()
+                && patterns.zip(components).all(|(pattern, component)| {
+                    git_glob::wildmatch(pattern.into(), component.into(), git_glob::wildmatch::Mode::empty())
+                })
+        }
+        (None, None) => true,
+        (Some(_), None) | (None, Some(_)) => false,
+    }
+}
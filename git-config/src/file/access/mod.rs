@@ -1,4 +1,5 @@
 mod comfort;
+mod credential;
 mod mutate;
 mod raw;
 mod read_only;
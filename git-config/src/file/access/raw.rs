@@ -435,6 +435,70 @@ impl<'event> File<'event> {
         Ok(section.set(key.try_into().map_err(section::key::Error::from)?, new_value))
     }
 
+    /// Like [`set_raw_value()`][Self::set_raw_value()], but accepts any typed value like [`Boolean`][crate::Boolean],
+    /// [`Integer`][crate::Integer] or [`Color`][crate::Color] and stores its canonical string representation.
+    ///
+    /// Special characters are escaped as needed, so reading the value back yields the same typed value.
+    ///
+    /// # Examples
+    ///
+    /// Read a configuration file, change it and write it back atomically by writing it to a temporary file
+    /// next to it first.
+    ///
+    /// ```
+    /// # let dir = tempfile::tempdir()?;
+    /// # let path = dir.path().join("config");
+    /// # std::fs::write(&path, "[core]\n\t# a comment\n\tbare = true\n")?;
+    /// let mut config = git_config::File::from_path_no_includes(&path, git_config::Source::Local)?;
+    /// config.set_value("core", None, "bare", git_config::Boolean(false))?;
+    /// let window_memory = git_config::Integer {
+    ///     value: 10,
+    ///     suffix: Some(git_config::integer::Suffix::Mebi),
+    /// };
+    /// config.set_value("pack", None, "windowMemory", window_memory)?;
+    ///
+    /// let mut tmp = tempfile::NamedTempFile::new_in(dir.path())?;
+    /// config.write_to(&mut tmp)?;
+    /// tmp.persist(&path)?;
+    ///
+    /// assert_eq!(
+    ///     std::fs::read_to_string(&path)?,
+    ///     "[core]\n\t# a comment\n\tbare = false\n[pack]\n\twindowMemory = 10m\n"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_value<Key, E>(
+        &mut self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&str>,
+        key: Key,
+        new_value: impl std::fmt::Display,
+    ) -> Result<Option<Cow<'event, BStr>>, crate::file::set_raw_value::Error>
+    where
+        Key: TryInto<section::Key<'event>, Error = E>,
+        section::key::Error: From<E>,
+    {
+        self.set_value_filter(section_name, subsection_name, key, new_value, &mut |_| true)
+    }
+
+    /// Similar to [`set_value()`][Self::set_value()], but only sets existing values in sections matching
+    /// `filter`, creating a new section otherwise.
+    pub fn set_value_filter<Key, E>(
+        &mut self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&str>,
+        key: Key,
+        new_value: impl std::fmt::Display,
+        filter: &mut MetadataFilter,
+    ) -> Result<Option<Cow<'event, BStr>>, crate::file::set_raw_value::Error>
+    where
+        Key: TryInto<section::Key<'event>, Error = E>,
+        section::key::Error: From<E>,
+    {
+        let value = new_value.to_string();
+        self.set_raw_value_filter(section_name, subsection_name, key, value.as_str(), filter)
+    }
+
     /// Sets a multivar in a given section, optional subsection, and key value.
     ///
     /// This internally zips together the new values and the existing values.
@@ -224,6 +224,24 @@ impl<'event> File<'event> {
         })
     }
 
+    /// Return the names of all subsections of sections named `name`, like `origin` in `[remote "origin"]`, without
+    /// duplicates and in order of their first appearance.
+    ///
+    /// Note that while section names are case-insensitive, subsection names are case-sensitive, hence `[remote "origin"]`
+    /// and `[REMOTE "Origin"]` yield two different names.
+    #[must_use]
+    pub fn subsection_names<'a>(&'a self, name: &'a str) -> Vec<&'a BStr> {
+        let mut names = Vec::new();
+        for section in self.sections_by_name(name).into_iter().flatten() {
+            if let Some(subsection_name) = section.header().subsection_name() {
+                if !names.contains(&subsection_name) {
+                    names.push(subsection_name);
+                }
+            }
+        }
+        names
+    }
+
     /// Returns the number of values in the config, no matter in which section.
     ///
     /// For example, a config with multiple empty sections will return 0.
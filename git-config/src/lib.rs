@@ -47,6 +47,6 @@ pub mod value;
 pub use git_config_value::{color, integer, path, Boolean, Color, Integer, Path};
 
 mod types;
-pub use types::{File, Source};
+pub use types::{CredentialConfig, File, Source};
 ///
 pub mod source;
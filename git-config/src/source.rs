@@ -72,6 +72,7 @@ impl Source {
             Git => match env_var("GIT_CONFIG_GLOBAL") {
                 Some(global_override) => Some(PathBuf::from(global_override).into()),
                 None => env_var("XDG_CONFIG_HOME")
+                    .filter(|home| !home.is_empty())
                     .map(|home| {
                         let mut p = PathBuf::from(home);
                         p.push("git");
@@ -115,3 +115,15 @@ pub struct File<'event> {
     /// The source of the File itself, which is attached to new sections automatically.
     pub(crate) meta: OwnShared<Metadata>,
 }
+
+/// The values of `[credential]` and `[credential "<url>"]` sections that apply to a particular url, as returned by
+/// [`File::credential_for_url()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CredentialConfig {
+    /// The value of `credential.username`, the user name to use if the url doesn't contain one.
+    pub username: Option<bstr::BString>,
+    /// The value of the last `credential.helper`, the program to invoke to obtain credentials.
+    pub helper: Option<bstr::BString>,
+    /// The value of `credential.useHttpPath`, true if the path of http urls should be considered when obtaining credentials.
+    pub use_http_path: bool,
+}
@@ -0,0 +1,132 @@
+use std::convert::TryFrom;
+
+use git_config::{CredentialConfig, File};
+
+fn credential_for(config: &File<'_>, url: &str) -> Option<CredentialConfig> {
+    config.credential_for_url(&git_url::parse(url.into()).expect("valid url"))
+}
+
+fn credential(username: &str, helper: &str, use_http_path: bool) -> Option<CredentialConfig> {
+    Some(CredentialConfig {
+        username: Some(username.into()),
+        helper: Some(helper.into()),
+        use_http_path,
+    })
+}
+
+#[test]
+fn the_most_specific_section_wins_while_others_provide_defaults() -> crate::Result {
+    let config = File::try_from(
+        r#"
+        [credential]
+            helper = store
+            username = default
+        [credential "https://example.com/org"]
+            helper = org-helper
+            useHttpPath = true
+        [credential "https://example.com"]
+            username = host-user
+        [credential "https://*.example.com"]
+            username = glob-user
+        [credential "ssh://example.com/"]
+            username = ssh-user
+        "#,
+    )?;
+
+    assert_eq!(
+        credential_for(&config, "https://example.com/org/repo.git"),
+        credential("host-user", "org-helper", true),
+        "sections with a longer matching path override those with a shorter one, independently of their order"
+    );
+    assert_eq!(
+        credential_for(&config, "https://example.com/organization/repo.git"),
+        credential("host-user", "store", false),
+        "paths match only at component boundaries"
+    );
+    assert_eq!(
+        credential_for(&config, "https://example.com:443/other"),
+        credential("host-user", "store", false),
+        "default ports are the same as no port"
+    );
+    assert_eq!(
+        credential_for(&config, "https://sub.example.com/org"),
+        credential("glob-user", "store", false),
+        "globs match one component of the host each"
+    );
+    assert_eq!(
+        credential_for(&config, "https://a.sub.example.com"),
+        credential("default", "store", false),
+    );
+    assert_eq!(
+        credential_for(&config, "ssh://example.com/org"),
+        credential("ssh-user", "store", false),
+        "the scheme has to match"
+    );
+    assert_eq!(
+        credential_for(&config, "https://example.com:8080/org"),
+        credential("default", "store", false),
+        "the port has to match as well"
+    );
+    Ok(())
+}
+
+#[test]
+fn user_names_in_urls_have_to_match_if_present_in_the_section() -> crate::Result {
+    let config = File::try_from(
+        r#"
+        [credential "https://alice@example.com"]
+            helper = alices-helper
+        "#,
+    )?;
+    assert_eq!(
+        credential_for(&config, "https://alice@example.com/repo"),
+        Some(CredentialConfig {
+            helper: Some("alices-helper".into()),
+            ..Default::default()
+        })
+    );
+    assert_eq!(credential_for(&config, "https://bob@example.com/repo"), None);
+    assert_eq!(credential_for(&config, "https://example.com/repo"), None);
+    Ok(())
+}
+
+#[test]
+fn empty_values_reset_previous_ones() -> crate::Result {
+    let config = File::try_from(
+        r#"
+        [credential]
+            helper = store
+            username = default
+        [credential "https://example.com"]
+            helper =
+            helper = cache
+            username =
+        [credential "https://example.com/reset"]
+            helper = ""
+        "#,
+    )?;
+    assert_eq!(
+        credential_for(&config, "https://example.com"),
+        Some(CredentialConfig {
+            username: None,
+            helper: Some("cache".into()),
+            use_http_path: false
+        }),
+        "the last helper is used"
+    );
+    assert_eq!(
+        credential_for(&config, "https://example.com/reset/repo"),
+        Some(CredentialConfig::default())
+    );
+    Ok(())
+}
+
+#[test]
+fn none_if_no_section_matches() -> crate::Result {
+    let config = File::try_from("[core]\n\tbare = false")?;
+    assert_eq!(credential_for(&config, "https://example.com"), None);
+
+    let config = File::try_from("[credential \"https://example.com\"]\n\tusername = user")?;
+    assert_eq!(credential_for(&config, "https://other.example.com"), None);
+    Ok(())
+}
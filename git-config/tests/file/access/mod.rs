@@ -1,3 +1,4 @@
+mod credential;
 mod mutate;
 mod raw;
 mod read_only;
@@ -2,3 +2,4 @@ mod raw_multi_value;
 mod raw_value;
 mod set_existing_raw_value;
 mod set_raw_value;
+mod set_value;
@@ -0,0 +1,43 @@
+use std::convert::TryFrom;
+
+use git_config::{color, integer, Boolean, Color, Integer};
+
+#[test]
+fn typed_values_round_trip() -> crate::Result {
+    let mut file = git_config::File::try_from("[core]\n\tbare = true")?;
+    let prev = file.set_value("core", None, "bare", Boolean(false))?;
+    assert_eq!(prev.expect("present").as_ref(), "true", "existing values are replaced");
+
+    let window_memory = Integer {
+        value: 32,
+        suffix: Some(integer::Suffix::Kibi),
+    };
+    file.set_value("pack", None, "windowMemory", window_memory)?;
+    let color = Color {
+        foreground: Some(color::Name::Red),
+        background: None,
+        attributes: color::Attribute::BOLD,
+    };
+    file.set_value("color", Some("diff"), "old", &color)?;
+
+    let file: git_config::File = file.to_string().parse()?;
+    assert_eq!(file.boolean("core", None, "bare").transpose()?, Some(false));
+    assert_eq!(file.integer("pack", None, "windowMemory").transpose()?, Some(32 * 1024));
+    assert_eq!(
+        Color::try_from(file.raw_value("color", Some("diff"), "old")?)?,
+        color,
+        "colors are serialized into a form they can be parsed from"
+    );
+    Ok(())
+}
+
+#[test]
+fn strings_with_special_characters_are_escaped() -> crate::Result {
+    let mut file = git_config::File::default();
+    let value = "quote\" backslash\\ newline\n";
+    file.set_value("a", None, "k", value)?;
+
+    let file: git_config::File = file.to_string().parse()?;
+    assert_eq!(file.string("a", None, "k").expect("present").as_ref(), value);
+    Ok(())
+}
@@ -146,6 +146,81 @@ fn get_value_for_all_provided_values() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn typed_getters_apply_suffixes_detect_overflows_accept_boolean_synonyms_and_interpolate_paths() -> crate::Result {
+    let config = File::try_from(
+        r#"
+        [int]
+            kibi = 1k
+            mebi = 2m
+            gibi = 3G
+            overflow = 9223372036854775807k
+        [bool]
+            yes = yes
+            on = ON
+            one = 1
+            true = true
+            no = no
+            off = off
+            zero = 0
+            empty =
+            invalid = maybe
+        [core]
+            filemode
+        [path]
+            home = ~/dir
+            user = ~alice/dir
+            prefix = "%(prefix)/dir"
+        "#,
+    )?;
+
+    assert_eq!(config.integer("int", None, "kibi").transpose()?, Some(1024));
+    assert_eq!(config.integer("int", None, "mebi").transpose()?, Some(2 * 1024 * 1024));
+    assert_eq!(
+        config.integer("int", None, "gibi").transpose()?,
+        Some(3 * 1024 * 1024 * 1024)
+    );
+    assert!(
+        config.integer("int", None, "overflow").expect("present").is_err(),
+        "applying the suffix multiplier must not overflow silently"
+    );
+
+    for key in ["yes", "on", "one", "true"] {
+        assert!(config.boolean("bool", None, key).expect("present")?, "{}", key);
+    }
+    for key in ["no", "off", "zero", "empty"] {
+        assert!(!config.boolean("bool", None, key).expect("present")?, "{}", key);
+    }
+    assert!(config.boolean("bool", None, "invalid").expect("present").is_err());
+    assert_eq!(
+        config.boolean("core", None, "filemode"),
+        Some(Ok(true)),
+        "keys without value are true"
+    );
+
+    fn home_for_user(name: &str) -> Option<std::path::PathBuf> {
+        (name == "alice").then(|| "/home/alice".into())
+    }
+    let (home, install_dir) = (std::path::Path::new("/home/me"), std::path::Path::new("/usr/local"));
+    let context = path::interpolate::Context {
+        git_install_dir: Some(install_dir),
+        home_dir: Some(home),
+        home_for_user: Some(home_for_user),
+    };
+    let interpolated = |key: &str| -> Result<std::path::PathBuf, path::interpolate::Error> {
+        Ok(config
+            .path("path", None, key)
+            .expect("present")
+            .interpolate(context)?
+            .into_owned())
+    };
+    assert_eq!(interpolated("home")?, home.join("dir"));
+    assert_eq!(interpolated("prefix")?, install_dir.join("dir"));
+    #[cfg(not(windows))]
+    assert_eq!(interpolated("user")?, std::path::Path::new("/home/alice/dir"));
+    Ok(())
+}
+
 /// There was a regression where lookup would fail because we only checked the
 /// last section entry for any given section and subsection
 #[test]
@@ -237,6 +312,63 @@ fn sections_by_name() {
     assert_eq!(value, cow_str("git@github.com:Byron/gitoxide.git"));
 }
 
+#[test]
+fn subsections_of_multiple_remotes() -> crate::Result {
+    let config = r#"
+    [remote "origin"]
+        url = https://example.com/origin
+        fetch = +refs/heads/*:refs/remotes/origin/*
+        fetch = +refs/tags/*:refs/tags/*
+    [branch "main"]
+        remote = origin
+    [REMOTE "upstream"]
+        URL = https://example.com/upstream
+    [remote "Origin"]
+        url = https://example.com/other-origin
+    [remote "origin"]
+        fetch = +refs/notes/*:refs/notes/*
+    [remote]
+        pushDefault = upstream
+    "#;
+    let config = File::try_from(config)?;
+
+    assert_eq!(
+        config.subsection_names("Remote"),
+        vec!["origin", "upstream", "Origin"],
+        "section names are case-insensitive, subsection names are not, and duplicates are omitted"
+    );
+    assert_eq!(config.subsection_names("branch"), vec!["main"]);
+    assert!(config.subsection_names("core").is_empty());
+
+    let section = config.section("remote", Some("upstream"))?;
+    assert_eq!(
+        section.value("url").as_deref(),
+        Some("https://example.com/upstream".into()),
+        "keys are case-insensitive"
+    );
+
+    assert_eq!(
+        config.section("remote", Some("Origin"))?.value("url").as_deref(),
+        Some("https://example.com/other-origin".into())
+    );
+    assert_eq!(
+        config.section("remote", Some("origin"))?.values("fetch"),
+        vec![cow_str("+refs/notes/*:refs/notes/*")],
+        "sections only see their own values, and the last one wins"
+    );
+    assert_eq!(
+        config.strings("remote", Some("origin"), "fetch").expect("present"),
+        vec![
+            cow_str("+refs/heads/*:refs/remotes/origin/*"),
+            cow_str("+refs/tags/*:refs/tags/*"),
+            cow_str("+refs/notes/*:refs/notes/*")
+        ],
+        "all values of all sections with the same name can be obtained from the file"
+    );
+    assert!(config.section("remote", Some("ORIGIN")).is_err());
+    Ok(())
+}
+
 #[test]
 fn multi_line_value_plain() {
     let config = r#"
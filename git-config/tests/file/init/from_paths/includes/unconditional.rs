@@ -216,6 +216,50 @@ fn simple() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn tilde_slash_expands_to_the_home_directory() -> crate::Result {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir(&home)?;
+
+    let a_path = dir.path().join("a");
+    fs::write(
+        a_path.as_path(),
+        "
+[core]
+  b = true
+[include]
+  path = ~/b",
+    )?;
+    fs::write(
+        home.join("b"),
+        "
+[core]
+  b = false",
+    )?;
+
+    let options = init::Options {
+        includes: includes::Options::follow_without_conditional(Some(&home)),
+        ..Default::default()
+    };
+    let config = File::from_paths_metadata(into_meta(vec![a_path.clone()]), options)?.expect("non-empty");
+    assert_eq!(
+        config.boolean("core", None, "b"),
+        Some(Ok(false)),
+        "the included file in the home directory overrides the value"
+    );
+
+    let options = init::Options {
+        includes: includes::Options::follow_without_conditional(None).strict(),
+        ..Default::default()
+    };
+    assert!(
+        File::from_paths_metadata(into_meta(vec![a_path]), options).is_err(),
+        "without home directory, `~/` can't be expanded which is an error in strict mode"
+    );
+    Ok(())
+}
+
 #[test]
 fn cycle_detection() -> crate::Result {
     let dir = tempdir()?;
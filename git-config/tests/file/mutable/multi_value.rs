@@ -151,6 +151,54 @@ mod delete {
     }
 }
 
+mod round_trip {
+    use crate::file::cow_str;
+
+    const REMOTES: &str = r#"[remote "origin"]
+	url = https://example.com/repo
+	fetch = +refs/heads/*:refs/remotes/origin/*
+	fetch = +refs/tags/*:refs/tags/*
+[remote "other"]
+	fetch = +refs/heads/main:refs/remotes/other/main
+[remote "origin"]
+	fetch = +refs/pull/*/head:refs/remotes/origin/pr/*
+"#;
+
+    #[test]
+    fn values_of_all_matching_subsections_are_returned_in_order_and_survive_serialization() -> crate::Result {
+        let config: git_config::File = REMOTES.parse()?;
+        let expected = vec![
+            cow_str("+refs/heads/*:refs/remotes/origin/*"),
+            cow_str("+refs/tags/*:refs/tags/*"),
+            cow_str("+refs/pull/*/head:refs/remotes/origin/pr/*"),
+        ];
+        assert_eq!(config.raw_values("remote", Some("origin"), "fetch")?, expected);
+
+        let config: git_config::File = config.to_string().parse()?;
+        assert_eq!(config.to_string(), REMOTES, "the multi-value structure is preserved");
+        assert_eq!(config.raw_values("remote", Some("origin"), "fetch")?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn deleting_all_values_leaves_other_keys_and_subsections_alone() -> crate::Result {
+        let mut config: git_config::File = REMOTES.parse()?;
+        config.raw_values_mut("remote", Some("origin"), "fetch")?.delete_all();
+
+        let config: git_config::File = config.to_string().parse()?;
+        assert!(config.raw_values("remote", Some("origin"), "fetch").is_err());
+        assert_eq!(
+            config.raw_value("remote", Some("origin"), "url")?,
+            cow_str("https://example.com/repo")
+        );
+        assert_eq!(
+            config.raw_values("remote", Some("other"), "fetch")?,
+            vec![cow_str("+refs/heads/main:refs/remotes/other/main")]
+        );
+        Ok(())
+    }
+}
+
 fn init_config() -> git_config::File<'static> {
     r#"[core]
     a = b"100"
@@ -67,15 +67,23 @@ pub(crate) mod function {
 
     fn parse_raw(input: &str) -> Option<Time> {
         let mut split = input.split_whitespace();
-        let seconds_since_unix_epoch: u32 = split.next()?.parse().ok()?;
+        let seconds = split.next()?;
+        let seconds_since_unix_epoch: u32 = seconds.strip_prefix('@').unwrap_or(seconds).parse().ok()?;
         let offset = split.next()?;
-        if offset.len() != 5 {
+        if offset.len() != 5 || split.next().is_some() {
             return None;
         }
-        let sign = if &offset[..1] == "-" { Sign::Plus } else { Sign::Minus };
+        let sign = match &offset[..1] {
+            "-" => Sign::Minus,
+            "+" => Sign::Plus,
+            _ => return None,
+        };
         let hours: i32 = offset[1..3].parse().ok()?;
         let minutes: i32 = offset[3..5].parse().ok()?;
-        let offset_in_seconds = hours * 3600 + minutes * 60;
+        let mut offset_in_seconds = hours * 3600 + minutes * 60;
+        if sign == Sign::Minus {
+            offset_in_seconds *= -1;
+        }
         let time = Time {
             seconds_since_unix_epoch,
             offset_in_seconds,
@@ -83,6 +83,30 @@ fn rfc2822() {
     );
 }
 
+#[test]
+fn raw() {
+    assert_eq!(
+        git_date::parse("1660874655 +0800", None).expect("parsed raw string"),
+        Time {
+            seconds_since_unix_epoch: 1660874655,
+            offset_in_seconds: 28800,
+            sign: Sign::Plus,
+        },
+    );
+    assert_eq!(
+        git_date::parse("@1660874655 -0230", None).expect("parsed raw string with @ prefix"),
+        Time {
+            seconds_since_unix_epoch: 1660874655,
+            offset_in_seconds: -9000,
+            sign: Sign::Minus,
+        },
+    );
+    assert!(
+        git_date::parse("1660874655 0800", None).is_err(),
+        "the sign is required"
+    );
+}
+
 #[test]
 fn invalid_dates_can_be_produced_without_current_time() {
     assert!(matches!(
@@ -0,0 +1,146 @@
+use std::ops::Range;
+
+use git_object::bstr::ByteSlice;
+
+/// The amount of marker characters git uses by default, as controlled by the `conflict-marker-size` attribute.
+const MARKER_SIZE: usize = 7;
+
+/// A conflict as left behind by a merge, with all line ranges being 0-based indices into the lines of the conflicted file.
+///
+/// None of the ranges include the lines with conflict markers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConflictRegion {
+    /// The lines between `<<<<<<<` and the next marker, representing our side.
+    pub ours_lines: Range<usize>,
+    /// The lines between `|||||||` and `=======`, representing the merge base, if conflicts are written in `diff3` style.
+    pub base_lines: Option<Range<usize>>,
+    /// The lines between `=======` and `>>>>>>>`, representing their side.
+    pub theirs_lines: Range<usize>,
+}
+
+impl ConflictRegion {
+    /// Return the range of all lines of this conflict, including the lines with conflict markers.
+    pub fn lines(&self) -> Range<usize> {
+        self.ours_lines.start - 1..self.theirs_lines.end + 1
+    }
+}
+
+/// The way to resolve conflicts with [`resolve_conflict()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConflictResolution {
+    /// Keep our side of each conflict, similar to `git checkout --ours`.
+    Ours,
+    /// Keep their side of each conflict, similar to `git checkout --theirs`.
+    Theirs,
+    /// Keep our side followed by their side of each conflict, similar to `git merge-file --union`.
+    Union,
+}
+
+/// Find all conflicts marked in `data` the way `git merge` and `git merge-file` write them, or return `None` if there is none.
+///
+/// Like `git rerere`, a conflict is only recognized if all of its markers are present and in order. Conflict markers
+/// are expected to be of the default size of 7 characters.
+pub fn detect_conflict_markers(data: &[u8]) -> Option<Vec<ConflictRegion>> {
+    enum State {
+        Outside,
+        Ours {
+            start: usize,
+        },
+        Base {
+            ours: Range<usize>,
+            start: usize,
+        },
+        Theirs {
+            ours: Range<usize>,
+            base: Option<Range<usize>>,
+            start: usize,
+        },
+    }
+
+    let mut out = Vec::new();
+    let mut state = State::Outside;
+    for (idx, line) in data.lines_with_terminator().enumerate() {
+        let next = idx + 1;
+        state = match (state, marker(line)) {
+            (_, Some(b'<')) => State::Ours { start: next },
+            (State::Ours { start }, Some(b'|')) => State::Base {
+                ours: start..idx,
+                start: next,
+            },
+            (State::Ours { start }, Some(b'=')) => State::Theirs {
+                ours: start..idx,
+                base: None,
+                start: next,
+            },
+            (State::Base { ours, start }, Some(b'=')) => State::Theirs {
+                ours,
+                base: Some(start..idx),
+                start: next,
+            },
+            (State::Theirs { ours, base, start }, Some(b'>')) => {
+                out.push(ConflictRegion {
+                    ours_lines: ours,
+                    base_lines: base,
+                    theirs_lines: start..idx,
+                });
+                State::Outside
+            }
+            (State::Outside, _) => State::Outside,
+            (_, Some(_)) => State::Outside,
+            (state, None) => state,
+        };
+    }
+    (!out.is_empty()).then(|| out)
+}
+
+/// Return the kind of conflict marker `line` is, identified by its first character, or `None` if it isn't one.
+fn marker(line: &[u8]) -> Option<u8> {
+    let kind = *line.first()?;
+    if !matches!(kind, b'<' | b'|' | b'=' | b'>') || line.len() <= MARKER_SIZE {
+        return None;
+    }
+    if line[..MARKER_SIZE].iter().any(|b| *b != kind) {
+        return None;
+    }
+    let next = line[MARKER_SIZE];
+    let is_valid = match kind {
+        b'<' | b'>' => next == b' ',
+        _ => next.is_ascii_whitespace(),
+    };
+    is_valid.then(|| kind)
+}
+
+/// Resolve all conflicts in `data` according to `resolution` and return the result, which is `data` itself if
+/// there are no conflicts.
+///
+/// The base version of `diff3` style conflicts is always dropped.
+pub fn resolve_conflict(data: &[u8], resolution: ConflictResolution) -> Vec<u8> {
+    let conflicts = match detect_conflict_markers(data) {
+        Some(conflicts) => conflicts,
+        None => return data.to_owned(),
+    };
+    let lines: Vec<_> = data.lines_with_terminator().collect();
+    let mut out = Vec::with_capacity(data.len());
+    let mut extend = |range: Range<usize>| {
+        for line in &lines[range] {
+            out.extend_from_slice(line);
+        }
+    };
+
+    let mut pos = 0;
+    for conflict in conflicts {
+        let region = conflict.lines();
+        extend(pos..region.start);
+        match resolution {
+            ConflictResolution::Ours => extend(conflict.ours_lines),
+            ConflictResolution::Theirs => extend(conflict.theirs_lines),
+            ConflictResolution::Union => {
+                extend(conflict.ours_lines);
+                extend(conflict.theirs_lines);
+            }
+        }
+        pos = region.end;
+    }
+    extend(pos..lines.len());
+    out
+}
@@ -0,0 +1,9 @@
+//! Diffing of blobs at a granularity finer than lines.
+
+///
+pub mod word;
+pub use word::{tokenize_chars, tokenize_whitespace, word_diff, WordDiff, WordHunk};
+
+///
+pub mod conflict;
+pub use conflict::{detect_conflict_markers, resolve_conflict, ConflictRegion, ConflictResolution};
@@ -0,0 +1,160 @@
+use git_object::bstr::ByteSlice;
+use similar::{Algorithm, DiffOp};
+
+/// A contiguous run of tokens that were all changed in the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordHunk<'a> {
+    /// Tokens only present in the new version.
+    Added(Vec<&'a [u8]>),
+    /// Tokens only present in the old version.
+    Removed(Vec<&'a [u8]>),
+    /// Tokens present in both versions.
+    Context(Vec<&'a [u8]>),
+}
+
+impl<'a> WordHunk<'a> {
+    /// Return all tokens of this hunk, independently of the kind of change.
+    pub fn tokens(&self) -> &[&'a [u8]] {
+        match self {
+            WordHunk::Added(tokens) | WordHunk::Removed(tokens) | WordHunk::Context(tokens) => tokens,
+        }
+    }
+
+    fn tokens_mut(&mut self) -> &mut Vec<&'a [u8]> {
+        match self {
+            WordHunk::Added(tokens) | WordHunk::Removed(tokens) | WordHunk::Context(tokens) => tokens,
+        }
+    }
+}
+
+/// The outcome of [`word_diff()`], a list of hunks which reproduce the old version when skipping all
+/// [added][WordHunk::Added] hunks, and the new version when skipping all [removed][WordHunk::Removed] ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordDiff<'a> {
+    /// All hunks in order of appearance, with removals placed before additions at the same position.
+    pub hunks: Vec<WordHunk<'a>>,
+}
+
+/// The way to mark changes when [writing][WordDiff::write_to()] a [`WordDiff`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Enclose removals in `[-…-]` and additions in `{+…+}`, similar to `git diff --word-diff=plain`.
+    Plain,
+    /// Print removals in red and additions in green using ANSI escape codes, similar to `git diff --word-diff=color`.
+    Color,
+}
+
+impl WordDiff<'_> {
+    /// Return true if there are no additions or removals.
+    pub fn is_unchanged(&self) -> bool {
+        self.hunks.iter().all(|hunk| matches!(hunk, WordHunk::Context(_)))
+    }
+
+    /// Write all hunks to `out`, marking changes according to `format`.
+    pub fn write_to(&self, mut out: impl std::io::Write, format: Format) -> std::io::Result<()> {
+        const RED: &[u8] = b"\x1b[31m";
+        const GREEN: &[u8] = b"\x1b[32m";
+        const RESET: &[u8] = b"\x1b[m";
+        for hunk in &self.hunks {
+            let (start, end): (&[u8], &[u8]) = match (hunk, format) {
+                (WordHunk::Context(_), _) => (b"", b""),
+                (WordHunk::Removed(_), Format::Plain) => (b"[-", b"-]"),
+                (WordHunk::Added(_), Format::Plain) => (b"{+", b"+}"),
+                (WordHunk::Removed(_), Format::Color) => (RED, RESET),
+                (WordHunk::Added(_), Format::Color) => (GREEN, RESET),
+            };
+            out.write_all(start)?;
+            for token in hunk.tokens() {
+                out.write_all(token)?;
+            }
+            out.write_all(end)?;
+        }
+        Ok(())
+    }
+}
+
+/// Split `input` into alternating runs of whitespace and non-whitespace, so that joining all tokens yields `input` again.
+///
+/// This is the equivalent of gits default word definition.
+pub fn tokenize_whitespace(input: &[u8]) -> Vec<&[u8]> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (idx, pair) in input.windows(2).enumerate() {
+        if pair[0].is_ascii_whitespace() != pair[1].is_ascii_whitespace() {
+            out.push(&input[start..=idx]);
+            start = idx + 1;
+        }
+    }
+    if start < input.len() {
+        out.push(&input[start..]);
+    }
+    out
+}
+
+/// Split `input` into individual characters assuming an UTF-8 encoding, keeping invalid bytes as separate tokens.
+pub fn tokenize_chars(input: &[u8]) -> Vec<&[u8]> {
+    input
+        .char_indices()
+        .map(|(start, end, _char)| &input[start..end])
+        .collect()
+}
+
+/// Compute the changes needed to turn `old` into `new` at the granularity of tokens, as produced by `tokenize`,
+/// using the Myers algorithm.
+///
+/// Typical tokenizers are [`tokenize_whitespace()`] and [`tokenize_chars()`].
+pub fn word_diff<'a>(old: &'a [u8], new: &'a [u8], tokenize: impl Fn(&[u8]) -> Vec<&[u8]>) -> WordDiff<'a> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    let mut out = WordDiff::default();
+    for op in similar::capture_diff_slices(Algorithm::Myers, &old_tokens, &new_tokens) {
+        match op {
+            DiffOp::Equal { old_index, len, .. } => push(
+                &mut out.hunks,
+                WordHunk::Context(old_tokens[old_index..][..len].to_vec()),
+            ),
+            DiffOp::Delete {
+                old_index, old_len, ..
+            } => push(
+                &mut out.hunks,
+                WordHunk::Removed(old_tokens[old_index..][..old_len].to_vec()),
+            ),
+            DiffOp::Insert {
+                new_index, new_len, ..
+            } => push(
+                &mut out.hunks,
+                WordHunk::Added(new_tokens[new_index..][..new_len].to_vec()),
+            ),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => {
+                push(
+                    &mut out.hunks,
+                    WordHunk::Removed(old_tokens[old_index..][..old_len].to_vec()),
+                );
+                push(
+                    &mut out.hunks,
+                    WordHunk::Added(new_tokens[new_index..][..new_len].to_vec()),
+                );
+            }
+        }
+    }
+    out
+}
+
+/// Append `hunk` to `hunks`, or merge its tokens into the last hunk if it is of the same kind.
+fn push<'a>(hunks: &mut Vec<WordHunk<'a>>, hunk: WordHunk<'a>) {
+    if hunk.tokens().is_empty() {
+        return;
+    }
+    match hunks.last_mut() {
+        Some(last) if std::mem::discriminant(last) == std::mem::discriminant(&hunk) => {
+            last.tokens_mut().extend_from_slice(hunk.tokens())
+        }
+        _ => hunks.push(hunk),
+    }
+}
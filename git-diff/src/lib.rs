@@ -7,3 +7,9 @@ pub mod tree;
 
 ///
 pub mod lines;
+
+///
+pub mod blob;
+
+///
+pub mod stat;
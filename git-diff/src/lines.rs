@@ -1,5 +1,7 @@
-use git_object::bstr::BStr;
-use similar::TextDiff;
+use std::{borrow::Cow, collections::HashMap, io::Write};
+
+use git_object::bstr::{BStr, ByteSlice};
+use similar::{DiffOp, DiffTag, TextDiff};
 
 /// The crate powering file diffs.
 pub use similar;
@@ -24,3 +26,189 @@ pub fn with<'old, 'new, 'bufs>(
 pub fn myers<'old, 'new, 'bufs>(old: &'old BStr, new: &'new BStr) -> TextDiff<'old, 'new, 'bufs, [u8]> {
     with(old, new, Algorithm::Myers)
 }
+
+/// Options to control which differences between lines are ignored by [`with_options()`], independently of the [`Algorithm`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Ignore all whitespace when comparing lines, similar to `git diff --ignore-all-space` or `-w`.
+    pub ignore_all_space: bool,
+    /// Ignore whitespace at the end of lines and consider all other runs of whitespace equal,
+    /// similar to `git diff --ignore-space-change` or `-b`.
+    pub ignore_space_change: bool,
+    /// Ignore a carriage-return at the end of lines, similar to `git diff --ignore-cr-at-eol`.
+    pub ignore_cr_at_eol: bool,
+    /// Ignore changes whose lines are all blank, similar to `git diff --ignore-blank-lines`.
+    pub ignore_blank_lines: bool,
+}
+
+impl Options {
+    /// Return `line` in the form used to compare it to other lines, which is `line` itself if no whitespace is ignored.
+    ///
+    /// Lines which are equal after normalization are considered unchanged.
+    pub fn normalize_line<'a>(&self, line: &'a [u8]) -> Cow<'a, [u8]> {
+        if self.ignore_all_space {
+            line.iter()
+                .copied()
+                .filter(|b| !is_space(*b))
+                .collect::<Vec<_>>()
+                .into()
+        } else if self.ignore_space_change {
+            let trimmed = &line[..line.len() - line.iter().rev().take_while(|b| is_space(**b)).count()];
+            let mut out = Vec::with_capacity(trimmed.len());
+            for (idx, b) in trimmed.iter().enumerate() {
+                if !is_space(*b) {
+                    out.push(*b);
+                } else if idx == 0 || !is_space(trimmed[idx - 1]) {
+                    out.push(b' ');
+                }
+            }
+            out.into()
+        } else if self.ignore_cr_at_eol {
+            match line {
+                [head @ .., b'\r', b'\n'] => {
+                    let mut out = head.to_vec();
+                    out.push(b'\n');
+                    out.into()
+                }
+                [head @ .., b'\r'] => head.into(),
+                _ => line.into(),
+            }
+        } else {
+            line.into()
+        }
+    }
+}
+
+/// Return true if `line` consists of whitespace only, as used for [`Options::ignore_blank_lines`].
+pub fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(|b| is_space(*b))
+}
+
+/// Whitespace as understood by `git`.
+fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// The outcome of [`with_options()`], the line-based changes needed to turn the old version into the new one.
+#[derive(Debug, Clone)]
+pub struct Diff<'old, 'new> {
+    /// All lines of the old version, including their line terminator.
+    pub old_lines: Vec<&'old [u8]>,
+    /// All lines of the new version, including their line terminator.
+    pub new_lines: Vec<&'new [u8]>,
+    /// The operations to turn `old_lines` into `new_lines`, indexing into them.
+    pub ops: Vec<DiffOp>,
+    /// The options used to compare lines.
+    pub options: Options,
+}
+
+/// Compute the changes needed to turn `old` into `new` with `algorithm`, comparing lines after [normalizing][Options::normalize_line()]
+/// them according to `options`.
+///
+/// The lines themselves remain unchanged, so unchanged lines may still differ in their whitespace.
+pub fn with_options<'old, 'new>(
+    old: &'old BStr,
+    new: &'new BStr,
+    algorithm: Algorithm,
+    options: Options,
+) -> Diff<'old, 'new> {
+    let old_lines: Vec<_> = old.lines_with_terminator().collect();
+    let new_lines: Vec<_> = new.lines_with_terminator().collect();
+
+    let mut tokens = HashMap::<Cow<'_, [u8]>, u32>::new();
+    let mut tokenize = |line| {
+        let next_token = tokens.len() as u32;
+        *tokens.entry(options.normalize_line(line)).or_insert(next_token)
+    };
+    let old_tokens: Vec<_> = old_lines.iter().map(|line| tokenize(line)).collect();
+    let new_tokens: Vec<_> = new_lines.iter().map(|line| tokenize(line)).collect();
+
+    Diff {
+        ops: similar::capture_diff_slices(algorithm, &old_tokens, &new_tokens),
+        old_lines,
+        new_lines,
+        options,
+    }
+}
+
+impl Diff<'_, '_> {
+    /// Group all changes into hunks, each surrounded by up to `context_lines` of unchanged lines.
+    ///
+    /// Hunks which only add or remove blank lines are omitted if [`Options::ignore_blank_lines`] is set.
+    pub fn hunks(&self, context_lines: usize) -> Vec<Vec<DiffOp>> {
+        let mut hunks = similar::group_diff_ops(self.ops.clone(), context_lines);
+        if self.options.ignore_blank_lines {
+            hunks.retain(|ops| !ops.iter().all(|op| op.tag() == DiffTag::Equal || self.is_ignorable(op)));
+        }
+        hunks
+    }
+
+    /// Return true if there are no changes, possibly after ignoring whitespace.
+    pub fn is_unchanged(&self) -> bool {
+        self.hunks(0).is_empty()
+    }
+
+    /// Write all [hunks][Diff::hunks()] with `context_lines` of context to `out`, similar to the body of `git diff` in its default format.
+    ///
+    /// Unchanged lines are written in their new version.
+    pub fn write_unified_to(&self, mut out: impl Write, context_lines: usize) -> std::io::Result<()> {
+        for hunk in self.hunks(context_lines) {
+            let (first, last) = (hunk.first().expect("non-empty"), hunk.last().expect("non-empty"));
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+            writeln!(
+                out,
+                "@@ -{} +{} @@",
+                HunkRange(old_range.start, old_range.len()),
+                HunkRange(new_range.start, new_range.len())
+            )?;
+            let mut ops = hunk.iter().peekable();
+            while let Some(op) = ops.next() {
+                if op.tag() == DiffTag::Equal {
+                    write_lines(&mut out, b' ', &self.new_lines[op.new_range()])?;
+                    continue;
+                }
+                // Like git, write all removals of adjacent changes before their additions.
+                let (mut old_range, mut new_range) = (op.old_range(), op.new_range());
+                while let Some(op) = ops.next_if(|op| op.tag() != DiffTag::Equal) {
+                    old_range.end = op.old_range().end;
+                    new_range.end = op.new_range().end;
+                }
+                write_lines(&mut out, b'-', &self.old_lines[old_range])?;
+                write_lines(&mut out, b'+', &self.new_lines[new_range])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_ignorable(&self, op: &DiffOp) -> bool {
+        let (_, old_range, new_range) = op.as_tag_tuple();
+        self.old_lines[old_range].iter().all(|line| is_blank(line))
+            && self.new_lines[new_range].iter().all(|line| is_blank(line))
+    }
+}
+
+/// A range of lines in a hunk header, formatted like `git` does.
+struct HunkRange(usize, usize);
+
+impl std::fmt::Display for HunkRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let HunkRange(start, len) = *self;
+        match len {
+            0 => write!(f, "{},0", start),
+            1 => write!(f, "{}", start + 1),
+            _ => write!(f, "{},{}", start + 1, len),
+        }
+    }
+}
+
+fn write_lines(out: &mut impl Write, prefix: u8, lines: &[&[u8]]) -> std::io::Result<()> {
+    for line in lines {
+        out.write_all(&[prefix])?;
+        out.write_all(line)?;
+        if !line.ends_with(b"\n") {
+            out.write_all(b"\n\\ No newline at end of file\n")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,345 @@
+//! Statistics about changed files, as shown by `git diff --stat`, `--numstat` and `--shortstat`.
+use std::io::Write;
+
+use git_object::bstr::{BStr, BString, ByteSlice, ByteVec};
+use similar::{Algorithm, DiffTag};
+
+use crate::lines;
+
+/// The amount of bytes `git` looks at to determine if a file is binary.
+const FIRST_FEW_BYTES: usize = 8000;
+
+/// The changes made to a single file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileStat {
+    /// The name of the file as displayed, like `dir/file`, or `dir/{old => new}` for [renames][rename_name()].
+    pub name: BString,
+    /// The amount of added lines, or the size of the new version in bytes if `is_binary` is true.
+    pub insertions: usize,
+    /// The amount of removed lines, or the size of the old version in bytes if `is_binary` is true.
+    pub deletions: usize,
+    /// If true, at least one version of the file is binary and no lines were counted.
+    pub is_binary: bool,
+}
+
+impl FileStat {
+    /// Count the lines needed to turn `old` into `new` for the file `name` using the Myers algorithm, like `git` does.
+    ///
+    /// Additions and deletions are represented by an empty `old` or `new` version respectively.
+    /// If any version [is binary][is_binary()], their sizes are recorded instead, unless they are equal.
+    pub fn from_blobs(name: impl Into<BString>, old: &[u8], new: &[u8]) -> Self {
+        let name = name.into();
+        if is_binary(old) || is_binary(new) {
+            let (insertions, deletions) = if old == new { (0, 0) } else { (new.len(), old.len()) };
+            return FileStat {
+                name,
+                insertions,
+                deletions,
+                is_binary: true,
+            };
+        }
+        let diff = lines::with_options(old.as_bstr(), new.as_bstr(), Algorithm::Myers, Default::default());
+        let (mut insertions, mut deletions) = (0, 0);
+        for op in &diff.ops {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            if tag != DiffTag::Equal {
+                insertions += new_range.len();
+                deletions += old_range.len();
+            }
+        }
+        FileStat {
+            name,
+            insertions,
+            deletions,
+            is_binary: false,
+        }
+    }
+}
+
+/// Return true if `data` is considered binary by `git`, which is the case if there is a null-byte within its first 8000 bytes.
+pub fn is_binary(data: &[u8]) -> bool {
+    data[..data.len().min(FIRST_FEW_BYTES)].contains(&0)
+}
+
+/// Produce the name `git` displays for a file renamed from `old` to `new`, which places the differing portion
+/// of both paths into braces, like `dir/{old => new}/file`.
+///
+/// Note that unlike `git`, special characters aren't quoted.
+pub fn rename_name(old: &BStr, new: &BStr) -> BString {
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter(|(_, (b, _))| **b == b'/')
+        .map(|(idx, _)| idx + 1)
+        .last()
+        .unwrap_or(0);
+
+    // If there is a common prefix, it ends in a slash which may also start the common suffix.
+    let min_len = prefix_len.saturating_sub(1);
+    let suffix_len = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(old.len().min(new.len()) - min_len)
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter(|(_, (b, _))| **b == b'/')
+        .map(|(idx, _)| idx + 1)
+        .last()
+        .unwrap_or(0);
+
+    let old_mid = &old[prefix_len..old.len().saturating_sub(suffix_len).max(prefix_len)];
+    let new_mid = &new[prefix_len..new.len().saturating_sub(suffix_len).max(prefix_len)];
+    let mut out = BString::default();
+    let has_braces = prefix_len + suffix_len != 0;
+    if has_braces {
+        out.push_str(&old[..prefix_len]);
+        out.push_byte(b'{');
+    }
+    out.push_str(old_mid);
+    out.push_str(" => ");
+    out.push_str(new_mid);
+    if has_braces {
+        out.push_byte(b'}');
+        out.push_str(&old[old.len() - suffix_len..]);
+    }
+    out
+}
+
+/// The totals of all changed files, displayed like the last line of `git diff --stat`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// The amount of changed files.
+    pub files_changed: usize,
+    /// The amount of added lines, without binary files.
+    pub insertions: usize,
+    /// The amount of removed lines, without binary files.
+    pub deletions: usize,
+}
+
+impl Summary {
+    /// Sum up the changes of all `files`.
+    pub fn from_files(files: &[FileStat]) -> Self {
+        files.iter().fold(Summary::default(), |mut summary, file| {
+            summary.files_changed += 1;
+            if !file.is_binary {
+                summary.insertions += file.insertions;
+                summary.deletions += file.deletions;
+            }
+            summary
+        })
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn plural(count: usize) -> &'static str {
+            if count == 1 {
+                ""
+            } else {
+                "s"
+            }
+        }
+        let Summary {
+            files_changed,
+            insertions,
+            deletions,
+        } = *self;
+        write!(f, " {} file{} changed", files_changed, plural(files_changed))?;
+        if files_changed == 0 {
+            return Ok(());
+        }
+        if insertions != 0 || deletions == 0 {
+            write!(f, ", {} insertion{}(+)", insertions, plural(insertions))?;
+        }
+        if deletions != 0 || insertions == 0 {
+            write!(f, ", {} deletion{}(-)", deletions, plural(deletions))?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `files` to `out` like `git diff --numstat`, one line per file with tab-separated insertions, deletions and name.
+///
+/// Binary files show `-` instead of counts.
+pub fn write_numstat_to(files: &[FileStat], mut out: impl Write) -> std::io::Result<()> {
+    for file in files {
+        if file.is_binary {
+            out.write_all(b"-\t-\t")?;
+        } else {
+            write!(out, "{}\t{}\t", file.insertions, file.deletions)?;
+        }
+        out.write_all(&file.name)?;
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Write the [summary][Summary] of `files` to `out`, like `git diff --shortstat`, or nothing if there are no files.
+pub fn write_shortstat_to(files: &[FileStat], mut out: impl Write) -> std::io::Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{}", Summary::from_files(files))
+}
+
+/// Configure the layout of [`write_stat_to()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The total amount of columns available, like `<width>` in `git diff --stat=<width>`. Defaults to 80.
+    pub width: usize,
+    /// The maximum amount of columns to use for file names, like `--stat-name-width`, or `None` to only be limited by `width`.
+    pub name_width: Option<usize>,
+    /// The maximum amount of columns to use for the graph, like `--stat-graph-width`, or `None` to only be limited by `width`.
+    pub graph_width: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            width: 80,
+            name_width: None,
+            graph_width: None,
+        }
+    }
+}
+
+/// Write `files` to `out` like `git diff --stat`, with one line per file showing the amount of changed lines and
+/// a histogram of `+` and `-`, followed by the [summary][Summary].
+///
+/// Names and graph are scaled to fit into the width of `options` just like `git` does, assuming each character of a name
+/// takes one column.
+pub fn write_stat_to(files: &[FileStat], options: Options, mut out: impl Write) -> std::io::Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    let (mut max_name_len, mut max_change, mut number_width, mut bin_width) = (0, 0, 0, 0);
+    for file in files {
+        max_name_len = max_name_len.max(display_width(&file.name));
+        if file.is_binary {
+            // "Bin XXX -> YYY bytes"
+            bin_width = bin_width.max(14 + decimal_width(file.insertions) + decimal_width(file.deletions));
+            // Change counts are aligned with "Bin".
+            number_width = 3;
+        } else {
+            max_change = max_change.max(file.insertions + file.deletions);
+        }
+    }
+    number_width = number_width.max(decimal_width(max_change));
+
+    // Guarantee 3/8*16 == 6 columns for the graph and 5/8*16 == 10 columns for the name.
+    let width = options.width.max(16 + 6 + number_width);
+    let mut graph_width = if max_change + 4 > bin_width {
+        max_change
+    } else {
+        bin_width - 4
+    };
+    if let Some(max) = options.graph_width.filter(|max| *max < graph_width) {
+        graph_width = max;
+    }
+    let mut name_width = options
+        .name_width
+        .filter(|max| *max < max_name_len)
+        .unwrap_or(max_name_len);
+
+    if name_width + number_width + 6 + graph_width > width {
+        let graph_share = (width * 3 / 8) as isize - (number_width + 6) as isize;
+        if graph_width as isize > graph_share {
+            graph_width = graph_share.max(6) as usize;
+        }
+        if let Some(max) = options.graph_width.filter(|max| graph_width > *max) {
+            graph_width = max;
+        }
+        let name_share = width.saturating_sub(number_width + 6 + graph_width);
+        if name_width > name_share {
+            name_width = name_share;
+        } else {
+            graph_width = width - number_width - 6 - name_width;
+        }
+    }
+
+    for file in files {
+        let (prefix, name) = scale_name(file.name.as_ref(), name_width);
+        let padding = name_width.saturating_sub(prefix.len() + display_width(name));
+        write!(out, " {}", prefix)?;
+        out.write_all(name)?;
+        write!(out, "{:padding$} | ", "", padding = padding)?;
+        if file.is_binary {
+            write!(out, "{:>width$}", "Bin", width = number_width)?;
+            if file.insertions != 0 || file.deletions != 0 {
+                write!(out, " {} -> {} bytes", file.deletions, file.insertions)?;
+            }
+            out.write_all(b"\n")?;
+            continue;
+        }
+
+        let (mut add, mut del) = (file.insertions, file.deletions);
+        if graph_width <= max_change {
+            let mut total = scale_linear(add + del, graph_width, max_change);
+            if total < 2 && add != 0 && del != 0 {
+                total = 2;
+            }
+            if add < del {
+                add = scale_linear(add, graph_width, max_change);
+                del = total - add;
+            } else {
+                del = scale_linear(del, graph_width, max_change);
+                add = total - del;
+            }
+        }
+        let changes = file.insertions + file.deletions;
+        write!(out, "{:>width$}", changes, width = number_width)?;
+        if changes != 0 {
+            write!(out, " {}{}", "+".repeat(add), "-".repeat(del))?;
+        }
+        out.write_all(b"\n")?;
+    }
+    writeln!(out, "{}", Summary::from_files(files))
+}
+
+/// Shorten `name` to fit into `width` columns by cutting off its beginning up to the next slash, returning the prefix
+/// to display before it as well.
+fn scale_name(name: &BStr, width: usize) -> (&'static str, &[u8]) {
+    let mut name_len = display_width(name);
+    if name_len <= width {
+        return ("", name);
+    }
+    let len = width.saturating_sub(3);
+    let mut chars = name.char_indices();
+    let mut start = 0;
+    while name_len > len {
+        match chars.next() {
+            Some((_, end, _)) => {
+                start = end;
+                name_len -= 1;
+            }
+            None => break,
+        }
+    }
+    let name = &name[start..];
+    let name = name.find_byte(b'/').map_or(name, |pos| &name[pos..]);
+    ("...", name)
+}
+
+/// Scale `change` linearly to fit into `width`, but show at least one column if there is any change.
+fn scale_linear(change: usize, width: usize, max_change: usize) -> usize {
+    if change == 0 {
+        return 0;
+    }
+    1 + change * width.saturating_sub(1) / max_change
+}
+
+fn display_width(name: &[u8]) -> usize {
+    name.chars().count()
+}
+
+fn decimal_width(mut num: usize) -> usize {
+    let mut width = 1;
+    while num >= 10 {
+        num /= 10;
+        width += 1;
+    }
+    width
+}
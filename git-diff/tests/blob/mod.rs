@@ -0,0 +1,188 @@
+mod word_diff {
+    use git_diff::blob::{
+        tokenize_chars, tokenize_whitespace,
+        word::Format,
+        word_diff, WordDiff,
+        WordHunk::{Added, Context, Removed},
+    };
+
+    fn plain(diff: &WordDiff<'_>) -> String {
+        let mut buf = Vec::new();
+        diff.write_to(&mut buf, Format::Plain).expect("no io error");
+        String::from_utf8(buf).expect("valid utf8")
+    }
+
+    #[test]
+    fn whitespace_tokens_reproduce_the_input() {
+        let input = b"  hello\tworld \n";
+        let tokens = tokenize_whitespace(input);
+        assert_eq!(
+            tokens,
+            vec![&b"  "[..], b"hello", b"\t", b"world", b" \n"],
+            "runs of whitespace and non-whitespace alternate"
+        );
+        assert_eq!(tokens.concat(), input);
+        assert!(tokenize_whitespace(b"").is_empty());
+    }
+
+    #[test]
+    fn char_tokens_respect_utf8() {
+        assert_eq!(
+            tokenize_chars("aä b".as_bytes()),
+            vec![&b"a"[..], "ä".as_bytes(), b" ", b"b"]
+        );
+    }
+
+    #[test]
+    fn a_changed_word_is_removed_and_added() {
+        let diff = word_diff(b"the quick fox", b"the slow fox", tokenize_whitespace);
+        assert_eq!(
+            diff.hunks,
+            vec![
+                Context(vec![&b"the"[..], b" "]),
+                Removed(vec![b"quick"]),
+                Added(vec![b"slow"]),
+                Context(vec![b" ", b"fox"]),
+            ]
+        );
+        assert_eq!(plain(&diff), "the [-quick-]{+slow+} fox");
+        assert!(!diff.is_unchanged());
+    }
+
+    #[test]
+    fn insertions_and_deletions() {
+        let diff = word_diff(b"a b c d", b"a c d e", tokenize_whitespace);
+        assert_eq!(plain(&diff), "a [-b -]c d{+ e+}");
+    }
+
+    #[test]
+    fn character_granularity() {
+        let diff = word_diff(b"color", b"colour", tokenize_chars);
+        assert_eq!(plain(&diff), "colo{+u+}r");
+    }
+
+    #[test]
+    fn identical_input_is_unchanged() {
+        let diff = word_diff(b"same words", b"same words", tokenize_whitespace);
+        assert!(diff.is_unchanged());
+        assert_eq!(plain(&diff), "same words");
+    }
+
+    #[test]
+    fn color_format_uses_ansi_escapes() {
+        let diff = word_diff(b"a b", b"a c", tokenize_whitespace);
+        let mut buf = Vec::new();
+        diff.write_to(&mut buf, Format::Color).unwrap();
+        assert_eq!(buf, b"a \x1b[31mb\x1b[m\x1b[32mc\x1b[m");
+    }
+}
+
+mod conflict {
+    use git_diff::blob::{detect_conflict_markers, resolve_conflict, ConflictRegion, ConflictResolution};
+
+    fn fixture(name: &str) -> crate::Result<Vec<u8>> {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_conflicts.sh")?;
+        Ok(std::fs::read(dir.join(name))?)
+    }
+
+    #[test]
+    fn regions_are_detected_in_merge_style() -> crate::Result {
+        let conflicts = detect_conflict_markers(&fixture("conflicted")?).expect("conflicts present");
+        assert_eq!(
+            conflicts,
+            vec![
+                ConflictRegion {
+                    ours_lines: 2..3,
+                    base_lines: None,
+                    theirs_lines: 4..5,
+                },
+                ConflictRegion {
+                    ours_lines: 14..16,
+                    base_lines: None,
+                    theirs_lines: 17..18,
+                }
+            ]
+        );
+        assert_eq!(
+            conflicts[1].lines(),
+            13..19,
+            "markers are included in the entire region"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn regions_are_detected_in_diff3_style() -> crate::Result {
+        let conflicts = detect_conflict_markers(&fixture("conflicted-diff3")?).expect("conflicts present");
+        assert_eq!(
+            conflicts,
+            vec![
+                ConflictRegion {
+                    ours_lines: 2..3,
+                    base_lines: Some(4..5),
+                    theirs_lines: 6..7,
+                },
+                ConflictRegion {
+                    ours_lines: 16..18,
+                    base_lines: Some(19..20),
+                    theirs_lines: 21..22,
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolutions_match_git() -> crate::Result {
+        for conflicted in ["conflicted", "conflicted-diff3"] {
+            let data = fixture(conflicted)?;
+            for (resolution, expected) in [
+                (ConflictResolution::Ours, "resolved-ours"),
+                (ConflictResolution::Theirs, "resolved-theirs"),
+                (ConflictResolution::Union, "resolved-union"),
+            ] {
+                assert_eq!(
+                    resolve_conflict(&data, resolution),
+                    fixture(expected)?,
+                    "{:?} of {}",
+                    resolution,
+                    conflicted
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn incomplete_or_malformed_markers_are_no_conflict() {
+        for input in [
+            &b"no conflict\n"[..],
+            b"<<<<<<< ours\na\n=======\nb\n",
+            b"<<<<<<< ours\na\n>>>>>>> theirs\n",
+            b"<<<<<<<ours\na\n=======\nb\n>>>>>>> theirs\n",
+            b"<<<<<<<< ours\na\n=======\nb\n>>>>>>> theirs\n",
+            b"=======\n>>>>>>> theirs\n",
+        ] {
+            assert_eq!(detect_conflict_markers(input), None, "{:?}", input);
+            assert_eq!(resolve_conflict(input, ConflictResolution::Ours), input);
+        }
+    }
+
+    #[test]
+    fn a_new_conflict_start_discards_the_incomplete_one() {
+        let input = b"<<<<<<< a\n<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs";
+        assert_eq!(
+            detect_conflict_markers(input),
+            Some(vec![ConflictRegion {
+                ours_lines: 2..3,
+                base_lines: None,
+                theirs_lines: 4..5,
+            }])
+        );
+        assert_eq!(
+            resolve_conflict(input, ConflictResolution::Theirs),
+            b"<<<<<<< a\ntheirs\n",
+            "lines that aren't part of a conflict are kept"
+        );
+    }
+}
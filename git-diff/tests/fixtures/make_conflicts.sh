@@ -0,0 +1,48 @@
+#!/bin/bash
+set -eu -o pipefail
+
+cat <<EOT >base
+1
+2
+3
+4
+5
+6
+7
+8
+9
+10
+EOT
+
+cat <<EOT >ours
+1
+ours-2
+3
+4
+5
+6
+7
+8
+9
+ours-10
+ours-11
+EOT
+
+cat <<EOT >theirs
+1
+theirs-2
+3
+4
+5
+6
+7
+8
+9
+theirs-10
+EOT
+
+git merge-file -p -L ours -L base -L theirs ours base theirs >conflicted || true
+git merge-file -p --diff3 -L ours -L base -L theirs ours base theirs >conflicted-diff3 || true
+for resolution in ours theirs union; do
+  git merge-file -p --"$resolution" ours base theirs >"resolved-$resolution"
+done
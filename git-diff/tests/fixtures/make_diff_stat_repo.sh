@@ -0,0 +1,47 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  git config core.autocrlf false
+
+  seq 1 10 >a.txt
+  seq 1 300 >many-changes.txt
+  mkdir -p dir/old-name sub deeply/nested/directory/structure/with/many/components
+  seq 1 20 >dir/old-name/file
+  printf 'moved\n' >moved.txt
+  printf 'binary\0content' >binary.dat
+  printf 'same\0binary' >same-binary.dat
+  seq 1 5 >deleted.txt
+  seq 1 3 >deeply/nested/directory/structure/with/many/components/and-a-rather-long-file-name.txt
+  git add .
+  git commit -qm "old"
+
+  { seq 1 5; echo changed; seq 7 12; } >a.txt
+  seq 1 2 300 >many-changes.txt
+  git mv dir/old-name dir/new-name
+  echo 21 >>dir/new-name/file
+  git mv moved.txt sub/moved.txt
+  printf 'binary\0content, but longer' >binary.dat
+  git mv same-binary.dat same-binary-renamed.dat
+  printf 'new\0binary' >new-binary.dat
+  git rm -q deleted.txt
+  echo 4 >>deeply/nested/directory/structure/with/many/components/and-a-rather-long-file-name.txt
+  seq 1 4 >added.txt
+  git add .
+  git commit -qm "new"
+)
+
+mkdir old new
+git -C repo archive HEAD~1 | tar -x -C old
+git -C repo archive HEAD | tar -x -C new
+
+git -C repo diff -M --name-status HEAD~1 HEAD >changes
+git -C repo diff -M --numstat HEAD~1 HEAD >numstat.baseline
+git -C repo diff -M --shortstat HEAD~1 HEAD >shortstat.baseline
+git -C repo diff -M --stat=80 HEAD~1 HEAD >stat-80.baseline
+git -C repo diff -M --stat=60 HEAD~1 HEAD >stat-60.baseline
+git -C repo diff -M --stat=200 HEAD~1 HEAD >stat-200.baseline
+git -C repo diff -M --stat=80 --stat-graph-width=10 HEAD~1 HEAD >stat-80-graph-10.baseline
+git -C repo diff -M --stat=80 --stat-name-width=20 HEAD~1 HEAD >stat-80-name-20.baseline
+git -C repo diff -M --stat=10 HEAD~1 HEAD >stat-10.baseline
@@ -0,0 +1,24 @@
+#!/bin/bash
+set -eu -o pipefail
+
+printf 'fn main() {\n\tlet a = 1;\n\tlet b = 2;\n\n\tprintln!("{}", a + b);\n}\n\nfn helper(x: u32) -> u32 {\n    x * 2\n}\n\nfn other() {\n    let unchanged = true;\n    let changed = 1;\n    let trailing = 0;\n}\n' >old
+printf 'fn main() {\n    let a = 1;\n    let  b =  2;\n\n\n\tprintln!("{}", a+b);\n}\n\nfn helper(x: u32) -> u32 {\n\tx * 2   \n}\n\nfn other() {\n    let unchanged = true;\n    let changed = 2;\n    let trailing = 0;\r\n}' >new
+
+printf 'a\r\nb\r\nc\nd\n' >crlf-old
+printf 'a\nb\r\nc\r\ne\n' >crlf-new
+
+function baseline() {
+  local name=${1:?first argument is the name of the baseline}
+  local old=${2:?second argument is the old file}
+  local new=${3:?third argument is the new file}
+  shift 3
+  git -c core.autocrlf=false diff --no-index --no-indent-heuristic "$@" "$old" "$new" | sed -n '/^@@/,$p' >"$name.baseline" || true
+}
+
+baseline ignore-all-space old new -w
+baseline ignore-space-change old new -b
+baseline ignore-space-change-and-blank-lines old new -b --ignore-blank-lines
+baseline ignore-all-space-and-blank-lines old new -w --ignore-blank-lines
+baseline ignore-space-change-no-context old new -b -U0
+baseline crlf crlf-old crlf-new
+baseline ignore-cr-at-eol crlf-old crlf-new --ignore-cr-at-eol
@@ -0,0 +1,147 @@
+mod with_options {
+    use git_diff::lines::{with_options, Algorithm, Options};
+    use git_object::bstr::ByteSlice;
+
+    fn fixture(name: &str) -> crate::Result<Vec<u8>> {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_whitespace_diffs.sh")?;
+        Ok(std::fs::read(dir.join(name))?)
+    }
+
+    /// Return the hunks of `name`, without the function name git writes after each hunk header.
+    fn baseline(name: &str) -> crate::Result<String> {
+        let baseline = fixture(&format!("{}.baseline", name))?;
+        let mut out = String::new();
+        for line in baseline.lines_with_terminator() {
+            let line = line.to_str()?;
+            match line
+                .strip_prefix("@@ ")
+                .and_then(|rest| rest.find(" @@").map(|pos| &line[..pos + 6]))
+            {
+                Some(header) => {
+                    out.push_str(header);
+                    out.push('\n');
+                }
+                None => out.push_str(line),
+            }
+        }
+        Ok(out)
+    }
+
+    fn unified(old: &str, new: &str, options: Options, context_lines: usize) -> crate::Result<String> {
+        let (old, new) = (fixture(old)?, fixture(new)?);
+        let diff = with_options(old.as_bstr(), new.as_bstr(), Algorithm::Myers, options);
+        let mut out = Vec::new();
+        diff.write_unified_to(&mut out, context_lines)?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn hunks_match_git() -> crate::Result {
+        let all_space = Options {
+            ignore_all_space: true,
+            ..Default::default()
+        };
+        let space_change = Options {
+            ignore_space_change: true,
+            ..Default::default()
+        };
+        for (baseline_name, options, context_lines) in [
+            ("ignore-all-space", all_space, 3),
+            ("ignore-space-change", space_change, 3),
+            ("ignore-space-change-no-context", space_change, 0),
+            (
+                "ignore-space-change-and-blank-lines",
+                Options {
+                    ignore_blank_lines: true,
+                    ..space_change
+                },
+                3,
+            ),
+            (
+                "ignore-all-space-and-blank-lines",
+                Options {
+                    ignore_blank_lines: true,
+                    ..all_space
+                },
+                3,
+            ),
+        ] {
+            assert_eq!(
+                unified("old", "new", options, context_lines)?,
+                baseline(baseline_name)?,
+                "{}",
+                baseline_name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn carriage_returns_at_end_of_line_match_git() -> crate::Result {
+        assert_eq!(
+            unified("crlf-old", "crlf-new", Options::default(), 3)?,
+            baseline("crlf")?
+        );
+        assert_eq!(
+            unified(
+                "crlf-old",
+                "crlf-new",
+                Options {
+                    ignore_cr_at_eol: true,
+                    ..Default::default()
+                },
+                3
+            )?,
+            baseline("ignore-cr-at-eol")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn normalization() {
+        let all_space = Options {
+            ignore_all_space: true,
+            ..Default::default()
+        };
+        assert_eq!(all_space.normalize_line(b" a \t b\r\n").as_ref(), b"ab");
+
+        let space_change = Options {
+            ignore_space_change: true,
+            ..Default::default()
+        };
+        assert_eq!(space_change.normalize_line(b"\t a \t b \n").as_ref(), b" a b");
+        assert_ne!(
+            space_change.normalize_line(b"a b"),
+            space_change.normalize_line(b"ab"),
+            "the presence of whitespace still matters"
+        );
+
+        let cr_at_eol = Options {
+            ignore_cr_at_eol: true,
+            ..Default::default()
+        };
+        assert_eq!(cr_at_eol.normalize_line(b"a\r\n").as_ref(), b"a\n");
+        assert_eq!(cr_at_eol.normalize_line(b"a\r").as_ref(), b"a");
+        assert_eq!(cr_at_eol.normalize_line(b"a\rb\n").as_ref(), b"a\rb\n");
+
+        assert_eq!(Options::default().normalize_line(b" a\r\n").as_ref(), b" a\r\n");
+    }
+
+    #[test]
+    fn whitespace_only_changes_can_be_ignored_entirely() {
+        let old = b"a\n  b\n".as_bstr();
+        let new = b"a\n\tb  \n".as_bstr();
+        assert!(!with_options(old, new, Algorithm::Myers, Options::default()).is_unchanged());
+        let diff = with_options(
+            old,
+            new,
+            Algorithm::Myers,
+            Options {
+                ignore_all_space: true,
+                ..Default::default()
+            },
+        );
+        assert!(diff.is_unchanged());
+        assert_eq!(diff.new_lines[1], b"\tb  \n", "lines are kept as they are");
+    }
+}
@@ -0,0 +1,133 @@
+use git_diff::stat::{self, FileStat, Options, Summary};
+use git_object::bstr::{BStr, ByteSlice};
+
+fn fixture_dir() -> crate::Result<std::path::PathBuf> {
+    git_testtools::scripted_fixture_repo_read_only("make_diff_stat_repo.sh")
+}
+
+/// Turn the output of `git diff --name-status` into statistics, reading file contents from the `old` and `new` directories.
+fn files() -> crate::Result<Vec<FileStat>> {
+    let dir = fixture_dir()?;
+    let read = |version: &str, path: &str| std::fs::read(dir.join(version).join(path));
+    let mut files = Vec::new();
+    for line in std::fs::read_to_string(dir.join("changes"))?.lines() {
+        let mut tokens = line.split('\t');
+        let status = tokens.next().expect("status");
+        let path = tokens.next().expect("path");
+        files.push(match &status[..1] {
+            "M" => FileStat::from_blobs(path, &read("old", path)?, &read("new", path)?),
+            "A" => FileStat::from_blobs(path, &[], &read("new", path)?),
+            "D" => FileStat::from_blobs(path, &read("old", path)?, &[]),
+            "R" => {
+                let new_path = tokens.next().expect("rename destination");
+                FileStat::from_blobs(
+                    stat::rename_name(path.into(), new_path.into()),
+                    &read("old", path)?,
+                    &read("new", new_path)?,
+                )
+            }
+            _ => unreachable!("unexpected status: {}", status),
+        });
+    }
+    Ok(files)
+}
+
+fn baseline(name: &str) -> crate::Result<String> {
+    Ok(std::fs::read_to_string(
+        fixture_dir()?.join(format!("{}.baseline", name)),
+    )?)
+}
+
+#[test]
+fn numstat_and_shortstat_match_git() -> crate::Result {
+    let files = files()?;
+    let mut out = Vec::new();
+    stat::write_numstat_to(&files, &mut out)?;
+    assert_eq!(out.as_bstr(), baseline("numstat")?.as_str());
+
+    out.clear();
+    stat::write_shortstat_to(&files, &mut out)?;
+    assert_eq!(out.as_bstr(), baseline("shortstat")?.as_str());
+    Ok(())
+}
+
+#[test]
+fn stat_matches_git() -> crate::Result {
+    let files = files()?;
+    for (name, options) in [
+        ("stat-80", Options::default()),
+        (
+            "stat-60",
+            Options {
+                width: 60,
+                ..Default::default()
+            },
+        ),
+        (
+            "stat-200",
+            Options {
+                width: 200,
+                ..Default::default()
+            },
+        ),
+        (
+            "stat-10",
+            Options {
+                width: 10,
+                ..Default::default()
+            },
+        ),
+        (
+            "stat-80-graph-10",
+            Options {
+                graph_width: Some(10),
+                ..Default::default()
+            },
+        ),
+        (
+            "stat-80-name-20",
+            Options {
+                name_width: Some(20),
+                ..Default::default()
+            },
+        ),
+    ] {
+        let mut out = Vec::new();
+        stat::write_stat_to(&files, options, &mut out)?;
+        assert_eq!(out.as_bstr(), baseline(name)?.as_str(), "{}", name);
+    }
+    Ok(())
+}
+
+#[test]
+fn summary() {
+    let summary = |files_changed, insertions, deletions| {
+        Summary {
+            files_changed,
+            insertions,
+            deletions,
+        }
+        .to_string()
+    };
+    assert_eq!(summary(0, 0, 0), " 0 files changed");
+    assert_eq!(summary(1, 0, 0), " 1 file changed, 0 insertions(+), 0 deletions(-)");
+    assert_eq!(summary(1, 1, 0), " 1 file changed, 1 insertion(+)");
+    assert_eq!(summary(2, 0, 1), " 2 files changed, 1 deletion(-)");
+    assert_eq!(summary(3, 2, 5), " 3 files changed, 2 insertions(+), 5 deletions(-)");
+}
+
+#[test]
+fn rename_name() {
+    let rename = |old: &str, new: &str| stat::rename_name(BStr::new(old), BStr::new(new)).to_string();
+    assert_eq!(rename("a", "b"), "a => b");
+    assert_eq!(
+        rename("a.txt", "b.txt"),
+        "a.txt => b.txt",
+        "suffixes must start with a slash"
+    );
+    assert_eq!(rename("dir/a", "dir/b"), "dir/{a => b}");
+    assert_eq!(rename("a/file", "b/file"), "{a => b}/file");
+    assert_eq!(rename("dir/a/file", "dir/b/file"), "dir/{a => b}/file");
+    assert_eq!(rename("dir/file", "dir/sub/file"), "dir/{ => sub}/file");
+    assert_eq!(rename("dir/sub/file", "dir/file"), "dir/{sub => }/file");
+}
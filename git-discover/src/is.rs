@@ -1,4 +1,8 @@
-use std::{borrow::Cow, ffi::OsStr, path::Path};
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 use crate::{DOT_GIT_DIR, MODULES};
 
@@ -143,3 +147,21 @@ pub fn git(git_dir: impl AsRef<Path>) -> Result<crate::repository::Kind, crate::
         }
     })
 }
+
+/// Classify `dir` if it is a git directory, or the `.git` directory or file within it otherwise, without looking at
+/// parent directories.
+///
+/// Return the git directory candidate along with its kind. If `dir` isn't a git directory but has a `.git` entry, the error of
+/// the latter is returned as it's more specific.
+pub(crate) fn dir_or_dot_git(dir: &Path) -> Result<(PathBuf, crate::repository::Kind), crate::is_git::Error> {
+    match git(dir) {
+        Ok(kind) => Ok((dir.to_owned(), kind)),
+        Err(err) => {
+            let dot_git = dir.join(DOT_GIT_DIR);
+            if dot_git.symlink_metadata().is_err() {
+                return Err(err);
+            }
+            git(&dot_git).map(|kind| (dot_git, kind))
+        }
+    }
+}
@@ -43,7 +43,9 @@ pub use is::{bare as is_bare, git as is_git, submodule_git_dir as is_submodule_g
 
 ///
 pub mod upwards;
-pub use upwards::function::{discover as upwards, discover_opts as upwards_opts};
+pub use upwards::function::{
+    discover as upwards, discover_opts as upwards_opts, discover_with_policy as upwards_with_policy,
+};
 
 ///
 pub mod path;
@@ -48,6 +48,23 @@ mod path {
         pub fn from_dot_git_dir(dir: impl Into<PathBuf>, kind: Kind) -> Self {
             Self::from_dot_git_dir_inner(dir, kind, std::env::current_dir().ok())
         }
+        /// Classify `path` as a repository without searching its parent directories, and return its normalized location.
+        ///
+        /// `path` may be a `.git` directory, a work tree containing a `.git` directory, a bare repository, or a work tree whose
+        /// `.git` file points to the git directory of a linked worktree or submodule.
+        /// Relative components are resolved lexically, using the current working directory only if needed.
+        pub fn classify(path: impl AsRef<std::path::Path>) -> Result<Self, crate::is_git::Error> {
+            let current_dir = std::env::current_dir().ok();
+            let path = git_path::absolutize(path.as_ref(), current_dir.as_deref());
+            let path = if path.as_os_str().is_empty() {
+                std::path::Path::new(".")
+            } else {
+                path.as_ref()
+            };
+            let (git_dir, kind) = crate::is::dir_or_dot_git(path)?;
+            Ok(Self::from_dot_git_dir_inner(git_dir, kind, current_dir))
+        }
+
         /// Returns the [kind][Kind] of this repository path.
         pub fn kind(&self) -> Kind {
             match self {
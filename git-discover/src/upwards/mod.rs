@@ -1,20 +1,19 @@
 mod types;
-pub use types::{Error, Options};
+pub use types::{Error, Options, Policy};
 
 mod util;
 
 pub(crate) mod function {
-    use std::path::Path;
+    use std::{path::Path, time::Instant};
 
     use git_sec::Trust;
 
-    use super::{Error, Options};
+    use super::{Error, Options, Policy};
     #[cfg(unix)]
     use crate::upwards::util::device_id;
     use crate::{
-        is_git,
+        is::dir_or_dot_git,
         upwards::util::{find_ceiling_height, shorten_path_with_cwd},
-        DOT_GIT_DIR,
     };
 
     /// Find the location of the git repository directly in `directory` or in any of its parent directories and provide
@@ -22,22 +21,47 @@ pub(crate) mod function {
     ///
     /// Fail if no valid-looking git repository could be found.
     // TODO: tests for trust-based discovery
-    #[cfg_attr(not(unix), allow(unused_variables))]
     pub fn discover_opts(
         directory: impl AsRef<Path>,
+        options: Options,
+    ) -> Result<(crate::repository::Path, Trust), Error> {
+        discover_inner(directory.as_ref(), options, Policy::default())
+    }
+
+    /// Find the location of the git repository directly in `directory` or in any of its parent directories like
+    /// [`discover()`], but fail if any of the bounds set in `policy` are exceeded.
+    ///
+    /// Use this when `directory` is controlled by untrusted parties.
+    pub fn discover_with_policy(
+        directory: impl AsRef<Path>,
+        policy: Policy,
+    ) -> Result<(crate::repository::Path, Trust), Error> {
+        discover_inner(directory.as_ref(), Options::default(), policy)
+    }
+
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    fn discover_inner(
+        directory: &Path,
         Options {
             required_trust,
             ceiling_dirs,
             match_ceiling_dir_or_error,
             cross_fs,
         }: Options,
+        Policy {
+            max_depth,
+            timeout,
+            allowed_root,
+            follow_symlinked_parents,
+        }: Policy,
     ) -> Result<(crate::repository::Path, Trust), Error> {
+        let start = Instant::now();
         // Absolutize the path so that `Path::parent()` _actually_ gives
         // us the parent directory. (`Path::parent` just strips off the last
         // path component, which means it will not do what you expect when
         // working with paths paths that contain '..'.)
         let cwd = std::env::current_dir().ok();
-        let dir = git_path::absolutize(directory.as_ref(), cwd.as_deref());
+        let dir = git_path::absolutize(directory, cwd.as_deref());
         let dir_metadata = dir.metadata().map_err(|_| Error::InaccessibleDirectory {
             path: dir.to_path_buf(),
         })?;
@@ -45,7 +69,7 @@ pub(crate) mod function {
         if !dir_metadata.is_dir() {
             return Err(Error::InaccessibleDirectory { path: dir.into_owned() });
         }
-        let mut dir_made_absolute = !directory.as_ref().is_absolute()
+        let mut dir_made_absolute = !directory.is_absolute()
             && cwd.as_deref().map_or(false, |cwd| {
                 cwd.strip_prefix(dir.as_ref())
                     .or_else(|_| dir.as_ref().strip_prefix(cwd))
@@ -67,18 +91,65 @@ pub(crate) mod function {
             None
         };
 
+        let allowed_root = allowed_root
+            .map(|root| git_path::realpath(&root).map_err(|_| Error::InaccessibleDirectory { path: root }))
+            .transpose()?;
+
         #[cfg(unix)]
         let initial_device = device_id(&dir_metadata);
 
         let mut cursor = dir.clone().into_owned();
         let mut current_height = 0;
-        'outer: loop {
+        loop {
             if max_height.map_or(false, |x| current_height > x) {
                 return Err(Error::NoGitRepositoryWithinCeiling {
                     path: dir.into_owned(),
                     ceiling_height: current_height,
                 });
             }
+            if max_depth.map_or(false, |max_depth| current_height > max_depth) {
+                return Err(Error::MaxDepthExceeded {
+                    path: dir.into_owned(),
+                    max_depth: max_depth.expect("set"),
+                });
+            }
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    return Err(Error::Timeout {
+                        path: dir.into_owned(),
+                        timeout,
+                    });
+                }
+            }
+            if !follow_symlinked_parents
+                && cursor
+                    .symlink_metadata()
+                    .map_or(false, |metadata| metadata.file_type().is_symlink())
+            {
+                return Err(Error::SymlinkedParent {
+                    path: dir.into_owned(),
+                    symlink: cursor,
+                });
+            }
+            if let Some(root) = allowed_root.as_deref() {
+                let location =
+                    git_path::realpath(&cursor).map_err(|_| Error::InaccessibleDirectory { path: cursor.clone() })?;
+                // Compare by components to prevent `/root-sibling` from passing for `/root`.
+                if !location.starts_with(root) {
+                    return Err(if current_height != 0 && root.starts_with(&location) {
+                        Error::NoGitRepositoryWithinAllowedRoot {
+                            path: dir.into_owned(),
+                            root: root.to_owned(),
+                        }
+                    } else {
+                        Error::OutsideAllowedRoot {
+                            path: dir.into_owned(),
+                            location,
+                            root: root.to_owned(),
+                        }
+                    });
+                }
+            }
             current_height += 1;
 
             #[cfg(unix)]
@@ -99,33 +170,23 @@ pub(crate) mod function {
                 }
             }
 
-            for append_dot_git in &[false, true] {
-                if *append_dot_git {
-                    cursor.push(DOT_GIT_DIR);
-                }
-                if let Ok(kind) = is_git(&cursor) {
-                    match filter_by_trust(&cursor)? {
-                        Some(trust) => {
-                            // TODO: test this more, it definitely doesn't always find the shortest path to a directory
-                            let path = if dir_made_absolute {
-                                shorten_path_with_cwd(cursor, cwd.as_deref())
-                            } else {
-                                cursor
-                            };
-                            break 'outer Ok((crate::repository::Path::from_dot_git_dir_inner(path, kind, cwd), trust));
-                        }
-                        None => {
-                            break 'outer Err(Error::NoTrustedGitRepository {
-                                path: dir.into_owned(),
-                                candidate: cursor,
-                                required: required_trust,
-                            })
-                        }
+            if let Ok((candidate, kind)) = dir_or_dot_git(&cursor) {
+                break match filter_by_trust(&candidate)? {
+                    Some(trust) => {
+                        // TODO: test this more, it definitely doesn't always find the shortest path to a directory
+                        let path = if dir_made_absolute {
+                            shorten_path_with_cwd(candidate, cwd.as_deref())
+                        } else {
+                            candidate
+                        };
+                        Ok((crate::repository::Path::from_dot_git_dir_inner(path, kind, cwd), trust))
                     }
-                }
-                if *append_dot_git {
-                    cursor.pop();
-                }
+                    None => Err(Error::NoTrustedGitRepository {
+                        path: dir.into_owned(),
+                        candidate,
+                        required: required_trust,
+                    }),
+                };
             }
             if !cursor.pop() {
                 if dir_made_absolute
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, env, path::PathBuf};
+use std::{borrow::Cow, env, path::PathBuf, time::Duration};
 
 use bstr::{ByteSlice, ByteVec};
 
@@ -8,11 +8,11 @@ use bstr::{ByteSlice, ByteVec};
 pub enum Error {
     #[error("Failed to access a directory, or path is not a directory: '{}'", .path.display())]
     InaccessibleDirectory { path: PathBuf },
-    #[error("Could find a git repository in '{}' or in any of its parents", .path.display())]
+    #[error("Could not find a git repository in '{}' or in any of its parents", .path.display())]
     NoGitRepository { path: PathBuf },
-    #[error("Could find a git repository in '{}' or in any of its parents within ceiling height of {}", .path.display(), .ceiling_height)]
+    #[error("Could not find a git repository in '{}' or in any of its parents within ceiling height of {}", .path.display(), .ceiling_height)]
     NoGitRepositoryWithinCeiling { path: PathBuf, ceiling_height: usize },
-    #[error("Could find a git repository in '{}' or in any of its parents within device limits below '{}'", .path.display(), .limit.display())]
+    #[error("Could not find a git repository in '{}' or in any of its parents within device limits below '{}'", .path.display(), .limit.display())]
     NoGitRepositoryWithinFs { path: PathBuf, limit: PathBuf },
     #[error("None of the passed ceiling directories prefixed the git-dir candidate, making them ineffective.")]
     NoMatchingCeilingDir,
@@ -28,6 +28,20 @@ pub enum Error {
         #[source]
         err: std::io::Error,
     },
+    #[error("Could not find a git repository in '{}' or in any of its parents within {} parent directories", .path.display(), .max_depth)]
+    MaxDepthExceeded { path: PathBuf, max_depth: usize },
+    #[error("Could not find a git repository in '{}' or in any of its parents within {:?}", .path.display(), .timeout)]
+    Timeout { path: PathBuf, timeout: Duration },
+    #[error("The directory at '{}' resolves to a location outside of the allowed root '{}'", .location.display(), .root.display())]
+    OutsideAllowedRoot {
+        path: PathBuf,
+        location: PathBuf,
+        root: PathBuf,
+    },
+    #[error("Could not find a git repository in '{}' or in any of its parents within the allowed root '{}'", .path.display(), .root.display())]
+    NoGitRepositoryWithinAllowedRoot { path: PathBuf, root: PathBuf },
+    #[error("Refusing to follow the symbolic link at '{}' while searching upwards from '{}'", .symlink.display(), .path.display())]
+    SymlinkedParent { path: PathBuf, symlink: PathBuf },
 }
 
 /// Options to help guide the [discovery][crate::upwards()] of repositories, along with their options
@@ -49,6 +63,37 @@ pub struct Options {
     pub cross_fs: bool,
 }
 
+/// Bounds to enforce when [discovering][crate::upwards_with_policy()] repositories from untrusted paths,
+/// for instance when a service resolves paths provided by its users.
+///
+/// The default policy doesn't impose any bounds.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    /// The maximum amount of parent directories to visit, with `0` meaning that only the starting directory is checked.
+    pub max_depth: Option<usize>,
+    /// The maximum amount of time to spend on the search.
+    ///
+    /// Note that it's only checked before each directory is examined, so the search may take longer if the
+    /// filesystem is slow to respond.
+    pub timeout: Option<Duration>,
+    /// If set, all directories we visit must be contained in this directory after resolving symbolic links,
+    /// and the search ends once it would leave it.
+    pub allowed_root: Option<PathBuf>,
+    /// If `false`, fail if the starting directory or any of the parent directories visited is a symbolic link.
+    pub follow_symlinked_parents: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            max_depth: None,
+            timeout: None,
+            allowed_root: None,
+            follow_symlinked_parents: true,
+        }
+    }
+}
+
 impl Default for Options {
     fn default() -> Self {
         Options {
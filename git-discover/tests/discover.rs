@@ -3,4 +3,5 @@ pub use git_testtools::Result;
 mod is_git;
 mod parse;
 mod path;
+mod repository;
 mod upwards;
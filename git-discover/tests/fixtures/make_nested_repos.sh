@@ -0,0 +1,14 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q outer
+(cd outer
+  touch file
+  git add file
+  mkdir -p ceiling/plain/dir ceiling/inner/dir
+  (cd ceiling/inner
+    git init -q
+    touch file
+    git add file
+  )
+)
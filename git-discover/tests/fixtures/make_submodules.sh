@@ -19,8 +19,8 @@ git init with-submodules
   git add dir
   git commit -m "init"
 
-  git submodule add ../module1 m1
+  git -c protocol.file.allow=always submodule add ../module1 m1
   git commit -m "add module 1"
 
-  git submodule add ../module1 dir/m1
+  git -c protocol.file.allow=always submodule add ../module1 dir/m1
 )
@@ -0,0 +1,74 @@
+mod classify {
+    use std::path::Path;
+
+    use git_discover::repository::{self, Kind};
+
+    fn repo_path() -> crate::Result<std::path::PathBuf> {
+        git_testtools::scripted_fixture_repo_read_only("make_basic_repo.sh")
+    }
+
+    #[test]
+    fn work_tree_and_its_dot_git_dir() -> crate::Result {
+        let repo = repo_path()?;
+        for path in [repo.clone(), repo.join(".git"), repo.join("some/../.git/")] {
+            let classified = repository::Path::classify(&path)?;
+            assert_eq!(classified, repository::Path::WorkTree(repo.clone()), "{:?}", path);
+            assert_eq!(classified.kind(), Kind::WorkTree { linked_git_dir: None });
+            assert_eq!(
+                classified.into_repository_and_work_tree_directories().0,
+                repo.join(".git")
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bare_repository() -> crate::Result {
+        let dir = repo_path()?.join("bare.git");
+        let classified = repository::Path::classify(&dir)?;
+        assert_eq!(classified, repository::Path::Repository(dir));
+        assert_eq!(classified.kind(), Kind::Bare);
+        Ok(())
+    }
+
+    #[test]
+    fn dot_git_file_pointing_to_the_git_dir_of_a_linked_worktree() -> crate::Result {
+        let repo = repo_path()?;
+        let work_dir = repo.join("worktrees/a");
+        for path in [work_dir.clone(), work_dir.join(".git")] {
+            let (git_dir, actual_work_dir) =
+                repository::Path::classify(&path)?.into_repository_and_work_tree_directories();
+            assert_eq!(
+                git_dir.strip_prefix(git_path::realpath(&repo)?),
+                Ok(Path::new(".git/worktrees/a")),
+                "the git dir is read from the .git file"
+            );
+            assert_eq!(actual_work_dir, Some(work_dir.clone()), "{:?}", path);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parent_directories_are_not_searched() -> crate::Result {
+        let dir = repo_path()?.join("some/very/deeply/nested");
+        assert!(
+            git_discover::upwards(&dir).is_ok(),
+            "discovery finds the enclosing repository"
+        );
+        assert!(repository::Path::classify(&dir).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn dot_git_file_with_invalid_content_is_an_error() -> crate::Result {
+        let dir = git_testtools::tempfile::tempdir()?;
+        std::fs::write(dir.path().join(".git"), b"garbage")?;
+        assert!(matches!(
+            repository::Path::classify(dir.path()),
+            Err(git_discover::is_git::Error::GitFile(
+                git_discover::path::from_gitdir_file::Error::Parse(_)
+            ))
+        ));
+        Ok(())
+    }
+}
@@ -165,3 +165,26 @@ fn ceilings_are_adjusted_to_match_search_dir() -> crate::Result {
     assert_repo_is_current_workdir(repo_path, &relative_work_dir);
     Ok(())
 }
+
+#[test]
+fn ceiling_dirs_between_nested_repositories_only_hide_the_outer_one() -> crate::Result {
+    let outer = git_testtools::scripted_fixture_repo_read_only("make_nested_repos.sh")?.join("outer");
+    let ceiling = outer.join("ceiling");
+    let options = || Options {
+        ceiling_dirs: vec![ceiling.clone()],
+        ..Default::default()
+    };
+
+    let (repo_path, _trust) = git_discover::upwards_opts(ceiling.join("inner/dir"), options())?;
+    assert_repo_is_current_workdir(repo_path, &ceiling.join("inner"));
+
+    let (repo_path, _trust) = git_discover::upwards(ceiling.join("plain/dir"))?;
+    assert_repo_is_current_workdir(repo_path, &outer);
+    let err = git_discover::upwards_opts(ceiling.join("plain/dir"), options())
+        .expect_err("the outer repository lies beyond the ceiling");
+    assert!(matches!(
+        err,
+        git_discover::upwards::Error::NoGitRepositoryWithinCeiling { .. }
+    ));
+    Ok(())
+}
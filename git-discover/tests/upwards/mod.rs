@@ -11,6 +11,8 @@ fn expected_trust() -> git_sec::Trust {
 }
 
 mod ceiling_dirs;
+#[cfg(unix)]
+mod policy;
 
 #[test]
 fn from_bare_git_dir() -> crate::Result {
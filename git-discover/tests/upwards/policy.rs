@@ -0,0 +1,152 @@
+use std::{os::unix::fs::symlink, time::Duration};
+
+use git_discover::upwards::{Error, Policy};
+
+use crate::upwards::repo_path;
+
+#[test]
+fn default_policy_does_not_limit_discovery() -> crate::Result {
+    let work_dir = repo_path()?;
+    let (path, _trust) =
+        git_discover::upwards_with_policy(work_dir.join("some/very/deeply/nested/subdir"), Policy::default())?;
+    assert_eq!(path.as_ref(), work_dir, "a working tree dir yields the git dir");
+    Ok(())
+}
+
+#[test]
+fn max_depth_limits_the_amount_of_parent_directories_to_visit() -> crate::Result {
+    let work_dir = repo_path()?;
+    let dir = work_dir.join("some/very/deeply/nested/subdir");
+
+    let err = git_discover::upwards_with_policy(
+        &dir,
+        Policy {
+            max_depth: Some(4),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::MaxDepthExceeded { max_depth: 4, ref path } if path == &dir),
+        "the work dir is 5 levels up, got {:?}",
+        err
+    );
+
+    let (path, _trust) = git_discover::upwards_with_policy(
+        &dir,
+        Policy {
+            max_depth: Some(5),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(path.as_ref(), work_dir, "a working tree dir yields the git dir");
+    Ok(())
+}
+
+#[test]
+fn timeout_aborts_the_search() -> crate::Result {
+    let err = git_discover::upwards_with_policy(
+        repo_path()?,
+        Policy {
+            timeout: Some(Duration::from_secs(0)),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, Error::Timeout { .. }), "got {:?}", err);
+    Ok(())
+}
+
+#[test]
+fn allowed_root_contains_the_search() -> crate::Result {
+    let work_dir = repo_path()?;
+    let (path, _trust) = git_discover::upwards_with_policy(
+        work_dir.join("some/very"),
+        Policy {
+            allowed_root: Some(work_dir.clone()),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(path.as_ref(), work_dir, "a working tree dir yields the git dir");
+
+    let tmp = tempfile::tempdir()?;
+    let root = tmp.path().join("root");
+    std::fs::create_dir_all(root.join("a/b"))?;
+    let err = git_discover::upwards_with_policy(
+        root.join("a/b"),
+        Policy {
+            allowed_root: Some(root.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::NoGitRepositoryWithinAllowedRoot { .. }),
+        "the search ends at the root, got {:?}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn allowed_root_is_compared_by_path_components() -> crate::Result {
+    let tmp = tempfile::tempdir()?;
+    let root = tmp.path().join("root");
+    let sibling = tmp.path().join("root-sibling");
+    std::fs::create_dir(&root)?;
+    std::fs::create_dir(&sibling)?;
+
+    let err = git_discover::upwards_with_policy(
+        &sibling,
+        Policy {
+            allowed_root: Some(root),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::OutsideAllowedRoot { .. }),
+        "a sibling sharing the root's name as prefix isn't contained in it, got {:?}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn symlinks_escaping_the_allowed_root_are_detected() -> crate::Result {
+    let work_dir = repo_path()?;
+    let tmp = tempfile::tempdir()?;
+    let root = tmp.path().join("root");
+    std::fs::create_dir(&root)?;
+    symlink(work_dir.join("some").canonicalize()?, root.join("link"))?;
+
+    let err = git_discover::upwards_with_policy(
+        root.join("link").join("very"),
+        Policy {
+            allowed_root: Some(root.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    match err {
+        Error::OutsideAllowedRoot { location, .. } => {
+            assert_eq!(location, work_dir.join("some/very").canonicalize()?)
+        }
+        err => unreachable!("expected the symlink to escape the root, got {:?}", err),
+    }
+
+    let err = git_discover::upwards_with_policy(
+        root.join("link").join("very"),
+        Policy {
+            follow_symlinked_parents: false,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    match err {
+        Error::SymlinkedParent { symlink, .. } => assert_eq!(symlink, root.join("link")),
+        err => unreachable!("expected the symlinked parent to be refused, got {:?}", err),
+    }
+
+    Ok(())
+}
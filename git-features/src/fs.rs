@@ -30,17 +30,32 @@ pub mod walkdir {
         }
     }
 
-    /// Instantiate a new directory iterator which will not skip hidden files and is sorted
+    /// Instantiate a new directory iterator which will not skip hidden files and is sorted like git sorts paths,
+    /// i.e. as if directory names had a trailing slash.
     pub fn walkdir_sorted_new(root: impl AsRef<Path>) -> WalkDir {
+        #[allow(clippy::ptr_arg)] // the signature is dictated by `jwalk`
+        fn sort(_depth: Option<usize>, _path: &Path, _state: &mut (), children: &mut Vec<Result<DirEntry, Error>>) {
+            children.sort_by(|a, b| match (a, b) {
+                (Ok(a), Ok(b)) => super::cmp_like_git(
+                    &a.file_name,
+                    a.file_type().is_dir(),
+                    &b.file_name,
+                    b.file_type().is_dir(),
+                ),
+                (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+                (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+                (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+            })
+        }
         #[cfg(not(feature = "fs-walkdir-single-threaded"))]
         {
-            WalkDir::new(root).skip_hidden(false).sort(true)
+            WalkDir::new(root).skip_hidden(false).process_read_dir(sort)
         }
         #[cfg(feature = "fs-walkdir-single-threaded")]
         {
             WalkDir::new(root)
                 .skip_hidden(false)
-                .sort(true)
+                .process_read_dir(sort)
                 .parallelism(jwalk::Parallelism::Serial)
         }
     }
@@ -61,9 +76,17 @@ pub mod walkdir {
         WalkDir::new(root)
     }
 
-    /// Instantiate a new directory iterator which will not skip hidden files and is sorted
+    /// Instantiate a new directory iterator which will not skip hidden files and is sorted like git sorts paths,
+    /// i.e. as if directory names had a trailing slash.
     pub fn walkdir_sorted_new(root: impl AsRef<Path>) -> WalkDir {
-        WalkDir::new(root).sort_by_file_name()
+        WalkDir::new(root).sort_by(|a, b| {
+            super::cmp_like_git(
+                a.file_name(),
+                a.file_type().is_dir(),
+                b.file_name(),
+                b.file_type().is_dir(),
+            )
+        })
     }
 
     /// The Iterator yielding directory items
@@ -73,6 +96,16 @@ pub mod walkdir {
 #[cfg(any(feature = "walkdir", feature = "jwalk"))]
 pub use self::walkdir::{walkdir_new, walkdir_sorted_new, WalkDir};
 
+/// Compare the file names `a` and `b` of directory entries like git compares paths, which is bytewise with a slash appended to
+/// names of directories. This makes the order of all files in a tree equal to the order of their relative paths.
+#[cfg(any(feature = "walkdir", feature = "jwalk"))]
+fn cmp_like_git(a: &std::ffi::OsStr, a_is_dir: bool, b: &std::ffi::OsStr, b_is_dir: bool) -> std::cmp::Ordering {
+    let (a, b) = (a.to_string_lossy(), b.to_string_lossy());
+    let a = a.as_bytes().iter().chain(a_is_dir.then(|| &b'/'));
+    let b = b.as_bytes().iter().chain(b_is_dir.then(|| &b'/'));
+    a.cmp(b)
+}
+
 /// Prepare open options which won't follow symlinks when the file is opened.
 ///
 /// Note: only effective on unix currently.
@@ -95,11 +128,32 @@ mod snapshot {
 
     use crate::threading::{get_mut, get_ref, MutableOnDemand, OwnShared};
 
-    /// A structure holding enough information to reload a value if its on-disk representation changes as determined by its modified time.
+    /// The modification time and size of a file, which are assumed to change whenever the file changes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Stamp {
+        /// The time at which the file was last modified.
+        pub modified: std::time::SystemTime,
+        /// The size of the file in bytes.
+        pub len: u64,
+    }
+
+    impl Stamp {
+        /// Obtain the stamp of the file at `path` with a single `stat` call, or `None` if it can't be obtained,
+        /// typically because the file doesn't exist.
+        pub fn at(path: impl AsRef<std::path::Path>) -> Option<Self> {
+            let meta = path.as_ref().metadata().ok()?;
+            Some(Stamp {
+                modified: meta.modified().ok()?,
+                len: meta.len(),
+            })
+        }
+    }
+
+    /// A structure holding enough information to reload a value if its on-disk representation changes as determined by its [`Stamp`].
     #[derive(Debug)]
     pub struct Snapshot<T: std::fmt::Debug> {
         value: T,
-        modified: std::time::SystemTime,
+        stamp: Stamp,
     }
 
     /// A snapshot of a resource which is up-to-date in the moment it is retrieved.
@@ -137,30 +191,28 @@ mod snapshot {
         }
 
         /// Refresh `state` forcefully by re-`open`ing the resource. Note that `open()` returns `None` if the resource isn't
-        /// present on disk, and that it's critical that the modified time is obtained _before_ opening the resource.
-        pub fn force_refresh<E>(
-            &self,
-            open: impl FnOnce() -> Result<Option<(std::time::SystemTime, T)>, E>,
-        ) -> Result<(), E> {
+        /// present on disk, and that it's critical that the [`Stamp`] is obtained _before_ opening the resource.
+        pub fn force_refresh<E>(&self, open: impl FnOnce() -> Result<Option<(Stamp, T)>, E>) -> Result<(), E> {
             let mut state = get_mut(&self.0);
-            *state = open()?.map(|(modified, value)| OwnShared::new(Snapshot { value, modified }));
+            *state = open()?.map(|(stamp, value)| OwnShared::new(Snapshot { value, stamp }));
             Ok(())
         }
 
-        /// Assure that the resource in `state` is up-to-date by comparing the `current_modification_time` with the one we know in `state`
-        /// and by acting accordingly.
+        /// Assure that the resource in `state` is up-to-date by comparing the `current_stamp` with the one we know in `state`
+        /// and by acting accordingly, which is reloading the resource if its stamp changed in any way, or dropping it if it
+        /// doesn't exist anymore.
         /// Returns the potentially updated/reloaded resource if it is still present on disk, which then represents a snapshot that is up-to-date
         /// in that very moment, or `None` if the underlying file doesn't exist.
         ///
         /// Note that even though this is racy, each time a request is made there is a chance to see the actual state.
         pub fn recent_snapshot<E>(
             &self,
-            mut current_modification_time: impl FnMut() -> Option<std::time::SystemTime>,
+            mut current_stamp: impl FnMut() -> Option<Stamp>,
             open: impl FnOnce() -> Result<Option<T>, E>,
         ) -> Result<Option<SharedSnapshot<T>>, E> {
             let state = get_ref(self);
-            let recent_modification = current_modification_time();
-            let buffer = match (&*state, recent_modification) {
+            let recent_stamp = current_stamp();
+            let buffer = match (&*state, recent_stamp) {
                 (None, None) => (*state).clone(),
                 (Some(_), None) => {
                     drop(state);
@@ -168,39 +220,30 @@ mod snapshot {
                     *state = None;
                     (*state).clone()
                 }
-                (Some(snapshot), Some(modified_time)) => {
-                    if snapshot.modified < modified_time {
+                (Some(snapshot), Some(stamp)) => {
+                    if snapshot.stamp != stamp {
                         drop(state);
                         let mut state = get_mut(self);
 
-                        if let (Some(_snapshot), Some(modified_time)) = (&*state, current_modification_time()) {
-                            *state = open()?.map(|value| {
-                                OwnShared::new(Snapshot {
-                                    value,
-                                    modified: modified_time,
-                                })
-                            });
+                        if let (Some(_snapshot), Some(stamp)) = (&*state, current_stamp()) {
+                            *state = open()?.map(|value| OwnShared::new(Snapshot { value, stamp }));
                         }
 
                         (*state).clone()
                     } else {
-                        // Note that this relies on sub-section precision or else is a race when the packed file was just changed.
+                        // Note that this relies on sub-second precision or else is a race when the file was just changed
+                        // without changing its size.
                         // It's nothing we can know though, so… up to the caller unfortunately.
                         Some(snapshot.clone())
                     }
                 }
-                (None, Some(_modified_time)) => {
+                (None, Some(_stamp)) => {
                     drop(state);
                     let mut state = get_mut(self);
                     // Still in the same situation? If so, load the buffer. This compensates for the trampling herd
-                    // during lazy-loading at the expense of another mtime check.
-                    if let (None, Some(modified_time)) = (&*state, current_modification_time()) {
-                        *state = open()?.map(|value| {
-                            OwnShared::new(Snapshot {
-                                value,
-                                modified: modified_time,
-                            })
-                        });
+                    // during lazy-loading at the expense of another `stat` call.
+                    if let (None, Some(stamp)) = (&*state, current_stamp()) {
+                        *state = open()?.map(|value| OwnShared::new(Snapshot { value, stamp }));
                     }
                     (*state).clone()
                 }
@@ -209,4 +252,4 @@ mod snapshot {
         }
     }
 }
-pub use snapshot::{MutableSnapshot, SharedSnapshot, Snapshot};
+pub use snapshot::{MutableSnapshot, SharedSnapshot, Snapshot, Stamp};
@@ -1,4 +1,4 @@
-pub use flate2::{Decompress, Status};
+pub use flate2::{Decompress, FlushDecompress, Status};
 
 /// non-streaming interfaces for decompression
 pub mod inflate {
@@ -19,6 +19,10 @@ pub use object_id::{decode, ObjectId};
 ///
 pub mod prefix;
 
+///
+pub mod validate;
+pub use validate::function::validate_hex_str;
+
 /// An partial owned hash possibly identifying an object uniquely,
 /// whose non-prefix bytes are zeroed.
 #[derive(PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy, Debug)]
@@ -30,6 +34,8 @@ pub struct Prefix {
 
 /// The size of a SHA1 hash digest in bytes
 const SIZE_OF_SHA1_DIGEST: usize = 20;
+/// The size of a SHA256 hash digest in bytes
+const SIZE_OF_SHA256_DIGEST: usize = 32;
 
 /// Denotes the kind of function to produce a `Id`
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
@@ -37,6 +43,8 @@ const SIZE_OF_SHA1_DIGEST: usize = 20;
 pub enum Kind {
     /// The Sha1 hash with 160 bits.
     Sha1 = 1,
+    /// The Sha256 hash with 256 bits.
+    Sha256 = 2,
 }
 
 mod kind;
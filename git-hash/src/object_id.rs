@@ -1,6 +1,6 @@
 use std::{borrow::Borrow, convert::TryInto, fmt, ops::Deref};
 
-use crate::{borrowed::oid, Kind, SIZE_OF_SHA1_DIGEST};
+use crate::{borrowed::oid, Kind, SIZE_OF_SHA1_DIGEST, SIZE_OF_SHA256_DIGEST};
 
 /// An owned hash identifying objects, most commonly Sha1
 #[derive(PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
@@ -8,6 +8,8 @@ use crate::{borrowed::oid, Kind, SIZE_OF_SHA1_DIGEST};
 pub enum ObjectId {
     /// A SHA 1 hash digest
     Sha1([u8; SIZE_OF_SHA1_DIGEST]),
+    /// A SHA 256 hash digest
+    Sha256([u8; SIZE_OF_SHA256_DIGEST]),
 }
 
 #[allow(missing_docs)]
@@ -28,20 +30,23 @@ pub mod decode {
 
     /// Hash decoding
     impl ObjectId {
-        /// Create an instance from a `buffer` of 40 bytes encoded with hexadecimal notation.
+        /// Create an instance from a `buffer` of 40 or 64 bytes encoded with hexadecimal notation, for Sha1 or Sha256
+        /// respectively.
         ///
         /// Such a buffer can be obtained using [`oid::write_hex_to(buffer)`][super::oid::write_hex_to()]
         pub fn from_hex(buffer: &[u8]) -> Result<ObjectId, Error> {
             use hex::FromHex;
+            fn map_err(err: hex::FromHexError) -> Error {
+                match err {
+                    hex::FromHexError::InvalidHexCharacter { c, index } => Error::Invalid { c, index },
+                    hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+                        unreachable!("BUG: This is already checked")
+                    }
+                }
+            }
             match buffer.len() {
-                40 => Ok(ObjectId::Sha1(<[u8; 20]>::from_hex(buffer).map_err(
-                    |err| match err {
-                        hex::FromHexError::InvalidHexCharacter { c, index } => Error::Invalid { c, index },
-                        hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
-                            unreachable!("BUG: This is already checked")
-                        }
-                    },
-                )?)),
+                40 => Ok(ObjectId::Sha1(<[u8; 20]>::from_hex(buffer).map_err(map_err)?)),
+                64 => Ok(ObjectId::Sha256(<[u8; 32]>::from_hex(buffer).map_err(map_err)?)),
                 len => Err(Error::InvalidHexEncodingLength(len)),
             }
         }
@@ -63,6 +68,7 @@ impl ObjectId {
     pub fn kind(&self) -> crate::Kind {
         match self {
             ObjectId::Sha1(_) => crate::Kind::Sha1,
+            ObjectId::Sha256(_) => crate::Kind::Sha256,
         }
     }
     /// Return the raw byte slice representing this hash
@@ -70,6 +76,7 @@ impl ObjectId {
     pub fn as_slice(&self) -> &[u8] {
         match self {
             Self::Sha1(b) => b.as_ref(),
+            Self::Sha256(b) => b.as_ref(),
         }
     }
     /// Return the raw mutable byte slice representing this hash
@@ -77,6 +84,7 @@ impl ObjectId {
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
         match self {
             Self::Sha1(b) => b.as_mut(),
+            Self::Sha256(b) => b.as_mut(),
         }
     }
 
@@ -87,6 +95,9 @@ impl ObjectId {
             Kind::Sha1 => {
                 ObjectId::Sha1(*b"\x4b\x82\x5d\xc6\x42\xcb\x6e\xb9\xa0\x60\xe5\x4b\xf8\xd6\x92\x88\xfb\xee\x49\x04")
             }
+            Kind::Sha256 => ObjectId::Sha256(
+                *b"\x6e\xf1\x9b\x41\x22\x5c\x53\x69\xf1\xc1\x04\xd4\x5d\x8d\x85\xef\xa9\xb0\x57\xb5\x3b\x14\xb4\xb9\xb9\x39\xdd\x74\xde\xcc\x53\x21",
+            ),
         }
     }
 
@@ -95,6 +106,7 @@ impl ObjectId {
     pub fn is_null(&self) -> bool {
         match self {
             ObjectId::Sha1(digest) => &digest[..] == oid::null_sha1().as_bytes(),
+            ObjectId::Sha256(digest) => &digest[..] == oid::null_sha256().as_bytes(),
         }
     }
 
@@ -103,6 +115,7 @@ impl ObjectId {
     pub const fn null(kind: crate::Kind) -> ObjectId {
         match kind {
             crate::Kind::Sha1 => Self::null_sha1(),
+            crate::Kind::Sha256 => Self::null_sha256(),
         }
     }
 }
@@ -132,10 +145,30 @@ impl ObjectId {
     }
 }
 
+/// Sha256 hash specific methods
+impl ObjectId {
+    /// Instantiate an Digest from a slice 32 borrowed bytes of a Sha256 digest.
+    ///
+    /// Panics of the slice doesn't have a length of 32.
+    #[inline]
+    pub(crate) fn from_32_bytes(b: &[u8]) -> ObjectId {
+        let mut id = [0; SIZE_OF_SHA256_DIGEST];
+        id.copy_from_slice(b);
+        ObjectId::Sha256(id)
+    }
+
+    /// Returns an Digest representing a Sha256 with whose memory is zeroed.
+    #[inline]
+    pub(crate) const fn null_sha256() -> ObjectId {
+        ObjectId::Sha256([0u8; 32])
+    }
+}
+
 impl std::fmt::Debug for ObjectId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ObjectId::Sha1(_hash) => f.write_str("Sha1(")?,
+            ObjectId::Sha256(_hash) => f.write_str("Sha256(")?,
         }
         for b in self.as_bytes() {
             write!(f, "{:02x}", b)?;
@@ -150,10 +183,17 @@ impl From<[u8; SIZE_OF_SHA1_DIGEST]> for ObjectId {
     }
 }
 
+impl From<[u8; SIZE_OF_SHA256_DIGEST]> for ObjectId {
+    fn from(v: [u8; 32]) -> Self {
+        Self::Sha256(v)
+    }
+}
+
 impl From<&[u8]> for ObjectId {
     fn from(v: &[u8]) -> Self {
         match v.len() {
             20 => Self::Sha1(v.try_into().expect("prior length validation")),
+            32 => Self::Sha256(v.try_into().expect("prior length validation")),
             other => panic!("BUG: unsupported hash len: {}", other),
         }
     }
@@ -163,6 +203,7 @@ impl From<&crate::oid> for ObjectId {
     fn from(v: &oid) -> Self {
         match v.kind() {
             crate::Kind::Sha1 => ObjectId::from_20_bytes(v.as_bytes()),
+            crate::Kind::Sha256 => ObjectId::from_32_bytes(v.as_bytes()),
         }
     }
 }
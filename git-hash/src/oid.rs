@@ -1,6 +1,6 @@
 use std::{convert::TryInto, fmt};
 
-use crate::{ObjectId, SIZE_OF_SHA1_DIGEST};
+use crate::{ObjectId, SIZE_OF_SHA1_DIGEST, SIZE_OF_SHA256_DIGEST};
 
 /// A borrowed reference to a hash identifying objects.
 ///
@@ -45,6 +45,7 @@ impl fmt::Debug for oid {
             "{}({})",
             match self.kind() {
                 crate::Kind::Sha1 => "Sha1",
+                crate::Kind::Sha256 => "Sha256",
             },
             self.to_hex(),
         )
@@ -63,7 +64,7 @@ impl oid {
     #[inline]
     pub fn try_from_bytes(digest: &[u8]) -> Result<&Self, Error> {
         match digest.len() {
-            20 => Ok(
+            SIZE_OF_SHA1_DIGEST | SIZE_OF_SHA256_DIGEST => Ok(
                 #[allow(unsafe_code)]
                 unsafe {
                     &*(digest as *const [u8] as *const oid)
@@ -127,7 +128,7 @@ impl oid {
     }
 }
 
-/// Sha1 specific methods
+/// Hex encoding
 impl oid {
     /// Write ourselves to the `out` in hexadecimal notation, returning the amount of written bytes.
     ///
@@ -147,12 +148,21 @@ impl oid {
         let hex_len = self.hex_to_buf(&mut hex);
         out.write_all(&hex[..hex_len])
     }
+}
 
+/// Hash specific methods
+impl oid {
     /// Returns a Sha1 digest with all bytes being initialized to zero.
     #[inline]
     pub(crate) fn null_sha1() -> &'static Self {
         oid::from_bytes([0u8; SIZE_OF_SHA1_DIGEST].as_ref())
     }
+
+    /// Returns a Sha256 digest with all bytes being initialized to zero.
+    #[inline]
+    pub(crate) fn null_sha256() -> &'static Self {
+        oid::from_bytes([0u8; SIZE_OF_SHA256_DIGEST].as_ref())
+    }
 }
 
 impl AsRef<oid> for &oid {
@@ -167,6 +177,7 @@ impl ToOwned for oid {
     fn to_owned(&self) -> Self::Owned {
         match self.kind() {
             crate::Kind::Sha1 => crate::ObjectId::Sha1(self.bytes.try_into().expect("no bug in hash detection")),
+            crate::Kind::Sha256 => crate::ObjectId::Sha256(self.bytes.try_into().expect("no bug in hash detection")),
         }
     }
 }
@@ -177,6 +188,12 @@ impl<'a> From<&'a [u8; SIZE_OF_SHA1_DIGEST]> for &'a oid {
     }
 }
 
+impl<'a> From<&'a [u8; SIZE_OF_SHA256_DIGEST]> for &'a oid {
+    fn from(v: &'a [u8; SIZE_OF_SHA256_DIGEST]) -> Self {
+        oid::from_bytes(v.as_ref())
+    }
+}
+
 impl fmt::Display for &oid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for b in self.as_bytes() {
@@ -0,0 +1,39 @@
+use crate::Prefix;
+
+/// The error returned by [validate_hex_str()][crate::validate_hex_str()].
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(
+        "The minimum hex length of a short object id is {}, got {hex_len}",
+        Prefix::MIN_HEX_LEN
+    )]
+    TooShort { hex_len: usize },
+    #[error("An id cannot be larger than {} chars in hex, but {hex_len} was requested", crate::Kind::longest().len_in_hex())]
+    TooLong { hex_len: usize },
+    #[error("Invalid character {c} at position {index}")]
+    Invalid { c: char, index: usize },
+}
+
+pub(crate) mod function {
+    use super::Error;
+    use crate::Prefix;
+
+    /// Assure `value` looks like a full or abbreviated hexadecimal object id and return its length in hex characters,
+    /// without allocating or decoding it.
+    ///
+    /// It must be at least [`Prefix::MIN_HEX_LEN`] and at most as long as the longest supported hash in hex,
+    /// and consist only of characters in `[0-9a-fA-F]`.
+    pub fn validate_hex_str(value: &str) -> Result<usize, Error> {
+        let hex_len = value.len();
+        if hex_len > crate::Kind::longest().len_in_hex() {
+            return Err(Error::TooLong { hex_len });
+        } else if hex_len < Prefix::MIN_HEX_LEN {
+            return Err(Error::TooShort { hex_len });
+        }
+        match value.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            Some((index, c)) => Err(Error::Invalid { c, index }),
+            None => Ok(hex_len),
+        }
+    }
+}
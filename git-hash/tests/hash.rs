@@ -2,3 +2,4 @@ mod kind;
 mod object_id;
 mod oid;
 mod prefix;
+mod validate;
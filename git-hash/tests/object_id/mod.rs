@@ -12,6 +12,16 @@ mod from_hex {
         fn twenty_hex_chars_uppercase() {
             assert!(ObjectId::from_hex(b"1234567890ABCDEFAAAAAAAAAAAAAAAAAAAAAAAA").is_ok());
         }
+
+        #[test]
+        fn thirty_two_hex_chars_yield_sha256() {
+            let hex = "6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321";
+            let id = ObjectId::from_hex(hex.as_bytes()).expect("valid");
+            assert_eq!(id.kind(), git_hash::Kind::Sha256);
+            assert_eq!(id.to_string(), hex);
+            assert_eq!(id, ObjectId::empty_tree(git_hash::Kind::Sha256));
+            assert_eq!(id.as_ref().to_owned(), id, "borrowed ids convert back losslessly");
+        }
     }
 
     mod invalid {
@@ -0,0 +1,38 @@
+use git_hash::{validate, validate_hex_str};
+
+#[test]
+fn valid_full_and_abbreviated_hashes_return_their_length() {
+    assert_eq!(validate_hex_str("abcd"), Ok(4));
+    assert_eq!(validate_hex_str("0123456789ABCDEFabcdef"), Ok(22));
+    assert_eq!(validate_hex_str("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"), Ok(40));
+    assert_eq!(
+        validate_hex_str("6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321"),
+        Ok(64)
+    );
+}
+
+#[test]
+fn too_short_or_too_long() {
+    assert_eq!(validate_hex_str(""), Err(validate::Error::TooShort { hex_len: 0 }));
+    assert_eq!(validate_hex_str("abc"), Err(validate::Error::TooShort { hex_len: 3 }));
+    assert_eq!(
+        validate_hex_str("6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc53210"),
+        Err(validate::Error::TooLong { hex_len: 65 })
+    );
+}
+
+#[test]
+fn invalid_characters() {
+    assert_eq!(
+        validate_hex_str("abcg"),
+        Err(validate::Error::Invalid { c: 'g', index: 3 })
+    );
+    assert_eq!(
+        validate_hex_str("ab cd"),
+        Err(validate::Error::Invalid { c: ' ', index: 2 })
+    );
+    assert_eq!(
+        validate_hex_str("äbcd"),
+        Err(validate::Error::Invalid { c: 'ä', index: 0 })
+    );
+}
@@ -9,6 +9,13 @@ impl State {
         self.version
     }
 
+    /// Return the time at which this state was created, which is the modification time of the index file it was read from.
+    ///
+    /// Entries modified at or after this time may have changed without their stat information showing it, and are called 'racy'.
+    pub fn timestamp(&self) -> filetime::FileTime {
+        self.timestamp
+    }
+
     /// Return our entries
     pub fn entries(&self) -> &[Entry] {
         &self.entries
@@ -97,6 +104,69 @@ impl State {
     }
 }
 
+/// Mutation
+impl State {
+    /// Remove all entries for which `should_remove(idx, path, entry)` returns true, with `idx` being the position of the entry
+    /// before any entry was removed.
+    ///
+    /// Note that the paths of removed entries remain in the path backing.
+    pub fn remove_entries(&mut self, mut should_remove: impl FnMut(usize, &BStr, &Entry) -> bool) {
+        let paths = &self.path_backing;
+        let mut idx = 0;
+        self.entries.retain(|e| {
+            let keep = !should_remove(idx, e.path_in(paths), e);
+            idx += 1;
+            keep
+        });
+    }
+
+    /// Append a new entry at `path` with the given `stat`, `id`, `flags` and `mode`, without maintaining the order of entries.
+    ///
+    /// Call [`sort_entries()`][State::sort_entries()] once all entries are pushed, as lookups and writing require sorted entries.
+    pub fn dangerously_push_entry(
+        &mut self,
+        stat: entry::Stat,
+        id: git_hash::ObjectId,
+        flags: entry::Flags,
+        mode: entry::Mode,
+        path: &BStr,
+    ) {
+        let path_start = self.path_backing.len();
+        self.path_backing.extend_from_slice(path);
+        self.entries.push(Entry {
+            stat,
+            id,
+            flags,
+            mode,
+            path: path_start..self.path_backing.len(),
+        });
+    }
+
+    /// Smudge all entries which are [racy][Entry::is_racy()] with respect to our [timestamp][State::timestamp()] by setting
+    /// their size to 0, and return the amount of smudged entries.
+    ///
+    /// Call this before writing a state that was read from disk, as otherwise its entries aren't considered racy anymore once
+    /// the index file receives a newer timestamp, even though their content may have changed without their stat information
+    /// showing it. Smudged entries of non-empty files are always hashed when compared to the worktree, just like `git` does.
+    pub fn smudge_racily_clean_entries(&mut self) -> usize {
+        let timestamp = self.timestamp;
+        let mut count = 0;
+        for entry in self.entries.iter_mut().filter(|entry| entry.is_racy(timestamp)) {
+            entry.stat.size = 0;
+            count += 1;
+        }
+        count
+    }
+
+    /// Sort all entries by path and stage, as needed after [pushing entries][State::dangerously_push_entry()].
+    pub fn sort_entries(&mut self) {
+        let paths = &self.path_backing;
+        self.entries.sort_by(|a, b| {
+            Entry::cmp_filepaths(a.path_in(paths), b.path_in(paths)).then_with(|| a.stage().cmp(&b.stage()))
+        });
+    }
+}
+
 /// Extensions
 impl State {
     /// Access the `tree` extension.
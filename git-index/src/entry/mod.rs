@@ -60,6 +60,15 @@ mod access {
         pub fn stage(&self) -> entry::Stage {
             self.flags.stage()
         }
+
+        /// Return true if this entry was modified at or after `timestamp`, typically the [timestamp][State::timestamp()] of
+        /// the index it belongs to.
+        ///
+        /// Such entries are called 'racily clean' as the file may have changed within the granularity of the filesystem's
+        /// timestamps without its stat information showing it, so their content must be compared to determine if they changed.
+        pub fn is_racy(&self, timestamp: filetime::FileTime) -> bool {
+            (self.stat.mtime.secs as i64, self.stat.mtime.nsecs) >= (timestamp.unix_seconds(), timestamp.nanoseconds())
+        }
     }
 }
 
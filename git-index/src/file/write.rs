@@ -10,7 +10,7 @@ impl File {
         let version = self.state.write_to(&mut hasher, options)?;
 
         let hash = hasher.hash.digest();
-        out.write_all(&hash)?;
+        out.write_all(hash.as_slice())?;
         Ok(version)
     }
 }
@@ -84,13 +84,34 @@ impl EntryBuilder {
             path: path_start..self.path_backing.len(),
         };
 
-        match self
+        let pos = match self
             .entries
             .binary_search_by(|entry| Entry::cmp_filepaths(entry.path_in(&self.path_backing), self.path.as_bstr()))
         {
-            Ok(pos) => self.entries[pos] = new_entry,
-            Err(pos) => self.entries.insert(pos, new_entry),
+            Ok(pos) => {
+                self.entries[pos] = new_entry;
+                pos
+            }
+            Err(pos) => {
+                self.entries.insert(pos, new_entry);
+                pos
+            }
         };
+        debug_assert!(
+            {
+                let path_backing = &self.path_backing;
+                let is_ordered = |a: &Entry, b: &Entry| {
+                    Entry::cmp_filepaths(a.path_in(path_backing), b.path_in(path_backing)) == std::cmp::Ordering::Less
+                };
+                let entries = &self.entries;
+                (pos == 0 || is_ordered(&entries[pos - 1], &entries[pos]))
+                    && entries
+                        .get(pos + 1)
+                        .map_or(true, |next| is_ordered(&entries[pos], next))
+            },
+            "BUG: entries must be inserted in order of their path, {:?} is out of place",
+            self.path
+        );
     }
 }
 
@@ -85,7 +85,6 @@ pub struct State {
     ///
     /// Note that on platforms that only have a precisions of a second for this time, we will treat all entries with the
     /// same timestamp as this as potentially changed, checking more thoroughly if a change actually happened.
-    #[allow(dead_code)]
     timestamp: FileTime,
     version: Version,
     entries: Vec<Entry>,
@@ -156,7 +155,7 @@ pub(crate) mod util {
 
 #[test]
 fn size_of_entry() {
-    assert_eq!(std::mem::size_of::<crate::Entry>(), 80);
+    assert_eq!(std::mem::size_of::<crate::Entry>(), 96);
 
     // the reason we have our own time is half the size.
     assert_eq!(std::mem::size_of::<crate::entry::Time>(), 8);
@@ -13,3 +13,63 @@ fn entry_by_path_and_stage() {
         assert_eq!(file.entry_by_path_and_stage(path, 0), Some(entry));
     }
 }
+
+#[test]
+fn remove_push_and_sort_entries() {
+    let mut file = read::file("v4_more_files_IEOT");
+    let num_entries = file.entries().len();
+    let first = file.entries()[0].clone();
+    let first_path = first.path(&file).to_owned();
+
+    let mut seen = Vec::new();
+    file.remove_entries(|idx, path, _entry| {
+        seen.push(idx);
+        path == first_path
+    });
+    assert_eq!(
+        seen,
+        (0..num_entries).collect::<Vec<_>>(),
+        "all entries are visited in order"
+    );
+    assert_eq!(file.entries().len(), num_entries - 1);
+    assert_eq!(file.entry_by_path_and_stage(first_path.as_ref(), 0), None);
+
+    file.dangerously_push_entry(first.stat, first.id, first.flags, first.mode, first_path.as_ref());
+    assert_eq!(
+        file.entry_by_path_and_stage(first_path.as_ref(), 0),
+        None,
+        "entries are out of order and the lookup fails"
+    );
+    file.sort_entries();
+    assert_eq!(file.entries().len(), num_entries);
+    let entry = file
+        .entry_by_path_and_stage(first_path.as_ref(), 0)
+        .expect("present after sorting");
+    assert_eq!(entry.id, first.id);
+    file.verify_entries().expect("entries are sorted");
+}
+
+#[test]
+fn racily_clean_entries_are_smudged() {
+    let mut file = read::file("v4_more_files_IEOT");
+    let timestamp = file.timestamp();
+    for (idx, entry) in file.entries_mut().iter_mut().enumerate() {
+        entry.stat.size = 42;
+        entry.stat.mtime = git_index::entry::Time {
+            secs: if idx == 0 { timestamp.unix_seconds() as u32 } else { 0 },
+            nsecs: timestamp.nanoseconds(),
+        };
+    }
+    assert!(
+        file.entries()[0].is_racy(timestamp),
+        "entries modified in the same instant as the index are racy"
+    );
+    assert!(!file.entries()[1].is_racy(timestamp));
+
+    assert_eq!(file.smudge_racily_clean_entries(), 1);
+    assert_eq!(file.entries()[0].stat.size, 0, "smudging sets the size to 0");
+    assert!(
+        file.entries()[1..].iter().all(|entry| entry.stat.size == 42),
+        "other entries are untouched"
+    );
+}
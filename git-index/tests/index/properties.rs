@@ -0,0 +1,106 @@
+//! Randomized tests for the ordering guarantees of trees and index entries, which must match the ones of `git`
+//! no matter if an index is created from a tree, written, or decoded with one or more threads.
+use std::{collections::BTreeSet, path::Path, process::Command};
+
+use git_index::{decode, State};
+use git_repository as git;
+use git_repository::prelude::FindExt;
+
+/// Return file paths made of components which are likely to reveal differences between sorting by path component
+/// and sorting by bytes, as `-` and `.` sort before `/`.
+fn random_paths(rng: &fastrand::Rng) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for _ in 0..rng.usize(1..40) {
+        let path = (0..rng.usize(1..4))
+            .map(|_| ["a", "a-b", "a.b", "a0", "b", "ab"][rng.usize(..6)])
+            .collect::<Vec<_>>()
+            .join("/");
+        let conflicts_with_existing_directory_or_file = paths.iter().any(|existing: &String| {
+            existing.starts_with(&format!("{}/", path)) || path.starts_with(&format!("{}/", existing))
+        });
+        if !conflicts_with_existing_directory_or_file {
+            paths.insert(path);
+        }
+    }
+    paths
+}
+
+fn entries_of(state: &State) -> Vec<(String, git_hash::ObjectId, git_index::entry::Mode)> {
+    state
+        .entries()
+        .iter()
+        .map(|e| (e.path(state).to_string(), e.id, e.mode))
+        .collect()
+}
+
+fn git(git_dir: &Path, args: &[&str]) -> crate::Result {
+    let status = Command::new("git").arg("--git-dir").arg(git_dir).args(args).status()?;
+    assert!(status.success(), "git {:?} failed", args);
+    Ok(())
+}
+
+#[test]
+fn index_entries_from_trees_are_sorted_and_equal_to_the_ones_of_git() -> crate::Result {
+    for seed in 0..25 {
+        let rng = fastrand::Rng::with_seed(seed);
+        let paths = random_paths(&rng);
+        let mut fixture = git::fixture::Builder::new()?;
+        for path in &paths {
+            fixture.write_file(path, path)?;
+        }
+        let commit = fixture.commit("main", "random")?;
+        let (repo, _keep) = fixture.finish()?;
+        let tree = repo.find_object(commit)?.into_commit().tree_id()?.detach();
+
+        git(repo.git_dir(), &["fsck", "--strict", "--no-progress", "--no-dangling"])?;
+
+        let state = State::from_tree(&tree, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?;
+        state.verify_entries()?;
+        assert_eq!(
+            state
+                .entries()
+                .iter()
+                .map(|e| e.path(&state).to_string())
+                .collect::<Vec<_>>(),
+            paths.iter().cloned().collect::<Vec<_>>(),
+            "seed {}: entries are sorted by path",
+            seed
+        );
+
+        git(repo.git_dir(), &["read-tree", &tree.to_string()])?;
+        let index_path = repo.git_dir().join("index");
+        let serial = git_index::File::at(
+            &index_path,
+            decode::Options {
+                thread_limit: Some(1),
+                ..Default::default()
+            },
+        )?;
+        let parallel = git_index::File::at(
+            &index_path,
+            decode::Options {
+                thread_limit: None,
+                min_extension_block_in_bytes_for_threading: 0,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            entries_of(&state),
+            entries_of(&serial),
+            "seed {}: git creates the same index from the tree",
+            seed
+        );
+        assert_eq!(entries_of(&serial), entries_of(&parallel), "seed {}", seed);
+
+        let mut buf = Vec::new();
+        serial.write_to(&mut buf, Default::default())?;
+        let (decoded, _checksum) = State::from_bytes(&buf, serial.timestamp(), Default::default())?;
+        assert_eq!(
+            entries_of(&decoded),
+            entries_of(&serial),
+            "seed {}: order is retained when writing and reading",
+            seed
+        );
+    }
+    Ok(())
+}
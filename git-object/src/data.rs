@@ -12,9 +12,16 @@ impl<'a> Data<'a> {
     ///
     /// **Note** that [mutable, decoded objects][crate::Object] can be created from [`Data`]
     /// using [`crate::ObjectRef::into_owned()`].
+    ///
+    /// Trees are assumed to refer to their entries by Sha1 hashes, use [`decode_with_hash()`][Self::decode_with_hash()] otherwise.
     pub fn decode(&self) -> Result<ObjectRef<'a>, crate::decode::Error> {
+        self.decode_with_hash(git_hash::Kind::Sha1)
+    }
+
+    /// Like [`decode()`][Self::decode()], but trees are assumed to refer to their entries by hashes of `object_hash`.
+    pub fn decode_with_hash(&self, object_hash: git_hash::Kind) -> Result<ObjectRef<'a>, crate::decode::Error> {
         Ok(match self.kind {
-            Kind::Tree => ObjectRef::Tree(TreeRef::from_bytes(self.data)?),
+            Kind::Tree => ObjectRef::Tree(TreeRef::from_bytes_with_hash(self.data, object_hash)?),
             Kind::Blob => ObjectRef::Blob(BlobRef { data: self.data }),
             Kind::Commit => ObjectRef::Commit(CommitRef::from_bytes(self.data)?),
             Kind::Tag => ObjectRef::Tag(TagRef::from_bytes(self.data)?),
@@ -23,9 +30,17 @@ impl<'a> Data<'a> {
 
     /// Returns this object as tree iterator to parse entries one at a time to avoid allocations, or
     /// `None` if this is not a tree object.
+    ///
+    /// Entries are assumed to be identified by Sha1 hashes, use [`try_into_tree_iter_with_hash()`][Self::try_into_tree_iter_with_hash()]
+    /// otherwise.
     pub fn try_into_tree_iter(self) -> Option<TreeRefIter<'a>> {
+        self.try_into_tree_iter_with_hash(git_hash::Kind::Sha1)
+    }
+
+    /// Like [`try_into_tree_iter()`][Self::try_into_tree_iter()], but entries are assumed to be identified by hashes of `object_hash`.
+    pub fn try_into_tree_iter_with_hash(self, object_hash: git_hash::Kind) -> Option<TreeRefIter<'a>> {
         match self.kind {
-            Kind::Tree => Some(TreeRefIter::from_bytes(self.data)),
+            Kind::Tree => Some(TreeRefIter::from_bytes_with_hash(self.data, object_hash)),
             _ => None,
         }
     }
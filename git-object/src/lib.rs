@@ -222,6 +222,8 @@ pub struct TreeRef<'a> {
 pub struct TreeRefIter<'a> {
     /// The directories and files contained in this tree.
     data: &'a [u8],
+    /// The kind of hash used for the ids of all entries.
+    object_hash: git_hash::Kind,
 }
 
 /// A mutable Tree, containing other trees, blobs or commits.
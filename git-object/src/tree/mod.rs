@@ -79,10 +79,10 @@ impl<'a> PartialOrd for EntryRef<'a> {
 }
 
 impl<'a> Ord for EntryRef<'a> {
-    /// Entries compare by the common portion of the filename. This is critical for proper functioning of algorithms working on trees.
+    /// Entries compare by filename like `git` does, as if the names of trees had a trailing `/`.
+    /// This is the order entries must have in a tree, and critical for proper functioning of algorithms working on trees.
     fn cmp(&self, other: &Self) -> Ordering {
-        let len = self.filename.len().min(other.filename.len());
-        self.filename[..len].cmp(&other.filename[..len])
+        cmp_like_git(self.filename, self.mode, other.filename, other.mode)
     }
 }
 
@@ -105,15 +105,22 @@ impl PartialOrd for Entry {
 }
 
 impl Ord for Entry {
-    /// Entries compare by the common portion of the filename. This is critical for proper functioning of algorithms working on trees.
+    /// Entries compare by filename like `git` does, as if the names of trees had a trailing `/`.
+    /// This is the order entries must have in a tree, and critical for proper functioning of algorithms working on trees.
     fn cmp(&self, other: &Self) -> Ordering {
-        let common_len = self.filename.len().min(other.filename.len());
-        self.filename[..common_len]
-            .cmp(&other.filename[..common_len])
-            .then_with(|| self.filename.len().cmp(&other.filename.len()))
+        cmp_like_git(self.filename.as_ref(), self.mode, other.filename.as_ref(), other.mode)
     }
 }
 
+fn cmp_like_git(a: &BStr, a_mode: EntryMode, b: &BStr, b_mode: EntryMode) -> Ordering {
+    let common_len = a.len().min(b.len());
+    a[..common_len].cmp(&b[..common_len]).then_with(|| {
+        let terminator =
+            |name: &BStr, mode: EntryMode| name.get(common_len).copied().or_else(|| mode.is_tree().then(|| b'/'));
+        terminator(a, a_mode).cmp(&terminator(b, b_mode))
+    })
+}
+
 /// Serialization
 impl EntryMode {
     /// Return the representation as used in the git internal format.
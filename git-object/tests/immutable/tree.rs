@@ -128,6 +128,34 @@ mod from_bytes {
         );
         Ok(())
     }
+
+    #[test]
+    fn sha256() -> crate::Result {
+        let id = git_hash::ObjectId::empty_tree(git_hash::Kind::Sha256);
+        let mut data = Vec::new();
+        for name in ["a", "b"] {
+            data.extend_from_slice(b"40000 ");
+            data.extend_from_slice(name.as_bytes());
+            data.push(0);
+            data.extend_from_slice(id.as_slice());
+        }
+
+        let tree = TreeRef::from_bytes_with_hash(&data, git_hash::Kind::Sha256)?;
+        assert_eq!(tree.entries.len(), 2);
+        assert!(tree
+            .entries
+            .iter()
+            .all(|e| e.oid == id && e.mode == tree::EntryMode::Tree));
+        assert_eq!(
+            git_object::TreeRefIter::from_bytes_with_hash(&data, git_hash::Kind::Sha256).entries()?,
+            tree.entries
+        );
+        assert!(
+            TreeRef::from_bytes(&data).is_err(),
+            "sha1 ids are too short to parse sha256 trees"
+        );
+        Ok(())
+    }
 }
 
 mod entry_mode {
@@ -142,3 +170,53 @@ mod entry_mode {
         );
     }
 }
+
+mod entry {
+    use git_object::{
+        bstr::ByteSlice,
+        tree::{Entry, EntryMode, EntryRef},
+    };
+
+    use crate::hex_to_id;
+
+    fn entry(mode: EntryMode, filename: &str) -> Entry {
+        Entry {
+            mode,
+            filename: filename.into(),
+            oid: hex_to_id("e69de29bb2d1d6434b8b29ae4b1e2d3c8b8b5b4a"),
+        }
+    }
+
+    #[test]
+    fn ordering_is_the_one_of_git_with_trees_sorting_as_if_they_had_a_trailing_slash() {
+        let expected = vec![
+            entry(EntryMode::Blob, "a"),
+            entry(EntryMode::Blob, "a-b"),
+            entry(EntryMode::Blob, "a.b"),
+            entry(EntryMode::Tree, "a"),
+            entry(EntryMode::Blob, "a0"),
+            entry(EntryMode::Blob, "ab.c"),
+            entry(EntryMode::Tree, "ab"),
+            entry(EntryMode::BlobExecutable, "b"),
+        ];
+        let mut actual: Vec<_> = expected.iter().rev().cloned().collect();
+        actual.sort();
+        assert_eq!(actual, expected);
+
+        let mut actual: Vec<_> = expected
+            .iter()
+            .rev()
+            .map(|e| EntryRef {
+                mode: e.mode,
+                filename: e.filename.as_bstr(),
+                oid: &e.oid,
+            })
+            .collect();
+        actual.sort();
+        assert_eq!(
+            actual.into_iter().map(|e| e.filename.to_owned()).collect::<Vec<_>>(),
+            expected.into_iter().map(|e| e.filename).collect::<Vec<_>>(),
+            "borrowed entries are ordered the same"
+        );
+    }
+}
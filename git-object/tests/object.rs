@@ -32,7 +32,7 @@ fn fixture_bytes(path: &str) -> Vec<u8> {
 fn size_in_memory() {
     assert_eq!(
         std::mem::size_of::<git_object::Object>(),
-        264,
+        288,
         "Prevent unexpected growth of what should be lightweight objects"
     )
 }
@@ -68,6 +68,19 @@ pub fn sink(object_hash: git_hash::Kind) -> Sink {
 ///
 pub mod sink;
 
+/// A temporary object directory inside of an object database to receive objects that must not become visible
+/// until they are accepted, similar to the quarantine `git receive-pack` uses for incoming pushes.
+///
+/// Objects written into it are [promoted][Quarantine::promote()] into the object database or [discarded][Quarantine::discard()],
+/// with the latter also happening when it is dropped.
+pub struct Quarantine {
+    dir: tempfile::TempDir,
+    objects_dir: PathBuf,
+}
+
+///
+pub mod quarantine;
+
 ///
 pub mod find;
 
@@ -0,0 +1,135 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::Quarantine;
+
+/// Returned by [`Quarantine::promote()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read quarantined objects in '{}'", path.display())]
+    ReadDir { source: io::Error, path: PathBuf },
+    #[error("Could not create directory '{}' to move quarantined objects into", path.display())]
+    CreateDir { source: io::Error, path: PathBuf },
+    #[error("Could not move quarantined object from '{}' to '{}'", from.display(), to.display())]
+    Move {
+        source: io::Error,
+        from: PathBuf,
+        to: PathBuf,
+    },
+    #[error("Could not remove the quarantine directory after promoting its objects")]
+    Remove(#[source] io::Error),
+}
+
+/// Lifecycle
+impl Quarantine {
+    /// Create a new quarantine directory inside of `objects_dir`, the directory containing loose objects and `pack/`.
+    ///
+    /// It refers to `objects_dir` as alternate, so a store [opened][crate::at()] on [its path][Quarantine::path()]
+    /// writes objects into the quarantine while finding all objects of `objects_dir` as well.
+    pub fn new(objects_dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let objects_dir = objects_dir.into();
+        let dir = tempfile::Builder::new()
+            .prefix("tmp_objdir-incoming-")
+            .tempdir_in(&objects_dir)?;
+
+        let info_dir = dir.path().join("info");
+        std::fs::create_dir(&info_dir)?;
+        let alternate = if objects_dir.is_absolute() {
+            objects_dir.clone()
+        } else {
+            std::env::current_dir()?.join(&objects_dir)
+        };
+        let mut alternates = git_path::into_bstr(alternate).into_owned();
+        alternates.push(b'\n');
+        std::fs::write(info_dir.join("alternates"), alternates)?;
+
+        Ok(Quarantine { dir, objects_dir })
+    }
+
+    /// Move all loose objects and packs of the quarantine into the object database and delete the quarantine afterwards.
+    ///
+    /// Objects that already exist in the object database are left untouched, and packs are moved before their indices
+    /// to never make an index visible without its pack.
+    pub fn promote(self) -> Result<(), Error> {
+        for entry in read_dir(self.dir.path())? {
+            let name = entry.file_name();
+            let is_fan_out_dir = name.len() == 2
+                && name
+                    .to_str()
+                    .map_or(false, |name| name.bytes().all(|b| b.is_ascii_hexdigit()))
+                && entry.file_type().map_or(false, |kind| kind.is_dir());
+            let mut files = if name == "pack" || is_fan_out_dir {
+                read_dir(&entry.path())?
+            } else {
+                continue;
+            };
+            if name == "pack" {
+                files.sort_by_key(|entry| pack_copy_priority(&entry.file_name().to_string_lossy()));
+            }
+
+            let destination_dir = self.objects_dir.join(&name);
+            if !files.is_empty() {
+                std::fs::create_dir_all(&destination_dir).map_err(|source| Error::CreateDir {
+                    source,
+                    path: destination_dir.clone(),
+                })?;
+            }
+            for file in files {
+                let to = destination_dir.join(file.file_name());
+                if to.exists() {
+                    continue;
+                }
+                let from = file.path();
+                std::fs::rename(&from, &to).map_err(|source| Error::Move { source, from, to })?;
+            }
+        }
+        self.dir.close().map_err(Error::Remove)
+    }
+
+    /// Delete the quarantine along with all objects in it.
+    pub fn discard(self) -> io::Result<()> {
+        self.dir.close()
+    }
+}
+
+/// Access
+impl Quarantine {
+    /// The objects directory of the quarantine, to be used to [open a store][crate::at()] that writes into it.
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// The objects directory the quarantine belongs to and which receives its objects when [promoted][Quarantine::promote()].
+    pub fn objects_dir(&self) -> &Path {
+        &self.objects_dir
+    }
+}
+
+fn read_dir(path: &Path) -> Result<Vec<std::fs::DirEntry>, Error> {
+    std::fs::read_dir(path)
+        .and_then(|entries| entries.collect())
+        .map_err(|source| Error::ReadDir {
+            source,
+            path: path.to_owned(),
+        })
+}
+
+/// The order in which to move pack related files, similar to what `git` does, so that indices come last.
+fn pack_copy_priority(name: &str) -> usize {
+    if !name.starts_with("pack") {
+        0
+    } else if name.ends_with(".keep") {
+        1
+    } else if name.ends_with(".pack") {
+        2
+    } else if name.ends_with(".rev") {
+        3
+    } else if name.ends_with(".idx") {
+        4
+    } else {
+        5
+    }
+}
@@ -0,0 +1,126 @@
+use std::ops::Deref;
+
+use git_hash::{oid, ObjectId};
+
+use crate::{store::handle, store_impls::dynamic};
+
+/// The error returned by [`Store::filter_known()`][crate::Store::filter_known()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    LoadIndex(#[from] dynamic::load_index::Error),
+    #[error("Could not list the loose object directory at '{}'", .path.display())]
+    ListLooseObjects {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return a vector with one entry per id in `sorted_ids`, set to `true` if the object exists in any of our packs or loose
+    /// object databases, including the ones of linked databases (via alternates).
+    ///
+    /// This is much faster than calling [`contains()`][crate::Find::contains()] for each id when many ids are queried at once,
+    /// as each pack index and each loose object directory is traversed at most once.
+    ///
+    /// `sorted_ids` must be sorted in ascending order, otherwise the result is unspecified.
+    pub fn filter_known(&self, sorted_ids: &[ObjectId]) -> Result<Vec<bool>, Error> {
+        self.store_ref().filter_known(sorted_ids)
+    }
+}
+
+impl dynamic::Store {
+    /// Like [`Handle::filter_known()`][super::Handle::filter_known()], but accessible directly on the store.
+    pub fn filter_known(&self, sorted_ids: &[ObjectId]) -> Result<Vec<bool>, Error> {
+        debug_assert!(sorted_ids.windows(2).all(|w| w[0] <= w[1]), "BUG: ids must be sorted");
+        let mut known = vec![false; sorted_ids.len()];
+        let snapshot = self.load_all_indices()?;
+        for index in &snapshot.indices {
+            mark_known_in_index(index, sorted_ids, &mut known);
+        }
+
+        let mut names = Vec::new();
+        for loose in snapshot.loose_dbs.iter() {
+            let mut remaining = sorted_ids;
+            let mut offset = 0;
+            while let Some(first) = remaining.first() {
+                let fan = first.as_bytes()[0];
+                let num_in_fan = remaining.partition_point(|id| id.as_bytes()[0] == fan);
+                let (in_fan, rest) = remaining.split_at(num_in_fan);
+                if known[offset..][..num_in_fan].iter().any(|known| !known) {
+                    let dir = loose.path().join(format!("{:02x}", fan));
+                    list_object_names(&dir, &mut names)
+                        .map_err(|source| Error::ListLooseObjects { path: dir, source })?;
+                    for (id, known) in in_fan.iter().zip(&mut known[offset..]) {
+                        if !*known {
+                            let hex = id.to_hex().to_string();
+                            *known = names.binary_search_by(|name| name.as_str().cmp(&hex[2..])).is_ok();
+                        }
+                    }
+                }
+                offset += num_in_fan;
+                remaining = rest;
+            }
+        }
+        Ok(known)
+    }
+}
+
+/// Mark all of `sorted_ids` which are contained in `index` as `known`, moving through the index only forward.
+fn mark_known_in_index(index: &handle::IndexLookup, sorted_ids: &[ObjectId], known: &mut [bool]) {
+    let num_objects = index.num_objects();
+    let mut pos = 0;
+    for (id, known) in sorted_ids.iter().zip(known.iter_mut()) {
+        if pos == num_objects {
+            break;
+        }
+        if *known {
+            continue;
+        }
+        pos = seek(index, pos, num_objects, id);
+        *known = pos < num_objects && index.oid_at_index(pos) == id.as_ref();
+    }
+}
+
+/// Return the position of the first entry in `index` at or after `start` which is greater or equal to `id`,
+/// galloping forward to skip large stretches of the index when queries are sparse.
+fn seek(index: &handle::IndexLookup, start: u32, end: u32, id: &oid) -> u32 {
+    let mut lower = start;
+    let mut step = 1;
+    let mut upper = start;
+    while upper < end && index.oid_at_index(upper) < id {
+        lower = upper + 1;
+        upper = upper.saturating_add(step).min(end);
+        step = step.saturating_mul(2);
+    }
+    while lower < upper {
+        let mid = lower + (upper - lower) / 2;
+        if index.oid_at_index(mid) < id {
+            lower = mid + 1;
+        } else {
+            upper = mid;
+        }
+    }
+    lower
+}
+
+/// Place the sorted names of all files in `dir` into `out`, which is empty if `dir` doesn't exist.
+fn list_object_names(dir: &std::path::Path, out: &mut Vec<String>) -> std::io::Result<()> {
+    out.clear();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        if let Ok(name) = entry?.file_name().into_string() {
+            out.push(name);
+        }
+    }
+    out.sort();
+    Ok(())
+}
@@ -85,6 +85,29 @@ mod error {
 }
 pub use error::Error;
 
+/// The place an object was read from, as returned by [`Handle::try_find_with_source()`][super::Handle::try_find_with_source()].
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub enum Source {
+    /// The object is a loose object in the objects directory the store was opened with.
+    LoosePrimary,
+    /// The object is a loose object in the alternate at the given index, in the order alternates were resolved.
+    LooseAlternate(usize),
+    /// The object is stored in a pack, which may belong to the objects directory or any of its alternates.
+    ///
+    /// Use the `pack_id` of the location to identify the pack.
+    Packed(Location),
+}
+
+impl Source {
+    /// Return the location within a pack if the object was packed, or `None` if it was a loose object.
+    pub fn into_location(self) -> Option<Location> {
+        match self {
+            Source::Packed(location) => Some(location),
+            Source::LoosePrimary | Source::LooseAlternate(_) => None,
+        }
+    }
+}
+
 use crate::{
     find::{PotentialPrefix, PrefixLookupResult},
     store::types::PackId,
@@ -126,7 +149,7 @@ where
         while candidate.hex_len() != max_hex_len {
             let res = self.lookup_prefix(candidate.to_prefix(), None)?;
             match res {
-                Some(Ok(_id)) => return Ok(Some(candidate.to_prefix())),
+                Some(Ok(id)) => return Ok((id == candidate.id()).then(|| candidate.to_prefix())),
                 Some(Err(())) => {
                     candidate.inc_hex_len();
                     continue;
@@ -207,6 +230,19 @@ where
         }
     }
 
+    /// Find the object with `id` like [`try_find()`][git_pack::Find::try_find()] and write it into `buffer`, but also return
+    /// the [`Source`] it was read from to learn how objects are distributed across the objects directory and its alternates.
+    ///
+    /// Note that replacements are applied, hence the source is the one of the replacement object if `id` was replaced.
+    pub fn try_find_with_source<'a>(
+        &self,
+        id: impl AsRef<oid>,
+        buffer: &'a mut Vec<u8>,
+    ) -> Result<Option<(git_object::Data<'a>, Source)>, Error> {
+        let mut snapshot = self.snapshot.borrow_mut();
+        self.try_find_cached_inner(id.as_ref(), buffer, &mut git_pack::cache::Never, &mut snapshot, None)
+    }
+
     fn try_find_cached_inner<'a, 'b>(
         &'b self,
         mut id: &'b oid,
@@ -214,7 +250,7 @@ where
         pack_cache: &mut impl DecodeEntry,
         snapshot: &mut load_index::Snapshot,
         recursion: Option<error::DeltaBaseRecursion<'_>>,
-    ) -> Result<Option<(git_object::Data<'a>, Option<Location>)>, Error> {
+    ) -> Result<Option<(git_object::Data<'a>, Source)>, Error> {
         if let Some(r) = recursion {
             if r.depth >= self.max_recursion_depth {
                 return Err(Error::DeltaBaseRecursionLimit {
@@ -284,7 +320,7 @@ where
                                     kind: r.kind,
                                     data: buffer.as_slice(),
                                 },
-                                Some(git_pack::data::entry::Location {
+                                Source::Packed(git_pack::data::entry::Location {
                                     pack_id: pack.id,
                                     pack_offset,
                                     entry_size: r.compressed_size + header_size,
@@ -381,7 +417,7 @@ where
                                             kind: r.kind,
                                             data: buffer.as_slice(),
                                         },
-                                        Some(git_pack::data::entry::Location {
+                                        Source::Packed(git_pack::data::entry::Location {
                                             pack_id: pack.id,
                                             pack_offset,
                                             entry_size: r.compressed_size + header_size,
@@ -400,12 +436,16 @@ where
                 }
             }
 
-            for lodb in snapshot.loose_dbs.iter() {
+            for (idx, lodb) in snapshot.loose_dbs.iter().enumerate() {
                 // TODO: remove this double-lookup once the borrow checker allows it.
                 if lodb.contains(id) {
+                    let source = match idx.checked_sub(1) {
+                        Some(alternate) => Source::LooseAlternate(alternate),
+                        None => Source::LoosePrimary,
+                    };
                     return lodb
                         .try_find(id, buffer)
-                        .map(|obj| obj.map(|obj| (obj, None)))
+                        .map(|obj| obj.map(|obj| (obj, source)))
                         .map_err(Into::into);
                 }
             }
@@ -471,6 +511,7 @@ where
         let id = id.as_ref();
         let mut snapshot = self.snapshot.borrow_mut();
         self.try_find_cached_inner(id, buffer, pack_cache, &mut snapshot, None)
+            .map(|res| res.map(|(obj, source)| (obj, source.into_location())))
     }
 
     fn location_by_oid(&self, id: impl AsRef<oid>, buf: &mut Vec<u8>) -> Option<Location> {
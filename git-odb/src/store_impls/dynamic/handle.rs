@@ -354,6 +354,7 @@ impl TryFrom<&super::Store> for super::Store {
                 slots: crate::store::init::Slots::Given(s.files.len().try_into().expect("BUG: too many slots")),
                 object_hash: Default::default(),
                 use_multi_pack_index: false,
+                require_writable_objects_dir: false,
             },
         )
     }
@@ -16,6 +16,12 @@ pub struct Options {
     pub object_hash: git_hash::Kind,
     /// If false, no multi-pack indices will be used. If true, they will be used if their hash matches `object_hash`.
     pub use_multi_pack_index: bool,
+    /// If true, fail to open the store if the objects directory can't be written to, which is where all new objects are
+    /// written to even if there are alternates. This is determined by creating and removing a temporary file within it.
+    ///
+    /// This is useful for applications that intend to write objects to detect a misconfiguration early. It's off by default
+    /// as reading from a store is possible without write access.
+    pub require_writable_objects_dir: bool,
 }
 
 impl Default for Options {
@@ -24,6 +30,7 @@ impl Default for Options {
             slots: Default::default(),
             object_hash: Default::default(),
             use_multi_pack_index: true,
+            require_writable_objects_dir: false,
         }
     }
 }
@@ -72,6 +79,7 @@ impl Store {
             slots,
             object_hash,
             use_multi_pack_index,
+            require_writable_objects_dir,
         }: Options,
     ) -> std::io::Result<Self> {
         let objects_dir = objects_dir.into();
@@ -81,6 +89,17 @@ impl Store {
                 format!("'{}' wasn't a directory", objects_dir.display()),
             ));
         }
+        if require_writable_objects_dir {
+            tempfile::Builder::new()
+                .prefix("tmp_writable_probe_")
+                .tempfile_in(&objects_dir)
+                .map_err(|err| {
+                    std::io::Error::new(
+                        err.kind(),
+                        format!("objects directory '{}' isn't writable: {}", objects_dir.display(), err),
+                    )
+                })?;
+        }
         let slot_count = match slots {
             Slots::Given(n) => n as usize,
             Slots::AsNeededByDiskState { multiplier, minimum } => {
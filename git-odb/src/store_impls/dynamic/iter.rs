@@ -126,6 +126,9 @@ where
 {
     /// Return an iterator over all objects, first the ones in all packs of all linked databases (via alternates),
     /// followed by all loose objects.
+    ///
+    /// The objects of each pack and of each loose object database are returned in ascending order of their id, but objects
+    /// may appear more than once if they are stored in multiple places.
     pub fn iter(&self) -> Result<AllObjects, dynamic::load_index::Error> {
         AllObjects::new(self.store_ref())
     }
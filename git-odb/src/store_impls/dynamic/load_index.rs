@@ -52,6 +52,16 @@ pub use error::Error;
 use crate::store::types::{Generation, IndexAndPacks, MutableIndexAndPack, SlotMapIndex};
 
 impl super::Store {
+    /// Check the objects directory and its alternates for new, changed or removed pack indices and pack data files right away,
+    /// instead of waiting for an object lookup to miss.
+    ///
+    /// Packs that were removed on disk are unloaded, just like it happens when refreshing automatically.
+    pub fn refresh(&self) -> Result<(), Error> {
+        let needs_init = !self.index.load().is_initialized();
+        self.consolidate_with_disk_state(needs_init, false /*load one new index*/)
+            .map(|_| ())
+    }
+
     /// Load all indices, refreshing from disk only if needed.
     pub(crate) fn load_all_indices(&self) -> Result<Snapshot, Error> {
         let mut snapshot = self.collect_snapshot();
@@ -58,6 +58,9 @@ pub mod find;
 ///
 pub mod iter;
 
+///
+pub mod filter_known;
+
 ///
 pub mod write;
 
@@ -75,6 +78,9 @@ pub mod load_index;
 ///
 pub mod verify;
 
+///
+pub mod stats;
+
 mod load_one;
 
 mod metrics;
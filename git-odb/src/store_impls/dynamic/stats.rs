@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+
+use crate::store::load_index;
+
+/// Statistics about the amount and size of objects on disk, similar to what `git count-objects -v` reports.
+///
+/// All object directories known to the store are considered, including alternates.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectSizeStats {
+    /// The amount of loose objects.
+    pub loose_count: u64,
+    /// The sum of the sizes of all loose object files in bytes.
+    pub loose_size_bytes: u64,
+    /// The amount of pack files which have an index.
+    pub pack_count: u64,
+    /// The amount of bytes used by compressed objects within all packs, which is the size of the pack without
+    /// its header and trailing checksum.
+    pub pack_in_pack_bytes: u64,
+    /// The sum of the sizes of all `.pack` files in bytes.
+    pub pack_size_bytes: u64,
+    /// The amount of `.pack` files without matching `.idx` file, which can't be used.
+    pub garbage_count: u64,
+    /// The sum of the sizes of all garbage files in bytes.
+    pub garbage_size_bytes: u64,
+}
+
+/// The error returned by [`Store::object_size_stats()`][crate::Store::object_size_stats()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    LoadIndex(#[from] load_index::Error),
+    #[error("Could not read information about {}", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+}
+
+impl super::Store {
+    /// Return statistics about the amount and size of loose objects and packs currently on disk.
+    ///
+    /// Note that this call is expensive as it visits each loose object and pack file, and that its result
+    /// may be inaccurate if objects are written or repacked concurrently.
+    pub fn object_size_stats(&self) -> Result<ObjectSizeStats, Error> {
+        let index = self.index.load();
+        if !index.is_initialized() {
+            self.consolidate_with_disk_state(true, false /*load one new index*/)?;
+        }
+        let index = self.index.load();
+        let mut stats = ObjectSizeStats::default();
+        for db in index.loose_dbs.iter() {
+            self.add_loose_objects(&db.path, &mut stats)?;
+            self.add_packs(&db.path.join("pack"), &mut stats)?;
+        }
+        Ok(stats)
+    }
+
+    fn add_loose_objects(&self, objects_dir: &Path, stats: &mut ObjectSizeStats) -> Result<(), Error> {
+        let file_name_len = self.object_hash.len_in_hex() - 2;
+        for fan_out in read_dir(objects_dir)? {
+            let name = fan_out.file_name();
+            let is_fan_out_dir = name.len() == 2 && name.to_str().map_or(false, is_hex);
+            if !is_fan_out_dir {
+                continue;
+            }
+            for entry in read_dir(&fan_out.path())? {
+                let name = entry.file_name();
+                if name.len() == file_name_len && name.to_str().map_or(false, is_hex) {
+                    stats.loose_count += 1;
+                    stats.loose_size_bytes += file_size(&entry)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_packs(&self, pack_dir: &Path, stats: &mut ObjectSizeStats) -> Result<(), Error> {
+        let header_and_trailer_len = 12 + self.object_hash.len_in_bytes() as u64;
+        for entry in read_dir(pack_dir)? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("pack") {
+                continue;
+            }
+            let size = file_size(&entry)?;
+            if path.with_extension("idx").is_file() {
+                stats.pack_count += 1;
+                stats.pack_size_bytes += size;
+                stats.pack_in_pack_bytes += size.saturating_sub(header_and_trailer_len);
+            } else {
+                stats.garbage_count += 1;
+                stats.garbage_size_bytes += size;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_hex(name: &str) -> bool {
+    name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Read all entries in `dir`, treating a missing directory as empty.
+fn read_dir(dir: &Path) -> Result<Vec<std::fs::DirEntry>, Error> {
+    let into_error = |source| Error::Io {
+        source,
+        path: dir.to_owned(),
+    };
+    match std::fs::read_dir(dir) {
+        Ok(entries) => entries.collect::<Result<_, _>>().map_err(into_error),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(into_error(err)),
+    }
+}
+
+fn file_size(entry: &std::fs::DirEntry) -> Result<u64, Error> {
+    entry.metadata().map(|m| m.len()).map_err(|source| Error::Io {
+        source,
+        path: entry.path(),
+    })
+}
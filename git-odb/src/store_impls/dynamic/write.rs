@@ -30,8 +30,11 @@ where
 {
     type Error = Error;
 
+    /// Write the object to the loose object database of the objects directory this store was opened with,
+    /// which is never one of its alternates as these are treated as read-only.
     fn write_stream(&self, kind: Kind, size: u64, from: impl Read) -> Result<ObjectId, Self::Error> {
         let mut snapshot = self.snapshot.borrow_mut();
+        // The first loose database is always our own objects directory, followed by the ones of alternates.
         Ok(match snapshot.loose_dbs.first() {
             Some(ldb) => ldb.write_stream(kind, size, from)?,
             None => {
@@ -57,7 +57,7 @@ impl loose::Store {
     /// Return an iterator over all objects contained in the database.
     ///
     /// The [`Id`][git_hash::ObjectId]s returned by the iterator can typically be used in the [`locate(…)`][loose::Store::try_find()] method.
-    /// _Note_ that the result is not sorted or stable, thus ordering can change between runs.
+    /// Objects are returned in ascending order of their id, no matter if the directory is traversed with one or more threads.
     ///
     /// # Notes
     ///
@@ -65,7 +65,7 @@ impl loose::Store {
     /// needed if iterators need to be implemented by hand in the absence of generators.
     pub fn iter(&self) -> loose::Iter {
         loose::Iter {
-            inner: fs::walkdir_new(&self.path)
+            inner: fs::walkdir_sorted_new(&self.path)
                 .min_depth(2)
                 .max_depth(3)
                 .follow_links(false)
@@ -53,6 +53,8 @@ pub mod find;
 ///
 pub mod iter;
 ///
+pub mod stream;
+///
 pub mod verify;
 
 /// The type for an iterator over `Result<git_hash::ObjectId, Error>)`
@@ -0,0 +1,143 @@
+use std::{
+    convert::TryFrom,
+    fs,
+    io::{self, BufReader, Read},
+};
+
+use git_features::zlib::{stream::inflate::ReadBoxed, Decompress};
+
+use crate::store_impls::loose::{find, hash_path, Store, HEADER_READ_UNCOMPRESSED_BYTES};
+
+/// Returned by [`Decoder::header()`]
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not decompress the object header")]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Decode(#[from] git_object::decode::LooseHeaderDecodeError),
+}
+
+/// Decompresses a loose object on the fly, yielding only its content without the header when used as [`io::Read`].
+///
+/// This allows to process objects of any size without holding them in memory entirely.
+pub struct Decoder<R> {
+    inner: ReadBoxed<BufReader<R>>,
+    header: Option<(git_object::Kind, u64)>,
+    /// Decompressed bytes obtained while looking for the end of the header.
+    buf: Vec<u8>,
+    /// The first byte in `buf` that wasn't yet handed out.
+    buf_pos: usize,
+    /// The amount of content bytes left to be read.
+    remaining: u64,
+}
+
+impl<R> Decoder<R>
+where
+    R: io::Read,
+{
+    /// Create a new instance reading zlib compressed loose object data from `read`.
+    pub fn new(read: R) -> Self {
+        Decoder {
+            inner: ReadBoxed {
+                inner: BufReader::new(read),
+                decompressor: Box::new(Decompress::new(true)),
+            },
+            header: None,
+            buf: Vec::new(),
+            buf_pos: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Return the kind of the object along with the size of its content in bytes.
+    ///
+    /// The header is decompressed and decoded on first call, or on the first [read][io::Read::read()].
+    pub fn header(&mut self) -> Result<(git_object::Kind, u64), Error> {
+        if let Some(header) = self.header {
+            return Ok(header);
+        }
+        let mut chunk = [0u8; 32];
+        loop {
+            let bytes_read = self.inner.read(&mut chunk)?;
+            if bytes_read == 0 {
+                break;
+            }
+            let search_start = self.buf.len();
+            self.buf.extend_from_slice(&chunk[..bytes_read]);
+            if self.buf[search_start..].contains(&0) || self.buf.len() > HEADER_READ_UNCOMPRESSED_BYTES {
+                break;
+            }
+        }
+        let (kind, size, header_size) = git_object::decode::loose_header(&self.buf)?;
+        let header = (kind, size as u64);
+        self.buf_pos = header_size;
+        self.remaining = header.1;
+        self.header = Some(header);
+        Ok(header)
+    }
+
+    /// Return the reader we were created with.
+    pub fn into_inner(self) -> R {
+        self.inner.inner.into_inner()
+    }
+}
+
+impl<R> io::Read for Decoder<R>
+where
+    R: io::Read,
+{
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        self.header().map_err(|err| match err {
+            Error::Io(err) => err,
+            err @ Error::Decode(_) => io::Error::new(io::ErrorKind::InvalidData, err),
+        })?;
+        let max = usize::try_from(self.remaining).unwrap_or(usize::MAX).min(out.len());
+        if max == 0 {
+            return Ok(0);
+        }
+        let out = &mut out[..max];
+        let bytes_read = if self.buf_pos < self.buf.len() {
+            let pending = &self.buf[self.buf_pos..];
+            let bytes_read = pending.len().min(out.len());
+            out[..bytes_read].copy_from_slice(&pending[..bytes_read]);
+            self.buf_pos += bytes_read;
+            bytes_read
+        } else {
+            match self.inner.read(out)? {
+                0 => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!(
+                            "Loose object stream ended {} bytes before the size given in its header",
+                            self.remaining
+                        ),
+                    ))
+                }
+                bytes_read => bytes_read,
+            }
+        };
+        self.remaining -= bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+/// Object streaming
+impl Store {
+    /// Return a [`Decoder`] to stream the object with `id`, or `None` if there is no such object.
+    ///
+    /// Use this instead of [`try_find()`][Store::try_find()] if the object shouldn't be held in memory entirely.
+    pub fn try_stream(&self, id: impl AsRef<git_hash::oid>) -> Result<Option<Decoder<fs::File>>, find::Error> {
+        debug_assert_eq!(self.object_hash, id.as_ref().kind());
+        let path = hash_path(id.as_ref(), self.path.clone());
+        match fs::File::open(&path) {
+            Ok(file) => Ok(Some(Decoder::new(file))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(find::Error::Io {
+                source: err,
+                action: "open",
+                path,
+            }),
+        }
+    }
+}
@@ -3,9 +3,55 @@ use std::{
     time::Instant,
 };
 
-use git_features::progress::Progress;
+use git_features::{
+    progress::Progress,
+    zlib::{Decompress, FlushDecompress, Status},
+};
 
-use crate::{loose::Store, Write};
+use crate::{
+    loose::{hash_path, Store},
+    Write,
+};
+
+///
+pub mod object {
+    use std::path::PathBuf;
+
+    /// The error returned by [`verify_object()`][super::Store::verify_object()], classifying how a loose object is corrupted.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read loose object at '{path}'")]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error("Loose object at '{path}' doesn't start with a zlib header")]
+        BadMagic { path: PathBuf },
+        #[error("Loose object at '{path}' is not a valid zlib stream")]
+        Inflate {
+            source: git_features::zlib::inflate::Error,
+            path: PathBuf,
+        },
+        #[error("The zlib stream of the loose object at '{path}' ended prematurely after {decompressed_bytes} decompressed bytes")]
+        TruncatedStream { path: PathBuf, decompressed_bytes: usize },
+        #[error("The header of the loose object at '{path}' could not be decoded")]
+        Header {
+            source: git_object::decode::LooseHeaderDecodeError,
+            path: PathBuf,
+        },
+        #[error("{kind} object {id} claims to have {expected} bytes in its header, but has {actual} bytes")]
+        SizeMismatch {
+            kind: git_object::Kind,
+            id: git_hash::ObjectId,
+            expected: usize,
+            actual: usize,
+        },
+        #[error("{kind} object stored under {expected} actually hashes to {actual}")]
+        HashMismatch {
+            kind: git_object::Kind,
+            expected: git_hash::ObjectId,
+            actual: git_hash::ObjectId,
+        },
+    }
+}
 
 ///
 pub mod integrity {
@@ -41,6 +87,74 @@ pub mod integrity {
 }
 
 impl Store {
+    /// Fully decompress and hash the loose object `id`, returning an error describing the kind of corruption if it isn't intact.
+    ///
+    /// Unlike [`try_find()`][Store::try_find()], the whole file is validated, which makes this suitable for `fsck`-like
+    /// reports. In case of a [hash mismatch][object::Error::HashMismatch] the id the object actually has is returned as well.
+    pub fn verify_object(&self, id: impl AsRef<git_hash::oid>) -> Result<(), object::Error> {
+        let id = id.as_ref();
+        debug_assert_eq!(self.object_hash, id.kind());
+        let path = hash_path(id, self.path.clone());
+        let compressed = std::fs::read(&path).map_err(|err| object::Error::Io {
+            source: err,
+            path: path.clone(),
+        })?;
+        if !is_zlib_header(&compressed) {
+            return Err(object::Error::BadMagic { path });
+        }
+
+        let mut inflate = Decompress::new(true);
+        let mut data = Vec::with_capacity(compressed.len() * 2);
+        loop {
+            if data.len() == data.capacity() {
+                data.reserve(data.capacity());
+            }
+            let consumed_in = inflate.total_in() as usize;
+            let produced_out = inflate.total_out();
+            let status = inflate
+                .decompress_vec(&compressed[consumed_in..], &mut data, FlushDecompress::None)
+                .map_err(|err| object::Error::Inflate {
+                    source: err.into(),
+                    path: path.clone(),
+                })?;
+            match status {
+                Status::StreamEnd => break,
+                Status::Ok | Status::BufError
+                    if inflate.total_in() as usize == compressed.len() && inflate.total_out() == produced_out =>
+                {
+                    return Err(object::Error::TruncatedStream {
+                        path,
+                        decompressed_bytes: data.len(),
+                    })
+                }
+                Status::Ok | Status::BufError => continue,
+            }
+        }
+
+        let (kind, size, header_size) =
+            git_object::decode::loose_header(&data).map_err(|err| object::Error::Header { source: err, path })?;
+        let content = &data[header_size..];
+        if content.len() != size {
+            return Err(object::Error::SizeMismatch {
+                kind,
+                id: id.to_owned(),
+                expected: size,
+                actual: content.len(),
+            });
+        }
+        let actual = crate::sink(self.object_hash)
+            .write_buf(kind, content)
+            .expect("sink never fails");
+        if actual != id {
+            return Err(object::Error::HashMismatch {
+                kind,
+                expected: id.to_owned(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+
     /// Check all loose objects for their integrity checking their hash matches the actual data and by decoding them fully.
     pub fn verify_integrity(
         &self,
@@ -67,11 +181,13 @@ impl Store {
                     expected: id,
                 });
             }
-            object.decode().map_err(|err| integrity::Error::ObjectDecode {
-                source: err,
-                kind: object.kind,
-                id,
-            })?;
+            object
+                .decode_with_hash(id.kind())
+                .map_err(|err| integrity::Error::ObjectDecode {
+                    source: err,
+                    kind: object.kind,
+                    id,
+                })?;
 
             progress.inc();
             num_objects += 1;
@@ -84,3 +200,11 @@ impl Store {
         Ok(integrity::Statistics { num_objects })
     }
 }
+
+/// Return `true` if `data` starts with a zlib header using the deflate method, as described in RFC 1950.
+fn is_zlib_header(data: &[u8]) -> bool {
+    match data {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
@@ -41,7 +41,11 @@ impl crate::traits::Write for Store {
             message: "stream all data into tempfile in",
             path: self.path.to_owned(),
         })?;
-        to.flush()?;
+        to.flush().map_err(|err| Error::Io {
+            source: err,
+            message: "flush tempfile in",
+            path: self.path.to_owned(),
+        })?;
         self.finalize_object(to)
     }
 
@@ -62,12 +66,18 @@ impl crate::traits::Write for Store {
             message: "stream all data into tempfile in",
             path: self.path.to_owned(),
         })?;
-        to.flush()?;
+        to.flush().map_err(|err| Error::Io {
+            source: err,
+            message: "flush tempfile in",
+            path: self.path.to_owned(),
+        })?;
         self.finalize_object(to)
     }
 
-    /// Write the given stream in `from` to disk with at least one syscall.
+    /// Write exactly `size` bytes of the given stream in `from` to disk, hashing and compressing it in chunks
+    /// of [`STREAM_BUF_SIZE`] bytes so that objects of any size can be written without holding them in memory.
     ///
+    /// It's an error if `from` yields less than `size` bytes, while additional bytes are left unread.
     /// This will cost at least 4 IO operations.
     fn write_stream(
         &self,
@@ -86,16 +96,32 @@ impl crate::traits::Write for Store {
             path: self.path.to_owned(),
         })?;
 
-        io::copy(&mut from, &mut to).map_err(|err| Error::Io {
+        let mut buf = vec![0; STREAM_BUF_SIZE];
+        let mut remaining = size;
+        while remaining != 0 {
+            let chunk = &mut buf[..remaining.min(STREAM_BUF_SIZE as u64) as usize];
+            from.read_exact(chunk)
+                .and_then(|_| to.write_all(chunk))
+                .map_err(|err| Error::Io {
+                    source: err,
+                    message: "stream all data into tempfile in",
+                    path: self.path.to_owned(),
+                })?;
+            remaining -= chunk.len() as u64;
+        }
+        to.flush().map_err(|err| Error::Io {
             source: err,
-            message: "stream all data into tempfile in",
+            message: "flush tempfile in",
             path: self.path.to_owned(),
         })?;
-        to.flush()?;
         self.finalize_object(to)
     }
 }
 
+/// The amount of bytes to read, hash and compress at a time when [streaming objects][crate::Write::write_stream()]
+/// into a loose object database.
+pub const STREAM_BUF_SIZE: usize = 64 * 1024;
+
 type CompressedTempfile = deflate::Write<NamedTempFile>;
 
 impl Store {
@@ -122,7 +148,13 @@ impl Store {
         if let Err(err) = fs::create_dir(object_dir) {
             match err.kind() {
                 io::ErrorKind::AlreadyExists => {}
-                _ => return Err(err.into()),
+                _ => {
+                    return Err(Error::Io {
+                        source: err,
+                        message: "create directory",
+                        path: object_dir.to_owned(),
+                    })
+                }
             }
         }
         let file = file.into_inner();
@@ -185,7 +185,10 @@ mod ext {
                     .ok_or_else(|| find::existing_object::Error::NotFound {
                         oid: id.as_ref().to_owned(),
                     })
-                    .and_then(|o| o.decode().map_err(find::existing_object::Error::Decode))
+                    .and_then(|o| {
+                        o.decode_with_hash(id.kind())
+                            .map_err(find::existing_object::Error::Decode)
+                    })
                     .and_then(|o| match o {
                         $object_variant(o) => return Ok(o),
                         _other => Err(find::existing_object::Error::ObjectKind {
@@ -197,7 +200,7 @@ mod ext {
     }
 
     macro_rules! make_iter_lookup {
-        ($method:ident, $object_kind:path, $object_type:ty, $into_iter:tt) => {
+        ($method:ident, $object_kind:path, $object_type:ty, $into_iter:expr) => {
             /// Like [`find(…)`][Self::find()], but flattens the `Result<Option<_>>` into a single `Result` making a non-existing object an error
             /// while returning the desired iterator type.
             fn $method<'a>(
@@ -212,10 +215,9 @@ mod ext {
                         oid: id.as_ref().to_owned(),
                     })
                     .and_then(|o| {
-                        o.$into_iter()
-                            .ok_or_else(|| find::existing_iter::Error::ObjectKind {
-                                expected: $object_kind,
-                            })
+                        ($into_iter)(o, id).ok_or_else(|| find::existing_iter::Error::ObjectKind {
+                            expected: $object_kind,
+                        })
                     })
             }
         };
@@ -241,9 +243,24 @@ mod ext {
         make_obj_lookup!(find_tree, ObjectRef::Tree, Kind::Tree, TreeRef<'a>);
         make_obj_lookup!(find_tag, ObjectRef::Tag, Kind::Tag, TagRef<'a>);
         make_obj_lookup!(find_blob, ObjectRef::Blob, Kind::Blob, BlobRef<'a>);
-        make_iter_lookup!(find_commit_iter, Kind::Commit, CommitRefIter<'a>, try_into_commit_iter);
-        make_iter_lookup!(find_tree_iter, Kind::Tree, TreeRefIter<'a>, try_into_tree_iter);
-        make_iter_lookup!(find_tag_iter, Kind::Tag, TagRefIter<'a>, try_into_tag_iter);
+        make_iter_lookup!(
+            find_commit_iter,
+            Kind::Commit,
+            CommitRefIter<'a>,
+            |o: git_object::Data<'a>, _id: &git_hash::oid| o.try_into_commit_iter()
+        );
+        make_iter_lookup!(
+            find_tree_iter,
+            Kind::Tree,
+            TreeRefIter<'a>,
+            |o: git_object::Data<'a>, id: &git_hash::oid| o.try_into_tree_iter_with_hash(id.kind())
+        );
+        make_iter_lookup!(
+            find_tag_iter,
+            Kind::Tag,
+            TagRefIter<'a>,
+            |o: git_object::Data<'a>, _id: &git_hash::oid| o.try_into_tag_iter()
+        );
     }
 
     impl<T: super::Find> FindExt for T {}
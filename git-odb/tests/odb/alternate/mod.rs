@@ -100,3 +100,91 @@ fn no_alternate_in_first_objects_dir() -> crate::Result {
     assert!(alternate::resolve(tmp.path())?.is_empty());
     Ok(())
 }
+
+mod store {
+    use git_odb::{store::find::Source, Write};
+
+    use crate::{fixture_path, hex_to_id};
+
+    #[test]
+    fn writes_go_to_the_objects_dir_while_reads_come_from_alternates() -> crate::Result {
+        let tmp = git_testtools::tempfile::TempDir::new()?;
+        let (objects_dir, alternate_dir) =
+            super::alternate(tmp.path().join("objects"), fixture_path("objects").canonicalize()?)?;
+        let db = git_odb::at(&objects_dir)?;
+
+        let mut buf = Vec::new();
+        let (_, source) = db
+            .try_find_with_source(hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980"), &mut buf)?
+            .expect("present");
+        assert_eq!(
+            source,
+            Source::LooseAlternate(0),
+            "loose objects are found in the alternate"
+        );
+
+        let (_, source) = db
+            .try_find_with_source(hex_to_id("501b297447a8255d3533c6858bb692575cdefaa0"), &mut buf)?
+            .expect("present");
+        assert!(
+            matches!(source, Source::Packed(_)),
+            "packed objects are found in the alternate too"
+        );
+
+        let id = db.write_buf(git_object::Kind::Blob, b"new content")?;
+        let hex = id.to_hex().to_string();
+        let loose_path = |dir: &std::path::Path| dir.join(&hex[..2]).join(&hex[2..]);
+        assert!(
+            loose_path(&objects_dir).is_file(),
+            "new objects are written into our own objects dir"
+        );
+        assert!(!loose_path(&alternate_dir).exists(), "alternates are never written to");
+
+        let (obj, source) = db.try_find_with_source(id, &mut buf)?.expect("just written");
+        assert_eq!(obj.data, b"new content");
+        assert_eq!(source, Source::LoosePrimary);
+
+        assert!(
+            db.try_find_with_source(hex_to_id("0000000000000000000000000000000000000001"), &mut buf)?
+                .is_none(),
+            "missing objects are not found anywhere"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn writable_objects_dir_can_be_required_at_open_time() -> crate::Result {
+        let tmp = git_testtools::tempfile::TempDir::new()?;
+        let options = git_odb::store::init::Options {
+            require_writable_objects_dir: true,
+            ..Default::default()
+        };
+        git_odb::at_opts(tmp.path(), None, options)?;
+        assert_eq!(
+            std::fs::read_dir(tmp.path())?.count(),
+            0,
+            "the probe doesn't leave anything behind"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let read_only = tmp.path().join("read-only");
+            std::fs::create_dir(&read_only)?;
+            std::fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o555))?;
+            if std::fs::write(read_only.join("probe"), b"").is_ok() {
+                // Privileged users can write anyway, so there is nothing to detect.
+                return Ok(());
+            }
+            match git_odb::at_opts(&read_only, None, options) {
+                Err(err) => assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied),
+                Ok(_) => unreachable!("cannot write into the objects dir"),
+            }
+            assert!(
+                git_odb::at(&read_only).is_ok(),
+                "by default, read-only objects directories can be opened"
+            );
+        }
+        Ok(())
+    }
+}
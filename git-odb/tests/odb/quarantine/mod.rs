@@ -0,0 +1,104 @@
+use git_odb::{Find, Quarantine, Write};
+
+use crate::fixture_path;
+
+const PACK: &str = "objects/pack/pack-a2bf8e71d8c18879e499335762dd95119d93d9f1";
+
+fn objects_dir_with_blob() -> crate::Result<(tempfile::TempDir, git_hash::ObjectId)> {
+    let dir = tempfile::tempdir()?;
+    let existing = git_odb::at(dir.path())?.write_buf(git_object::Kind::Blob, b"existing")?;
+    Ok((dir, existing))
+}
+
+fn quarantine_pack(quarantine: &Quarantine) -> crate::Result<git_hash::ObjectId> {
+    let pack_dir = quarantine.path().join("pack");
+    std::fs::create_dir(&pack_dir)?;
+    for ext in ["pack", "idx"] {
+        let file = fixture_path(format!("{}.{}", PACK, ext));
+        std::fs::copy(&file, pack_dir.join(file.file_name().expect("file name")))?;
+    }
+    let index = git_odb::pack::index::File::at(fixture_path(format!("{}.idx", PACK)), git_hash::Kind::Sha1)?;
+    Ok(index.oid_at_index(0).to_owned())
+}
+
+#[test]
+fn quarantined_objects_are_only_visible_after_promotion() -> crate::Result {
+    let (dir, existing) = objects_dir_with_blob()?;
+    let quarantine = Quarantine::new(dir.path())?;
+    assert!(
+        quarantine.path().starts_with(dir.path()),
+        "quarantines live in the object directory"
+    );
+    assert_eq!(quarantine.objects_dir(), dir.path());
+
+    let quarantined = git_odb::at(quarantine.path())?;
+    assert!(
+        quarantined.contains(existing),
+        "reads fall through to the object database"
+    );
+    let new = quarantined.write_buf(git_object::Kind::Blob, b"new")?;
+    let packed = quarantine_pack(&quarantine)?;
+    assert!(quarantined.contains(new));
+    assert!(quarantined.contains(packed));
+
+    let main = git_odb::at(dir.path())?;
+    assert!(!main.contains(new), "writes go into the quarantine");
+    assert!(!main.contains(packed));
+
+    let quarantine_path = quarantine.path().to_owned();
+    quarantine.promote()?;
+    assert!(!quarantine_path.exists(), "the quarantine is removed after promotion");
+
+    let main = git_odb::at(dir.path())?;
+    for id in [existing, new, packed] {
+        assert!(main.contains(id));
+    }
+    assert_eq!(
+        std::fs::read_dir(dir.path().join("pack"))?.count(),
+        2,
+        "pack and index are moved"
+    );
+    Ok(())
+}
+
+#[test]
+fn promotion_keeps_existing_objects() -> crate::Result {
+    let (dir, existing) = objects_dir_with_blob()?;
+    let quarantine = Quarantine::new(dir.path())?;
+    let loose = git_odb::loose::Store::at(quarantine.path(), git_hash::Kind::Sha1);
+    assert_eq!(
+        loose.write_buf(git_object::Kind::Blob, b"existing")?,
+        existing,
+        "the same object can be written into the quarantine"
+    );
+    quarantine.promote()?;
+    assert!(git_odb::at(dir.path())?.contains(existing));
+    Ok(())
+}
+
+#[test]
+fn discarding_deletes_all_quarantined_objects() -> crate::Result {
+    let (dir, existing) = objects_dir_with_blob()?;
+    for discard in [true, false] {
+        let quarantine = Quarantine::new(dir.path())?;
+        let quarantine_path = quarantine.path().to_owned();
+        let new = git_odb::at(quarantine.path())?.write_buf(git_object::Kind::Blob, b"new")?;
+        quarantine_pack(&quarantine)?;
+        if discard {
+            quarantine.discard()?;
+        } else {
+            drop(quarantine);
+        }
+        assert!(!quarantine_path.exists(), "dropping a quarantine discards it, too");
+
+        let main = git_odb::at(dir.path())?;
+        assert!(main.contains(existing));
+        assert!(!main.contains(new));
+    }
+    assert_eq!(
+        std::fs::read_dir(dir.path())?.count(),
+        1,
+        "only the fan-out directory of the existing object remains"
+    );
+    Ok(())
+}
@@ -193,6 +193,80 @@ fn multi_index_keep_open() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn multi_index_is_picked_up_after_repack() -> crate::Result {
+    let dir = git_testtools::scripted_fixture_repo_writable("make_repo_multi_index.sh")?;
+    let git = |args: &[&str]| -> crate::Result<Vec<u8>> {
+        let output = Command::new("git")
+            .current_dir(dir.path())
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "author")
+            .env("GIT_AUTHOR_EMAIL", "author@example.com")
+            .env("GIT_COMMITTER_NAME", "committer")
+            .env("GIT_COMMITTER_EMAIL", "committer@example.com")
+            .output()?;
+        assert!(output.status.success(), "git {:?} should succeed", args);
+        Ok(output.stdout)
+    };
+    let mut handle = git_odb::at(dir.path().join(".git/objects"))?;
+    handle.prevent_pack_unload();
+    let ids = handle.iter()?.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(ids.len(), 1732);
+
+    git(&["commit", "-q", "--allow-empty", "-m", "written before repacking"])?;
+    let new_id = ObjectId::from_hex(&git(&["rev-parse", "HEAD"])?[..40])?;
+    let mut buf = Vec::new();
+    assert!(
+        git_pack::Find::location_by_oid(&handle, new_id, &mut buf).is_none(),
+        "the new commit is loose"
+    );
+
+    git(&["repack", "-a", "-d", "-q"])?;
+    git(&["multi-pack-index", "write"])?;
+    assert!(dir.path().join(".git/objects/pack/multi-pack-index").is_file());
+
+    assert!(
+        git_pack::Find::location_by_oid(&handle, new_id, &mut buf).is_some(),
+        "the store refreshes automatically and finds the previously loose object in the new multi-pack index"
+    );
+    for id in ids {
+        assert!(handle.find(id, &mut buf).is_ok(), "{} can still be found", id);
+    }
+    assert_eq!(
+        handle.store_ref().metrics().known_packs,
+        1,
+        "all objects are in a single pack now"
+    );
+    Ok(())
+}
+
+#[test]
+fn object_size_stats() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    git_testtools::copy_recursively_into_existing_dir(fixture_path("objects"), dir.path())?;
+    let pack_dir = dir.path().join("pack");
+    std::fs::copy(
+        pack_dir.join("pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.pack"),
+        pack_dir.join("pack-0000000000000000000000000000000000000000.pack"),
+    )?;
+
+    let handle = git_odb::at(dir.path())?;
+    assert_eq!(
+        handle.store_ref().object_size_stats()?,
+        git_odb::store::stats::ObjectSizeStats {
+            loose_count: 7,
+            loose_size_bytes: 55341,
+            pack_count: 3,
+            pack_in_pack_bytes: 104720 - 3 * (12 + 20),
+            pack_size_bytes: 51875 + 3732 + 49113,
+            garbage_count: 1,
+            garbage_size_bytes: 3732,
+        },
+        "packs without index are garbage"
+    );
+    Ok(())
+}
+
 #[test]
 fn write() -> crate::Result {
     let dir = tempfile::tempdir()?;
@@ -687,6 +761,39 @@ fn iterate_over_a_bunch_of_loose_and_packed_objects() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn filter_known_matches_individual_lookups() -> crate::Result {
+    let (db, _tmp) = db_with_all_object_sources()?;
+    let mut ids = db.iter()?.collect::<Result<Vec<_>, _>>()?;
+    ids.extend(
+        [
+            "0000000000000000000000000000000000000000",
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+            "ffffffffffffffffffffffffffffffffffffffff",
+            "37d4e6c5c48ba0d245164c4e10d5f41140cab981",
+        ]
+        .iter()
+        .map(|hex| hex_to_id(hex)),
+    );
+    ids.extend(ids.clone().iter().map(|id| {
+        let mut unknown = id.to_owned();
+        unknown.as_mut_slice()[19] ^= 0xff;
+        unknown
+    }));
+    ids.sort();
+    ids.dedup();
+
+    let expected: Vec<_> = ids.iter().map(|id| db.contains(id)).collect();
+    assert_eq!(db.filter_known(&ids)?, expected);
+    assert!(expected.iter().any(|known| *known) && expected.iter().any(|known| !known));
+
+    for sparse_ids in [&ids[..1], &ids[ids.len() / 2..ids.len() / 2 + 3], &[]] {
+        let expected: Vec<_> = sparse_ids.iter().map(|id| db.contains(id)).collect();
+        assert_eq!(db.filter_known(sparse_ids)?, expected);
+    }
+    Ok(())
+}
+
 #[test]
 fn auto_refresh_with_and_without_id_stability() -> crate::Result {
     let tmp = git_testtools::tempfile::TempDir::new()?;
@@ -26,9 +26,8 @@ pub fn object_ids() -> Vec<git_hash::ObjectId> {
 
 #[test]
 fn iter() {
-    let mut oids = ldb().iter().map(Result::unwrap).collect::<Vec<_>>();
-    oids.sort();
-    assert_eq!(oids, object_ids());
+    let oids = ldb().iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(oids, object_ids(), "objects are returned in order of their id");
 }
 pub fn locate_oid(id: git_hash::ObjectId, buf: &mut Vec<u8>) -> git_object::Data<'_> {
     ldb().try_find(id, buf).expect("read success").expect("id present")
@@ -42,6 +41,7 @@ fn verify_integrity() {
 }
 
 mod write {
+    use git_object::bstr::ByteSlice;
     use git_odb::{loose, Write};
 
     use crate::store::loose::{locate_oid, object_ids};
@@ -70,6 +70,116 @@ mod write {
         }
         Ok(())
     }
+
+    #[test]
+    fn read_and_write_sha256() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), git_hash::Kind::Sha256);
+        assert_eq!(db.object_hash(), git_hash::Kind::Sha256);
+
+        let id = db.write_buf(git_object::Kind::Blob, b"hello world")?;
+        assert_eq!(
+            id,
+            git_hash::ObjectId::from_hex(b"fee53a18d32820613c0527aa79be5cb30173c823a9b448fa4817767cc84c6f03")?,
+            "the same id as produced by `git hash-object` in a sha256 repository"
+        );
+        assert!(
+            dir.path()
+                .join("fe")
+                .join("e53a18d32820613c0527aa79be5cb30173c823a9b448fa4817767cc84c6f03")
+                .is_file(),
+            "objects are stored by their full hex id"
+        );
+
+        let mut buf = Vec::new();
+        let obj = db.try_find(id, &mut buf)?.expect("id present");
+        assert_eq!(obj.kind, git_object::Kind::Blob);
+        assert_eq!(obj.data, b"hello world");
+        assert_eq!(db.iter().map(Result::unwrap).collect::<Vec<_>>(), vec![id]);
+        Ok(())
+    }
+
+    /// A reader producing `remaining` bytes of a repeating pattern without ever holding all of them.
+    struct Pattern {
+        pos: u64,
+        remaining: u64,
+    }
+
+    impl Pattern {
+        fn new(len: u64) -> Self {
+            Pattern { pos: 0, remaining: len }
+        }
+    }
+
+    impl std::io::Read for Pattern {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = (buf.len() as u64).min(self.remaining) as usize;
+            for byte in &mut buf[..len] {
+                *byte = (self.pos % 251) as u8;
+                self.pos += 1;
+            }
+            self.remaining -= len as u64;
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn write_stream_hashes_and_compresses_in_chunks() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), git_hash::Kind::Sha1);
+        let size = 16 * loose::write::STREAM_BUF_SIZE as u64 + 7;
+
+        let id = db.write_stream(git_object::Kind::Blob, size, Pattern::new(size))?;
+
+        let mut git = std::process::Command::new("git")
+            .args(["hash-object", "--stdin"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+        std::io::copy(&mut Pattern::new(size), git.stdin.as_mut().expect("piped"))?;
+        drop(git.stdin.take());
+        let output = git.wait_with_output()?;
+        assert!(output.status.success());
+        assert_eq!(
+            id,
+            git_hash::ObjectId::from_hex(output.stdout.trim())?,
+            "the same id as produced by `git hash-object`"
+        );
+
+        let mut buf = Vec::new();
+        let obj = db.try_find(id, &mut buf)?.expect("id present");
+        assert_eq!(obj.kind, git_object::Kind::Blob);
+        assert_eq!(obj.data.len() as u64, size);
+        assert!(
+            obj.data
+                .iter()
+                .enumerate()
+                .all(|(pos, byte)| *byte == (pos % 251) as u8),
+            "the content is stored as streamed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_stream_fails_if_the_stream_is_shorter_than_its_size() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), git_hash::Kind::Sha1);
+        let size = loose::write::STREAM_BUF_SIZE as u64 + 1;
+
+        match db.write_stream(git_object::Kind::Blob, size, Pattern::new(size - 1)) {
+            Err(loose::write::Error::Io { source, .. }) => {
+                assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof)
+            }
+            res => unreachable!("unexpected result: {:?}", res),
+        }
+        assert_eq!(db.iter().count(), 0, "nothing was written");
+        assert_eq!(
+            std::fs::read_dir(dir.path())?.count(),
+            0,
+            "the temporary file was removed"
+        );
+        Ok(())
+    }
 }
 
 mod contains {
@@ -84,6 +194,215 @@ mod contains {
     }
 }
 
+mod verify_object {
+    use std::io::Write;
+
+    use git_features::zlib::stream::deflate;
+    use git_odb::loose::{verify::object::Error, Store};
+    use git_testtools::{fixture_path, hex_to_id, tempfile::TempDir};
+
+    use crate::store::loose::{ldb, object_ids};
+
+    const BLOB: &str = "37d4e6c5c48ba0d245164c4e10d5f41140cab980";
+
+    fn writable_store() -> crate::Result<(Store, TempDir)> {
+        let objects_dir = git_testtools::tempfile::tempdir()?;
+        git_testtools::copy_recursively_into_existing_dir(fixture_path("objects"), &objects_dir)?;
+        Ok((Store::at(objects_dir.path(), git_hash::Kind::Sha1), objects_dir))
+    }
+
+    fn object_path(store: &Store, hex: &str) -> std::path::PathBuf {
+        store.path().join(&hex[..2]).join(&hex[2..])
+    }
+
+    fn compress(data: &[u8]) -> crate::Result<Vec<u8>> {
+        let mut compressed = deflate::Write::new(Vec::new());
+        compressed.write_all(data)?;
+        compressed.flush()?;
+        Ok(compressed.into_inner())
+    }
+
+    #[test]
+    fn intact_objects_pass() {
+        let store = ldb();
+        for id in object_ids() {
+            store.verify_object(id).unwrap();
+        }
+    }
+
+    #[test]
+    fn missing_objects_are_an_io_error() {
+        let err = ldb()
+            .verify_object(hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab989"))
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound),
+            "the caller can tell missing objects apart"
+        );
+    }
+
+    #[test]
+    fn data_without_zlib_header_has_bad_magic() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        std::fs::write(object_path(&store, BLOB), b"blob 3\0abc")?;
+        assert!(matches!(
+            store.verify_object(hex_to_id(BLOB)),
+            Err(Error::BadMagic { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_deflate_data_fails_to_inflate() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        std::fs::write(object_path(&store, BLOB), b"\x78\x9c\xff\xff\xff\xff\xff\xff")?;
+        assert!(matches!(
+            store.verify_object(hex_to_id(BLOB)),
+            Err(Error::Inflate { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_streams_are_detected() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        let path = object_path(&store, BLOB);
+        let mut data = std::fs::read(&path)?;
+        data.truncate(data.len() - 6);
+        std::fs::write(&path, data)?;
+        assert!(matches!(
+            store.verify_object(hex_to_id(BLOB)),
+            Err(Error::TruncatedStream { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn undecodable_headers_are_detected() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        std::fs::write(object_path(&store, BLOB), compress(b"bogus 3\0abc")?)?;
+        assert!(matches!(
+            store.verify_object(hex_to_id(BLOB)),
+            Err(Error::Header { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn size_in_header_must_match_the_content() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        std::fs::write(object_path(&store, BLOB), compress(b"blob 10\0abc")?)?;
+        match store.verify_object(hex_to_id(BLOB)) {
+            Err(Error::SizeMismatch {
+                kind,
+                id,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(kind, git_object::Kind::Blob);
+                assert_eq!(id, hex_to_id(BLOB));
+                assert_eq!((expected, actual), (10, 3));
+            }
+            res => unreachable!("expected size mismatch, got {:?}", res),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn objects_stored_under_the_wrong_id_report_their_actual_id() -> crate::Result {
+        let (store, _tmp) = writable_store()?;
+        let other = "595dfd62fc1ad283d61bb47a24e7a1f66398f84d";
+        std::fs::copy(object_path(&store, other), object_path(&store, BLOB))?;
+        match store.verify_object(hex_to_id(BLOB)) {
+            Err(Error::HashMismatch { kind, expected, actual }) => {
+                assert_eq!(kind, git_object::Kind::Blob);
+                assert_eq!(expected, hex_to_id(BLOB));
+                assert_eq!(actual, hex_to_id(other));
+            }
+            res => unreachable!("expected hash mismatch, got {:?}", res),
+        }
+        Ok(())
+    }
+}
+
+mod stream {
+    use std::io::{Read, Write};
+
+    use git_features::zlib::stream::deflate;
+    use git_odb::loose::stream::Decoder;
+    use git_testtools::hex_to_id;
+
+    use crate::store::loose::{ldb, locate_oid, object_ids};
+
+    #[test]
+    fn all_objects_stream_the_same_content_as_if_they_were_found() -> crate::Result {
+        for id in object_ids() {
+            let mut buf = Vec::new();
+            let expected = locate_oid(id, &mut buf);
+            let mut stream = ldb().try_stream(id)?.expect("object exists");
+            assert_eq!(stream.header()?, (expected.kind, expected.data.len() as u64));
+
+            let mut actual = Vec::new();
+            let mut chunk = [0u8; 7];
+            loop {
+                match stream.read(&mut chunk)? {
+                    0 => break,
+                    bytes_read => actual.extend_from_slice(&chunk[..bytes_read]),
+                }
+            }
+            assert_eq!(actual, expected.data, "small reads yield all content, without header");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn the_header_is_decoded_when_reading_content_right_away() -> crate::Result {
+        let mut stream = ldb()
+            .try_stream(hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980"))?
+            .expect("present");
+        let mut content = Vec::new();
+        stream.read_to_end(&mut content)?;
+        assert_eq!(content, b"hi there\n");
+        assert_eq!(stream.header()?, (git_object::Kind::Blob, 9));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_objects_yield_none() -> crate::Result {
+        assert!(ldb()
+            .try_stream(hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab989"))?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn content_longer_than_its_header_claims_is_truncated() -> crate::Result {
+        let mut compressed = deflate::Write::new(Vec::new());
+        compressed.write_all(b"blob 3\0abcdef")?;
+        compressed.flush()?;
+        let mut content = Vec::new();
+        let compressed = compressed.into_inner();
+        let mut stream = Decoder::new(compressed.as_slice());
+        stream.read_to_end(&mut content)?;
+        assert_eq!(content, b"abc");
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_content_is_an_error() -> crate::Result {
+        let mut compressed = deflate::Write::new(Vec::new());
+        compressed.write_all(b"blob 10\0abc")?;
+        compressed.flush()?;
+        let compressed = compressed.into_inner();
+        let mut content = Vec::new();
+        let err = Decoder::new(compressed.as_slice())
+            .read_to_end(&mut content)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        Ok(())
+    }
+}
+
 mod lookup_prefix {
     use std::collections::HashSet;
 
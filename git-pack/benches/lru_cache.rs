@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use git_pack::cache::{lru, DecodeEntry};
+
+/// Objects of varying sizes whose total size exceeds `MEM_LIMIT`, so the caches have to evict entries continuously.
+const NUM_OBJECTS: u64 = 1024;
+const MEM_LIMIT: usize = 1024 * 1024;
+
+fn object_size(offset: u64) -> usize {
+    (offset as usize % 16 + 1) * 8 * 1024
+}
+
+fn put_and_get(cache: &mut impl DecodeEntry, data: &[u8], out: &mut Vec<u8>) {
+    for offset in 0..NUM_OBJECTS {
+        cache.get(0, offset.saturating_sub(8), out);
+        cache.put(0, offset, &data[..object_size(offset)], git_object::Kind::Blob, 1);
+    }
+}
+
+fn memory_limited_caches(c: &mut Criterion) {
+    let data = vec![0u8; object_size(15)];
+    let mut out = Vec::new();
+    c.bench_function("StaticLinkedList<64> evicting under memory pressure", |b| {
+        let mut cache = lru::StaticLinkedList::<64>::new(MEM_LIMIT);
+        b.iter(|| put_and_get(black_box(&mut cache), &data, &mut out))
+    });
+    c.bench_function("MemoryCappedHashmap evicting under memory pressure", |b| {
+        let mut cache = lru::MemoryCappedHashmap::new(MEM_LIMIT);
+        b.iter(|| put_and_get(black_box(&mut cache), &data, &mut out))
+    });
+}
+
+criterion_group!(benches, memory_limited_caches);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+use crate::bitmap::{ewah, reachable, PackBitmap};
+
+/// Reachability queries
+impl PackBitmap {
+    /// Return true if `target` is reachable from any of the given `commits`, without traversing the commit graph.
+    ///
+    /// Objects that aren't contained in the pack are never reachable, as bitmaps only exist for packs that contain
+    /// all objects reachable from their commits.
+    /// It's an error if one of the `commits` doesn't have a bitmap.
+    pub fn is_reachable_from_commits(
+        &self,
+        commits: &[git_hash::ObjectId],
+        target: &git_hash::oid,
+    ) -> Result<bool, reachable::Error> {
+        let bit = self.index.lookup(target).map(|entry_index| {
+            let offset = self.index.pack_offset_at_index(entry_index);
+            self.sorted_offsets
+                .binary_search(&offset)
+                .expect("all offsets of the index are present")
+        });
+        let mut is_reachable = false;
+        for commit in commits {
+            let entry = self
+                .entry_by_commit(commit)
+                .ok_or(reachable::Error::MissingBitmap { id: *commit })?;
+            if let Some(bit) = bit {
+                let words = self.bitmap_at(entry)?;
+                is_reachable |= words.get(bit / 64).map_or(false, |word| word & (1 << (bit % 64)) != 0);
+            }
+        }
+        Ok(is_reachable)
+    }
+
+    /// Return true if `commit` has a bitmap.
+    pub fn contains_commit(&self, commit: &git_hash::oid) -> bool {
+        self.entry_by_commit(commit).is_some()
+    }
+
+    fn entry_by_commit(&self, commit: &git_hash::oid) -> Option<usize> {
+        let index_position = self.index.lookup(commit)?;
+        self.entries_by_index_position
+            .binary_search_by_key(&index_position, |idx| self.entries[*idx].index_position)
+            .ok()
+            .map(|pos| self.entries_by_index_position[pos])
+    }
+
+    /// Decode the bitmap of the entry at `idx`, resolving all bitmaps it is XORed with.
+    fn bitmap_at(&self, mut idx: usize) -> Result<Vec<u64>, ewah::decode::Error> {
+        let mut out = Vec::new();
+        loop {
+            let entry = &self.entries[idx];
+            let words = ewah::decode(&self.data[entry.bitmap.clone()])?;
+            if out.len() < words.len() {
+                out.resize(words.len(), 0);
+            }
+            for (out, word) in out.iter_mut().zip(words) {
+                *out ^= word;
+            }
+            match entry.xor_offset {
+                0 => break,
+                xor_offset => idx -= usize::from(xor_offset),
+            }
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,62 @@
+//! Decoding of bitmaps compressed with the _Enhanced Word-Aligned Hybrid_ scheme, as used in `.bitmap` files.
+//!
+//! Compressed bitmaps are stored as the amount of bits, the amount of 64 bit words, the words themselves and the
+//! position of the last _run length word_, all as big-endian numbers.
+
+///
+pub mod decode {
+    /// Returned by [`decode()`][super::decode()] and [`size()`][super::size()].
+    #[derive(thiserror::Error, Debug)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The EWAH bitmap is truncated")]
+        Truncated,
+        #[error("A run length word announces {expected} literal words, but only {actual} are left")]
+        MissingLiterals { expected: usize, actual: usize },
+    }
+}
+
+const HEADER_LEN: usize = 4 /* bit size */ + 4 /* word count */;
+const TRAILER_LEN: usize = 4 /* position of last run length word */;
+const WORD_LEN: usize = std::mem::size_of::<u64>();
+
+/// Return the size in bytes of the compressed bitmap at the beginning of `data`.
+pub fn size(data: &[u8]) -> Result<usize, decode::Error> {
+    if data.len() < HEADER_LEN {
+        return Err(decode::Error::Truncated);
+    }
+    let num_words = crate::read_u32(&data[4..8]) as usize;
+    let size = HEADER_LEN + num_words * WORD_LEN + TRAILER_LEN;
+    if data.len() < size {
+        return Err(decode::Error::Truncated);
+    }
+    Ok(size)
+}
+
+/// Decode the compressed bitmap at the beginning of `data` into uncompressed words, with bit `n` being
+/// `words[n / 64] & (1 << (n % 64))`.
+///
+/// The returned words may be fewer than needed to hold all bits, in which case the missing ones are zero.
+pub fn decode(data: &[u8]) -> Result<Vec<u64>, decode::Error> {
+    let size = size(data)?;
+    let mut words = data[HEADER_LEN..size - TRAILER_LEN]
+        .chunks(WORD_LEN)
+        .map(crate::read_u64);
+
+    let mut out = Vec::with_capacity(words.len());
+    while let Some(rlw) = words.next() {
+        let running_bit = rlw & 1 == 1;
+        let running_len = ((rlw >> 1) & u64::from(u32::MAX)) as usize;
+        let num_literals = (rlw >> 33) as usize;
+
+        out.resize(out.len() + running_len, if running_bit { u64::MAX } else { 0 });
+        if words.len() < num_literals {
+            return Err(decode::Error::MissingLiterals {
+                expected: num_literals,
+                actual: words.len(),
+            });
+        }
+        out.extend(words.by_ref().take(num_literals));
+    }
+    Ok(out)
+}
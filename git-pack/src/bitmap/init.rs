@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use crate::{
+    bitmap::{ewah, Entry, PackBitmap},
+    index,
+};
+
+/// Returned by [`PackBitmap::at()`] and [`PackBitmap::from_index()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not open pack bitmap file at '{path}'")]
+    Io {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error(transparent)]
+    Index(#[from] index::init::Error),
+    #[error("{message}")]
+    Corrupt { message: String },
+    #[error("Unsupported bitmap version: {version}")]
+    UnsupportedVersion { version: u16 },
+    #[error("The bitmap file doesn't have bitmaps that include all reachable objects")]
+    NoFullDag,
+    #[error("The bitmap belongs to pack {actual}, but the index is for pack {expected}")]
+    PackMismatch {
+        expected: git_hash::ObjectId,
+        actual: git_hash::ObjectId,
+    },
+    #[error(transparent)]
+    Ewah(#[from] ewah::decode::Error),
+}
+
+const SIGNATURE: &[u8] = b"BITM";
+const VERSION: u16 = 1;
+/// Set if each bitmap includes all objects reachable from its commit, which is always the case nowadays.
+const FLAG_FULL_DAG: u16 = 0x1;
+/// The amount of bitmaps for each object type which precede the commit bitmaps.
+const NUM_TYPE_BITMAPS: usize = 4;
+const ENTRY_HEADER_LEN: usize = 4 /* index position */ + 1 /* xor offset */ + 1 /* flags */;
+
+/// Instantiation
+impl PackBitmap {
+    /// Open the pack index at `index_path` along with the `.bitmap` file next to it.
+    ///
+    /// The `object_hash` is the kind of hash used in the index and bitmap, as neither of them stores it.
+    pub fn at(index_path: impl AsRef<Path>, object_hash: git_hash::Kind) -> Result<Self, Error> {
+        Self::from_index(index::File::at(index_path, object_hash)?)
+    }
+
+    /// Open the `.bitmap` file next to the file of `index`, which is kept to map objects to bits.
+    pub fn from_index(index: index::File) -> Result<Self, Error> {
+        let path = index.path().with_extension("bitmap");
+        let data = crate::mmap::read_only(&path).map_err(|source| Error::Io {
+            source,
+            path: path.clone(),
+        })?;
+        let hash_len = index.object_hash().len_in_bytes();
+        let corrupt = |message: &str| Error::Corrupt {
+            message: message.into(),
+        };
+
+        let header_len = SIGNATURE.len() + 2 /* version */ + 2 /* flags */ + 4 /* entry count */ + hash_len;
+        if data.len() < header_len + hash_len {
+            return Err(corrupt("Pack bitmap file is truncated and too short"));
+        }
+        let (signature, rest) = data.split_at(SIGNATURE.len());
+        if signature != SIGNATURE {
+            return Err(corrupt("Invalid signature"));
+        }
+        let version = read_u16(&rest[..2]);
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion { version });
+        }
+        if read_u16(&rest[2..4]) & FLAG_FULL_DAG == 0 {
+            return Err(Error::NoFullDag);
+        }
+        let num_entries = crate::read_u32(&rest[4..8]) as usize;
+        let pack_checksum = git_hash::ObjectId::from(&rest[8..][..hash_len]);
+        if pack_checksum != index.pack_checksum() {
+            return Err(Error::PackMismatch {
+                expected: index.pack_checksum(),
+                actual: pack_checksum,
+            });
+        }
+
+        let mut ofs = header_len;
+        for _ in 0..NUM_TYPE_BITMAPS {
+            ofs += ewah::size(&data[ofs..])?;
+        }
+        let mut entries = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            let header = data
+                .get(ofs..ofs + ENTRY_HEADER_LEN)
+                .ok_or_else(|| corrupt("Pack bitmap entry is truncated"))?;
+            let index_position = crate::read_u32(&header[..4]);
+            let xor_offset = header[4];
+            if index_position >= index.num_objects() {
+                return Err(corrupt("Pack bitmap entry refers to an object that isn't in the index"));
+            }
+            if usize::from(xor_offset) > entries.len() {
+                return Err(corrupt("Pack bitmap entry refers to a bitmap before the first one"));
+            }
+            ofs += ENTRY_HEADER_LEN;
+            let size = ewah::size(&data[ofs..])?;
+            entries.push(Entry {
+                index_position,
+                xor_offset,
+                bitmap: ofs..ofs + size,
+            });
+            ofs += size;
+        }
+
+        let mut entries_by_index_position: Vec<_> = (0..entries.len()).collect();
+        entries_by_index_position.sort_by_key(|idx| entries[*idx].index_position);
+        let sorted_offsets = index.sorted_offsets();
+        Ok(PackBitmap {
+            data,
+            path,
+            index,
+            entries,
+            entries_by_index_position,
+            sorted_offsets,
+        })
+    }
+}
+
+fn read_u16(b: &[u8]) -> u16 {
+    u16::from_be_bytes([b[0], b[1]])
+}
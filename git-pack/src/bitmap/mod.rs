@@ -0,0 +1,69 @@
+//! Reachability bitmaps as stored in `.bitmap` files alongside packs, as written by `git repack -b`.
+//!
+//! Each bitmap belongs to a commit and has one bit set for each object in the pack that is reachable from it,
+//! with bits being assigned to objects in the order they appear in the pack.
+use std::ops::Range;
+
+use crate::index;
+
+///
+pub mod ewah;
+
+mod access;
+
+///
+pub mod init;
+
+/// The bitmap of a single commit as listed in a bitmap file.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+struct Entry {
+    /// The position of the commit in the pack index.
+    index_position: index::EntryIndex,
+    /// If not 0, the bitmap of this entry has to be XORed with the one of the entry this many entries before it.
+    xor_offset: u8,
+    /// The location of the EWAH compressed bitmap in the bitmap file.
+    bitmap: Range<usize>,
+}
+
+/// A reachability bitmap file for a single pack, along with the index of the pack it belongs to.
+pub struct PackBitmap {
+    data: memmap2::Mmap,
+    path: std::path::PathBuf,
+    index: index::File,
+    /// All bitmaps in order of appearance, which matters to resolve XOR offsets.
+    entries: Vec<Entry>,
+    /// The position of each entry in `entries` sorted by the index position of its commit, for lookups by commit.
+    entries_by_index_position: Vec<usize>,
+    /// The pack offsets of all objects in ascending order, which is the order in which bits are assigned.
+    sorted_offsets: Vec<crate::data::Offset>,
+}
+
+/// Basic file information
+impl PackBitmap {
+    /// The path of the opened bitmap file.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+    /// The index of the pack this bitmap belongs to.
+    pub fn index(&self) -> &index::File {
+        &self.index
+    }
+    /// The amount of commits with a bitmap.
+    pub fn num_bitmaps(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+///
+pub mod reachable {
+    /// Returned by [`PackBitmap::is_reachable_from_commits()`][super::PackBitmap::is_reachable_from_commits()].
+    #[derive(thiserror::Error, Debug)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Commit {id} doesn't have a bitmap and reachability can't be determined from it")]
+        MissingBitmap { id: git_hash::ObjectId },
+        #[error(transparent)]
+        Decode(#[from] crate::bitmap::ewah::decode::Error),
+    }
+}
+pub use reachable::Error;
@@ -10,7 +10,7 @@ use git_tempfile::{handle::Writable, AutoRemove, ContainingDirectory};
 use crate::data;
 
 mod error;
-use error::Error;
+pub use error::Error;
 
 mod types;
 use types::{LockWriter, PassThrough};
@@ -85,7 +85,7 @@ impl crate::Bundle {
                         writer: data_file.clone(),
                     },
                     pack_kind,
-                    git_hash::Kind::Sha1,
+                    object_hash,
                 );
                 (Box::new(pack_entries_iter), pack_kind)
             }
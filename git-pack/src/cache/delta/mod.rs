@@ -218,7 +218,7 @@ mod tests {
         );
         assert_eq!(
             std::mem::size_of::<[TreeItemOption<Entry>; 7_500_000]>(),
-            480_000_000,
+            540_000_000,
             "it should be as small as possible"
         );
     }
@@ -238,7 +238,7 @@ mod tests {
 
         assert_eq!(
             std::mem::size_of::<[TreeItem<EntryWithDefault>; 7_500_000]>(),
-            780_000_000
+            900_000_000
         );
     }
 }
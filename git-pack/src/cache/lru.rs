@@ -97,53 +97,108 @@ mod _static {
         data: Vec<u8>,
         kind: git_object::Kind,
         compressed_size: usize,
+        /// If true, `data` was released to make room for other entries, and this entry must not be returned anymore.
+        evicted: bool,
     }
 
     /// A cache using a least-recently-used implementation capable of storing the `SIZE` most recent objects.
     /// The cache must be small as the search is 'naive' and the underlying data structure is a linked list.
     /// Values of 64 seem to improve performance.
+    ///
+    /// The memory used for object data is limited as well, and the least recently used objects are evicted if storing another object would exceed it.
     pub struct StaticLinkedList<const SIZE: usize> {
         inner: uluru::LRUCache<Entry, SIZE>,
         free_list: Vec<Vec<u8>>,
         debug: git_features::cache::Debug,
+        /// The amount of bytes allocated for the data of all entries and the free list.
+        mem_used: usize,
+        /// The amount of bytes we may allocate at most.
+        mem_limit: usize,
     }
 
-    impl<const SIZE: usize> Default for StaticLinkedList<SIZE> {
-        fn default() -> Self {
+    impl<const SIZE: usize> StaticLinkedList<SIZE> {
+        /// Create a new instance which stores at most `SIZE` objects, using at most `mem_limit` bytes for their data.
+        pub fn new(mem_limit: usize) -> Self {
             StaticLinkedList {
                 inner: Default::default(),
                 free_list: Vec::new(),
                 debug: git_features::cache::Debug::new(format!("StaticLinkedList<{}>", SIZE)),
+                mem_used: 0,
+                mem_limit,
+            }
+        }
+    }
+
+    /// Limit the memory used for object data to 96MB, the default of `core.deltaBaseCacheLimit` in `git`.
+    impl<const SIZE: usize> Default for StaticLinkedList<SIZE> {
+        fn default() -> Self {
+            Self::new(96 * 1024 * 1024)
+        }
+    }
+
+    impl<const SIZE: usize> StaticLinkedList<SIZE> {
+        /// Release free buffers, and then the data of the least recently used entries, until `additional` bytes fit into our memory limit.
+        ///
+        /// Evicted entries remain in the list as they can't be removed individually, but as they are never touched again
+        /// they are the first to be replaced by new entries.
+        fn evict_until_fits(&mut self, additional: usize) {
+            for buf in self.free_list.drain(..) {
+                self.mem_used -= buf.capacity();
             }
+            let budget = self.mem_limit.saturating_sub(additional);
+            let (mut kept, mut freed, mut evicting) = (0, 0, false);
+            self.inner.lookup(|e: &mut Entry| -> Option<()> {
+                if !e.evicted {
+                    if !evicting && kept + e.data.capacity() <= budget {
+                        kept += e.data.capacity();
+                    } else {
+                        evicting = true;
+                        freed += e.data.capacity();
+                        e.data = Vec::new();
+                        e.evicted = true;
+                    }
+                }
+                None
+            });
+            self.mem_used -= freed;
         }
     }
 
     impl<const SIZE: usize> DecodeEntry for StaticLinkedList<SIZE> {
         fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: git_object::Kind, compressed_size: usize) {
+            if data.len() > self.mem_limit {
+                return;
+            }
             self.debug.put();
+            let mut buf = self.free_list.pop().unwrap_or_default();
+            self.mem_used -= buf.capacity();
+            buf.clear();
+            buf.reserve_exact(data.len());
+            buf.extend_from_slice(data);
+            if self.mem_used + buf.capacity() > self.mem_limit {
+                if buf.capacity() > self.mem_limit {
+                    buf = Vec::from(data);
+                }
+                self.evict_until_fits(buf.capacity());
+            }
+            self.mem_used += buf.capacity();
             if let Some(previous) = self.inner.insert(Entry {
                 offset,
                 pack_id,
-                data: self
-                    .free_list
-                    .pop()
-                    .map(|mut v| {
-                        v.clear();
-                        v.resize(data.len(), 0);
-                        v.copy_from_slice(data);
-                        v
-                    })
-                    .unwrap_or_else(|| Vec::from(data)),
+                data: buf,
                 kind,
                 compressed_size,
+                evicted: false,
             }) {
-                self.free_list.push(previous.data)
+                if !previous.evicted {
+                    self.free_list.push(previous.data)
+                }
             }
         }
 
         fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(git_object::Kind, usize)> {
             let res = self.inner.lookup(|e: &mut Entry| {
-                if e.pack_id == pack_id && e.offset == offset {
+                if !e.evicted && e.pack_id == pack_id && e.offset == offset {
                     out.resize(e.data.len(), 0);
                     out.copy_from_slice(&e.data);
                     Some((e.kind, e.compressed_size))
@@ -159,6 +214,53 @@ mod _static {
             res
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn memory_limit_is_respected() {
+            let mut cache = StaticLinkedList::<4>::new(10);
+            let mut out = Vec::new();
+
+            cache.put(0, 0, b"too large to cache", git_object::Kind::Blob, 1);
+            assert!(
+                cache.get(0, 0, &mut out).is_none(),
+                "objects exceeding the limit are not stored"
+            );
+
+            cache.put(0, 1, b"12345", git_object::Kind::Blob, 1);
+            cache.put(0, 2, b"12345", git_object::Kind::Blob, 1);
+            assert!(cache.mem_used <= cache.mem_limit);
+            assert_eq!(cache.get(0, 1, &mut out), Some((git_object::Kind::Blob, 1)));
+            assert_eq!(out, b"12345");
+
+            cache.put(0, 3, b"1", git_object::Kind::Tree, 1);
+            assert!(cache.mem_used <= cache.mem_limit);
+            assert!(
+                cache.get(0, 2, &mut out).is_none(),
+                "the least recently used object is evicted to make room"
+            );
+            assert_eq!(cache.get(0, 3, &mut out), Some((git_object::Kind::Tree, 1)));
+            assert_eq!(out, b"1");
+            assert_eq!(
+                cache.get(0, 1, &mut out),
+                Some((git_object::Kind::Blob, 1)),
+                "recently used objects are kept"
+            );
+            assert_eq!(out, b"12345");
+
+            for offset in 4..8 {
+                cache.put(0, offset, b"123", git_object::Kind::Blob, 1);
+                assert!(cache.mem_used <= cache.mem_limit);
+            }
+            assert!(cache.get(0, 1, &mut out).is_none() && cache.get(0, 3, &mut out).is_none());
+            for offset in 5..8 {
+                assert_eq!(cache.get(0, offset, &mut out), Some((git_object::Kind::Blob, 1)));
+            }
+        }
+    }
 }
 
 #[cfg(feature = "pack-cache-lru-static")]
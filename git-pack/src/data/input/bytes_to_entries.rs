@@ -2,7 +2,7 @@ use std::{fs, io};
 
 use git_features::{
     hash,
-    hash::Sha1,
+    hash::Hasher,
     zlib::{stream::inflate::ReadBoxed, Decompress},
 };
 
@@ -18,11 +18,11 @@ pub struct BytesToEntriesIter<BR> {
     had_error: bool,
     kind: crate::data::Version,
     objects_left: u32,
-    hash: Option<Sha1>,
+    hash: Option<Hasher>,
     mode: input::Mode,
     compressed: input::EntryDataMode,
     compressed_buf: Option<Vec<u8>>,
-    hash_len: usize,
+    object_hash: git_hash::Kind,
 }
 
 impl<BR> BytesToEntriesIter<BR>
@@ -73,7 +73,7 @@ where
             }),
             mode,
             compressed_buf: None,
-            hash_len: object_hash.len_in_bytes(),
+            object_hash,
         })
     }
 
@@ -90,11 +90,11 @@ where
                         hash,
                     },
                 );
-                let res = crate::data::Entry::from_read(&mut read, self.offset, self.hash_len);
+                let res = crate::data::Entry::from_read(&mut read, self.offset, self.object_hash.len_in_bytes());
                 self.hash = Some(read.write.hash);
                 res
             }
-            None => crate::data::Entry::from_read(&mut self.read, self.offset, self.hash_len),
+            None => crate::data::Entry::from_read(&mut self.read, self.offset, self.object_hash.len_in_bytes()),
         }
         .map_err(input::Error::from)?;
 
@@ -159,7 +159,7 @@ where
 
         // Last objects gets trailer (which is potentially verified)
         let trailer = if self.objects_left == 0 {
-            let mut id = git_hash::ObjectId::from([0; 20]);
+            let mut id = git_hash::ObjectId::null(self.object_hash);
             if let Err(err) = self.read.read_exact(id.as_mut_slice()) {
                 if self.mode != input::Mode::Restore {
                     return Err(err.into());
@@ -167,7 +167,7 @@ where
             }
 
             if let Some(hash) = self.hash.take() {
-                let actual_id = git_hash::ObjectId::from(hash.digest());
+                let actual_id = hash.digest();
                 if self.mode == input::Mode::Restore {
                     id = actual_id;
                 }
@@ -181,7 +181,7 @@ where
             Some(id)
         } else if self.mode == input::Mode::Restore {
             let hash = self.hash.clone().expect("in restore mode a hash is set");
-            Some(git_hash::ObjectId::from(hash.digest()))
+            Some(hash.digest())
         } else {
             None
         };
@@ -39,16 +39,12 @@ where
     ///
     /// # Panics
     ///
-    /// Not all combinations of `object_hash` and `version` are supported currently triggering assertion errors.
+    /// Only pack version 2 can be written currently, triggering assertion errors otherwise.
     pub fn new(input: I, output: W, version: crate::data::Version, object_hash: git_hash::Kind) -> Self {
         assert!(
             matches!(version, crate::data::Version::V2),
             "currently only pack version 2 can be written",
         );
-        assert!(
-            matches!(object_hash, git_hash::Kind::Sha1),
-            "currently only Sha1 is supported, right now we don't know how other hashes are encoded",
-        );
         EntriesToBytesIter {
             input: input.peekable(),
             output,
@@ -228,7 +228,7 @@ mod expand {
                                     push_obj_count_unique(
                                         &mut out, seen_objs, &tree_id, location, progress, stats, true,
                                     );
-                                    git_object::TreeRefIter::from_bytes(obj.data)
+                                    git_object::TreeRefIter::from_bytes_with_hash(obj.data, tree_id.kind())
                                 };
 
                                 let objects = if parent_commit_ids.is_empty() {
@@ -243,7 +243,7 @@ mod expand {
                                                     progress.inc();
                                                     stats.expanded_objects += 1;
                                                     out.push(output::Count::from_data(oid, location));
-                                                    obj.try_into_tree_iter()
+                                                    obj.try_into_tree_iter_with_hash(oid.kind())
                                                 }
                                                 None => None,
                                             }
@@ -275,7 +275,10 @@ mod expand {
                                                 stats,
                                                 true,
                                             );
-                                            git_object::TreeRefIter::from_bytes(parent_tree_obj.data)
+                                            git_object::TreeRefIter::from_bytes_with_hash(
+                                                parent_tree_obj.data,
+                                                parent_tree_id.kind(),
+                                            )
                                         };
 
                                         changes_delegate.clear();
@@ -311,7 +314,7 @@ mod expand {
                             Tree => {
                                 traverse_delegate.clear();
                                 git_traverse::tree::breadthfirst(
-                                    git_object::TreeRefIter::from_bytes(obj.0.data),
+                                    git_object::TreeRefIter::from_bytes_with_hash(obj.0.data, id.kind()),
                                     &mut tree_traversal_state,
                                     |oid, buf| {
                                         stats.decoded_objects += 1;
@@ -320,7 +323,7 @@ mod expand {
                                                 progress.inc();
                                                 stats.expanded_objects += 1;
                                                 out.push(output::Count::from_data(oid, location));
-                                                obj.try_into_tree_iter()
+                                                obj.try_into_tree_iter_with_hash(oid.kind())
                                             }
                                             None => None,
                                         }
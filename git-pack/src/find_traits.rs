@@ -85,7 +85,7 @@ mod ext {
                         oid: id.as_ref().to_owned(),
                     })
                     .and_then(|(o, l)| {
-                        o.decode()
+                        o.decode_with_hash(id.kind())
                             .map_err(find::existing_object::Error::Decode)
                             .map(|o| (o, l))
                     })
@@ -100,7 +100,7 @@ mod ext {
     }
 
     macro_rules! make_iter_lookup {
-        ($method:ident, $object_kind:path, $object_type:ty, $into_iter:tt) => {
+        ($method:ident, $object_kind:path, $object_type:ty, $into_iter:expr) => {
             /// Like [`find(…)`][Self::find()], but flattens the `Result<Option<_>>` into a single `Result` making a non-existing object an error
             /// while returning the desired iterator type.
             fn $method<'a>(
@@ -115,7 +115,7 @@ mod ext {
                         oid: id.as_ref().to_owned(),
                     })
                     .and_then(|(o, l)| {
-                        o.$into_iter()
+                        ($into_iter)(o, id)
                             .ok_or_else(|| find::existing_iter::Error::ObjectKind {
                                 expected: $object_kind,
                             })
@@ -146,9 +146,24 @@ mod ext {
         make_obj_lookup!(find_tree, ObjectRef::Tree, Kind::Tree, TreeRef<'a>);
         make_obj_lookup!(find_tag, ObjectRef::Tag, Kind::Tag, TagRef<'a>);
         make_obj_lookup!(find_blob, ObjectRef::Blob, Kind::Blob, BlobRef<'a>);
-        make_iter_lookup!(find_commit_iter, Kind::Blob, CommitRefIter<'a>, try_into_commit_iter);
-        make_iter_lookup!(find_tree_iter, Kind::Tree, TreeRefIter<'a>, try_into_tree_iter);
-        make_iter_lookup!(find_tag_iter, Kind::Tag, TagRefIter<'a>, try_into_tag_iter);
+        make_iter_lookup!(
+            find_commit_iter,
+            Kind::Blob,
+            CommitRefIter<'a>,
+            |o: git_object::Data<'a>, _id: &git_hash::oid| o.try_into_commit_iter()
+        );
+        make_iter_lookup!(
+            find_tree_iter,
+            Kind::Tree,
+            TreeRefIter<'a>,
+            |o: git_object::Data<'a>, id: &git_hash::oid| o.try_into_tree_iter_with_hash(id.kind())
+        );
+        make_iter_lookup!(
+            find_tag_iter,
+            Kind::Tag,
+            TagRefIter<'a>,
+            |o: git_object::Data<'a>, _id: &git_hash::oid| o.try_into_tag_iter()
+        );
     }
 
     impl<T: super::Find> FindExt for T {}
@@ -91,7 +91,9 @@ impl Default for Version {
 }
 
 impl Version {
-    /// The kind of hash to produce to be compatible to this kind of index
+    /// The kind of hash to produce to be compatible to this kind of index by default.
+    ///
+    /// Note that V2 indices can also hold Sha256 ids, which is determined by the repository they belong to.
     pub fn hash(&self) -> git_hash::Kind {
         match self {
             Version::V1 | Version::V2 => git_hash::Kind::Sha1,
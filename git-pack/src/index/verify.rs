@@ -231,13 +231,12 @@ impl index::File {
             use git_object::Kind::*;
             match object_kind {
                 Tree | Commit | Tag => {
-                    let object = git_object::ObjectRef::from_bytes(object_kind, buf).map_err(|err| {
-                        integrity::Error::ObjectDecode {
+                    let object = git_object::ObjectRef::from_bytes_with_hash(object_kind, buf, index_entry.oid.kind())
+                        .map_err(|err| integrity::Error::ObjectDecode {
                             source: err,
                             kind: object_kind,
                             id: index_entry.oid,
-                        }
-                    })?;
+                        })?;
                     if let Mode::HashCrc32DecodeEncode = verify_mode {
                         encode_buf.clear();
                         object
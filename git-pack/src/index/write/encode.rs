@@ -31,7 +31,7 @@ pub(crate) fn write_to(
     // Write header
     let mut out = Count::new(std::io::BufWriter::with_capacity(
         8 * 4096,
-        hash::Write::new(out, kind.hash()),
+        hash::Write::new(out, pack_hash.kind()),
     ));
     out.write_all(V2_SIGNATURE)?;
     out.write_all(&(kind as u32).to_be_bytes())?;
@@ -83,14 +83,14 @@ pub(crate) fn write_to(
 
     let bytes_written_without_trailer = out.bytes;
     let mut out = out.inner.into_inner()?;
-    let index_hash: git_hash::ObjectId = out.hash.digest().into();
+    let index_hash = out.hash.digest();
     out.inner.write_all(index_hash.as_slice())?;
     out.inner.flush()?;
 
     progress.inc();
     progress.show_throughput_with(
         start,
-        (bytes_written_without_trailer + 20) as usize,
+        bytes_written_without_trailer as usize + index_hash.as_slice().len(),
         progress::bytes().expect("unit always set"),
         progress::MessageLevel::Success,
     );
@@ -168,7 +168,7 @@ impl crate::index::File {
                      decompressed: bytes,
                      ..
                  }| {
-                    modify_base(data, entry, bytes, kind.hash());
+                    modify_base(data, entry, bytes, object_hash);
                     Ok::<_, Error>(())
                 },
                 crate::cache::delta::traverse::Options {
@@ -225,7 +225,7 @@ fn modify_base(
         let mut hasher = git_features::hash::hasher(object_hash);
         hasher.update(&git_object::encode::loose_header(kind, bytes.len()));
         hasher.update(bytes);
-        git_hash::ObjectId::from(hasher.digest())
+        hasher.digest()
     }
 
     let object_kind = pack_entry.header.as_kind().expect("base object as source of iteration");
@@ -18,6 +18,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![deny(missing_docs, rust_2018_idioms, unsafe_code)]
 
+///
+pub mod bitmap;
+
 ///
 pub mod bundle;
 /// A bundle of pack data and the corresponding pack index
@@ -0,0 +1,40 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+# many objects make for large bitmaps, which git stores XORed with similar ones
+mkdir many
+for file in $(seq 500); do
+  echo "$file" > "many/$file"
+done
+git add many
+git commit -qm "many files"
+
+for round in $(seq 20); do
+  seq "$round" > "file-$((round % 4))"
+  mkdir -p "dir-$((round % 3))"
+  echo "$round" > "dir-$((round % 3))/file"
+  git add .
+  git commit -qm "main $round"
+  if [ "$round" = 5 ]; then
+    git tag -m "annotated" annotated
+  fi
+done
+
+git checkout -q -b side HEAD~10
+for round in $(seq 5); do
+  echo "side $round" > side-file
+  git add .
+  git commit -qm "side $round"
+done
+git checkout -q main
+
+git repack -adbq
+
+mkdir reachable
+for commit in $(git rev-list --all); do
+  git rev-list --objects "$commit" | cut -d' ' -f1 > "reachable/$commit"
+done
+git rev-parse annotated > tag
@@ -0,0 +1,102 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use git_hash::ObjectId;
+use git_pack::bitmap::{self, PackBitmap};
+
+use crate::scripted_fixture_repo_read_only;
+
+fn fixture() -> crate::Result<(PackBitmap, PathBuf)> {
+    let dir = scripted_fixture_repo_read_only("make_pack_bitmap_repo.sh")?;
+    let index_path = std::fs::read_dir(dir.join(".git/objects/pack"))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|path| path.extension().map_or(false, |ext| ext == "idx"))
+        .expect("a single pack was written");
+    Ok((PackBitmap::at(index_path, git_hash::Kind::Sha1)?, dir))
+}
+
+fn ids_in(path: &std::path::Path) -> crate::Result<Vec<ObjectId>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(|line| ObjectId::from_hex(line.as_bytes()))
+        .collect::<Result<_, _>>()?)
+}
+
+fn reachable_by_commit(dir: &std::path::Path) -> crate::Result<Vec<(ObjectId, BTreeSet<ObjectId>)>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir.join("reachable"))? {
+        let path = entry?.path();
+        let commit = ObjectId::from_hex(path.file_name().expect("file name").to_str().expect("hex").as_bytes())?;
+        out.push((commit, ids_in(&path)?.into_iter().collect()));
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[test]
+fn reachability_matches_rev_list() -> crate::Result {
+    let (bitmap, dir) = fixture()?;
+    assert_eq!(
+        bitmap.num_bitmaps(),
+        26,
+        "with less than 100 commits, all of them get a bitmap, most of which are XORed with others"
+    );
+    let all_objects: Vec<_> = bitmap.index().iter().map(|e| e.oid).collect();
+    let reachable_by_commit = reachable_by_commit(&dir)?;
+    assert_eq!(reachable_by_commit.len(), bitmap.num_bitmaps());
+
+    let mut all_reachable = BTreeSet::new();
+    for (commit, reachable) in &reachable_by_commit {
+        assert!(bitmap.contains_commit(commit));
+        for id in &all_objects {
+            assert_eq!(
+                bitmap.is_reachable_from_commits(&[*commit], id)?,
+                reachable.contains(id),
+                "{} reachable from {}",
+                id,
+                commit
+            );
+        }
+        all_reachable.extend(reachable.iter().copied());
+    }
+
+    let commits: Vec<_> = reachable_by_commit.iter().map(|(commit, _)| *commit).collect();
+    for id in &all_objects {
+        assert_eq!(
+            bitmap.is_reachable_from_commits(&commits, id)?,
+            all_reachable.contains(id),
+            "objects are reachable if any commit can reach them"
+        );
+    }
+    let tag = ids_in(&dir.join("tag"))?[0];
+    assert_eq!(
+        all_objects
+            .iter()
+            .filter(|id| !all_reachable.contains(*id))
+            .collect::<Vec<_>>(),
+        vec![&tag],
+        "only the annotated tag itself can't be reached from commits"
+    );
+
+    assert!(!bitmap.is_reachable_from_commits(&commits, &git_hash::Kind::Sha1.null())?);
+    assert!(
+        !bitmap.is_reachable_from_commits(&[], &commits[0])?,
+        "nothing is reachable without commits"
+    );
+    Ok(())
+}
+
+#[test]
+fn commits_without_bitmap_are_an_error() -> crate::Result {
+    let (bitmap, dir) = fixture()?;
+    let (commit, reachable) = reachable_by_commit(&dir)?.remove(0);
+    let blob = reachable
+        .into_iter()
+        .find(|id| !bitmap.contains_commit(id))
+        .expect("trees and blobs have no bitmap");
+
+    let err = bitmap.is_reachable_from_commits(&[blob], &commit).unwrap_err();
+    assert!(matches!(err, bitmap::Error::MissingBitmap { id } if id == blob));
+    Ok(())
+}
@@ -104,7 +104,9 @@ mod with_io {
             }
         }
 
-        pub(crate) fn default_features(
+        /// Returns the features we would like to use for the given `version` of the protocol, which is
+        /// also supported by the server as per `server_capabilities`.
+        pub fn default_features(
             &self,
             version: git_transport::Protocol,
             server_capabilities: &Capabilities,
@@ -29,6 +29,10 @@ pub use response::Response;
 pub mod handshake;
 pub use handshake::function::handshake;
 
+///
+pub mod negotiate;
+pub use negotiate::{function::negotiate, Negotiation};
+
 /// Send a message to indicate the remote side that there is nothing more to expect from us, indicating a graceful shutdown.
 #[maybe_async::maybe_async]
 pub async fn indicate_end_of_interaction(
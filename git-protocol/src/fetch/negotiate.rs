@@ -0,0 +1,384 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use git_hash::{oid, ObjectId};
+
+use crate::fetch::{response::Acknowledgement, Ref};
+
+/// The amount of `have`s to send in the first round of negotiation. Each following round sends twice as many as the previous one,
+/// up to [`MAX_BATCH_SIZE`].
+pub const INITIAL_BATCH_SIZE: usize = 16;
+/// The maximum amount of `have`s to send in a single round of negotiation.
+pub const MAX_BATCH_SIZE: usize = 1024;
+/// The amount of `have`s that may be sent after the last new acknowledgement of the server before giving up on negotiation,
+/// the same value `git` uses.
+pub const MAX_IN_VAIN: usize = 256;
+
+/// Options for [`negotiate()`][crate::fetch::negotiate()] and [`Negotiation`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `Some(depth)`, the fetch is shallow and the server will send no more than `depth` commits of each wanted history.
+    /// Then only commits within `depth` generations of the tips of local references are sent as `have`s, as the server
+    /// won't send the history beyond that anyway.
+    pub depth: Option<u32>,
+}
+
+/// The result of [`negotiate()`][crate::fetch::negotiate()] or [`Negotiation::into_outcome()`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The ids of remote references which aren't present locally, sorted and without duplicates.
+    pub wants: Vec<ObjectId>,
+    /// All ids sent as `have`s in the order they were sent.
+    pub haves: Vec<ObjectId>,
+    /// The ids of all `have`s the server acknowledged as common, in the order of acknowledgement.
+    pub common: Vec<ObjectId>,
+}
+
+mod error {
+    /// The error returned by [`negotiate()`][crate::fetch::negotiate()] and the methods of [`Negotiation`][super::Negotiation].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not decode a local commit or tag")]
+        Decode(#[from] git_object::decode::Error),
+        #[error("Could not obtain the acknowledgements of the server")]
+        Acknowledge(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+}
+pub use error::Error;
+
+const SEEN: u8 = 1 << 0;
+const COMMON: u8 = 1 << 1;
+const COMMON_REF: u8 = 1 << 2;
+const POPPED: u8 = 1 << 3;
+
+#[derive(Default, Clone, Copy)]
+struct State {
+    flags: u8,
+    generation: u32,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Queued {
+    commit_time: u32,
+    id: ObjectId,
+}
+
+/// The state of a negotiation which determines which objects to ask the server for, and which `have`s to send to it
+/// to minimize the size of the pack it sends.
+///
+/// Local history is traversed from the tips of local references in order of commit time, newest first, assuming that recent
+/// commits are the most likely to be known to the server despite potential clock skew. Each round of negotiation
+/// [produces a batch of `have`s][Negotiation::next_haves()] to send to the server, whose [acknowledgements][Negotiation::acknowledged()]
+/// mark commits and their ancestors as common so they won't be sent anymore.
+pub struct Negotiation<Find> {
+    find: Find,
+    buf: Vec<u8>,
+    depth: Option<u32>,
+    state: HashMap<ObjectId, State>,
+    queue: BinaryHeap<Queued>,
+    non_common_revs: usize,
+    batch_size: usize,
+    in_vain: usize,
+    got_acknowledgement: bool,
+    is_ready: bool,
+    outcome: Outcome,
+}
+
+/// Lifecycle
+impl<Find> Negotiation<Find>
+where
+    Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
+{
+    /// Prepare a negotiation for obtaining the objects of `remote_refs` which aren't yet present locally, with `local_refs` being
+    /// the ids local references point to, and `find` being used to lookup local objects.
+    ///
+    /// Objects pointed to by remote references which are present locally are known to be common with the server, so they
+    /// are sent as `have`s but none of their ancestors is.
+    pub fn new(local_refs: &[ObjectId], remote_refs: &[Ref], find: Find, options: Options) -> Result<Self, Error> {
+        let mut negotiation = Negotiation {
+            find,
+            buf: Vec::new(),
+            depth: options.depth,
+            state: HashMap::new(),
+            queue: BinaryHeap::new(),
+            non_common_revs: 0,
+            batch_size: INITIAL_BATCH_SIZE,
+            in_vain: 0,
+            got_acknowledgement: false,
+            is_ready: false,
+            outcome: Outcome::default(),
+        };
+
+        let mut wants = Vec::new();
+        for remote_ref in remote_refs {
+            let id = remote_ref.unpack().1;
+            match negotiation.peel_to_commit(id)? {
+                Some(Some(commit)) => {
+                    if negotiation.state_of(&commit).flags & SEEN == 0 {
+                        negotiation.push(commit, COMMON_REF | SEEN, 0)?;
+                        negotiation.mark_common(commit, true)?;
+                    }
+                }
+                Some(None) => {}
+                None => wants.push(id.to_owned()),
+            }
+        }
+        wants.sort();
+        wants.dedup();
+        negotiation.outcome.wants = wants;
+
+        for id in local_refs {
+            if let Some(Some(commit)) = negotiation.peel_to_commit(id)? {
+                negotiation.push(commit, SEEN, 0)?;
+            }
+        }
+        Ok(negotiation)
+    }
+
+    /// Consume this instance and return the wants along with all `have`s sent and acknowledged so far.
+    pub fn into_outcome(self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Access
+impl<Find> Negotiation<Find> {
+    /// The ids of remote references which aren't present locally, sorted and without duplicates.
+    ///
+    /// If empty, there is nothing to fetch.
+    pub fn wants(&self) -> &[ObjectId] {
+        &self.outcome.wants
+    }
+
+    /// The ids of `have`s the server acknowledged as common so far, in the order of acknowledgement.
+    ///
+    /// These must be sent along with the next batch of `have`s if the server doesn't retain state between rounds of negotiation,
+    /// as is the case with protocol V2 or stateless transports like HTTP.
+    pub fn common(&self) -> &[ObjectId] {
+        &self.outcome.common
+    }
+
+    /// Return true if the server indicated that it is ready to send a pack, in which case no more `have`s are produced.
+    pub fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+}
+
+/// Negotiation
+impl<Find> Negotiation<Find>
+where
+    Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
+{
+    /// Return the next batch of ids to send as `have`s to the server, or an empty batch if negotiation is complete and the
+    /// pack should be requested by sending `done`.
+    ///
+    /// Negotiation is complete if the server is ready, if all local history is known to be common or was sent, or if
+    /// [too many `have`s][MAX_IN_VAIN] were sent since the last new acknowledgement.
+    pub fn next_haves(&mut self) -> Result<Vec<ObjectId>, Error> {
+        if self.is_ready || (self.got_acknowledgement && self.in_vain >= MAX_IN_VAIN) {
+            return Ok(Vec::new());
+        }
+        let mut haves = Vec::new();
+        while haves.len() < self.batch_size {
+            match self.next_have()? {
+                Some(id) => haves.push(id),
+                None => break,
+            }
+        }
+        self.in_vain += haves.len();
+        self.batch_size = (self.batch_size * 2).min(MAX_BATCH_SIZE);
+        self.outcome.haves.extend_from_slice(&haves);
+        Ok(haves)
+    }
+
+    /// Process the `acknowledgements` the server sent in response to the previous batch of `have`s.
+    ///
+    /// Commits acknowledged as common and all their ancestors won't be sent as `have`s anymore.
+    pub fn acknowledged(&mut self, acknowledgements: &[Acknowledgement]) -> Result<(), Error> {
+        for ack in acknowledgements {
+            match ack {
+                Acknowledgement::Common(id) => {
+                    self.got_acknowledgement = true;
+                    if self.state_of(id).flags & COMMON == 0 {
+                        self.in_vain = 0;
+                    }
+                    if !self.outcome.common.contains(id) {
+                        self.outcome.common.push(*id);
+                    }
+                    self.mark_common(*id, false)?;
+                }
+                Acknowledgement::Ready => self.is_ready = true,
+                Acknowledgement::Nak => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Pop the newest commit from the queue and return it if it should be sent as `have`, after queuing its parents.
+    fn next_have(&mut self) -> Result<Option<ObjectId>, Error> {
+        loop {
+            if self.non_common_revs == 0 {
+                return Ok(None);
+            }
+            let id = match self.queue.pop() {
+                Some(queued) => queued.id,
+                None => return Ok(None),
+            };
+            let state = self.state.entry(id).or_default();
+            state.flags |= POPPED;
+            let (flags, generation) = (state.flags, state.generation);
+            if flags & COMMON == 0 {
+                self.non_common_revs -= 1;
+            }
+
+            let (mark, is_have) = if flags & COMMON != 0 {
+                (COMMON | SEEN, false)
+            } else if flags & COMMON_REF != 0 {
+                (COMMON | SEEN, true)
+            } else {
+                (SEEN, true)
+            };
+            for parent in self.parents_of(&id)?.unwrap_or_default() {
+                if self.state_of(&parent).flags & SEEN == 0 {
+                    self.push(parent, mark, generation + 1)?;
+                }
+                if mark & COMMON != 0 {
+                    self.mark_common(parent, true)?;
+                }
+            }
+            if is_have {
+                return Ok(Some(id));
+            }
+        }
+    }
+
+    /// Add `id` to the queue of commits to traverse if it wasn't seen yet and is within the configured depth.
+    fn push(&mut self, id: ObjectId, mark: u8, generation: u32) -> Result<(), Error> {
+        if self.state_of(&id).flags & mark != 0 || self.depth.map_or(false, |depth| generation >= depth) {
+            return Ok(());
+        }
+        let commit_time = match (self.find)(&id, &mut self.buf).and_then(|data| data.try_into_commit_iter()) {
+            Some(commit) => commit.committer()?.time.seconds_since_unix_epoch,
+            None => return Ok(()),
+        };
+        let state = self.state.entry(id).or_default();
+        state.flags |= mark;
+        state.generation = generation;
+        if state.flags & COMMON == 0 {
+            self.non_common_revs += 1;
+        }
+        self.queue.push(Queued { commit_time, id });
+        Ok(())
+    }
+
+    /// Mark `id`, unless `ancestors_only` is set, and all of its ancestors we have seen as common.
+    fn mark_common(&mut self, id: ObjectId, ancestors_only: bool) -> Result<(), Error> {
+        let mut stack = vec![(id, ancestors_only)];
+        while let Some((id, ancestors_only)) = stack.pop() {
+            let State { flags, generation } = self.state_of(&id);
+            if flags & COMMON != 0 {
+                continue;
+            }
+            if !ancestors_only {
+                self.state.entry(id).or_default().flags |= COMMON;
+            }
+            if flags & SEEN == 0 {
+                self.push(id, SEEN, generation)?;
+                continue;
+            }
+            if !ancestors_only && flags & POPPED == 0 {
+                self.non_common_revs -= 1;
+            }
+            for parent in self.parents_of(&id)?.unwrap_or_default() {
+                if self.state_of(&parent).flags & SEEN == 0 {
+                    self.state.entry(parent).or_default().generation = generation + 1;
+                }
+                stack.push((parent, false));
+            }
+        }
+        Ok(())
+    }
+
+    /// Peel `id` to a commit, returning `None` if it doesn't exist locally or `Some(None)` if it doesn't point to a commit.
+    fn peel_to_commit(&mut self, id: &oid) -> Result<Option<Option<ObjectId>>, Error> {
+        let mut id = id.to_owned();
+        loop {
+            let data = match (self.find)(&id, &mut self.buf) {
+                Some(data) => data,
+                None => return Ok(None),
+            };
+            match data.kind {
+                git_object::Kind::Commit => return Ok(Some(Some(id))),
+                git_object::Kind::Tag => id = git_object::TagRefIter::from_bytes(data.data).target_id()?,
+                git_object::Kind::Tree | git_object::Kind::Blob => return Ok(Some(None)),
+            }
+        }
+    }
+
+    fn parents_of(&mut self, id: &oid) -> Result<Option<Vec<ObjectId>>, Error> {
+        let commit = match (self.find)(id, &mut self.buf).and_then(|data| data.try_into_commit_iter()) {
+            Some(commit) => commit,
+            None => return Ok(None),
+        };
+        let mut parents = Vec::new();
+        for token in commit {
+            match token? {
+                git_object::commit::ref_iter::Token::Tree { .. } => continue,
+                git_object::commit::ref_iter::Token::Parent { id } => parents.push(id),
+                _a_token_past_the_parents => break,
+            }
+        }
+        Ok(Some(parents))
+    }
+}
+
+impl<Find> Negotiation<Find> {
+    fn state_of(&self, id: &oid) -> State {
+        // Lookups must use the owned type, as its hash differs from the one of `oid`.
+        self.state.get(&id.to_owned()).copied().unwrap_or_default()
+    }
+}
+
+pub(crate) mod function {
+    use git_hash::{oid, ObjectId};
+
+    use super::{Error, Negotiation, Options, Outcome};
+    use crate::fetch::{response::Acknowledgement, Ref};
+
+    /// Determine the objects to fetch from `remote_refs` and negotiate the `have`s to send to the server, given the ids of
+    /// `local_refs` and `find` to lookup local objects.
+    ///
+    /// `acknowledge(haves)` is called with each batch of `have`s and is expected to send them to the server and return
+    /// its acknowledgements. Negotiation ends once the server is ready, or there are no more `have`s worth sending,
+    /// after which the pack can be requested with the returned [`Outcome`].
+    /// If there is nothing to fetch, no `have`s are sent.
+    ///
+    /// See [`Negotiation`] for the algorithm, and for negotiating step by step.
+    pub fn negotiate<Find, Acknowledge, E>(
+        local_refs: &[ObjectId],
+        remote_refs: &[Ref],
+        find: Find,
+        mut acknowledge: Acknowledge,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
+        Acknowledge: FnMut(&[ObjectId]) -> Result<Vec<Acknowledgement>, E>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut negotiation = Negotiation::new(local_refs, remote_refs, find, options)?;
+        if negotiation.wants().is_empty() {
+            return Ok(negotiation.into_outcome());
+        }
+        loop {
+            let haves = negotiation.next_haves()?;
+            if haves.is_empty() {
+                break;
+            }
+            let acknowledgements = acknowledge(&haves).map_err(|err| Error::Acknowledge(Box::new(err)))?;
+            negotiation.acknowledged(&acknowledgements)?;
+        }
+        Ok(negotiation.into_outcome())
+    }
+}
@@ -31,5 +31,8 @@ pub use fetch_fn::{fetch, FetchConnection};
 mod remote_progress;
 pub use remote_progress::RemoteProgress;
 
+#[cfg(feature = "blocking-client")]
+pub mod server;
+
 #[cfg(all(feature = "blocking-client", feature = "async-client"))]
 compile_error!("Cannot set both 'blocking-client' and 'async-client' features as they are mutually exclusive");
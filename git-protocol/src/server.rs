@@ -0,0 +1,164 @@
+//! Formatting of reference advertisements the way `git upload-pack` produces them.
+//!
+//! Everything here is independent of the transport and only deals with getting the byte-exact output right,
+//! which includes the order of references, the placement of capabilities and peeled references.
+use std::io::{self, Write};
+
+use bstr::{BStr, BString, ByteSlice};
+use git_hash::ObjectId;
+use git_transport::packetline::{encode, Writer};
+
+/// A reference to advertise to a client.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ref {
+    /// The full name of the reference, like `HEAD` or `refs/heads/main`.
+    pub full_ref_name: BString,
+    /// The object the reference points to, which for symbolic references is the object its target points to.
+    pub object: ObjectId,
+    /// The object an annotated tag peels to, or `None` if `object` isn't an annotated tag.
+    pub peeled: Option<ObjectId>,
+    /// The full name of the reference this one points to if it is symbolic, like `refs/heads/main` for `HEAD`.
+    pub symref_target: Option<BString>,
+}
+
+/// Options to control the output of [`write_ls_refs()`], as sent by the client along with the `ls-refs` command.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+pub struct LsRefsOptions {
+    /// If true, the `symref-target:<name>` attribute is added to symbolic references.
+    pub symrefs: bool,
+    /// If true, the `peeled:<id>` attribute is added to annotated tags.
+    pub peel: bool,
+    /// If not empty, only references whose names start with one of these prefixes are listed.
+    pub ref_prefixes: Vec<BString>,
+}
+
+/// Sort `refs` in the order `git upload-pack` advertises them, that is `HEAD` first and all other references by the bytes
+/// of their name, independently of any locale.
+pub fn sort(refs: &mut [Ref]) {
+    refs.sort_by(|a, b| {
+        let (a, b) = (a.full_ref_name.as_bstr(), b.full_ref_name.as_bstr());
+        (a != "HEAD").cmp(&(b != "HEAD")).then_with(|| a.cmp(b))
+    });
+}
+
+/// Write a protocol V0 reference advertisement of `refs` with `capabilities` attached to the first line to `out`,
+/// finishing it with a flush packet. `refs` don't have to be sorted.
+///
+/// `capabilities` are advertised as given, but a `symref=HEAD:<target>` capability is added if `HEAD` is symbolic.
+/// Like `git upload-pack` does, it is placed before the `object-format` or `agent` capabilities if one of them is present.
+/// If there are no `refs`, a null id of kind `object_hash` is advertised under the name `capabilities^{}` to transmit the
+/// `capabilities`.
+pub fn write_v0_advertisement<'a>(
+    refs: impl IntoIterator<Item = Ref>,
+    capabilities: impl IntoIterator<Item = &'a BStr>,
+    object_hash: git_hash::Kind,
+    out: impl io::Write,
+) -> io::Result<()> {
+    let mut out = Writer::new(out);
+    let mut refs: Vec<_> = refs.into_iter().collect();
+    sort(&mut refs);
+
+    let mut capabilities: Vec<BString> = capabilities.into_iter().map(ToOwned::to_owned).collect();
+    if let Some(target) = refs
+        .iter()
+        .find(|r| r.full_ref_name == "HEAD")
+        .and_then(|head| head.symref_target.as_ref())
+    {
+        let pos = capabilities
+            .iter()
+            .position(|c| c.starts_with(b"object-format=") || c.starts_with(b"agent="))
+            .unwrap_or(capabilities.len());
+        let mut symref = BString::from("symref=HEAD:");
+        symref.extend_from_slice(target);
+        capabilities.insert(pos, symref);
+    }
+    let capabilities = bstr::join(" ", &capabilities);
+
+    let mut line = Vec::new();
+    if refs.is_empty() {
+        push_hex(&mut line, &object_hash.null());
+        line.extend_from_slice(b" capabilities^{}\0");
+        line.extend_from_slice(&capabilities);
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    for (index, r) in refs.iter().enumerate() {
+        line.clear();
+        push_id_and_name(&mut line, &r.object, r.full_ref_name.as_ref());
+        if index == 0 {
+            line.push(0);
+            line.extend_from_slice(&capabilities);
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+
+        if let Some(peeled) = &r.peeled {
+            line.clear();
+            push_id_and_name(&mut line, peeled, r.full_ref_name.as_ref());
+            line.extend_from_slice(b"^{}\n");
+            out.write_all(&line)?;
+        }
+    }
+    encode::flush_to_write(out.inner_mut()).map(|_| ())
+}
+
+/// Write a protocol V1 reference advertisement to `out`, which is a [V0 advertisement][write_v0_advertisement()] preceded
+/// by the `version 1` line.
+pub fn write_v1_advertisement<'a>(
+    refs: impl IntoIterator<Item = Ref>,
+    capabilities: impl IntoIterator<Item = &'a BStr>,
+    object_hash: git_hash::Kind,
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    Writer::new(&mut out).write_all(b"version 1\n")?;
+    write_v0_advertisement(refs, capabilities, object_hash, out)
+}
+
+/// Write the response to a protocol V2 `ls-refs` command listing `refs` according to `options` to `out`, finishing it
+/// with a flush packet. `refs` don't have to be sorted.
+pub fn write_ls_refs(
+    refs: impl IntoIterator<Item = Ref>,
+    options: &LsRefsOptions,
+    out: impl io::Write,
+) -> io::Result<()> {
+    let mut out = Writer::new(out);
+    let mut refs: Vec<_> = refs
+        .into_iter()
+        .filter(|r| {
+            options.ref_prefixes.is_empty()
+                || options
+                    .ref_prefixes
+                    .iter()
+                    .any(|prefix| r.full_ref_name.starts_with(prefix))
+        })
+        .collect();
+    sort(&mut refs);
+
+    let mut line = Vec::new();
+    for r in &refs {
+        line.clear();
+        push_id_and_name(&mut line, &r.object, r.full_ref_name.as_ref());
+        if let Some(target) = r.symref_target.as_ref().filter(|_| options.symrefs) {
+            line.extend_from_slice(b" symref-target:");
+            line.extend_from_slice(target);
+        }
+        if let Some(peeled) = r.peeled.as_ref().filter(|_| options.peel) {
+            line.extend_from_slice(b" peeled:");
+            push_hex(&mut line, peeled);
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    encode::flush_to_write(out.inner_mut()).map(|_| ())
+}
+
+fn push_id_and_name(line: &mut Vec<u8>, id: &ObjectId, name: &BStr) {
+    push_hex(line, id);
+    line.push(b' ');
+    line.extend_from_slice(name);
+}
+
+fn push_hex(line: &mut Vec<u8>, id: &ObjectId) {
+    id.write_hex_to(line).expect("writing to a vec never fails");
+}
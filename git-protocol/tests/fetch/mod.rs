@@ -293,6 +293,7 @@ pub fn transport<W: std::io::Write>(
     )
 }
 
+mod negotiate;
 pub mod response;
 mod v1;
 mod v2;
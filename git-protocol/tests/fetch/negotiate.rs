@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use git_hash::{oid, ObjectId};
+use git_odb::Write;
+use git_protocol::fetch::{self, negotiate, response::Acknowledgement, Ref};
+
+/// An object database keeping all objects in memory.
+#[derive(Default)]
+struct Odb(HashMap<ObjectId, (git_object::Kind, Vec<u8>)>);
+
+impl Odb {
+    fn write(&mut self, kind: git_object::Kind, data: Vec<u8>) -> ObjectId {
+        let id = git_odb::sink(git_hash::Kind::Sha1)
+            .write_buf(kind, &data)
+            .expect("hashing works");
+        self.0.insert(id, (kind, data));
+        id
+    }
+
+    /// Write a commit with an empty tree, `parents` and a committer time of `time`, unique by `message`.
+    fn commit(&mut self, message: &str, parents: &[ObjectId], time: u32) -> ObjectId {
+        let tree = self.write(git_object::Kind::Tree, Vec::new());
+        let mut data = format!("tree {}\n", tree);
+        for parent in parents {
+            data.push_str(&format!("parent {}\n", parent));
+        }
+        data.push_str(&format!(
+            "author a <a@example.com> {time} +0000\ncommitter c <c@example.com> {time} +0000\n\n{}\n",
+            message,
+            time = time
+        ));
+        self.write(git_object::Kind::Commit, data.into_bytes())
+    }
+
+    /// Write a linear history of `count` commits on top of `parent`, with the first commit at `time` and each following one a
+    /// minute later, and return all commits from oldest to newest.
+    fn history(&mut self, name: &str, parent: Option<ObjectId>, count: usize, time: u32) -> Vec<ObjectId> {
+        let mut commits: Vec<ObjectId> = Vec::new();
+        for idx in 0..count {
+            let parents: Vec<_> = commits.last().copied().or(parent).into_iter().collect();
+            commits.push(self.commit(&format!("{} {}", name, idx), &parents, time + idx as u32 * 60));
+        }
+        commits
+    }
+
+    fn find(&self) -> impl for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Option<git_object::Data<'a>> + '_ {
+        move |id, buf| {
+            let (kind, data) = self.0.get(&id.to_owned())?;
+            buf.clear();
+            buf.extend_from_slice(data);
+            Some(git_object::Data::new(*kind, buf))
+        }
+    }
+
+    /// Acknowledge all `haves` we have, like a server would.
+    fn acknowledge(&self, haves: &[ObjectId]) -> Result<Vec<Acknowledgement>, std::convert::Infallible> {
+        let acks: Vec<_> = haves
+            .iter()
+            .filter(|id| self.0.contains_key(*id))
+            .map(|id| Acknowledgement::Common(*id))
+            .collect();
+        Ok(if acks.is_empty() {
+            vec![Acknowledgement::Nak]
+        } else {
+            acks
+        })
+    }
+}
+
+fn direct(name: &str, id: ObjectId) -> Ref {
+    Ref::Direct {
+        full_ref_name: name.into(),
+        object: id,
+    }
+}
+
+/// Create a local and a remote object database sharing `shared` commits of history.
+fn shared_history(shared: usize) -> (Odb, Odb, Vec<ObjectId>) {
+    let (mut local, mut remote) = (Odb::default(), Odb::default());
+    let commits = local.history("shared", None, shared, 1000);
+    assert_eq!(remote.history("shared", None, shared, 1000), commits);
+    (local, remote, commits)
+}
+
+#[test]
+fn nothing_is_wanted_or_sent_if_all_remote_objects_are_present() -> crate::Result {
+    let (local, remote, shared) = shared_history(3);
+    let tip = *shared.last().expect("non-empty");
+    let outcome = fetch::negotiate(
+        &[tip],
+        &[direct("refs/heads/main", tip), direct("HEAD", tip)],
+        local.find(),
+        |haves| remote.acknowledge(haves),
+        Default::default(),
+    )?;
+    assert_eq!(outcome, negotiate::Outcome::default());
+    Ok(())
+}
+
+#[test]
+fn wants_are_deduplicated_and_remote_objects_we_have_are_sent_as_haves_without_their_ancestors() -> crate::Result {
+    let (mut local, mut remote, shared) = shared_history(50);
+    let shared_tip = *shared.last().expect("non-empty");
+    let local_only = local.history("local", Some(shared_tip), 3, 100_000);
+    let remote_tip = *remote
+        .history("remote", Some(shared_tip), 2, 100_000)
+        .last()
+        .expect("non-empty");
+
+    let outcome = fetch::negotiate(
+        &[*local_only.last().expect("non-empty")],
+        &[
+            Ref::Symbolic {
+                full_ref_name: "HEAD".into(),
+                target: "refs/heads/main".into(),
+                object: remote_tip,
+            },
+            direct("refs/heads/main", remote_tip),
+            direct("refs/heads/old", shared_tip),
+        ],
+        local.find(),
+        |haves| remote.acknowledge(haves),
+        Default::default(),
+    )?;
+    assert_eq!(outcome.wants, vec![remote_tip], "each missing object is wanted once");
+    assert_eq!(
+        outcome.haves,
+        vec![local_only[2], local_only[1], local_only[0], shared_tip],
+        "the shared history is known to be common and isn't sent"
+    );
+    assert_eq!(outcome.common, vec![shared_tip]);
+    Ok(())
+}
+
+#[test]
+fn haves_are_sent_in_batches_until_the_server_acknowledges_common_history() -> crate::Result {
+    let (mut local, mut remote, shared) = shared_history(100);
+    let shared_tip = *shared.last().expect("non-empty");
+    let local_only = local.history("local", Some(shared_tip), 40, 100_000);
+    let remote_tip = remote.commit("remote", &[shared_tip], 200_000);
+
+    let mut batches = Vec::new();
+    let outcome = fetch::negotiate(
+        &[*local_only.last().expect("non-empty")],
+        &[direct("refs/heads/main", remote_tip)],
+        local.find(),
+        |haves| {
+            batches.push(haves.len());
+            remote.acknowledge(haves)
+        },
+        Default::default(),
+    )?;
+    assert_eq!(outcome.wants, vec![remote_tip]);
+    assert_eq!(
+        batches,
+        vec![negotiate::INITIAL_BATCH_SIZE, negotiate::INITIAL_BATCH_SIZE * 2],
+        "the second batch reaches into the common history, after which there is nothing left to send"
+    );
+
+    let expected_haves: Vec<_> = local_only
+        .iter()
+        .rev()
+        .chain(shared.iter().rev())
+        .take(16 + 32)
+        .copied()
+        .collect();
+    assert_eq!(outcome.haves, expected_haves, "newest commits are sent first");
+    assert_eq!(outcome.common, shared.iter().rev().take(8).copied().collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn haves_are_ordered_by_commit_time_across_branches() -> crate::Result {
+    let (mut local, remote) = (Odb::default(), Odb::default());
+    let root = local.commit("root", &[], 0);
+    let b1 = local.commit("b1", &[root], 10);
+    let b2 = local.commit("b2", &[root], 20);
+    let b1 = local.commit("b1", &[b1], 30);
+    let b2 = local.commit("b2", &[b2], 40);
+    let merge = local.commit("merge", &[b1, b2], 50);
+
+    let outcome = fetch::negotiate(
+        &[merge],
+        &[direct("refs/heads/main", ObjectId::null(git_hash::Kind::Sha1))],
+        local.find(),
+        |haves| remote.acknowledge(haves),
+        Default::default(),
+    )?;
+    let times: Vec<_> = outcome
+        .haves
+        .iter()
+        .map(|id| {
+            let mut buf = Vec::new();
+            let data = local.find()(id, &mut buf).expect("present");
+            data.try_into_commit_iter()
+                .expect("commit")
+                .committer()
+                .expect("valid")
+                .time
+                .seconds_since_unix_epoch
+        })
+        .collect();
+    assert_eq!(times, vec![50, 40, 30, 20, 10, 0]);
+    Ok(())
+}
+
+#[test]
+fn depth_limits_the_local_history_to_send() -> crate::Result {
+    let (mut local, remote) = (Odb::default(), Odb::default());
+    let commits = local.history("local", None, 40, 1000);
+
+    let outcome = fetch::negotiate(
+        &[*commits.last().expect("non-empty")],
+        &[direct("refs/heads/main", ObjectId::null(git_hash::Kind::Sha1))],
+        local.find(),
+        |haves| remote.acknowledge(haves),
+        negotiate::Options { depth: Some(5) },
+    )?;
+    assert_eq!(outcome.haves, commits.iter().rev().take(5).copied().collect::<Vec<_>>());
+    assert!(outcome.common.is_empty());
+    Ok(())
+}
+
+#[test]
+fn negotiation_can_be_driven_step_by_step() -> crate::Result {
+    let (mut local, mut remote, shared) = shared_history(2);
+    let local_tip = local.commit("local", &[shared[1]], 100_000);
+    let remote_tip = remote.commit("remote", &[shared[1]], 100_000);
+
+    let mut negotiation = fetch::Negotiation::new(
+        &[local_tip],
+        &[direct("refs/heads/main", remote_tip)],
+        local.find(),
+        Default::default(),
+    )?;
+    assert_eq!(negotiation.wants(), [remote_tip]);
+    let haves = negotiation.next_haves()?;
+    assert_eq!(haves, vec![local_tip, shared[1], shared[0]]);
+
+    negotiation.acknowledged(&[Acknowledgement::Common(shared[1]), Acknowledgement::Ready])?;
+    assert!(negotiation.is_ready());
+    assert_eq!(negotiation.common(), [shared[1]]);
+    assert!(
+        negotiation.next_haves()?.is_empty(),
+        "once the server is ready, the pack can be requested"
+    );
+    Ok(())
+}
@@ -0,0 +1,42 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function transcripts() {
+  git upload-pack --advertise-refs . > advertise-refs.v0
+  GIT_PROTOCOL=version=1 git upload-pack --advertise-refs . > advertise-refs.v1
+  printf '0014command=ls-refs\n0001000csymrefs\n0009peel\n0000' \
+    | GIT_PROTOCOL=version=2 git upload-pack --stateless-rpc . > ls-refs.v2
+  printf '0014command=ls-refs\n00010009peel\n001aref-prefix refs/tags/\n001cref-prefix refs/heads/b\n0000' \
+    | GIT_PROTOCOL=version=2 git upload-pack --stateless-rpc . > ls-refs-with-prefix.v2
+}
+
+git init -q base
+(cd base
+  git checkout -q -b main
+  git commit -q --allow-empty -m c1
+  git tag lightweight
+  git tag -a -m "annotated" v1.0
+  git branch b
+  git checkout -q -b Z-upper
+  git commit -q --allow-empty -m c2
+  git branch "a-ümlaut"
+  git checkout -q main
+  git symbolic-ref refs/remotes/origin/HEAD refs/heads/b
+  git update-ref refs/remotes/origin/b b
+
+  { echo "$(git rev-parse HEAD) HEAD  $(git symbolic-ref HEAD)"
+    git for-each-ref --format='%(objectname) %(refname) %(*objectname) %(symref)'
+  } > refs
+  transcripts
+)
+
+git init -q detached
+(cd detached
+  git commit -q --allow-empty -m c1
+  git checkout -q --detach
+
+  { echo "$(git rev-parse HEAD) HEAD  "
+    git for-each-ref --format='%(objectname) %(refname) %(*objectname) %(symref)'
+  } > refs
+  transcripts
+)
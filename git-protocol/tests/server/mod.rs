@@ -0,0 +1,137 @@
+use bstr::{BStr, BString, ByteSlice};
+use git_protocol::server::{self, LsRefsOptions, Ref};
+
+fn fixture(name: &str) -> git_testtools::Result<std::path::PathBuf> {
+    Ok(git_testtools::scripted_fixture_repo_read_only("make_advertisement_repo.sh")?.join(name))
+}
+
+/// Parse the refs listed by the fixture script, in reverse order to assure they are sorted by us.
+fn refs(repo: &std::path::Path) -> git_testtools::Result<Vec<Ref>> {
+    let refs = std::fs::read(repo.join("refs"))?;
+    Ok(refs
+        .lines()
+        .map(|line| {
+            let mut tokens = line.splitn(4, |b| *b == b' ');
+            let mut next = || tokens.next().expect("four tokens");
+            let object = git_hash::ObjectId::from_hex(next()).expect("valid id");
+            let full_ref_name = next().into();
+            let peeled = Some(next())
+                .filter(|id| !id.is_empty())
+                .map(|id| git_hash::ObjectId::from_hex(id).expect("valid id"));
+            let symref_target = Some(next()).filter(|name| !name.is_empty()).map(Into::into);
+            Ref {
+                full_ref_name,
+                object,
+                peeled,
+                symref_target,
+            }
+        })
+        .rev()
+        .collect())
+}
+
+/// Extract the capabilities from the first line of a v0 or v1 transcript, leaving out those we add ourselves.
+fn capabilities(transcript: &[u8]) -> Vec<BString> {
+    let caps = transcript.split_str("\0").nth(1).expect("capabilities after NUL");
+    caps[..caps.find_byte(b'\n').expect("end of line")]
+        .split_str(" ")
+        .filter(|c| !c.starts_with(b"symref="))
+        .map(Into::into)
+        .collect()
+}
+
+fn assert_same(actual: &[u8], expected: &[u8]) {
+    assert_eq!(
+        actual.as_bstr(),
+        expected.as_bstr(),
+        "our output is byte-for-byte what git produces"
+    );
+}
+
+#[test]
+fn v0_and_v1_advertisements_match_git() -> crate::Result {
+    for repo in ["base", "detached"] {
+        let repo = fixture(repo)?;
+        for (file, is_v1) in [("advertise-refs.v0", false), ("advertise-refs.v1", true)] {
+            let expected = std::fs::read(repo.join(file))?;
+            let caps = capabilities(&expected);
+            let caps = caps.iter().map(|c| c.as_bstr());
+            let mut out = Vec::new();
+            if is_v1 {
+                server::write_v1_advertisement(refs(&repo)?, caps, git_hash::Kind::Sha1, &mut out)?;
+            } else {
+                server::write_v0_advertisement(refs(&repo)?, caps, git_hash::Kind::Sha1, &mut out)?;
+            }
+            assert_same(&out, &expected);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn v2_ls_refs_matches_git() -> crate::Result {
+    for repo in ["base", "detached"] {
+        let repo = fixture(repo)?;
+        for (file, options) in [
+            (
+                "ls-refs.v2",
+                LsRefsOptions {
+                    symrefs: true,
+                    peel: true,
+                    ref_prefixes: Vec::new(),
+                },
+            ),
+            (
+                "ls-refs-with-prefix.v2",
+                LsRefsOptions {
+                    symrefs: false,
+                    peel: true,
+                    ref_prefixes: vec!["refs/tags/".into(), "refs/heads/b".into()],
+                },
+            ),
+        ] {
+            let mut out = Vec::new();
+            server::write_ls_refs(refs(&repo)?, &options, &mut out)?;
+            assert_same(&out, &std::fs::read(repo.join(file))?);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn v0_advertisement_without_refs_transmits_capabilities() -> crate::Result {
+    let mut out = Vec::new();
+    server::write_v0_advertisement(
+        None,
+        ["multi_ack", "object-format=sha1"].iter().map(BStr::new),
+        git_hash::Kind::Sha1,
+        &mut out,
+    )?;
+    assert_same(
+        &out,
+        b"005a0000000000000000000000000000000000000000 capabilities^{}\0multi_ack object-format=sha1\n0000",
+    );
+    Ok(())
+}
+
+#[test]
+fn sorting_puts_head_first_and_orders_by_bytes() {
+    let id = git_hash::Kind::Sha1.null();
+    let r = |name: &str| Ref {
+        full_ref_name: name.into(),
+        object: id,
+        peeled: None,
+        symref_target: None,
+    };
+    let mut refs = vec![
+        r("refs/heads/a-ümlaut"),
+        r("refs/heads/a"),
+        r("refs/heads/Z"),
+        r("HEAD"),
+    ];
+    server::sort(&mut refs);
+    assert_eq!(
+        refs.into_iter().map(|r| r.full_ref_name).collect::<Vec<_>>(),
+        ["HEAD", "refs/heads/Z", "refs/heads/a", "refs/heads/a-ümlaut"]
+    );
+}
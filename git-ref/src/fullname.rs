@@ -80,10 +80,13 @@ impl FullNameRef {
 
     /// Strip well-known prefixes from the name and return it.
     ///
+    /// Names in `refs/` which don't belong to any [category][Category] lose that prefix, so `refs/stash` becomes `stash`,
+    /// similar to how `git` abbreviates them.
     /// If there is no such prefix, the original name is returned.
     pub fn shorten(&self) -> &BStr {
         self.category_and_short_name()
             .map(|(_, short)| short)
+            .or_else(|| self.0.strip_prefix(b"refs/").map(ByteSlice::as_bstr))
             .unwrap_or_else(|| self.0.as_bstr())
     }
 
@@ -27,6 +27,12 @@ impl Namespace {
         self.0.push_str(prefix.as_ref());
         git_path::to_native_path_on_windows(self.0).into_owned()
     }
+    /// Nest `other` within this namespace, so that `refs/namespaces/foo/` joined with `refs/namespaces/bar/`
+    /// becomes `refs/namespaces/foo/refs/namespaces/bar/`, similar to what [`expand()`] produces for `foo/bar`.
+    pub fn join(mut self, other: &Namespace) -> Namespace {
+        self.0.push_str(other.0.as_bstr());
+        self
+    }
     pub(crate) fn into_namespaced_name(mut self, name: &FullNameRef) -> FullName {
         self.0.push_str(name.as_bstr());
         FullName(self.0)
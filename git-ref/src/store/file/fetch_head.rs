@@ -0,0 +1,148 @@
+use std::io::Write;
+
+use git_hash::ObjectId;
+use git_object::bstr::{BStr, BString, ByteSlice};
+
+use crate::file;
+
+/// A single line of the `FETCH_HEAD` file, written after each fetch to record which references were fetched from where.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// The object the fetched reference pointed to.
+    pub id: ObjectId,
+    /// If true, the reference was fetched but isn't meant to be merged by a subsequent `git pull`.
+    pub not_for_merge: bool,
+    /// The full name of the fetched reference on the remote side, like `refs/heads/main`, or `HEAD` if the remote `HEAD` was fetched.
+    ///
+    /// Note that this is what was asked for while fetching, which might not be a full name at all if such a name was
+    /// explicitly given on the command-line.
+    pub ref_name: BString,
+    /// The url of the remote the reference was fetched from, or `.` for the repository itself.
+    pub remote_url: BString,
+}
+
+/// Kinds of references along with the prefix git strips from their names when describing them in `FETCH_HEAD`.
+const KINDS: &[(&str, &str)] = &[
+    ("branch '", "refs/heads/"),
+    ("tag '", "refs/tags/"),
+    ("remote-tracking branch '", "refs/remotes/"),
+    ("'", ""),
+];
+
+impl Entry {
+    /// Parse a single `line` of a `FETCH_HEAD` file without its trailing newline, or return `None` if it is malformed.
+    pub fn from_line(line: &BStr) -> Option<Self> {
+        let mut tokens = line.splitn(3, |b| *b == b'\t');
+        let id = ObjectId::from_hex(tokens.next()?).ok()?;
+        let not_for_merge = match tokens.next()? {
+            b"" => false,
+            b"not-for-merge" => true,
+            _ => return None,
+        };
+        let description = tokens.next()?.as_bstr();
+        let (ref_name, remote_url) = KINDS
+            .iter()
+            .find_map(|(kind, prefix)| {
+                let name_and_url = description.strip_prefix(kind.as_bytes())?;
+                let end = name_and_url.find("' of ")?;
+                let mut ref_name = BString::from(*prefix);
+                ref_name.extend_from_slice(&name_and_url[..end]);
+                Some((ref_name, name_and_url[end + "' of ".len()..].into()))
+            })
+            .unwrap_or_else(|| ("HEAD".into(), description.into()));
+        Some(Entry {
+            id,
+            not_for_merge,
+            ref_name,
+            remote_url,
+        })
+    }
+
+    /// Return the way git describes the fetched reference, like `branch 'main'`, or `None` if the remote `HEAD` was fetched.
+    pub fn note(&self) -> Option<BString> {
+        if self.ref_name == "HEAD" {
+            return None;
+        }
+        let (kind, name) = KINDS
+            .iter()
+            .find_map(|(kind, prefix)| {
+                self.ref_name
+                    .strip_prefix(prefix.as_bytes())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| (*kind, name))
+            })
+            .expect("the last kind matches everything");
+        let mut note = BString::from(kind);
+        note.extend_from_slice(name);
+        note.push(b'\'');
+        Some(note)
+    }
+
+    /// Serialize this instance as line to `out` in the format used by git, including the trailing newline.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        write!(
+            out,
+            "{}\t{}\t",
+            self.id,
+            if self.not_for_merge { "not-for-merge" } else { "" }
+        )?;
+        if let Some(note) = self.note() {
+            out.write_all(&note)?;
+            out.write_all(b" of ")?;
+        }
+        out.write_all(&self.remote_url)?;
+        out.write_all(b"\n")
+    }
+}
+
+///
+pub mod read {
+    use git_object::bstr::BString;
+
+    /// The error returned by [`file::Store::read_fetch_head()`][crate::file::Store::read_fetch_head()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read FETCH_HEAD")]
+        Io(#[from] std::io::Error),
+        #[error("Line {line_number} of FETCH_HEAD is malformed: {line:?}")]
+        Parse { line_number: usize, line: BString },
+    }
+}
+
+impl file::Store {
+    /// Read and parse the `FETCH_HEAD` file in our [`git_dir()`][file::Store::git_dir()], which records all references
+    /// obtained by the most recent fetch, or return an empty list if there is no such file.
+    pub fn read_fetch_head(&self) -> Result<Vec<Entry>, read::Error> {
+        let content = match std::fs::read(self.fetch_head_path()) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        content
+            .lines()
+            .enumerate()
+            .map(|(line_number, line)| {
+                Entry::from_line(line.as_bstr()).ok_or_else(|| read::Error::Parse {
+                    line_number: line_number + 1,
+                    line: line.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Replace the `FETCH_HEAD` file in our [`git_dir()`][file::Store::git_dir()] with one listing all `entries`,
+    /// which should be sorted such that references to merge come first.
+    pub fn write_fetch_head(&self, entries: &[Entry]) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        for entry in entries {
+            entry.write_to(&mut buf)?;
+        }
+        std::fs::File::create(self.fetch_head_path())?.write_all(&buf)
+    }
+
+    fn fetch_head_path(&self) -> std::path::PathBuf {
+        self.git_dir.join("FETCH_HEAD")
+    }
+}
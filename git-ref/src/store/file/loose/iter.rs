@@ -8,15 +8,22 @@ use crate::{file::iter::LooseThenPacked, store_impl::file, BString, FullName};
 /// An iterator over all valid loose reference paths as seen from a particular base directory.
 pub(in crate::store_impl::file) struct SortedLoosePaths {
     pub(crate) base: PathBuf,
+    root: PathBuf,
+    /// If set, only paths whose first component below `root` starts with this prefix are returned.
     filename_prefix: Option<BString>,
-    file_walk: DirEntryIter,
+    /// The traversal, or `None` if `root` doesn't exist as directory, which happens if all matching references are packed.
+    file_walk: Option<DirEntryIter>,
 }
 
 impl SortedLoosePaths {
     pub fn at(path: impl AsRef<Path>, base: impl Into<PathBuf>, filename_prefix: Option<BString>) -> Self {
-        let file_walk = git_features::fs::walkdir_sorted_new(path).into_iter();
+        let root = path.as_ref().to_owned();
+        let file_walk = root
+            .is_dir()
+            .then(|| git_features::fs::walkdir_sorted_new(path).into_iter());
         SortedLoosePaths {
             base: base.into(),
+            root,
             filename_prefix,
             file_walk,
         }
@@ -27,18 +34,21 @@ impl Iterator for SortedLoosePaths {
     type Item = std::io::Result<(PathBuf, FullName)>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        for entry in self.file_walk.by_ref() {
+        let root = &self.root;
+        for entry in self.file_walk.as_mut()?.by_ref() {
             match entry {
                 Ok(entry) => {
                     if !entry.file_type().is_file() {
                         continue;
                     }
                     let full_path = entry.path().to_owned();
-                    if let Some((prefix, name)) = self
-                        .filename_prefix
-                        .as_deref()
-                        .and_then(|prefix| full_path.file_name().map(|name| (prefix, name)))
-                    {
+                    if let Some((prefix, name)) = self.filename_prefix.as_deref().and_then(|prefix| {
+                        full_path
+                            .strip_prefix(root)
+                            .ok()
+                            .and_then(|path| path.components().next())
+                            .map(|name| (prefix, name.as_os_str()))
+                    }) {
                         match git_path::os_str_into_bstr(name) {
                             Ok(name) => {
                                 if !name.starts_with(prefix) {
@@ -73,14 +73,46 @@ impl file::Store {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Delete the reflog for the given reference `name` and all directories that became empty as a result, returning `true`
+    /// if there was a reflog to delete.
+    ///
+    /// Like `git`, the `logs/refs` directory and category directories like `logs/refs/heads` are kept even if they are empty.
+    pub fn remove_reflog<'a, Name, E>(&self, name: Name) -> Result<bool, Error>
+    where
+        Name: TryInto<&'a FullNameRef, Error = E>,
+        crate::name::Error: From<E>,
+    {
+        let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+        Ok(self.remove_reflog_inner(name)?)
+    }
 }
 
 impl file::Store {
-    /// Implements the logic required to transform a fully qualified refname into its log name
-    pub(crate) fn reflog_path(&self, name: &FullNameRef) -> PathBuf {
+    /// Implements the logic required to transform a fully qualified refname into its log name.
+    ///
+    /// The path honors the namespace, and points into the common directory for all but worktree-private references
+    /// if this store is for a linked worktree.
+    pub fn reflog_path(&self, name: &FullNameRef) -> PathBuf {
         let (base, rela_path) = self.reflog_base_and_relative_path(name);
         base.join(rela_path)
     }
+
+    pub(in crate::store_impl::file) fn remove_reflog_inner(&self, name: &FullNameRef) -> std::io::Result<bool> {
+        let (base, relative_path) = self.reflog_base_and_relative_path(name);
+        let path = base.join(&relative_path);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        }
+        git_tempfile::remove_dir::empty_upward_until_boundary(
+            path.parent().expect("never without parent"),
+            &file::cleanup_boundary(&base, &relative_path),
+        )
+        .ok();
+        Ok(true)
+    }
 }
 
 ///
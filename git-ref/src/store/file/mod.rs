@@ -100,5 +100,26 @@ pub mod transaction;
 ///
 pub mod packed;
 
+///
+pub mod fetch_head;
+
+///
+pub mod write_symbolic;
+
+///
+pub mod pack_refs;
+
 mod raw_ext;
 pub use raw_ext::ReferenceExt;
+
+/// Like git, never remove `refs/` or category directories like `refs/heads/` when cleaning up empty directories
+/// after a reference or its log was deleted, as git wouldn't recognize the repository without the former.
+pub(in crate::store_impl::file) fn cleanup_boundary(base: &Path, relative_path: &Path) -> PathBuf {
+    let num_components = relative_path.components().count();
+    base.join(
+        relative_path
+            .components()
+            .take(num_components.saturating_sub(1).min(2))
+            .collect::<PathBuf>(),
+    )
+}
@@ -25,6 +25,9 @@ pub struct LooseThenPacked<'p, 's> {
     #[allow(dead_code)]
     iter_common_dir: Option<Peekable<SortedLoosePaths>>,
     buf: Vec<u8>,
+    /// The name of the last returned reference, to assure the merged output is sorted.
+    #[cfg(debug_assertions)]
+    last_name: Option<FullName>,
 }
 
 enum IterKind {
@@ -116,6 +119,24 @@ impl<'p, 's> Iterator for LooseThenPacked<'p, 's> {
     type Item = Result<Reference, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let res = self.next_merged();
+        #[cfg(debug_assertions)]
+        if let Some(Ok(r)) = &res {
+            if let Some(previous) = self.last_name.replace(r.name.clone()) {
+                debug_assert!(
+                    previous < r.name,
+                    "BUG: loose and packed references must be merged in order, but {} was followed by {}",
+                    previous.as_bstr(),
+                    r.name.as_bstr()
+                );
+            }
+        }
+        res
+    }
+}
+
+impl<'p, 's> LooseThenPacked<'p, 's> {
+    fn next_merged(&mut self) -> Option<Result<Reference, Error>> {
         fn advance_to_non_private(iter: &mut Peekable<SortedLoosePaths>) {
             while let Some(Ok((_path, name))) = iter.peek() {
                 if name.category().map_or(true, |cat| cat.is_worktree_private()) {
@@ -243,14 +264,20 @@ pub(crate) enum IterInfo<'a> {
 }
 
 impl<'a> IterInfo<'a> {
-    fn prefix(&self) -> Option<&Path> {
-        match self {
-            IterInfo::Base { .. } => None,
-            IterInfo::PrefixAndBase { prefix, .. } => Some(*prefix),
-            IterInfo::ComputedIterationRoot { prefix, .. } | IterInfo::BaseAndIterRoot { prefix, .. } => {
-                prefix.as_ref().into()
-            }
+    /// Return the prefix to match the names of packed references with, which ends with a slash if only references
+    /// in a directory are to be found, just like when iterating loose references.
+    fn packed_prefix(&self) -> Option<BString> {
+        let (prefix, is_dir) = match self {
+            IterInfo::Base { .. } => return None,
+            IterInfo::PrefixAndBase { prefix, .. } => (*prefix, true),
+            IterInfo::BaseAndIterRoot { prefix, .. } => (prefix.as_ref(), true),
+            IterInfo::ComputedIterationRoot { prefix, .. } => (prefix.as_ref(), false),
+        };
+        let mut prefix = path_to_name(prefix).into_owned();
+        if is_dir && !prefix.ends_with(b"/") {
+            prefix.push(b'/');
         }
+        Some(prefix)
     }
 
     fn into_iter(self) -> Peekable<SortedLoosePaths> {
@@ -287,7 +314,8 @@ impl<'a> IterInfo<'a> {
             ));
         }
         let iter_root = base.join(prefix.as_ref());
-        if iter_root.is_dir() {
+        let is_dir_prefix = path_to_name(prefix.as_ref()).ends_with(b"/");
+        if is_dir_prefix || iter_root.is_dir() {
             Ok(IterInfo::BaseAndIterRoot {
                 base,
                 iter_root,
@@ -388,8 +416,8 @@ impl file::Store {
             common_dir: self.common_dir(),
             iter_packed: match packed {
                 Some(packed) => Some(
-                    match git_dir_info.prefix() {
-                        Some(prefix) => packed.iter_prefixed(path_to_name(prefix).into_owned()),
+                    match git_dir_info.packed_prefix() {
+                        Some(prefix) => packed.iter_prefixed(prefix),
                         None => packed.iter(),
                     }
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
@@ -401,6 +429,8 @@ impl file::Store {
             iter_common_dir: common_dir_info.map(IterInfo::into_iter),
             buf: Vec::new(),
             namespace: self.namespace.as_ref(),
+            #[cfg(debug_assertions)]
+            last_name: None,
         })
     }
 }
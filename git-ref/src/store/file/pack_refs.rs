@@ -0,0 +1,90 @@
+use crate::{
+    file,
+    file::transaction::{FindObjectFn, PackedRefs},
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    Category,
+};
+
+/// Options for [`file::Store::pack_refs()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// If `true`, pack all references like `git pack-refs --all`, instead of only tags and references that are packed already.
+    pub all: bool,
+    /// If `true`, keep the loose references after packing them like `git pack-refs --no-prune`.
+    ///
+    /// Otherwise they are deleted, which is the default in `git` as well.
+    pub keep_loose_refs: bool,
+}
+
+/// The error returned by [`file::Store::pack_refs()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    PackedOpen(#[from] crate::packed::buffer::open::Error),
+    #[error("Could not traverse loose references")]
+    Traversal(#[from] std::io::Error),
+    #[error(transparent)]
+    Iter(#[from] file::iter::loose_then_packed::Error),
+    #[error(transparent)]
+    Prepare(#[from] file::transaction::prepare::Error),
+    #[error(transparent)]
+    Commit(#[from] file::transaction::commit::Error),
+}
+
+impl file::Store {
+    /// Write loose references into the `packed-refs` file and return the edits that were performed, similar to `git pack-refs`.
+    ///
+    /// Tags are always packed, other references only if they are packed already or if [`Options::all`] is set.
+    /// Symbolic references, references private to worktrees and loose references that can't be parsed are never packed.
+    /// `find` is used to look up objects to write the peeled object of annotated tags alongside them.
+    ///
+    /// The `packed-refs` file is written to a temporary file first and then moved into place.
+    pub fn pack_refs(&self, options: Options, find: Box<FindObjectFn>) -> Result<Vec<RefEdit>, Error> {
+        let packed = self.cached_packed_buffer()?;
+        let mut edits = Vec::new();
+        for reference in self.loose_iter()? {
+            let reference = match reference {
+                Ok(reference) => reference,
+                Err(file::iter::loose_then_packed::Error::ReferenceCreation { .. }) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if reference.target.kind() == crate::Kind::Symbolic {
+                continue;
+            }
+            let is_packed = packed.as_ref().map_or(false, |packed| {
+                packed.try_find(reference.name.as_ref()).ok().flatten().is_some()
+            });
+            match reference.name.category() {
+                Some(category) if category.is_worktree_private() => continue,
+                Some(Category::Tag) => {}
+                _ if options.all || is_packed => {}
+                _ => continue,
+            }
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustExistAndMatch(reference.target.clone()),
+                    new: reference.target,
+                },
+                name: reference.name,
+                deref: false,
+            });
+        }
+        if edits.is_empty() {
+            return Ok(edits);
+        }
+
+        let packed_refs = if options.keep_loose_refs {
+            PackedRefs::DeletionsAndNonSymbolicUpdates(find)
+        } else {
+            PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(find)
+        };
+        Ok(self
+            .transaction()
+            .packed_refs(packed_refs)
+            .prepare(edits, git_lock::acquire::Fail::Immediately)?
+            // The reflog isn't changed as references keep their value.
+            .commit(None)?)
+    }
+}
@@ -75,15 +75,15 @@ pub(crate) mod modifiable {
     impl file::Store {
         pub(crate) fn force_refresh_packed_buffer(&self) -> Result<(), packed::buffer::open::Error> {
             self.packed.force_refresh(|| {
-                let modified = self.packed_refs_path().metadata()?.modified()?;
-                self.open_packed_buffer().map(|packed| Some(modified).zip(packed))
+                let stamp = git_features::fs::Stamp::at(self.packed_refs_path());
+                self.open_packed_buffer().map(|packed| stamp.zip(packed))
             })
         }
         pub(crate) fn assure_packed_refs_uptodate(
             &self,
         ) -> Result<Option<super::SharedBufferSnapshot>, packed::buffer::open::Error> {
             self.packed.recent_snapshot(
-                || self.packed_refs_path().metadata().and_then(|m| m.modified()).ok(),
+                || git_features::fs::Stamp::at(self.packed_refs_path()),
                 || self.open_packed_buffer(),
             )
         }
@@ -1,5 +1,8 @@
 use crate::{
-    store_impl::file::{transaction::PackedRefs, Transaction},
+    store_impl::{
+        file,
+        file::{transaction::PackedRefs, Transaction},
+    },
     transaction::{Change, LogChange, RefEdit, RefLog},
     Target,
 };
@@ -8,7 +11,7 @@ impl<'s> Transaction<'s> {
     /// Make all [prepared][Transaction::prepare()] permanent and return the performed edits which represent the current
     /// state of the affected refs in the ref store in that instant. Please note that the obtained edits may have been
     /// adjusted to contain more dependent edits or additional information.
-    /// `committer` is used in the reflog.
+    /// `committer` is used in the reflog, and if it is `None`, no reflog is written at all, like for `git symbolic-ref`.
     ///
     /// On error the transaction may have been performed partially, depending on the nature of the error, and no attempt to roll back
     /// partial changes is made.
@@ -23,7 +26,8 @@ impl<'s> Transaction<'s> {
     ///   along with empty parent directories
     ///
     /// Note that transactions will be prepared automatically as needed.
-    pub fn commit(self, committer: git_actor::SignatureRef<'_>) -> Result<Vec<RefEdit>, Error> {
+    pub fn commit<'a>(self, committer: impl Into<Option<git_actor::SignatureRef<'a>>>) -> Result<Vec<RefEdit>, Error> {
+        let committer = committer.into();
         let mut updates = self.updates.expect("BUG: must call prepare before commit");
         let delete_loose_refs = matches!(
             self.packed_refs,
@@ -41,12 +45,48 @@ impl<'s> Transaction<'s> {
                         RefLog::Only => (false, true),
                         RefLog::AndReference => (true, true),
                     };
-                    if update_reflog {
+                    if let Some(committer) = committer.filter(|_| update_reflog) {
                         match new {
-                            Target::Symbolic(_) => {} // no reflog for symref changes
+                            Target::Symbolic(referent) => {
+                                // Like git, only log symref changes if the new referent points to an object.
+                                let is_unchanged = matches!(
+                                    expected,
+                                    PreviousValue::MustExistAndMatch(Target::Symbolic(previous)) if previous == referent
+                                );
+                                let new_oid = if is_unchanged {
+                                    None
+                                } else {
+                                    peeled_id(self.store, referent.as_ref())
+                                };
+                                if let Some(new_oid) = new_oid {
+                                    let previous = match expected {
+                                        PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
+                                        PreviousValue::MustExistAndMatch(Target::Symbolic(name)) => {
+                                            peeled_id(self.store, name.as_ref())
+                                        }
+                                        _ => None,
+                                    };
+                                    self.store.reflog_create_or_append(
+                                        change.update.name.as_ref(),
+                                        &lock,
+                                        previous,
+                                        &new_oid,
+                                        committer,
+                                        log.message.as_ref(),
+                                        log.force_create_reflog,
+                                    )?;
+                                }
+                            }
                             Target::Peeled(new_oid) => {
                                 let previous = match expected {
                                     PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
+                                    // A symbolic ref is detached, which doesn't happen when its referent is updated.
+                                    PreviousValue::MustExistAndMatch(Target::Symbolic(name))
+                                        if log.mode == RefLog::AndReference
+                                            && change.leaf_referent_previous_oid.is_none() =>
+                                    {
+                                        peeled_id(self.store, name.as_ref())
+                                    }
                                     _ => None,
                                 }
                                 .or(change.leaf_referent_previous_oid);
@@ -98,27 +138,17 @@ impl<'s> Transaction<'s> {
         }
 
         for change in updates.iter_mut() {
-            let (reflog_root, relative_name) = self.store.reflog_base_and_relative_path(change.update.name.as_ref());
             match &change.update.change {
                 Change::Update { .. } => {}
                 Change::Delete { .. } => {
                     // Reflog deletion happens first in case it fails a ref without log is less terrible than
                     // a log without a reference.
-                    let reflog_path = reflog_root.join(relative_name);
-                    if let Err(err) = std::fs::remove_file(&reflog_path) {
-                        if err.kind() != std::io::ErrorKind::NotFound {
-                            return Err(Error::DeleteReflog {
-                                source: err,
-                                full_name: change.name(),
-                            });
-                        }
-                    } else {
-                        git_tempfile::remove_dir::empty_upward_until_boundary(
-                            reflog_path.parent().expect("never without parent"),
-                            &reflog_root,
-                        )
-                        .ok();
-                    }
+                    self.store
+                        .remove_reflog_inner(change.update.name.as_ref())
+                        .map_err(|err| Error::DeleteReflog {
+                            source: err,
+                            full_name: change.name(),
+                        })?;
                 }
             }
         }
@@ -155,6 +185,14 @@ impl<'s> Transaction<'s> {
         Ok(updates.into_iter().map(|edit| edit.update).collect())
     }
 }
+
+/// Return the object id the reference `name` points to, or `None` if it doesn't exist, can't be read or is symbolic.
+fn peeled_id(store: &file::Store, name: &crate::FullNameRef) -> Option<git_hash::ObjectId> {
+    match store.try_find(name).ok()??.target {
+        Target::Peeled(oid) => Some(oid),
+        Target::Symbolic(_) => None,
+    }
+}
 mod error {
     use git_object::bstr::BString;
 
@@ -3,7 +3,7 @@ use crate::{
     store_impl::{
         file,
         file::{
-            loose,
+            cleanup_boundary, loose,
             transaction::{Edit, PackedRefs},
             Transaction,
         },
@@ -53,9 +53,9 @@ impl<'s> Transaction<'s> {
             Change::Delete { expected, .. } => {
                 let (base, relative_path) = store.reference_path_with_base(change.update.name.as_ref());
                 let lock = git_lock::Marker::acquire_to_hold_resource(
-                    base.join(relative_path),
+                    base.join(&relative_path),
                     lock_fail_mode,
-                    Some(base.into_owned()),
+                    Some(cleanup_boundary(&base, &relative_path)),
                 )
                 .map_err(|err| Error::LockAcquire {
                     source: err,
@@ -99,9 +99,9 @@ impl<'s> Transaction<'s> {
             Change::Update { expected, new, .. } => {
                 let (base, relative_path) = store.reference_path_with_base(change.update.name.as_ref());
                 let mut lock = git_lock::File::acquire_to_update_resource(
-                    base.join(relative_path),
+                    base.join(&relative_path),
                     lock_fail_mode,
-                    Some(base.into_owned()),
+                    Some(cleanup_boundary(&base, &relative_path)),
                 )
                 .map_err(|err| Error::LockAcquire {
                     source: err,
@@ -182,13 +182,20 @@ impl<'s> Transaction<'s> {
         let store = self.store;
         let mut updates: Vec<_> = edits
             .into_iter()
-            .map(|update| Edit {
-                update,
-                lock: None,
-                parent_index: None,
-                leaf_referent_previous_oid: None,
+            .map(|update| {
+                if is_fetch_head(update.name.as_ref()) {
+                    return Err(Error::FetchHead {
+                        full_name: update.name.as_bstr().to_owned(),
+                    });
+                }
+                Ok(Edit {
+                    update,
+                    lock: None,
+                    parent_index: None,
+                    leaf_referent_previous_oid: None,
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         updates
             .pre_process(
                 |name| {
@@ -349,6 +356,15 @@ impl<'s> Transaction<'s> {
     }
 }
 
+/// `FETCH_HEAD` looks like a pseudo-ref but may list many objects along with their origin, so it can't be edited like one.
+fn is_fetch_head(name: &FullNameRef) -> bool {
+    use crate::Category::*;
+    matches!(
+        name.category_and_short_name(),
+        Some((PseudoRef | MainPseudoRef | LinkedPseudoRef { .. }, short_name)) if short_name == "FETCH_HEAD"
+    )
+}
+
 fn possibly_adjust_name_for_prefixes(name: &FullNameRef) -> Option<FullName> {
     match name.category_and_short_name() {
         Some((c, sn)) => {
@@ -415,6 +431,8 @@ mod error {
         },
         #[error("Could not read reference")]
         ReferenceDecode(#[from] file::loose::reference::decode::Error),
+        #[error("{full_name:?} is not a reference and can't be edited in a transaction, use `Store::write_fetch_head()` instead")]
+        FetchHead { full_name: BString },
     }
 }
 
@@ -0,0 +1,46 @@
+use crate::{
+    file,
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    FullName, Reference, Target,
+};
+
+/// The error returned by [`file::Store::write_symbolic()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Prepare(#[from] file::transaction::prepare::Error),
+    #[error(transparent)]
+    Commit(#[from] file::transaction::commit::Error),
+}
+
+impl file::Store {
+    /// Create or overwrite the loose reference `name` to be a symbolic reference pointing to `target`, like
+    /// `refs/remotes/origin/HEAD -> refs/remotes/origin/main`, and return it.
+    ///
+    /// `target` doesn't have to exist. If `name` currently is a symbolic reference, it is changed itself instead of its referent.
+    /// No reflog is written, just like `git symbolic-ref` does by default, and the [namespace][file::Store::namespace] is applied to `name`.
+    pub fn write_symbolic(&self, name: FullName, target: FullName) -> Result<Reference, Error> {
+        let edits = self
+            .transaction()
+            .prepare(
+                Some(RefEdit {
+                    change: Change::Update {
+                        log: LogChange::default(),
+                        expected: PreviousValue::Any,
+                        new: Target::Symbolic(target.clone()),
+                    },
+                    name,
+                    deref: false,
+                }),
+                git_lock::acquire::Fail::Immediately,
+            )?
+            .commit(None)?;
+        let edit = edits.into_iter().next().expect("one edit in, one edit out");
+        Ok(Reference {
+            name: edit.name,
+            target: Target::Symbolic(target),
+            peeled: None,
+        })
+    }
+}
@@ -17,6 +17,9 @@ use git_object::bstr::BString;
 use crate::{FullName, Target};
 
 /// A change to the reflog.
+///
+/// Updates of symbolic references are only logged if their new referent points to an object, in which case the entry
+/// records the objects pointed to by the previous and the new referent.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 pub struct LogChange {
     /// How to treat the reference log.
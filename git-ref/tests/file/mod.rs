@@ -32,6 +32,7 @@ fn store_writable(name: &str) -> crate::Result<(git_testtools::tempfile::TempDir
 }
 
 mod log;
+mod properties;
 mod reference;
 mod store;
 pub(crate) mod transaction;
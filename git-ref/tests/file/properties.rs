@@ -0,0 +1,169 @@
+//! Randomized tests for the ordering guarantees of reference iteration, which must be the same no matter if references
+//! are loose, packed or both, and no matter how the packed references are loaded.
+use std::{collections::BTreeSet, convert::TryInto};
+
+use git_ref::{
+    file::{transaction::PackedRefs, Store},
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    Target,
+};
+use git_testtools::hex_to_id;
+
+use crate::file::transaction::prepare_and_commit::{committer, empty_store};
+
+/// Return valid reference names made of path components which are likely to reveal differences between sorting by path
+/// component and sorting by bytes, as `-` and `.` sort before `/`.
+fn random_names(rng: &fastrand::Rng) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    for _ in 0..rng.usize(1..40) {
+        let mut name = String::from("refs/heads");
+        for _ in 0..rng.usize(1..4) {
+            name.push('/');
+            name.push_str(["a", "a-b", "a.b", "a0", "b", "ab"][rng.usize(..6)]);
+        }
+        let conflicts_with_existing_directory_or_file = names.iter().any(|existing: &String| {
+            existing.starts_with(&format!("{}/", name)) || name.starts_with(&format!("{}/", existing))
+        });
+        if !conflicts_with_existing_directory_or_file {
+            names.insert(name);
+        }
+    }
+    names
+}
+
+fn update(name: &str, target: Target) -> crate::Result<RefEdit> {
+    Ok(RefEdit {
+        change: Change::Update {
+            log: LogChange::default(),
+            expected: PreviousValue::Any,
+            new: target,
+        },
+        name: name.try_into()?,
+        deref: false,
+    })
+}
+
+fn names_of(
+    iter: impl Iterator<Item = Result<git_ref::Reference, impl std::error::Error + 'static>>,
+) -> crate::Result<Vec<String>> {
+    iter.map(|r| Ok(r?.name.as_bstr().to_string())).collect()
+}
+
+/// Write all `names` as loose references, and pack the given `packed` ones, which optionally also remain loose.
+fn store_with(
+    names: &BTreeSet<String>,
+    packed: &BTreeSet<&String>,
+    keep_loose: bool,
+) -> crate::Result<(tempfile::TempDir, Store)> {
+    let (dir, store) = empty_store()?;
+    let target = Target::Peeled(hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"));
+    store
+        .transaction()
+        .prepare(
+            names
+                .iter()
+                .map(|name| update(name, target.clone()))
+                .collect::<Result<Vec<_>, _>>()?,
+            git_lock::acquire::Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    if !packed.is_empty() {
+        store
+            .transaction()
+            .packed_refs(if keep_loose {
+                PackedRefs::DeletionsAndNonSymbolicUpdates(Box::new(|_, _| Ok(Some(git_object::Kind::Commit))))
+            } else {
+                PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(Box::new(|_, _| {
+                    Ok(Some(git_object::Kind::Commit))
+                }))
+            })
+            .prepare(
+                packed
+                    .iter()
+                    .map(|name| update(name, target.clone()))
+                    .collect::<Result<Vec<_>, _>>()?,
+                git_lock::acquire::Fail::Immediately,
+            )?
+            .commit(committer().to_ref())?;
+    }
+    Ok((dir, store))
+}
+
+#[test]
+fn references_are_sorted_by_name_no_matter_where_they_are_stored() -> crate::Result {
+    for seed in 0..25 {
+        let rng = fastrand::Rng::with_seed(seed);
+        let names = random_names(&rng);
+        let expected: Vec<_> = names.iter().cloned().collect();
+        let packed: BTreeSet<_> = names.iter().filter(|_| rng.bool()).collect();
+        let keep_loose = rng.bool();
+        let (_keep, store) = store_with(&names, &packed, keep_loose)?;
+
+        assert_eq!(
+            names_of(store.iter()?.all()?)?,
+            expected,
+            "seed {}: all references, loose and packed, are merged in order",
+            seed
+        );
+
+        let loose: Vec<_> = names
+            .iter()
+            .filter(|name| keep_loose || !packed.contains(name))
+            .cloned()
+            .collect();
+        assert_eq!(
+            names_of(store.loose_iter()?)?,
+            loose,
+            "seed {}: loose references are traversed in the same order as packed ones",
+            seed
+        );
+
+        // Prefixes ending in a slash are directories, while `a.` can never be one and matches partially.
+        for prefix in ["refs/heads/", "refs/heads/a/", "refs/heads/a."] {
+            let prefixed: Vec<_> = expected
+                .iter()
+                .filter(|name| name.starts_with(prefix))
+                .cloned()
+                .collect();
+            assert_eq!(
+                names_of(store.iter()?.prefixed(prefix)?)?,
+                prefixed,
+                "seed {}: iteration with prefix {:?} is a sorted subset",
+                seed,
+                prefix
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn packed_references_are_the_same_whether_memory_mapped_or_read_into_memory() -> crate::Result {
+    for seed in 0..25 {
+        let rng = fastrand::Rng::with_seed(seed);
+        let names = random_names(&rng);
+        let packed: BTreeSet<_> = names.iter().collect();
+        let (_keep, store) = store_with(&names, &packed, false)?;
+
+        let read = git_ref::packed::Buffer::open(store.packed_refs_path(), u64::MAX)?;
+        let mapped = git_ref::packed::Buffer::open(store.packed_refs_path(), 0)?;
+        let read: Vec<_> = read
+            .iter()?
+            .map(|r| r.map(|r| (r.name.to_owned(), r.target())))
+            .collect::<Result<_, _>>()?;
+        let mapped: Vec<_> = mapped
+            .iter()?
+            .map(|r| r.map(|r| (r.name.to_owned(), r.target())))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(read, mapped, "seed {}", seed);
+        assert_eq!(
+            read.iter()
+                .map(|(name, _)| name.as_bstr().to_string())
+                .collect::<Vec<_>>(),
+            names.iter().cloned().collect::<Vec<_>>(),
+            "seed {}: packed references are sorted by name",
+            seed
+        );
+    }
+    Ok(())
+}
@@ -226,3 +226,58 @@ mod parse {
         );
     }
 }
+
+mod write_symbolic {
+    use std::convert::TryInto;
+
+    use git_ref::{file::ReferenceExt, peel, Target};
+
+    #[test]
+    fn creates_and_overwrites_symbolic_refs_which_can_then_be_followed() -> crate::Result {
+        let (_keep, store) = crate::file::store_writable("make_ref_repository.sh")?;
+        let r = store.write_symbolic("refs/heads/sym".try_into()?, "refs/heads/main".try_into()?)?;
+        assert_eq!(r.name.as_bstr(), "refs/heads/sym");
+        assert_eq!(r.target, Target::Symbolic("refs/heads/main".try_into()?));
+
+        let found = store.find_loose("refs/heads/sym")?;
+        assert_eq!(found.target, r.target, "the change is persisted");
+        assert_eq!(
+            r.follow(&store).expect("symbolic")?.name.as_bstr(),
+            "refs/heads/main",
+            "one level is followed"
+        );
+
+        let r = store.write_symbolic("refs/heads/sym-of-sym".try_into()?, "refs/heads/sym".try_into()?)?;
+        let mut leaf = r.clone();
+        let id = leaf.peel_to_id_in_place(&store, peel::none)?;
+        assert_eq!(
+            id,
+            store.find_loose("main")?.target.into_id(),
+            "all levels are followed"
+        );
+
+        store.write_symbolic("refs/heads/sym".try_into()?, "refs/heads/sym-of-sym".try_into()?)?;
+        let mut r = r;
+        assert!(
+            matches!(
+                r.peel_to_id_in_place(&store, peel::none).unwrap_err(),
+                peel::to_id::Error::Cycle { .. }
+            ),
+            "overwriting creates a cycle, which is detected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_reflog_is_written_even_if_the_target_exists() -> crate::Result {
+        let (keep, store) = crate::file::store_writable("make_ref_repository.sh")?;
+        store.write_symbolic("refs/heads/sym".try_into()?, "refs/heads/main".try_into()?)?;
+        assert!(store.find_loose("main").is_ok(), "the target points to an object");
+        assert!(
+            !keep.path().join(".git/logs/refs/heads/sym").exists(),
+            "like `git symbolic-ref`, no reflog is created"
+        );
+        assert!(store.reflog_iter("refs/heads/sym", &mut Vec::new())?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use git_ref::file::fetch_head::Entry;
+
+use crate::file::Store;
+
+fn store_at(dir: &std::path::Path) -> Store {
+    Store::at(
+        dir.join("local").join(".git"),
+        git_ref::store::WriteReflog::Normal,
+        git_hash::Kind::Sha1,
+    )
+}
+
+#[test]
+fn read_parses_all_kinds_of_references() -> crate::Result {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_fetch_head_repository.sh")?;
+    let entries = store_at(&dir).read_fetch_head()?;
+    assert_eq!(
+        entries
+            .iter()
+            .map(|e| (e.ref_name.to_string(), e.remote_url.to_string(), e.not_for_merge))
+            .collect::<Vec<_>>(),
+        ["HEAD", "refs/heads/other", "refs/tags/v1", "refs/pull/1/head"]
+            .iter()
+            .map(|name| (name.to_string(), "../remote".to_string(), false))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        entries[0].id, entries[1].id,
+        "HEAD and the branch point to the same commit"
+    );
+    assert_ne!(entries[0].id, entries[2].id, "the annotated tag is fetched as is");
+    assert_eq!(
+        entries.iter().map(Entry::note).collect::<Vec<_>>(),
+        vec![
+            None,
+            Some("branch 'other'".into()),
+            Some("tag 'v1'".into()),
+            Some("'refs/pull/1/head'".into())
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn write_reproduces_what_git_writes() -> crate::Result {
+    let dir = git_testtools::scripted_fixture_repo_writable("make_fetch_head_repository.sh")?;
+    let store = store_at(dir.path());
+    for name in ["FETCH_HEAD.explicit", "FETCH_HEAD.remote-tracking"] {
+        let expected = std::fs::read(dir.path().join(name))?;
+        let entries = expected
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| Entry::from_line(line.into()).expect("valid"))
+            .collect::<Vec<_>>();
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.not_for_merge == (name == "FETCH_HEAD.remote-tracking")),
+            "without configured upstream, nothing is merged"
+        );
+
+        store.write_fetch_head(&entries)?;
+        assert_eq!(std::fs::read(store.git_dir().join("FETCH_HEAD"))?, expected);
+        assert_eq!(store.read_fetch_head()?, entries);
+    }
+    Ok(())
+}
+
+#[test]
+fn read_fails_on_malformed_lines_and_is_empty_if_there_is_no_fetch_head() -> crate::Result {
+    let dir = git_testtools::scripted_fixture_repo_writable("make_fetch_head_repository.sh")?;
+    let store = store_at(dir.path());
+    let path = store.git_dir().join("FETCH_HEAD");
+    std::fs::write(
+        &path,
+        "0000000000000000000000000000000000000000\t\t../remote\nnot-a-hash\t\t../remote\n",
+    )?;
+    assert!(matches!(
+        store.read_fetch_head(),
+        Err(git_ref::file::fetch_head::read::Error::Parse { line_number: 2, .. })
+    ));
+
+    std::fs::remove_file(path)?;
+    assert_eq!(store.read_fetch_head()?, vec![]);
+    Ok(())
+}
@@ -1,3 +1,5 @@
+mod fetch_head;
 mod find;
 mod iter;
+mod packed;
 mod reflog;
@@ -0,0 +1,117 @@
+mod cached_packed_buffer {
+    use std::convert::TryInto;
+
+    use git_ref::{
+        file,
+        transaction::{Change, PreviousValue, RefEdit, RefLog},
+    };
+
+    use crate::file::{store_writable, transaction::prepare_and_commit::committer};
+
+    #[test]
+    fn changes_made_through_another_store_are_picked_up() -> crate::Result {
+        let (_keep, store) = store_writable("make_packed_ref_repository.sh")?;
+        let packed = store.cached_packed_buffer()?.expect("packed-refs present");
+        assert!(packed.try_find("main")?.is_some());
+        assert!(
+            git_features::threading::OwnShared::ptr_eq(&packed, &store.cached_packed_buffer()?.expect("still present")),
+            "the cached buffer is reused while the file is unchanged"
+        );
+
+        let other_store = file::Store::at(
+            store.git_dir(),
+            git_ref::store::WriteReflog::Normal,
+            git_hash::Kind::Sha1,
+        );
+        other_store
+            .transaction()
+            .prepare(
+                Some(RefEdit {
+                    change: Change::Delete {
+                        expected: PreviousValue::MustExist,
+                        log: RefLog::AndReference,
+                    },
+                    name: "refs/heads/main".try_into()?,
+                    deref: false,
+                }),
+                git_lock::acquire::Fail::Immediately,
+            )?
+            .commit(committer().to_ref())?;
+
+        let reloaded = store.cached_packed_buffer()?.expect("packed-refs still present");
+        assert!(
+            reloaded.try_find("main")?.is_none(),
+            "the deletion through the other store is visible"
+        );
+        assert!(packed.try_find("main")?.is_some(), "previous snapshots don't change");
+
+        std::fs::remove_file(store.packed_refs_path())?;
+        assert!(
+            store.cached_packed_buffer()?.is_none(),
+            "the cache is cleared once the file is gone"
+        );
+        Ok(())
+    }
+}
+
+mod pack_refs {
+    use git_object::bstr::BString;
+    use git_odb::Find;
+    use git_ref::file::{pack_refs, transaction::FindObjectFn};
+
+    use crate::file::{store_with_packed_refs, store_writable};
+
+    fn find_in(odb: git_odb::Handle) -> Box<FindObjectFn> {
+        Box::new(move |oid, buf| {
+            odb.try_find(oid, buf)
+                .map(|obj| obj.map(|obj| obj.kind))
+                .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync>)
+        })
+    }
+
+    #[test]
+    fn all_with_pruning_matches_git() -> crate::Result {
+        let (_keep, store) = store_writable("make_ref_repository.sh")?;
+        let odb = git_odb::at(store.git_dir().join("objects"))?;
+        let edits = store.pack_refs(
+            pack_refs::Options {
+                all: true,
+                ..Default::default()
+            },
+            find_in(odb),
+        )?;
+        assert_eq!(edits.len(), 8, "broken and symbolic refs are skipped");
+        assert!(
+            store
+                .loose_iter()?
+                .filter_map(Result::ok)
+                .all(|r| r.kind() == git_ref::Kind::Symbolic),
+            "only symbolic refs are left"
+        );
+
+        let expected: BString = std::fs::read(store_with_packed_refs()?.packed_refs_path())?.into();
+        let actual: BString = std::fs::read(store.packed_refs_path())?.into();
+        assert_eq!(actual, expected, "peeled tags are written just like git does");
+        Ok(())
+    }
+
+    #[test]
+    fn only_tags_are_packed_by_default_and_loose_refs_can_be_kept() -> crate::Result {
+        let (_keep, store) = store_writable("make_ref_repository.sh")?;
+        let odb = git_odb::at(store.git_dir().join("objects"))?;
+        let edits = store.pack_refs(
+            pack_refs::Options {
+                keep_loose_refs: true,
+                ..Default::default()
+            },
+            find_in(odb),
+        )?;
+        assert!(!edits.is_empty());
+        assert!(edits.iter().all(|edit| edit.name.as_bstr().starts_with(b"refs/tags/")));
+
+        let packed = store.open_packed_buffer()?.expect("packed-refs written");
+        assert_eq!(packed.iter()?.count(), edits.len());
+        assert!(store.find_loose("refs/tags/t1").is_ok(), "loose refs are kept");
+        Ok(())
+    }
+}
@@ -65,3 +65,56 @@ mod iter_rev {
         Ok(())
     }
 }
+
+mod remove_reflog {
+    use std::convert::TryInto;
+
+    fn store_writable() -> crate::Result<(git_testtools::tempfile::TempDir, crate::file::Store)> {
+        let dir = git_testtools::scripted_fixture_repo_writable("make_repo_for_reflog.sh")?;
+        let store = crate::file::Store::at(
+            dir.path().join(".git"),
+            git_ref::store::WriteReflog::Normal,
+            git_hash::Kind::Sha1,
+        );
+        Ok((dir, store))
+    }
+
+    #[test]
+    fn reflog_path_is_in_the_logs_directory() -> crate::Result {
+        let (dir, store) = store_writable()?;
+        assert_eq!(
+            store.reflog_path("refs/heads/main".try_into()?),
+            dir.path()
+                .join(".git")
+                .join("logs")
+                .join("refs")
+                .join("heads")
+                .join("main")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_parent_directories_are_pruned_up_to_the_category_directory() -> crate::Result {
+        let (dir, store) = store_writable()?;
+        let nested = "refs/heads/a/b/c";
+        let nested_path = store.reflog_path(nested.try_into()?);
+        std::fs::create_dir_all(nested_path.parent().expect("parent"))?;
+        std::fs::copy(store.reflog_path("refs/heads/main".try_into()?), &nested_path)?;
+        assert!(store.reflog_exists(nested)?);
+
+        assert!(store.remove_reflog(nested)?, "the log existed");
+        assert!(!store.reflog_exists(nested)?);
+        let heads = dir.path().join(".git").join("logs").join("refs").join("heads");
+        assert!(!heads.join("a").exists(), "all empty leading directories are removed");
+        assert!(heads.join("main").is_file(), "other logs are untouched");
+
+        assert!(store.remove_reflog("refs/heads/main")?);
+        assert!(heads.is_dir(), "category directories remain even if empty, like in git");
+        assert!(
+            !store.remove_reflog("refs/heads/main")?,
+            "removing a log that doesn't exist isn't an error"
+        );
+        Ok(())
+    }
+}
@@ -395,6 +395,100 @@ fn cancellation_after_preparation_leaves_no_change() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn pseudo_refs_are_locked_next_to_them_and_only_get_a_reflog_if_forced() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    let new = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    for (name, force_create_reflog) in [("ORIG_HEAD", false), ("CHERRY_PICK_HEAD", true)] {
+        let tx = store.transaction().prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        force_create_reflog,
+                        ..Default::default()
+                    },
+                    new: Target::Peeled(new),
+                    expected: PreviousValue::MustNotExist,
+                },
+                name: name.try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+        )?;
+        assert!(
+            dir.path().join(format!("{}.lock", name)).is_file(),
+            "pseudo-refs are locked right next to them"
+        );
+        tx.commit(committer().to_ref())?;
+
+        assert_eq!(store.find_loose(name)?.target, Target::Peeled(new));
+        assert_eq!(
+            store.reflog_exists(name)?,
+            force_create_reflog,
+            "pseudo-refs don't get a reflog unless it's forced or already present"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn pseudo_refs_can_not_be_updated_while_locked() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    let _lock = git_lock::Marker::acquire_to_hold_resource(dir.path().join("ORIG_HEAD"), Fail::Immediately, None)?;
+
+    let res = store.transaction().prepare(
+        Some(RefEdit {
+            change: Change::Update {
+                log: LogChange::default(),
+                new: Target::Peeled(hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242")),
+                expected: PreviousValue::Any,
+            },
+            name: "ORIG_HEAD".try_into()?,
+            deref: false,
+        }),
+        Fail::Immediately,
+    );
+    match res {
+        Err(transaction::prepare::Error::LockAcquire {
+            source: git_lock::acquire::Error::PermanentlyLocked { resource_path, .. },
+            full_name,
+        }) => {
+            assert_eq!(full_name, "ORIG_HEAD");
+            assert_eq!(resource_path, dir.path().join("ORIG_HEAD"));
+        }
+        Err(err) => unreachable!("unexpected error: {:?}", err),
+        Ok(_) => unreachable!("the lock is held elsewhere"),
+    }
+    assert!(!dir.path().join("ORIG_HEAD").exists(), "nothing was written");
+    Ok(())
+}
+
+#[test]
+fn fetch_head_can_not_be_edited_as_it_is_no_reference() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    for name in ["FETCH_HEAD", "main-worktree/FETCH_HEAD"] {
+        let res = store.transaction().prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    new: Target::Peeled(hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242")),
+                    expected: PreviousValue::Any,
+                },
+                name: name.try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+        );
+        match res {
+            Err(transaction::prepare::Error::FetchHead { full_name }) => assert_eq!(full_name, name),
+            Err(err) => unreachable!("unexpected error: {:?}", err),
+            Ok(_) => unreachable!("FETCH_HEAD can't be edited"),
+        }
+    }
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 0, "no lock was created");
+    Ok(())
+}
+
 #[test]
 fn symbolic_head_missing_referent_then_update_referent() -> crate::Result {
     for reflog_writemode in &[WriteReflog::Normal, WriteReflog::Disable, WriteReflog::Always] {
@@ -541,6 +635,100 @@ fn symbolic_head_missing_referent_then_update_referent() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn symbolic_head_pointing_to_existing_referent_is_logged_with_previous_and_new_object() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let previous_oid = store.find_loose("main")?.target.into_id();
+    let other_oid = reflog_lines(&store, "HEAD")?[0].new_oid;
+    assert_ne!(previous_oid, other_oid);
+    store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    new: Target::Peeled(other_oid),
+                    expected: PreviousValue::MustNotExist,
+                },
+                name: "refs/heads/other".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    let previous_reflog_count = reflog_lines(&store, "HEAD")?.len();
+
+    let switch_to = |branch: &str| -> crate::Result {
+        store
+            .transaction()
+            .prepare(
+                Some(RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            message: format!("checkout: moving to {}", branch).into(),
+                            ..Default::default()
+                        },
+                        new: Target::Symbolic(format!("refs/heads/{}", branch).try_into()?),
+                        expected: PreviousValue::Any,
+                    },
+                    name: "HEAD".try_into()?,
+                    deref: false,
+                }),
+                Fail::Immediately,
+            )?
+            .commit(committer().to_ref())?;
+        Ok(())
+    };
+    switch_to("other")?;
+    let lines = reflog_lines(&store, "HEAD")?;
+    assert_eq!(lines.len(), previous_reflog_count + 1);
+    assert_eq!(
+        lines.last(),
+        Some(&log_line(previous_oid, other_oid, "checkout: moving to other")),
+        "the objects of the previous and the new referent are logged"
+    );
+
+    switch_to("other")?;
+    assert_eq!(
+        reflog_lines(&store, "HEAD")?.len(),
+        previous_reflog_count + 1,
+        "unchanged symbolic refs aren't logged"
+    );
+
+    switch_to("does-not-exist")?;
+    assert_eq!(
+        reflog_lines(&store, "HEAD")?.len(),
+        previous_reflog_count + 1,
+        "referents which don't exist aren't logged"
+    );
+
+    switch_to("other")?;
+    store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        message: "detach".into(),
+                        ..Default::default()
+                    },
+                    new: Target::Peeled(previous_oid),
+                    expected: PreviousValue::Any,
+                },
+                name: "HEAD".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        reflog_lines(&store, "HEAD")?.last(),
+        Some(&log_line(other_oid, previous_oid, "detach")),
+        "detaching logs the object of the previous referent"
+    );
+    Ok(())
+}
+
 #[test]
 /// Writing a peeled ref to which head points to doesn't update HEAD on the fly even though that might be what's would
 /// be needed to keep the reflog consistent
@@ -445,3 +445,74 @@ fn all_contained_references_deletes_the_packed_ref_file_too() -> crate::Result {
     }
     Ok(())
 }
+
+#[test]
+fn delete_keeps_the_refs_directory_and_its_categories_like_git() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    let names = ["refs/heads/feature/a", "refs/tags/t1", "refs/other"];
+    for change in [
+        Change::Update {
+            log: Default::default(),
+            expected: PreviousValue::MustNotExist,
+            new: Target::Peeled(hex_to_id("02a7a22d90d7c02fb494ed25551850b868e634f0")),
+        },
+        Change::Delete {
+            expected: PreviousValue::MustExist,
+            log: RefLog::AndReference,
+        },
+    ] {
+        store
+            .transaction()
+            .prepare(
+                names.iter().map(|name| RefEdit {
+                    change: change.clone(),
+                    name: (*name).try_into().expect("valid"),
+                    deref: false,
+                }),
+                Fail::Immediately,
+            )?
+            .commit(committer().to_ref())?;
+    }
+
+    assert!(dir.path().join("refs/heads").is_dir(), "category directories are kept");
+    assert!(dir.path().join("refs/tags").is_dir());
+    assert!(
+        !dir.path().join("refs/heads/feature").exists(),
+        "directories below categories are removed when empty"
+    );
+    Ok(())
+}
+
+#[test]
+fn packing_with_pruning_keeps_the_refs_directory_and_its_categories_like_git() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    store
+        .transaction()
+        .prepare(
+            ["refs/heads/main", "refs/tags/t1"].iter().map(|name| RefEdit {
+                change: Change::Update {
+                    log: Default::default(),
+                    expected: PreviousValue::MustNotExist,
+                    new: Target::Peeled(hex_to_id("02a7a22d90d7c02fb494ed25551850b868e634f0")),
+                },
+                name: (*name).try_into().expect("valid"),
+                deref: false,
+            }),
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    store.pack_refs(
+        git_ref::file::pack_refs::Options {
+            all: true,
+            keep_loose_refs: false,
+        },
+        Box::new(|_id, _buf| Ok(Some(git_object::Kind::Commit))),
+    )?;
+    assert_eq!(store.loose_iter()?.count(), 0, "all loose references were pruned");
+    assert!(
+        dir.path().join("refs/heads").is_dir() && dir.path().join("refs/tags").is_dir(),
+        "category directories are kept"
+    );
+    Ok(())
+}
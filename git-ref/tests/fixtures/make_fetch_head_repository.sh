@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q remote
+(cd remote
+  git commit -q --allow-empty -m "initial"
+  git branch other
+  git tag -m "annotated" v1
+  git update-ref refs/pull/1/head HEAD
+)
+
+git init -q local
+(cd local
+  git remote add origin ../remote
+  git fetch -q origin
+  cp .git/FETCH_HEAD ../FETCH_HEAD.remote-tracking
+
+  git fetch -q ../remote HEAD other tag v1 refs/pull/1/head
+  cp .git/FETCH_HEAD ../FETCH_HEAD.explicit
+)
@@ -97,6 +97,16 @@ fn shorten_and_category() {
         );
         assert_eq!(name.category(), None);
     }
+
+    for (uncategorized, expected) in [("refs/stash", "stash"), ("refs/custom/name", "custom/name")] {
+        let name: git_ref::FullName = uncategorized.try_into().unwrap();
+        assert_eq!(
+            name.shorten(),
+            expected,
+            "like git, names in refs/ without category are shortened by removing refs/"
+        );
+        assert_eq!(name.category(), None);
+    }
 }
 
 #[test]
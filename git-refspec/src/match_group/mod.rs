@@ -1,4 +1,6 @@
-use std::collections::BTreeSet;
+use std::{borrow::Cow, collections::BTreeSet};
+
+use bstr::BStr;
 
 use crate::{parse::Operation, types::Mode, MatchGroup, RefSpecRef};
 
@@ -101,6 +103,40 @@ impl<'a> MatchGroup<'a> {
     }
 }
 
+/// Matching of individual names
+impl<'a> RefSpecRef<'a> {
+    /// Return true if `full_ref_name`, like `refs/heads/main`, matches the source (or left-hand) side of this spec,
+    /// either exactly, as partial name like `main`, or through a glob like `refs/heads/*`.
+    ///
+    /// Note that specs whose source is an object id never match, and that negative specs match like any other,
+    /// as negation is only applied when [matching groups][MatchGroup::match_remotes()].
+    pub fn matches_source(&self, full_ref_name: &BStr) -> bool {
+        self.match_source(full_ref_name).0
+    }
+
+    /// Return the destination that `full_ref_name` maps to if it [matches the source][Self::matches_source()] of this spec,
+    /// with the portion matched by a glob substituted into the destination, like `refs/remotes/origin/main` for
+    /// `refs/heads/main` and `refs/heads/*:refs/remotes/origin/*`.
+    ///
+    /// Return `None` if there was no match or if this spec has no destination.
+    pub fn expand_mapping(&self, full_ref_name: &BStr) -> Option<Cow<'a, BStr>> {
+        self.match_source(full_ref_name).1
+    }
+
+    fn match_source(&self, full_ref_name: &BStr) -> (bool, Option<Cow<'a, BStr>>) {
+        let matcher = Matcher::from(*self);
+        if matches!(matcher.lhs, None | Some(Needle::Object(_))) {
+            return (false, None);
+        }
+        let null_id = git_hash::Kind::Sha1.null();
+        matcher.matches_lhs(Item {
+            full_ref_name,
+            target: &null_id,
+            object: None,
+        })
+    }
+}
+
 fn calculate_hash<T: std::hash::Hash>(t: &T) -> u64 {
     use std::hash::Hasher;
     let mut s = std::collections::hash_map::DefaultHasher::new();
@@ -103,8 +103,12 @@ impl<'a> Needle<'a> {
                 if !item.full_ref_name.ends_with(tail) {
                     return Match::None;
                 }
+                // Like in `git`, the glob matches everything between prefix and suffix, including slashes.
                 let end = item.full_ref_name.len() - tail.len();
-                let end = item.full_ref_name[*asterisk_pos..end].find_byte(b'/').unwrap_or(end);
+                if end < *asterisk_pos {
+                    // prefix and suffix overlap, like `refs/heads/a` for `refs/heads/a*a`.
+                    return Match::None;
+                }
                 Match::GlobRange(*asterisk_pos..end)
             }
             Needle::Object(id) => {
@@ -184,3 +184,139 @@ mod multiple {
         )
     }
 }
+
+mod single_name {
+    use git_hash::ObjectId;
+    use git_refspec::{instruction::Fetch, match_group::Item, parse::Operation, Instruction, MatchGroup, RefSpecRef};
+
+    fn fetch(spec: &str) -> RefSpecRef<'_> {
+        git_refspec::parse(spec.into(), Operation::Fetch).unwrap()
+    }
+
+    #[test]
+    fn single_glob() {
+        let spec = fetch("+refs/heads/*:refs/remotes/origin/*");
+        assert!(spec.matches_source("refs/heads/main".into()));
+        assert_eq!(
+            spec.expand_mapping("refs/heads/main".into()).expect("match").as_ref(),
+            "refs/remotes/origin/main"
+        );
+        assert!(!spec.matches_source("refs/tags/v1".into()));
+        assert_eq!(spec.expand_mapping("refs/tags/v1".into()), None);
+
+        let spec = fetch("refs/heads/f*:refs/remotes/origin/a*");
+        assert_eq!(
+            spec.expand_mapping("refs/heads/f1".into()).expect("match").as_ref(),
+            "refs/remotes/origin/a1",
+            "globs may be part of a path component"
+        );
+        assert!(!spec.matches_source("refs/heads/main".into()));
+
+        let spec = fetch("refs/heads/a*a:refs/remotes/origin/*");
+        assert!(
+            !spec.matches_source("refs/heads/a".into()),
+            "prefix and suffix of the glob must not overlap"
+        );
+        assert!(spec.matches_source("refs/heads/aa".into()));
+    }
+
+    #[test]
+    fn multi_level_paths() {
+        let spec = fetch("refs/heads/*:refs/remotes/origin/*");
+        assert_eq!(
+            spec.expand_mapping("refs/heads/feature/a".into())
+                .expect("match")
+                .as_ref(),
+            "refs/remotes/origin/feature/a"
+        );
+        let spec = fetch("refs/heads/*/main:refs/remotes/origin/*/main");
+        assert_eq!(
+            spec.expand_mapping("refs/heads/team/main".into())
+                .expect("match")
+                .as_ref(),
+            "refs/remotes/origin/team/main"
+        );
+        assert!(!spec.matches_source("refs/heads/team/other".into()));
+    }
+
+    #[test]
+    fn exact_matches() {
+        let spec = fetch("refs/heads/main:refs/remotes/origin/main");
+        assert!(spec.matches_source("refs/heads/main".into()));
+        assert!(!spec.matches_source("refs/heads/main2".into()));
+        assert_eq!(
+            spec.expand_mapping("refs/heads/main".into()).expect("match").as_ref(),
+            "refs/remotes/origin/main"
+        );
+
+        let spec = fetch("main:topic");
+        assert!(spec.matches_source("refs/heads/main".into()), "partial names match too");
+        assert_eq!(
+            spec.expand_mapping("refs/heads/main".into()).expect("match").as_ref(),
+            "refs/heads/topic"
+        );
+
+        let spec = fetch("refs/heads/main");
+        assert!(spec.matches_source("refs/heads/main".into()));
+        assert_eq!(
+            spec.expand_mapping("refs/heads/main".into()),
+            None,
+            "there is no destination to map to"
+        );
+
+        let spec = fetch("78b1c1be9421b33a49a7a8176d93eeeafa112da1");
+        assert!(
+            !spec.matches_source("refs/heads/main".into()),
+            "object ids never match names"
+        );
+    }
+
+    #[test]
+    fn names_matching_no_spec_have_no_mapping() {
+        let specs = [
+            fetch("+refs/heads/*:refs/remotes/origin/*"),
+            fetch("refs/tags/v1:refs/tags/v1"),
+        ];
+        let null = ObjectId::null(git_hash::Kind::Sha1);
+        let names = ["refs/heads/main", "refs/tags/v1", "refs/notes/commits"];
+        let items = names.iter().map(|name| Item {
+            full_ref_name: (*name).into(),
+            target: &null,
+            object: None,
+        });
+        let out = MatchGroup::from_fetch_specs(specs).match_remotes(items);
+        let mappings: Vec<_> = out
+            .mappings
+            .iter()
+            .map(|m| {
+                let force = matches!(
+                    out.group.specs[m.spec_index].instruction(),
+                    Instruction::Fetch(Fetch::AndUpdate {
+                        allow_non_fast_forward: true,
+                        ..
+                    })
+                );
+                (
+                    m.lhs.to_owned().to_string(),
+                    m.rhs.as_deref().map(ToString::to_string),
+                    force,
+                )
+            })
+            .collect();
+        assert_eq!(
+            mappings,
+            [
+                (
+                    "refs/heads/main".to_string(),
+                    Some("refs/remotes/origin/main".to_string()),
+                    true
+                ),
+                ("refs/tags/v1".into(), Some("refs/tags/v1".into()), false),
+            ],
+            "refs/notes/commits matches no spec"
+        );
+        assert!(specs
+            .iter()
+            .all(|spec| !spec.matches_source("refs/notes/commits".into())));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::bstr::BString;
+
+/// The kind of archive to produce with [`Repository::archive()`][crate::Repository::archive()].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// An uncompressed `tar` archive.
+    Tar,
+    /// A `tar` archive compressed with `gzip`.
+    TarGz,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::Tar
+    }
+}
+
+/// Options for [`Repository::archive()`][crate::Repository::archive()].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The kind of archive to write.
+    pub format: Format,
+    /// A string to prepend to the path of each entry, similar to `git archive --prefix`.
+    ///
+    /// It's used verbatim, so it should end with a slash to put all entries into a directory, like `project-1.0/`.
+    pub prefix: BString,
+}
+
+/// The error returned by [`Repository::archive()`][crate::Repository::archive()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindExistingObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToTree(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    Decode(#[from] git_object::decode::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] git_traverse::tree::breadthfirst::Error),
+    #[error("The path '{path}' can't be represented on this platform")]
+    IllformedPath { path: BString },
+    #[error("Could not write the archive entry for '{path}'")]
+    WriteEntry { source: std::io::Error, path: BString },
+    #[error("Could not finish writing the archive")]
+    Finish(#[from] std::io::Error),
+}
@@ -0,0 +1,45 @@
+use std::ops::Range;
+
+use git_hash::ObjectId;
+
+use crate::bstr::BString;
+
+/// Options for [`Repository::blame()`][crate::Repository::blame()].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, only follow the first parent of merge commits, similar to `git blame --first-parent`.
+    pub first_parent: bool,
+    /// If `true`, lines that only differ in whitespace are considered unchanged, similar to `git blame -w`.
+    pub ignore_whitespace: bool,
+}
+
+/// A range of lines in the blamed file which were introduced by the same commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The commit which introduced the lines.
+    pub commit_id: ObjectId,
+    /// The author of [`commit_id`][Entry::commit_id].
+    pub author: git_actor::Signature,
+    /// The zero-based line numbers in the blamed file.
+    pub lines: Range<usize>,
+    /// The zero-based line numbers in the version of the file in [`commit_id`][Entry::commit_id].
+    pub original_lines: Range<usize>,
+    /// The path of the file in [`commit_id`][Entry::commit_id], which differs from the blamed path if it was renamed since.
+    pub original_path: BString,
+}
+
+/// The error returned by [`Repository::blame()`][crate::Repository::blame()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The file '{path}' doesn't exist in commit {commit_id}")]
+    FileNotFound { path: BString, commit_id: ObjectId },
+    #[error(transparent)]
+    FindExistingObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    Decode(#[from] git_object::decode::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] git_traverse::tree::breadthfirst::Error),
+}
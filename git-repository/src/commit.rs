@@ -22,7 +22,11 @@ pub mod describe {
     use git_hash::ObjectId;
     use git_odb::Find;
 
-    use crate::{bstr::BStr, ext::ObjectIdExt, Repository};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        ext::ObjectIdExt,
+        Repository,
+    };
 
     /// The result of [try_resolve()][Platform::try_resolve()].
     pub struct Resolution<'repo> {
@@ -69,8 +73,21 @@ pub mod describe {
         fn names(
             &self,
             repo: &Repository,
+            patterns: &[BString],
         ) -> Result<git_revision::hash_hasher::HashedMap<ObjectId, Cow<'static, BStr>>, Error> {
             let platform = repo.references()?;
+            let is_selected = |name: &git_ref::FullNameRef| {
+                patterns.is_empty()
+                    || name.as_bstr().strip_prefix(b"refs/tags/").map_or(false, |tag_name| {
+                        patterns.iter().any(|pattern| {
+                            git_glob::wildmatch(
+                                pattern.as_ref(),
+                                tag_name.as_bstr(),
+                                git_glob::wildmatch::Mode::empty(),
+                            )
+                        })
+                    })
+            };
 
             Ok(match self {
                 SelectRef::AllTags | SelectRef::AllRefs => {
@@ -80,6 +97,7 @@ pub mod describe {
                         _ => unreachable!(),
                     }
                     .filter_map(Result::ok)
+                    .filter(|r: &crate::Reference<'_>| is_selected(r.name()))
                     .filter_map(|mut r: crate::Reference<'_>| {
                         let target_id = r.target().try_id().map(ToOwned::to_owned);
                         let peeled_id = r.peel_to_id_in_place().ok()?;
@@ -106,6 +124,7 @@ pub mod describe {
                     let mut peeled_commits_and_tag_date: Vec<_> = platform
                         .tags()?
                         .filter_map(Result::ok)
+                        .filter(|r: &crate::Reference<'_>| is_selected(r.name()))
                         .filter_map(|r: crate::Reference<'_>| {
                             // TODO: we assume direct refs for tags, which is the common case, but it doesn't have to be
                             //       so rather follow symrefs till the first object and then peel tags after the first object was found.
@@ -142,7 +161,9 @@ pub mod describe {
         pub(crate) select: SelectRef,
         pub(crate) first_parent: bool,
         pub(crate) id_as_fallback: bool,
+        pub(crate) long_format: bool,
         pub(crate) max_candidates: usize,
+        pub(crate) patterns: Vec<BString>,
     }
 
     impl<'repo> Platform<'repo> {
@@ -164,18 +185,38 @@ pub mod describe {
             self
         }
 
+        /// Only consider tags whose name without the `refs/tags/` prefix matches the glob `pattern`, similar to
+        /// `git describe --match`. If called multiple times, tags matching any of the patterns are considered.
+        ///
+        /// Note that with patterns set, references other than tags are never used for names.
+        pub fn matching(mut self, pattern: impl Into<BString>) -> Self {
+            self.patterns.push(pattern.into());
+            self
+        }
+
         /// If true, even if no candidate is available a format will always be produced.
         pub fn id_as_fallback(mut self, use_fallback: bool) -> Self {
             self.id_as_fallback = use_fallback;
             self
         }
 
+        /// If true, the formatted name always includes the distance to the name and the abbreviated id, even if the commit
+        /// is named exactly, similar to `git describe --long`.
+        pub fn long(mut self, long: bool) -> Self {
+            self.long_format = long;
+            self
+        }
+
         /// Try to find a name for the configured commit id using all prior configuration, returning `Some(describe::Format)`
         /// if one was found.
         ///
         /// Note that there will always be `Some(format)`
         pub fn try_format(&self) -> Result<Option<git_revision::describe::Format<'static>>, Error> {
-            self.try_resolve()?.map(|r| r.format()).transpose()
+            let mut format = self.try_resolve()?.map(|r| r.format()).transpose()?;
+            if let Some(format) = format.as_mut() {
+                format.long(self.long_format);
+            }
+            Ok(format)
         }
 
         /// Try to find a name for the configured commit id using all prior configuration, returning `Some(Outcome)`
@@ -199,7 +240,7 @@ pub mod describe {
                         .and_then(|d| d.try_into_commit_iter()))
                 },
                 git_revision::describe::Options {
-                    name_by_oid: self.select.names(self.repo)?,
+                    name_by_oid: self.select.names(self.repo, &self.patterns)?,
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
@@ -7,6 +7,8 @@ pub(crate) struct StageOne {
     pub buf: Vec<u8>,
 
     pub is_bare: bool,
+    /// If `true`, `extensions.worktreeConfig` is set and the worktree specific configuration was loaded.
+    pub worktree_config: bool,
     pub lossy: Option<bool>,
     pub object_hash: git_hash::Kind,
     pub reflog: Option<git_ref::store::WriteReflog>,
@@ -14,15 +16,19 @@ pub(crate) struct StageOne {
 
 /// Initialization
 impl StageOne {
+    /// Note that `git_dir` is the private directory of the worktree and `common_dir` is the one shared by all worktrees,
+    /// which are the same unless a linked worktree is opened.
     pub fn new(
+        common_dir: &std::path::Path,
         git_dir: &std::path::Path,
         git_dir_trust: git_sec::Trust,
         lossy: Option<bool>,
         lenient: bool,
+        ignore_extension_check: bool,
     ) -> Result<Self, Error> {
         let mut buf = Vec::with_capacity(512);
-        let config = {
-            let config_path = git_dir.join("config");
+        let mut config = {
+            let config_path = common_dir.join("config");
             std::io::copy(&mut std::fs::File::open(&config_path)?, &mut buf)?;
 
             git_config::File::from_bytes_owned(
@@ -37,16 +43,50 @@ impl StageOne {
             )?
         };
 
-        let is_bare = util::config_bool(&config, "core.bare", false, lenient)?;
         let repo_format_version = config
             .value::<git_config::Integer>("core", None, "repositoryFormatVersion")
             .map_or(0, |v| v.to_decimal().unwrap_or_default());
+        if !ignore_extension_check {
+            util::check_repository_format(&config, repo_format_version)?;
+        }
+
+        let worktree_config = util::config_bool(&config, "extensions.worktreeConfig", false, lenient)?;
+        if worktree_config {
+            let config_path = git_dir.join("config.worktree");
+            buf.clear();
+            match std::fs::File::open(&config_path) {
+                Ok(mut file) => {
+                    std::io::copy(&mut file, &mut buf)?;
+                    config.append(git_config::File::from_bytes_owned(
+                        &mut buf,
+                        git_config::file::Metadata::from(git_config::Source::Worktree)
+                            .at(config_path)
+                            .with(git_dir_trust),
+                        git_config::file::init::Options {
+                            includes: git_config::file::includes::Options::no_follow(),
+                            ..util::base_options(lossy)
+                        },
+                    )?);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        // Without worktree specific configuration, `core.bare` in the shared configuration only applies to the main worktree.
+        let is_bare = if worktree_config || common_dir == git_dir {
+            util::config_bool(&config, "core.bare", false, lenient)?
+        } else {
+            false
+        };
         let object_hash = (repo_format_version != 1)
             .then(|| Ok(git_hash::Kind::Sha1))
             .or_else(|| {
                 config.string("extensions", None, "objectFormat").map(|format| {
                     if format.as_ref().eq_ignore_ascii_case(b"sha1") {
                         Ok(git_hash::Kind::Sha1)
+                    } else if format.as_ref().eq_ignore_ascii_case(b"sha256") {
+                        Ok(git_hash::Kind::Sha256)
                     } else {
                         Err(Error::UnsupportedObjectFormat {
                             name: format.to_vec().into(),
@@ -62,6 +102,7 @@ impl StageOne {
             git_dir_config: config,
             buf,
             is_bare,
+            worktree_config,
             lossy,
             object_hash,
             reflog,
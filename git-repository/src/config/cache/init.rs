@@ -3,6 +3,9 @@ use std::path::PathBuf;
 use super::{interpolate_context, util, Error, StageOne};
 use crate::{config::Cache, repository, revision::spec::parse::ObjectKindHint};
 
+/// The default of `core.deltaBaseCacheLimit` in `git`.
+const DEFAULT_PACK_CACHE_BYTES: usize = 96 * 1024 * 1024;
+
 /// Initialization
 impl Cache {
     #[allow(clippy::too_many_arguments)]
@@ -12,6 +15,7 @@ impl Cache {
             mut buf,
             lossy,
             is_bare,
+            worktree_config: _,
             object_hash,
             reflog: _,
         }: StageOne,
@@ -129,10 +133,16 @@ impl Cache {
             Err(err) => return Err(err),
         };
 
-        use util::config_bool;
+        use util::{config_bool, config_usize};
         let reflog = util::query_refupdates(&config);
         let ignore_case = config_bool(&config, "core.ignoreCase", false, lenient_config)?;
         let use_multi_pack_index = config_bool(&config, "core.multiPackIndex", true, lenient_config)?;
+        let pack_cache_bytes = config_usize(
+            &config,
+            "core.deltaBaseCacheLimit",
+            DEFAULT_PACK_CACHE_BYTES,
+            lenient_config,
+        )?;
         let object_kind_hint = config.string("core", None, "disambiguate").and_then(|value| {
             Some(match value.as_ref().as_ref() {
                 b"commit" => ObjectKindHint::Commit,
@@ -151,6 +161,7 @@ impl Cache {
             reflog,
             is_bare,
             ignore_case,
+            pack_cache_bytes,
             hex_len,
             filter_config_section,
             excludes_file,
@@ -164,19 +175,25 @@ impl Cache {
         })
     }
 
-    /// Return a path by using the `$XDF_CONFIG_HOME` or `$HOME/.config/…` environment variables locations.
+    /// Return the path to `resource_file_name` within the [XDG configuration directory][crate::path::xdg_config_home()],
+    /// using the `$XDG_CONFIG_HOME` or `$HOME/.config/…` environment variables locations if we are allowed to.
     pub fn xdg_config_path(
         &self,
         resource_file_name: &str,
     ) -> Result<Option<PathBuf>, git_sec::permission::Error<PathBuf>> {
-        std::env::var_os("XDG_CONFIG_HOME")
-            .map(|path| (path, &self.xdg_config_home_env))
-            .or_else(|| std::env::var_os("HOME").map(|path| (path, &self.home_env)))
-            .and_then(|(base, permission)| {
-                let resource = std::path::PathBuf::from(base).join("git").join(resource_file_name);
-                permission.check(resource).transpose()
-            })
-            .transpose()
+        let mut permission = None;
+        crate::path::xdg_home("XDG_CONFIG_HOME", ".config", &mut |name| {
+            permission = Some(match name {
+                "XDG_CONFIG_HOME" => &self.xdg_config_home_env,
+                _ => &self.home_env,
+            });
+            std::env::var_os(name).filter(|value| !value.is_empty())
+        })
+        .and_then(|dir| {
+            let permission = permission.expect("set when reading the environment");
+            permission.check(dir.join(resource_file_name)).transpose()
+        })
+        .transpose()
     }
 
     /// Return the home directory if we are allowed to read it and if it is set in the environment.
@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use super::Error;
-use crate::bstr::ByteSlice;
+use crate::bstr::{BString, ByteSlice};
 
 pub(crate) fn interpolate_context<'a>(
     git_install_dir: Option<&'a std::path::Path>,
@@ -41,6 +41,59 @@ pub(crate) fn config_bool(
     }
 }
 
+pub(crate) fn config_usize(
+    config: &git_config::File<'_>,
+    key: &str,
+    default: usize,
+    lenient: bool,
+) -> Result<usize, Error> {
+    let (section, name) = key.split_once('.').expect("valid section.key format");
+    let value = match config.integer(section, None, name) {
+        Some(value) => value
+            .map_err(|err| err.input)
+            .and_then(|value| usize::try_from(value).map_err(|_| value.to_string().into())),
+        None => return Ok(default),
+    };
+    match value {
+        Ok(v) => Ok(v),
+        Err(_value) if lenient => Ok(default),
+        Err(value) => Err(Error::DecodeInteger { value, key: key.into() }),
+    }
+}
+
+/// Fail like `git` does if the repository uses a format `version` we don't know or extensions we can't handle,
+/// to avoid misinterpreting its data.
+///
+/// With version 0, unknown extensions are ignored as they predate the introduction of extensions,
+/// but those that only exist since version 1 aren't allowed.
+pub(crate) fn check_repository_format(config: &git_config::File<'static>, version: i64) -> Result<(), Error> {
+    const KNOWN_SINCE_V0: &[&str] = &["noop", "preciousObjects", "partialClone", "worktreeConfig"];
+    const KNOWN_SINCE_V1: &[&str] = &["noop-v1", "objectFormat"];
+    if !(0..=1).contains(&version) {
+        return Err(Error::UnsupportedRepositoryFormatVersion { version });
+    }
+    let is_one_of = |names: &[&str], name: &[u8]| names.iter().any(|n| n.as_bytes().eq_ignore_ascii_case(name));
+    let extensions = config
+        .sections_by_name("extensions")
+        .into_iter()
+        .flatten()
+        .filter(|section| section.header().subsection_name().is_none())
+        .flat_map(|section| section.keys());
+    for name in extensions {
+        let since_v1 = is_one_of(KNOWN_SINCE_V1, name);
+        if is_one_of(KNOWN_SINCE_V0, name) || (version == 1 && since_v1) {
+            continue;
+        }
+        let name: BString = name.as_ref().into();
+        match version {
+            0 if since_v1 => return Err(Error::ExtensionRequiresRepositoryFormatVersion1 { name }),
+            1 => return Err(Error::UnknownExtension { name }),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn query_refupdates(config: &git_config::File<'static>) -> Option<git_ref::store::WriteReflog> {
     config.string("core", None, "logallrefupdates").map(|val| {
         (val.eq_ignore_ascii_case(b"always"))
@@ -42,6 +42,12 @@ pub enum Error {
     ResolveIncludes(#[from] git_config::file::includes::Error),
     #[error(transparent)]
     FromEnv(#[from] git_config::file::init::from_env::Error),
+    #[error("Expected git repository format version 0 or 1, found {}", .version)]
+    UnsupportedRepositoryFormatVersion { version: i64 },
+    #[error("Unknown repository extension '{}'", .name)]
+    UnknownExtension { name: BString },
+    #[error("Repository extension '{}' requires repository format version 1, but version 0 was found", .name)]
+    ExtensionRequiresRepositoryFormatVersion1 { name: BString },
     #[error("Cannot handle objects formatted as {:?}", .name)]
     UnsupportedObjectFormat { name: BString },
     #[error("The value for '{}' cannot be empty", .key)]
@@ -50,6 +56,8 @@ pub enum Error {
     CoreAbbrev { value: BString, max: u8 },
     #[error("Value '{}' at key '{}' could not be decoded as boolean", .value, .key)]
     DecodeBoolean { key: String, value: BString },
+    #[error("Value '{}' at key '{}' could not be decoded as non-negative integer", .value, .key)]
+    DecodeInteger { key: String, value: BString },
     #[error(transparent)]
     PathInterpolation(#[from] git_config::path::interpolate::Error),
 }
@@ -81,6 +89,9 @@ pub(crate) struct Cache {
     pub object_kind_hint: Option<spec::parse::ObjectKindHint>,
     /// If true, we are on a case-insensitive file system.
     pub ignore_case: bool,
+    /// The amount of bytes to use at most for caching delta bases of packed objects, as configured by `core.deltaBaseCacheLimit`.
+    #[cfg_attr(not(feature = "max-performance-safe"), allow(dead_code))]
+    pub pack_cache_bytes: usize,
     /// The path to the user-level excludes file to ignore certain files in the worktree.
     pub excludes_file: Option<std::path::PathBuf>,
     /// Define how we can use values obtained with `xdg_config(…)` and its `XDG_CONFIG_HOME` variable.
@@ -29,6 +29,7 @@ mod _impls {
     impl Drop for SnapshotMut<'_> {
         fn drop(&mut self) {
             self.repo.config.resolved = std::mem::take(&mut self.config).into();
+            self.repo.config.personas = Default::default();
         }
     }
 
@@ -98,7 +98,7 @@ fn create_dir(p: &Path) -> Result<(), Error> {
 }
 
 /// Options for use in [`into()`];
-#[derive(Copy, Clone)]
+#[derive(Default, Copy, Clone)]
 pub struct Options {
     /// If true, the repository will be a bare repository without a worktree.
     pub bare: bool,
@@ -106,13 +106,22 @@ pub struct Options {
     /// If set, use these filesystem capabilities to populate the respective git-config fields.
     /// If `None`, the directory will be probed.
     pub fs_capabilities: Option<git_worktree::fs::Capabilities>,
+
+    /// The kind of hash to use for all objects of the repository, defaulting to `Sha1`.
+    ///
+    /// Other kinds are recorded in `extensions.objectFormat`, which requires version 1 of the repository format.
+    pub object_hash: git_hash::Kind,
 }
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
 /// and return its path.
 pub fn into(
     directory: impl Into<PathBuf>,
-    Options { bare, fs_capabilities }: Options,
+    Options {
+        bare,
+        fs_capabilities,
+        object_hash,
+    }: Options,
 ) -> Result<git_discover::repository::Path, Error> {
     let mut dot_git = directory.into();
 
@@ -183,7 +192,11 @@ pub fn into(
             let caps = fs_capabilities.unwrap_or_else(|| git_worktree::fs::Capabilities::probe(&dot_git));
             let mut core = config.new_section("core", None).expect("valid section name");
 
-            core.push(key("repositoryformatversion"), Some("0".into()));
+            let format_version = match object_hash {
+                git_hash::Kind::Sha1 => "0",
+                git_hash::Kind::Sha256 => "1",
+            };
+            core.push(key("repositoryformatversion"), Some(format_version.into()));
             core.push(key("filemode"), Some(bool(caps.executable_bit).into()));
             core.push(key("bare"), Some(bool(bare).into()));
             core.push(key("logallrefupdates"), Some(bool(!bare).into()));
@@ -191,6 +204,10 @@ pub fn into(
             core.push(key("ignorecase"), Some(bool(caps.ignore_case).into()));
             core.push(key("precomposeunicode"), Some(bool(caps.precompose_unicode).into()));
         }
+        if object_hash == git_hash::Kind::Sha256 {
+            let mut extensions = config.new_section("extensions", None).expect("valid section name");
+            extensions.push(key("objectformat"), Some("sha256".into()));
+        }
         let mut cursor = PathCursor(&mut dot_git);
         let config_path = cursor.at("config");
         std::fs::write(&config_path, &config.to_bstring()).map_err(|err| Error::IoWrite {
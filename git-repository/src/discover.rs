@@ -42,6 +42,13 @@ impl ThreadSafeRepository {
         Self::open_from_paths(git_dir, worktree_dir, options).map_err(Into::into)
     }
 
+    /// Open the repository the way git does, that is by honoring `GIT_DIR`, `GIT_WORK_TREE` and `GIT_COMMON_DIR`
+    /// if `GIT_DIR` is set, or by discovering it upwards from the current working directory otherwise,
+    /// while respecting `GIT_CEILING_DIRECTORIES` and `GIT_DISCOVERY_ACROSS_FILESYSTEM`.
+    pub fn open_from_env() -> Result<Self, Error> {
+        Self::discover_with_environment_overrides(".")
+    }
+
     /// Try to open a git repository directly from the environment.
     /// If that fails, discover upwards from `directory` until one is found,
     /// while applying discovery options from the environment.
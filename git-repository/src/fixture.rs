@@ -0,0 +1,361 @@
+use std::{collections::BTreeMap, convert::TryInto, ops::Bound, sync::atomic::AtomicBool};
+
+use git_hash::ObjectId;
+use git_object::bstr::{BStr, BString, ByteSlice, ByteVec};
+use git_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+/// The time of the first commit or tag written by a [`Builder`], 2000-01-01 00:00:00 +0000.
+pub const START_TIME: u32 = 946684800;
+/// The amount of seconds the time advances with each commit or tag object written by a [`Builder`].
+pub const TICK: u32 = 60;
+
+/// The error returned by the methods of [`Builder`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not create the temporary directory to hold the repository")]
+    TempDir(#[from] std::io::Error),
+    #[error(transparent)]
+    Init(#[from] crate::init::Error),
+    #[error(transparent)]
+    Open(#[from] crate::open::Error),
+    #[error(transparent)]
+    ReferenceName(#[from] git_validate::refname::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error("The reference '{name}' is symbolic and can't be committed to")]
+    SymbolicReference { name: FullName },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    Commit(#[from] crate::commit::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Tag(#[from] crate::tag::Error),
+    #[error(transparent)]
+    IterLooseObjects(#[from] git_odb::loose::iter::Error),
+    #[error(transparent)]
+    FindLooseObject(#[from] git_odb::loose::find::Error),
+    #[error(transparent)]
+    PackEntry(#[from] git_pack::data::output::entry::Error),
+    #[error(transparent)]
+    PackWrite(#[from] git_pack::data::output::bytes::Error<std::convert::Infallible>),
+    #[error(transparent)]
+    PackIndex(#[from] git_pack::bundle::write::Error),
+    #[error(transparent)]
+    PackRefs(#[from] git_ref::file::pack_refs::Error),
+}
+
+/// A utility to create repositories with deterministic content in a temporary directory, without shelling out to `git`.
+///
+/// All commits and tags use fixed identities, `author <author@example.com>` and `committer <committer@example.com>`,
+/// and a time that starts at [`START_TIME`] and advances by [`TICK`] with each object, unless [set][Builder::time()] explicitly.
+/// Thus the same sequence of calls produces the same object ids on every run and every machine, and the same ids
+/// `git` would produce given the same content, identities and dates.
+///
+/// Files are staged similar to an index shared by all branches. Each commit records all files that were
+/// [written][Builder::write_file()] and not [removed][Builder::remove_file()] up to that point.
+/// Note that neither the index nor the worktree of non-bare repositories are populated.
+pub struct Builder {
+    repo: crate::Repository,
+    dir: ::tempfile::TempDir,
+    files: BTreeMap<BString, ObjectId>,
+    time: u32,
+}
+
+/// Initialization
+impl Builder {
+    /// Create a new repository with a worktree, and `HEAD` pointing to `refs/heads/main`.
+    pub fn new() -> Result<Self, Error> {
+        Self::init(false)
+    }
+
+    /// Create a new bare repository, with `HEAD` pointing to `refs/heads/main`.
+    pub fn bare() -> Result<Self, Error> {
+        Self::init(true)
+    }
+
+    fn init(bare: bool) -> Result<Self, Error> {
+        let dir = ::tempfile::tempdir()?;
+        let repo = crate::ThreadSafeRepository::init_opts(
+            dir.path(),
+            crate::create::Options {
+                bare,
+                ..Default::default()
+            },
+            crate::open::Options::isolated(),
+        )?
+        .to_thread_local();
+        Ok(Builder {
+            repo,
+            dir,
+            files: Default::default(),
+            time: START_TIME,
+        })
+    }
+}
+
+/// Access
+impl Builder {
+    /// Return the repository we are writing to.
+    pub fn repo(&self) -> &crate::Repository {
+        &self.repo
+    }
+
+    /// Re-open the repository to observe all changes and return it along with the temporary directory it is contained in,
+    /// which is deleted when dropped.
+    pub fn finish(self) -> Result<(crate::Repository, ::tempfile::TempDir), Error> {
+        let repo = crate::open_opts(self.repo.git_dir(), crate::open::Options::isolated())?;
+        Ok((repo, self.dir))
+    }
+}
+
+/// Objects and references
+impl Builder {
+    /// Set the time in seconds since the unix epoch to use for the next commit or tag, after which it advances by [`TICK`] as usual.
+    pub fn time(&mut self, seconds_since_unix_epoch: u32) -> &mut Self {
+        self.time = seconds_since_unix_epoch;
+        self
+    }
+
+    /// Write a blob with `content` and stage it at `path`, like `dir/file`, to be part of all following commits.
+    pub fn write_file(&mut self, path: impl AsRef<str>, content: impl AsRef<[u8]>) -> Result<&mut Self, Error> {
+        let id = self.repo.write_blob(content)?.detach();
+        self.files.insert(path.as_ref().into(), id);
+        Ok(self)
+    }
+
+    /// Remove the file at `path` so it won't be part of the following commits.
+    pub fn remove_file(&mut self, path: impl AsRef<str>) -> &mut Self {
+        self.files.remove(path.as_ref().as_bytes().as_bstr());
+        self
+    }
+
+    /// Commit all staged files onto `branch`, like `main` or `refs/heads/main`, with `message`, using its current tip as parent
+    /// if it exists, and return the id of the new commit.
+    pub fn commit(&mut self, branch: &str, message: &str) -> Result<ObjectId, Error> {
+        self.commit_with_parents(branch, message, None)
+    }
+
+    /// Like [`commit()`][Self::commit()], but add `other` as second parent to create a merge commit.
+    pub fn merge(&mut self, branch: &str, message: &str, other: impl Into<ObjectId>) -> Result<ObjectId, Error> {
+        self.commit_with_parents(branch, message, Some(other.into()))
+    }
+
+    /// Create or overwrite the reference `name`, like `refs/heads/feature` or `refs/remotes/origin/main`, to point to `target`.
+    pub fn reference(&mut self, name: &str, target: impl Into<ObjectId>) -> Result<&mut Self, Error> {
+        let name: FullName = name.try_into()?;
+        let committer = self.signature("committer", false);
+        self.repo.edit_reference(
+            RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "fixture".into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(target.into()),
+                },
+                name,
+                deref: false,
+            },
+            git_lock::acquire::Fail::Immediately,
+            committer.to_ref(),
+        )?;
+        Ok(self)
+    }
+
+    /// Create a lightweight tag `name`, like `v1.0`, pointing to `target`.
+    pub fn tag(&mut self, name: &str, target: impl Into<ObjectId>) -> Result<&mut Self, Error> {
+        self.reference(&format!("refs/tags/{}", name), target)
+    }
+
+    /// Create an annotated tag `name`, like `v1.0`, with `message`, pointing to `target`, and return the id of the tag object.
+    pub fn annotated_tag(&mut self, name: &str, target: impl Into<ObjectId>, message: &str) -> Result<ObjectId, Error> {
+        let target = target.into();
+        let target_kind = self.repo.find_object(target)?.kind;
+        let tagger = self.signature("committer", true);
+        let tag = self.repo.tag(
+            name,
+            target,
+            target_kind,
+            Some(tagger.to_ref()),
+            with_trailing_newline(message),
+            PreviousValue::Any,
+        )?;
+        Ok(tag.id().detach())
+    }
+
+    fn commit_with_parents(&mut self, branch: &str, message: &str, other: Option<ObjectId>) -> Result<ObjectId, Error> {
+        let name = if branch.starts_with("refs/") {
+            branch.to_owned()
+        } else {
+            format!("refs/heads/{}", branch)
+        };
+        let tip = match self.repo.try_find_reference(name.as_str())? {
+            Some(reference) => match reference.target().try_id() {
+                Some(id) => Some(id.to_owned()),
+                None => {
+                    return Err(Error::SymbolicReference {
+                        name: reference.detach().name,
+                    })
+                }
+            },
+            None => None,
+        };
+        let tree = self.write_tree("".into())?;
+        let author = self.signature("author", false);
+        let committer = self.signature("committer", true);
+        let id = self.repo.commit(
+            name.as_str(),
+            author.to_ref(),
+            committer.to_ref(),
+            with_trailing_newline(message),
+            tree,
+            tip.into_iter().chain(other),
+        )?;
+        Ok(id.detach())
+    }
+
+    fn write_tree(&self, prefix: &BStr) -> Result<ObjectId, Error> {
+        let mut entries = Vec::new();
+        for (path, id) in self.files.range::<BStr, _>((Bound::Included(prefix), Bound::Unbounded)) {
+            let relative = match path.strip_prefix(prefix.as_bytes()) {
+                Some(relative) => relative,
+                None => break,
+            };
+            match relative.find_byte(b'/') {
+                Some(pos) => {
+                    let name = &relative[..pos];
+                    if matches!(entries.last(), Some(git_object::tree::Entry { mode, filename, .. }) if *mode == git_object::tree::EntryMode::Tree && filename.as_bytes() == name)
+                    {
+                        continue;
+                    }
+                    let mut sub_prefix = prefix.to_owned();
+                    sub_prefix.push_str(name);
+                    sub_prefix.push_byte(b'/');
+                    entries.push(git_object::tree::Entry {
+                        mode: git_object::tree::EntryMode::Tree,
+                        filename: name.into(),
+                        oid: self.write_tree(sub_prefix.as_ref())?,
+                    });
+                }
+                None => entries.push(git_object::tree::Entry {
+                    mode: git_object::tree::EntryMode::Blob,
+                    filename: relative.into(),
+                    oid: *id,
+                }),
+            }
+        }
+        entries.sort();
+        Ok(self.repo.write_object(&git_object::Tree { entries })?.detach())
+    }
+
+    fn signature(&mut self, role: &str, advance_time: bool) -> git_actor::Signature {
+        let signature = git_actor::Signature {
+            name: role.into(),
+            email: format!("{}@example.com", role).into(),
+            time: git_date::Time::new(self.time, 0),
+        };
+        if advance_time {
+            self.time += TICK;
+        }
+        signature
+    }
+}
+
+/// Messages written by `git commit -m` and `git tag -m` end with a newline, and so do ours to produce the same objects.
+fn with_trailing_newline(message: &str) -> String {
+    let mut message = message.to_owned();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    message
+}
+
+/// Packing
+impl Builder {
+    /// Move all loose objects into a single pack with an index, similar to `git repack -a -d`.
+    pub fn pack_objects(&mut self) -> Result<&mut Self, Error> {
+        let object_hash = self.repo.object_hash();
+        let objects_dir = self.repo.objects.store_ref().path().to_owned();
+        let loose = git_odb::loose::Store::at(&objects_dir, object_hash);
+        let mut ids = loose.iter().collect::<Result<Vec<_>, _>>()?;
+        if ids.is_empty() {
+            return Ok(self);
+        }
+        ids.sort();
+
+        let mut buf = Vec::new();
+        let mut entries = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let data = loose.try_find(id, &mut buf)?.expect("listed objects exist");
+            entries.push(git_pack::data::output::Entry::from_data(
+                &git_pack::data::output::Count::from_data(*id, None),
+                &data,
+            )?);
+        }
+
+        let mut pack = Vec::new();
+        let num_entries = entries.len() as u32;
+        for res in git_pack::data::output::bytes::FromEntriesIter::new(
+            std::iter::once(Ok::<_, std::convert::Infallible>(entries)),
+            &mut pack,
+            num_entries,
+            git_pack::data::Version::V2,
+            object_hash,
+        ) {
+            res?;
+        }
+
+        let pack_dir = objects_dir.join("pack");
+        std::fs::create_dir_all(&pack_dir)?;
+        git_pack::Bundle::write_to_directory(
+            pack.as_slice(),
+            Some(pack_dir),
+            git_features::progress::Discard,
+            &AtomicBool::default(),
+            None,
+            git_pack::bundle::write::Options {
+                thread_limit: Some(1),
+                iteration_mode: git_pack::data::input::Mode::Verify,
+                index_kind: Default::default(),
+                object_hash,
+            },
+        )?;
+
+        for id in ids {
+            let hex = id.to_hex().to_string();
+            let fan_out = objects_dir.join(&hex[..2]);
+            std::fs::remove_file(fan_out.join(&hex[2..]))?;
+            std::fs::remove_dir(fan_out).ok();
+        }
+        Ok(self)
+    }
+
+    /// Move all loose references into the `packed-refs` file, similar to `git pack-refs --all`.
+    pub fn pack_refs(&mut self) -> Result<&mut Self, Error> {
+        let objects = self.repo.objects.clone();
+        self.repo.refs.pack_refs(
+            git_ref::file::pack_refs::Options {
+                all: true,
+                keep_loose_refs: false,
+            },
+            Box::new(move |id, buf| {
+                use git_odb::Find;
+                objects
+                    .try_find(id, buf)
+                    .map(|obj| obj.map(|obj| obj.kind))
+                    .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync>)
+            }),
+        )?;
+        Ok(self)
+    }
+}
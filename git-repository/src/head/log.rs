@@ -3,7 +3,7 @@ use std::convert::TryInto;
 use git_hash::ObjectId;
 
 use crate::{
-    bstr::{BString, ByteSlice},
+    bstr::{BStr, BString, ByteSlice},
     Head,
 };
 
@@ -24,12 +24,34 @@ impl<'repo> Head<'repo> {
         Ok(self.log_iter().all()?.map(|log| {
             log.filter_map(Result::ok)
                 .filter_map(|line| {
-                    line.message
-                        .strip_prefix(b"checkout: moving from ")
-                        .and_then(|from_to| from_to.find(" to ").map(|pos| &from_to[..pos]))
-                        .map(|from_branch| (from_branch.as_bstr().to_owned(), line.previous_oid()))
+                    checked_out_from(line.message).map(|from_branch| (from_branch.to_owned(), line.previous_oid()))
                 })
                 .collect()
         }))
     }
+
+    /// Return the `nth` most recently checked out branch along with the commit it was pointing to at the time, with `1` being
+    /// the branch checked out before the current one, similar to `@{-<nth>}` or `git checkout -` for `nth == 1`.
+    ///
+    /// The reference log is read backwards, so only as many entries as needed are parsed and unparsable ones are skipped.
+    /// Returns `None` if there is no reference log or if fewer branches were checked out.
+    pub fn prior_checked_out_branch(&self, nth: usize) -> std::io::Result<Option<(BString, ObjectId)>> {
+        let mut platform = self.log_iter();
+        let log = match platform.rev()? {
+            Some(log) => log,
+            None => return Ok(None),
+        };
+        Ok(log
+            .filter_map(Result::ok)
+            .filter_map(|line| checked_out_from(line.message.as_ref()).map(|from| (from.to_owned(), line.previous_oid)))
+            .nth(nth.saturating_sub(1)))
+    }
+}
+
+/// Return the name of the branch that was checked out before, if `message` is the one of a reference log entry written by
+/// `git checkout` or `git switch`.
+pub(crate) fn checked_out_from(message: &BStr) -> Option<&BStr> {
+    message
+        .strip_prefix(b"checkout: moving from ")
+        .and_then(|from_to| from_to.find(" to ").map(|pos| from_to[..pos].as_bstr()))
 }
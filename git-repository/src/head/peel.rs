@@ -85,7 +85,6 @@ impl<'repo> Head<'repo> {
     }
 
     // TODO: tests
-    // TODO: something similar in `crate::Reference`
     /// Follow the symbolic reference of this head until its target object and peel it by following tag objects until there is no
     /// more object to follow, transform the id into a commit if possible and return that.
     ///
@@ -0,0 +1,202 @@
+//! Discover and run the hooks of a repository, like `pre-commit` or `post-checkout`.
+use std::{
+    ffi::OsStr,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::bstr::ByteSlice;
+
+/// A platform to find and run the hooks of a repository, obtained with [`Repository::hooks()`][crate::Repository::hooks()].
+pub struct Platform<'repo> {
+    pub(crate) repo: &'repo crate::Repository,
+}
+
+/// The outcome of running a hook with [`Platform::run()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The exit status of the hook process.
+    pub status: std::process::ExitStatus,
+    /// Everything the hook wrote to stdout.
+    pub stdout: Vec<u8>,
+    /// Everything the hook wrote to stderr.
+    pub stderr: Vec<u8>,
+}
+
+impl Outcome {
+    /// Return true if the hook exited successfully, which for hooks like `pre-commit` means the operation may proceed.
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+///
+pub mod run {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Platform::run()`][super::Platform::run()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        HooksPath(#[from] git_config::path::interpolate::Error),
+        #[error("Could not spawn the hook at '{}'", .path.display())]
+        Spawn { path: PathBuf, source: std::io::Error },
+        #[error("Could not read the shebang line of the hook at '{}'", .path.display())]
+        ReadShebang { path: PathBuf, source: std::io::Error },
+        #[error("Failed to pass input to or to collect the output of the hook at '{}'", .path.display())]
+        Io { path: PathBuf, source: std::io::Error },
+    }
+}
+
+impl<'repo> Platform<'repo> {
+    /// Return the directory containing hooks, which is `core.hooksPath` if set or the `hooks` directory of the common
+    /// repository directory otherwise.
+    ///
+    /// Relative values of `core.hooksPath` are relative to the directory hooks are run in, which is the root of the worktree
+    /// or the `.git` directory of bare repositories.
+    pub fn dir(&self) -> Result<PathBuf, git_config::path::interpolate::Error> {
+        Ok(
+            match self.repo.config_snapshot().trusted_path("core.hooksPath").transpose()? {
+                Some(path) => self.working_dir().join(path),
+                None => self.repo.common_dir().join("hooks"),
+            },
+        )
+    }
+
+    /// Return the path to the hook with `name`, like `pre-commit`, if it exists and is executable.
+    pub fn path(&self, name: &str) -> Result<Option<PathBuf>, git_config::path::interpolate::Error> {
+        let path = self.dir()?.join(name);
+        Ok(is_executable(&path).then(|| path))
+    }
+
+    /// Return true if the hook with `name` exists and would be run by [`run()`][Self::run()].
+    ///
+    /// Invalid values of `core.hooksPath` are treated as if there was no hook.
+    pub fn exists(&self, name: &str) -> bool {
+        self.path(name).ok().flatten().is_some()
+    }
+
+    /// Run the hook with `name` and `args`, passing `stdin` as its standard input, and wait for it to finish.
+    /// Return `None` if there is no such hook, or the [outcome][Outcome] with its exit status and output otherwise.
+    ///
+    /// The hook is run in the root of the worktree, or in the `.git` directory of bare repositories, with `GIT_DIR`
+    /// set to the `.git` directory.
+    ///
+    /// On Windows, hooks starting with a shebang like `#!/bin/sh` are run by passing them to the named interpreter,
+    /// which is expected to be in the `PATH`.
+    pub fn run(
+        &self,
+        name: &str,
+        args: impl IntoIterator<Item = impl AsRef<OsStr>>,
+        stdin: Option<&[u8]>,
+    ) -> Result<Option<Outcome>, run::Error> {
+        let path = match self.path(name)? {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let mut cmd = command(&path)?;
+        cmd.args(args)
+            .current_dir(self.working_dir())
+            .env("GIT_DIR", absolute(self.repo.git_dir()))
+            .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = cmd.spawn().map_err(|source| run::Error::Spawn {
+            path: path.clone(),
+            source,
+        })?;
+
+        let writer = match (stdin, child.stdin.take()) {
+            (Some(input), Some(mut child_stdin)) => {
+                let input = input.to_owned();
+                Some(std::thread::spawn(move || match child_stdin.write_all(&input) {
+                    // Hooks don't have to consume their input.
+                    Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+                    res => res,
+                }))
+            }
+            _ => None,
+        };
+        let output = child.wait_with_output().map_err(|source| run::Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        if let Some(writer) = writer {
+            writer
+                .join()
+                .expect("writer thread doesn't panic")
+                .map_err(|source| run::Error::Io { path, source })?;
+        }
+        Ok(Some(Outcome {
+            status: output.status,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        }))
+    }
+
+    fn working_dir(&self) -> &'repo Path {
+        self.repo.work_dir().unwrap_or_else(|| self.repo.git_dir())
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_owned()
+    } else {
+        std::env::current_dir().map_or_else(|_| path.to_owned(), |cwd| cwd.join(path))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn command(path: &Path) -> Result<Command, run::Error> {
+    let path = absolute(path);
+    if cfg!(windows) {
+        let interpreter = shebang_interpreter(&path).map_err(|source| run::Error::ReadShebang {
+            path: path.clone(),
+            source,
+        })?;
+        if let Some((program, arg)) = interpreter {
+            let mut cmd = Command::new(program);
+            cmd.args(arg).arg(path);
+            return Ok(cmd);
+        }
+    }
+    Ok(Command::new(path))
+}
+
+/// Parse the first line of the script at `path` and return the name of the interpreter and its optional argument,
+/// like `sh` for `#!/bin/sh` or `python3` for `#!/usr/bin/env python3`, or `None` if there is no shebang.
+fn shebang_interpreter(path: &Path) -> std::io::Result<Option<(String, Option<String>)>> {
+    let mut buf = [0; 256];
+    let mut file = std::fs::File::open(path)?;
+    let len = std::io::Read::read(&mut file, &mut buf)?;
+    let first_line = buf[..len].lines().next().unwrap_or_default();
+    let line = match first_line.strip_prefix(b"#!") {
+        Some(line) => line.to_str_lossy(),
+        None => return Ok(None),
+    };
+    let mut tokens = line.split_whitespace();
+    let program = match tokens.next() {
+        Some(program) => program.rsplit('/').next().unwrap_or(program),
+        None => return Ok(None),
+    };
+    let arg = tokens.next();
+    Ok(Some(match (program, arg) {
+        ("env", Some(program)) => (program.to_owned(), None),
+        (program, arg) => (program.to_owned(), arg.map(ToOwned::to_owned)),
+    }))
+}
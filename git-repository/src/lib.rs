@@ -39,9 +39,10 @@
 //!
 //! ### Shortcomings & Limitations
 //!
-//! - Only a single `crate::object` or derivatives can be held in memory at a time, _per `Easy*`_.
-//! - Changes made to the configuration, packs, and alternates aren't picked up automatically, but the current object store
-//!   needs a manual refresh.
+//! - Objects and [buffers][crate::Repository::buffer()] are backed by a free-list of buffers per `Repository`, which grows with the amount
+//!   of them held in memory at the same time.
+//! - Changes made to the configuration aren't picked up automatically. New packs and alternates are discovered when an object
+//!   lookup misses, or when calling [`refresh_object_database()`][crate::Repository::refresh_object_database()].
 //!
 //! ### Design Sketch
 //!
@@ -179,8 +180,8 @@ pub(crate) type Config = OwnShared<git_config::File<'static>>;
 ///
 mod types;
 pub use types::{
-    Commit, Head, Id, Kind, Object, ObjectDetached, Reference, Remote, Repository, Tag, ThreadSafeRepository, Tree,
-    Worktree,
+    Buffer, Commit, Head, Id, Kind, Object, ObjectDetached, Reference, Remote, Repository, Tag, ThreadSafeRepository,
+    Tree, Worktree,
 };
 
 pub mod commit;
@@ -196,13 +197,18 @@ pub fn discover(directory: impl AsRef<std::path::Path>) -> Result<Repository, di
     ThreadSafeRepository::discover(directory).map(Into::into)
 }
 
+/// See [ThreadSafeRepository::open_from_env()], but returns a [`Repository`] instead.
+pub fn open_from_env() -> Result<Repository, discover::Error> {
+    ThreadSafeRepository::open_from_env().map(Into::into)
+}
+
 /// See [ThreadSafeRepository::init()], but returns a [`Repository`] instead.
 pub fn init(directory: impl AsRef<std::path::Path>) -> Result<Repository, init::Error> {
     ThreadSafeRepository::init(
         directory,
         create::Options {
             bare: false,
-            fs_capabilities: None,
+            ..Default::default()
         },
     )
     .map(Into::into)
@@ -214,7 +220,7 @@ pub fn init_bare(directory: impl AsRef<std::path::Path>) -> Result<Repository, i
         directory,
         create::Options {
             bare: true,
-            fs_capabilities: None,
+            ..Default::default()
         },
     )
     .map(Into::into)
@@ -241,6 +247,21 @@ pub mod permission {
         }
     }
 }
+///
+pub mod identity {
+    /// The error returned by [`Repository::committer_or_err()`][crate::Repository::committer_or_err()] and
+    /// [`Repository::author_or_err()`][crate::Repository::author_or_err()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("No {} identity is configured, searched {}", .role, .searched.join(", "))]
+        MissingIdentity {
+            role: &'static str,
+            searched: Vec<&'static str>,
+        },
+    }
+}
+
 ///
 pub mod permissions {
     pub use crate::repository::permissions::{Config, Environment};
@@ -256,17 +277,48 @@ pub mod open;
 ///
 pub mod config;
 
+///
+pub mod hooks;
+
 ///
 pub mod mailmap;
 
+///
+pub mod merge;
+
+///
+pub mod blame;
+
+///
+#[cfg(feature = "archive")]
+pub mod archive;
+
 ///
 pub mod worktree;
 
+///
+pub mod status;
+
+///
+pub mod switch;
+
+///
+pub mod shallow;
+
+///
+pub mod submodule;
+
 pub mod revision;
 
 ///
 pub mod remote;
 
+/// Build repositories with deterministic content in tests, without shelling out to `git`.
+///
+/// **This API is unstable** and may change with any release.
+#[cfg(feature = "fixture")]
+pub mod fixture;
+
 ///
 pub mod init {
     use std::path::Path;
@@ -335,6 +387,25 @@ pub mod state {
         /// A revert operation with multiple commits pending.
         RevertSequence,
     }
+
+    ///
+    pub mod head {
+        use std::path::PathBuf;
+
+        /// The error returned by [`Repository::merge_heads()`][crate::Repository::merge_heads()] and related methods.
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error("Could not read '{}'", path.display())]
+            Io { source: std::io::Error, path: PathBuf },
+            #[error("Line {line_number} of '{}' is not a valid object id", path.display())]
+            Decode {
+                source: git_hash::decode::Error,
+                path: PathBuf,
+                line_number: usize,
+            },
+        }
+    }
 }
 
 ///
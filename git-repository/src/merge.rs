@@ -0,0 +1,25 @@
+///
+pub mod message {
+    /// The error returned by [`Repository::merge_message_from_fetch_head()`][crate::Repository::merge_message_from_fetch_head()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ReadFetchHead(#[from] git_ref::file::fetch_head::read::Error),
+        #[error("The value of '{key}' must be a boolean or a non-negative integer")]
+        InvalidLogLength {
+            key: &'static str,
+            source: Option<git_config::value::Error>,
+        },
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FindExistingObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        Decode(#[from] git_object::decode::Error),
+        #[error(transparent)]
+        MergeBase(#[from] crate::revision::merge_base::Error),
+    }
+}
@@ -138,14 +138,7 @@ impl<'repo> Commit<'repo> {
     /// Create a platform to further configure a `git describe` operation to find a name for this commit by looking
     /// at the closest annotated tags (by default) in its past.
     pub fn describe(&self) -> crate::commit::describe::Platform<'repo> {
-        crate::commit::describe::Platform {
-            id: self.id,
-            repo: self.repo,
-            select: Default::default(),
-            first_parent: false,
-            id_as_fallback: false,
-            max_candidates: 10,
-        }
+        self.repo.describe(self.id)
     }
 }
 
@@ -25,6 +25,36 @@ pub mod find {
         /// An object could not be found in the database, or an error occurred when trying to obtain it.
         pub type Error = git_odb::find::existing::Error<git_odb::store::find::Error>;
     }
+
+    ///
+    pub mod by_prefix {
+        /// The error returned by [`Repository::find_object_by_prefix()`][crate::Repository::find_object_by_prefix()].
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            Find(#[from] git_odb::store::find::Error),
+            #[error("Short id {prefix} is ambiguous as {} objects match it", .candidates.len())]
+            Ambiguous {
+                prefix: git_hash::Prefix,
+                /// All matching object ids, sorted.
+                candidates: Vec<git_hash::ObjectId>,
+            },
+        }
+    }
+
+    ///
+    pub mod disambiguate_prefix {
+        /// The error returned by [`Repository::disambiguate_prefix()`][crate::Repository::disambiguate_prefix()].
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            Prefix(#[from] git_hash::prefix::from_hex::Error),
+            #[error(transparent)]
+            Find(#[from] super::by_prefix::Error),
+        }
+    }
 }
 
 ///
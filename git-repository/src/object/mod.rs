@@ -32,6 +32,11 @@ pub mod try_into {
 }
 
 impl ObjectDetached {
+    /// Decode our data into an object whose fields reference our data buffer, depending on our [`kind`][ObjectDetached::kind].
+    pub fn decode(&self) -> Result<git_object::ObjectRef<'_>, git_object::decode::Error> {
+        git_object::ObjectRef::from_bytes_with_hash(self.kind, &self.data, self.id.kind())
+    }
+
     /// Infuse this owned object with `repo` access.
     pub fn attach(self, repo: &crate::Repository) -> Object<'_> {
         Object {
@@ -97,12 +102,12 @@ impl<'repo> Object<'repo> {
         })
     }
 
-    /// Transform this object into a tag, or return it as part of the `Err` if it is no commit.
+    /// Transform this object into a tag, or return it as part of the `Err` if it is no tag.
     pub fn try_into_tag(self) -> Result<Tag<'repo>, try_into::Error> {
         self.try_into().map_err(|this: Self| try_into::Error {
             id: this.id,
             actual: this.kind,
-            expected: git_object::Kind::Commit,
+            expected: git_object::Kind::Tag,
         })
     }
 
@@ -134,6 +139,13 @@ impl<'repo> Object<'repo> {
 
 /// Conversions to detached, lower-level object types.
 impl<'repo> Object<'repo> {
+    /// Decode our data into an object whose fields reference our data buffer, depending on our [`kind`][Object::kind].
+    ///
+    /// Use it to handle objects of all kinds without knowing their kind in advance.
+    pub fn decode(&self) -> Result<git_object::ObjectRef<'_>, git_object::decode::Error> {
+        git_object::ObjectRef::from_bytes_with_hash(self.kind, &self.data, self.id.kind())
+    }
+
     /// Obtain a fully parsed commit whose fields reference our data buffer,
     ///
     /// # Panic
@@ -13,3 +13,9 @@ impl<'repo> Tag<'repo> {
         git_object::TagRefIter::from_bytes(&self.data).tagger()
     }
 }
+
+impl<'r> std::fmt::Debug for Tag<'r> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tag({})", self.id)
+    }
+}
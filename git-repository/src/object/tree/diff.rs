@@ -1,6 +1,6 @@
 use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
 use crate::ext::ObjectIdExt;
-use crate::{Repository, Tree};
+use crate::{Id, Repository, Tree};
 use git_object::TreeRefIter;
 use git_odb::FindExt;
 use std::collections::VecDeque;
@@ -115,6 +115,16 @@ pub mod change {
         ) -> git_diff::lines::similar::TextDiff<'_, '_, 'bufs, [u8]> {
             git_diff::lines::with(self.old.data.as_bstr(), self.new.data.as_bstr(), algorithm)
         }
+
+        /// Like [`text()`][Self::text()], but compare lines with `options` to ignore changes in whitespace or blank lines,
+        /// similar to `git diff -w`, for example.
+        pub fn text_with_options(
+            &self,
+            algorithm: git_diff::lines::Algorithm,
+            options: git_diff::lines::Options,
+        ) -> git_diff::lines::Diff<'_, '_> {
+            git_diff::lines::with_options(self.old.data.as_bstr(), self.new.data.as_bstr(), algorithm, options)
+        }
     }
 }
 
@@ -186,8 +196,12 @@ impl<'a, 'old> Platform<'a, 'old> {
             visit: for_each,
             err: None,
         };
-        git_diff::tree::Changes::from(TreeRefIter::from_bytes(&self.lhs.data)).needed_to_obtain(
-            TreeRefIter::from_bytes(&other.data),
+        git_diff::tree::Changes::from(TreeRefIter::from_bytes_with_hash(
+            &self.lhs.data,
+            self.lhs.repo.object_hash(),
+        ))
+        .needed_to_obtain(
+            TreeRefIter::from_bytes_with_hash(&other.data, other.repo.object_hash()),
             &mut self.state,
             |oid, buf| repo.objects.find_tree_iter(oid, buf),
             &mut delegate,
@@ -199,6 +213,55 @@ impl<'a, 'old> Platform<'a, 'old> {
     }
 }
 
+/// Statistics
+impl<'a, 'old> Platform<'a, 'old> {
+    /// Count the lines that changed in each file to turn the source of the diff into `other`, which can be used to display
+    /// them like `git diff --stat --no-renames` would, see [`git_diff::stat`] for details.
+    ///
+    /// Paths are always tracked, and changes to directories and submodules are skipped.
+    pub fn stats_to_obtain_tree(&mut self, other: &Tree<'_>) -> Result<Vec<git_diff::stat::FileStat>, Error> {
+        fn is_blob(mode: git_object::tree::EntryMode) -> bool {
+            mode.is_blob() || mode == git_object::tree::EntryMode::Link
+        }
+        fn data(id: Option<Id<'_>>) -> Result<Vec<u8>, crate::object::find::existing::Error> {
+            Ok(id
+                .map(|id| id.object())
+                .transpose()?
+                .map(|obj| obj.detach().data)
+                .unwrap_or_default())
+        }
+        let mut files = Vec::new();
+        self.track_path().for_each_to_obtain_tree(
+            other,
+            |change| -> Result<_, crate::object::find::existing::Error> {
+                let (old, new) = match change.event {
+                    change::Event::Addition { entry_mode, id } => (None, is_blob(entry_mode).then(|| id)),
+                    change::Event::Deletion { entry_mode, id } => (is_blob(entry_mode).then(|| id), None),
+                    change::Event::Modification {
+                        previous_entry_mode,
+                        previous_id,
+                        entry_mode,
+                        id,
+                    } => (
+                        is_blob(previous_entry_mode).then(|| previous_id),
+                        is_blob(entry_mode).then(|| id),
+                    ),
+                };
+                if old.is_none() && new.is_none() {
+                    return Ok(Action::Continue);
+                }
+                files.push(git_diff::stat::FileStat::from_blobs(
+                    change.location,
+                    &data(old)?,
+                    &data(new)?,
+                ));
+                Ok(Action::Continue)
+            },
+        )?;
+        Ok(files)
+    }
+}
+
 struct Delegate<'old, 'new, VisitFn, E> {
     repo: &'old Repository,
     other_repo: &'new Repository,
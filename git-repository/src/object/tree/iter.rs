@@ -48,6 +48,7 @@ impl<'repo> Tree<'repo> {
     /// Return an iterator over tree entries.
     pub fn iter(&self) -> impl Iterator<Item = Result<EntryRef<'repo, '_>, git_object::decode::Error>> {
         let repo = self.repo;
-        git_object::TreeRefIter::from_bytes(&self.data).map(move |e| e.map(|entry| EntryRef { inner: entry, repo }))
+        git_object::TreeRefIter::from_bytes_with_hash(&self.data, repo.object_hash())
+            .map(move |e| e.map(|entry| EntryRef { inner: entry, repo }))
     }
 }
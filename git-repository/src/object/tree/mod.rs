@@ -44,7 +44,7 @@ impl<'repo> Tree<'repo> {
     {
         let mut path = path.into_iter().peekable();
         while let Some(component) = path.next() {
-            match TreeRefIter::from_bytes(&self.data)
+            match TreeRefIter::from_bytes_with_hash(&self.data, self.repo.object_hash())
                 .filter_map(Result::ok)
                 .find(|entry| component.eq(entry.filename))
             {
@@ -48,7 +48,7 @@ impl<'a, 'repo> Platform<'a, 'repo> {
     where
         V: git_traverse::tree::Visit,
     {
-        let root = git_object::TreeRefIter::from_bytes(&self.root.data);
+        let root = git_object::TreeRefIter::from_bytes_with_hash(&self.root.data, self.root.repo.object_hash());
         let state = git_traverse::tree::breadthfirst::State::default();
         git_traverse::tree::breadthfirst(
             root,
@@ -72,6 +72,7 @@ pub struct Options {
     pub(crate) lossy_config: Option<bool>,
     pub(crate) lenient_config: bool,
     pub(crate) bail_if_untrusted: bool,
+    pub(crate) ignore_extension_check: bool,
 }
 
 impl Default for Options {
@@ -85,6 +86,7 @@ impl Default for Options {
             lossy_config: None,
             lenient_config: true,
             bail_if_untrusted: false,
+            ignore_extension_check: false,
         }
     }
 }
@@ -99,6 +101,11 @@ pub(crate) struct EnvironmentOverrides {
     ///
     /// If set, the passed in `git_dir` parameter will be ignored in favor of this one.
     git_dir: Option<PathBuf>,
+    /// An override for the directory containing objects, references and the configuration shared by all worktrees,
+    /// typically from the environment.
+    ///
+    /// If set, it's used instead of the directory stored in the `commondir` file of the git directory.
+    common_dir: Option<PathBuf>,
 }
 
 impl EnvironmentOverrides {
@@ -111,7 +118,15 @@ impl EnvironmentOverrides {
         if let Some(path) = std::env::var_os("GIT_DIR") {
             git_dir = PathBuf::from(path).into();
         }
-        Ok(EnvironmentOverrides { worktree_dir, git_dir })
+        let mut common_dir = None;
+        if let Some(path) = std::env::var_os("GIT_COMMON_DIR") {
+            common_dir = PathBuf::from(path).into();
+        }
+        Ok(EnvironmentOverrides {
+            worktree_dir,
+            git_dir,
+            common_dir,
+        })
     }
 }
 
@@ -207,6 +222,15 @@ impl Options {
         self
     }
 
+    /// If set, default is false, repositories with a `core.repositoryFormatVersion` other than 0 or 1 or with
+    /// `extensions.*` we don't know will be opened anyway.
+    ///
+    /// By default, and just like `git`, these are refused to avoid misinterpreting or even corrupting their data.
+    pub fn ignore_extension_check(mut self, toggle: bool) -> Self {
+        self.ignore_extension_check = toggle;
+        self
+    }
+
     /// Open a repository at `path` with the options set so far.
     pub fn open(self, path: impl Into<PathBuf>) -> Result<ThreadSafeRepository, Error> {
         ThreadSafeRepository::open_opts(path, self)
@@ -225,6 +249,7 @@ impl git_sec::trust::DefaultForLevel for Options {
                 lossy_config: None,
                 bail_if_untrusted: false,
                 lenient_config: true,
+                ignore_extension_check: false,
             },
             git_sec::Trust::Reduced => Options {
                 object_store_slots: git_odb::store::init::Slots::Given(32), // limit resource usage
@@ -235,6 +260,7 @@ impl git_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 lossy_config: None,
+                ignore_extension_check: false,
             },
         }
     }
@@ -292,7 +318,11 @@ impl ThreadSafeRepository {
     /// The `.git` directory whether given or computed is used for trust checks.
     ///
     /// Note that this will read various `GIT_*` environment variables to check for overrides, and is probably most useful when implementing
-    /// custom hooks.
+    /// custom hooks. These are:
+    ///
+    /// - `GIT_DIR` for the `.git` directory to open instead of `fallback_directory`
+    /// - `GIT_WORK_TREE` for the worktree, which takes precedence over the one implied by the `.git` directory
+    /// - `GIT_COMMON_DIR` for the directory with objects, references and configuration shared among worktrees
     // TODO: tests, with hooks, GIT_QUARANTINE for ref-log and transaction control (needs git-sec support to remove write access in git-ref)
     pub fn open_with_environment_overrides(
         fallback_directory: impl Into<PathBuf>,
@@ -309,15 +339,27 @@ impl ThreadSafeRepository {
 
         let (git_dir, worktree_dir) = git_discover::repository::Path::from_dot_git_dir(path, path_kind)
             .into_repository_and_work_tree_directories();
-        let worktree_dir = worktree_dir.or(overrides.worktree_dir);
+        let worktree_dir = overrides.worktree_dir.or(worktree_dir);
 
         let git_dir_trust = git_sec::Trust::from_path_ownership(&git_dir)?;
-        let options = trust_map.into_value_by_level(git_dir_trust);
-        ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, options)
+        let mut options = trust_map.into_value_by_level(git_dir_trust);
+        options.git_dir_trust = git_dir_trust.into();
+        ThreadSafeRepository::open_from_paths_with_common_dir(git_dir, overrides.common_dir, worktree_dir, options)
     }
 
     pub(crate) fn open_from_paths(
         git_dir: PathBuf,
+        worktree_dir: Option<PathBuf>,
+        options: Options,
+    ) -> Result<Self, Error> {
+        Self::open_from_paths_with_common_dir(git_dir, None, worktree_dir, options)
+    }
+
+    /// Like [`open_from_paths()`][Self::open_from_paths()], but use `common_dir` instead of the one stored in `git_dir`
+    /// if it is set.
+    fn open_from_paths_with_common_dir(
+        git_dir: PathBuf,
+        common_dir: Option<PathBuf>,
         mut worktree_dir: Option<PathBuf>,
         options: Options,
     ) -> Result<Self, Error> {
@@ -329,20 +371,29 @@ impl ThreadSafeRepository {
             lossy_config,
             lenient_config,
             bail_if_untrusted,
+            ignore_extension_check,
             permissions: Permissions { ref env, config },
         } = options;
         let git_dir_trust = git_dir_trust.expect("trust must be been determined by now");
 
-        // TODO: assure we handle the worktree-dir properly as we can have config per worktree with an extension.
-        //       This would be something read in later as have to first check for extensions. Also this means
-        //       that each worktree, even if accessible through this instance, has to come in its own Repository instance
-        //       as it may have its own configuration. That's fine actually.
-        let common_dir = git_discover::path::from_plain_file(git_dir.join("commondir"))
-            .transpose()?
-            .map(|cd| git_dir.join(cd));
+        let common_dir = match common_dir {
+            Some(common_dir) => Some(common_dir),
+            None => git_discover::path::from_plain_file(git_dir.join("commondir"))
+                .transpose()?
+                .map(|cd| git_dir.join(cd)),
+        };
         let common_dir_ref = common_dir.as_deref().unwrap_or(&git_dir);
 
-        let repo_config = config::cache::StageOne::new(common_dir_ref, git_dir_trust, lossy_config, lenient_config)?;
+        let repo_config = config::cache::StageOne::new(
+            common_dir_ref,
+            &git_dir,
+            git_dir_trust,
+            lossy_config,
+            lenient_config,
+            ignore_extension_check,
+        )?;
+        // Without worktree specific configuration, `core.worktree` in the shared configuration only applies to the main worktree.
+        let use_shared_core_worktree = repo_config.worktree_config || common_dir.is_none();
         let mut refs = {
             let reflog = repo_config.reflog.unwrap_or(git_ref::store::WriteReflog::Disable);
             let object_hash = repo_config.object_hash;
@@ -376,10 +427,9 @@ impl ThreadSafeRepository {
 
         // core.worktree might be used to overwrite the worktree directory
         if !config.is_bare {
-            if let Some(wt) = config
-                .resolved
-                .path_filter("core", None, "worktree", &mut filter_config_section)
-            {
+            if let Some(wt) = config.resolved.path_filter("core", None, "worktree", &mut move |meta| {
+                (use_shared_core_worktree || meta.source != git_config::Source::Local) && filter_config_section(meta)
+            }) {
                 let wt_path = wt
                     .interpolate(interpolate_context(git_install_dir.as_deref(), home.as_deref()))
                     .map_err(config::Error::PathInterpolation)?;
@@ -437,6 +487,7 @@ impl ThreadSafeRepository {
                     slots: object_store_slots,
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
+                    ..Default::default()
                 },
             )?),
             common_dir,
@@ -446,6 +497,7 @@ impl ThreadSafeRepository {
             // used when spawning new repositories off this one when following worktrees
             linked_worktree_options: options,
             index: git_features::fs::MutableSnapshot::new().into(),
+            shallow_commits: git_features::fs::MutableSnapshot::new().into(),
         })
     }
 }
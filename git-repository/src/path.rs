@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
+};
 
 pub use git_path::*;
 
@@ -9,3 +12,62 @@ pub(crate) fn install_dir() -> std::io::Result<PathBuf> {
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "no parent for current executable"))
     })
 }
+
+/// Return the directory in which git looks for its per-user configuration files, like `config`, `ignore` or `attributes`.
+///
+/// It's `$XDG_CONFIG_HOME/git` if the variable is set and not empty, or `$HOME/.config/git` otherwise. `None` is returned if
+/// neither variable is set.
+pub fn xdg_config_home() -> Option<PathBuf> {
+    xdg_home("XDG_CONFIG_HOME", ".config", &mut |name| std::env::var_os(name))
+}
+
+/// Return the directory in which git stores per-user data, like the `credentials` file of the store credential helper.
+///
+/// It's `$XDG_DATA_HOME/git` if the variable is set and not empty, or `$HOME/.local/share/git` otherwise. `None` is returned
+/// if neither variable is set.
+pub fn xdg_data_home() -> Option<PathBuf> {
+    xdg_home("XDG_DATA_HOME", ".local/share", &mut |name| std::env::var_os(name))
+}
+
+/// Obtain the `git` directory within the directory named by the XDG environment variable `xdg_var`, or within
+/// `home_relative_dir` of `$HOME` if it is unset or empty, using `env_var` to read environment variables.
+pub(crate) fn xdg_home(
+    xdg_var: &str,
+    home_relative_dir: &str,
+    env_var: &mut dyn FnMut(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    env_var(xdg_var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env_var("HOME").map(|home| PathBuf::from(home).join(home_relative_dir)))
+        .map(|dir| dir.join("git"))
+}
+
+/// Return `true` if all components of `prefix` are equal to the leading components of `path`, comparing them case-insensitively
+/// if `ignore_case` is set.
+///
+/// Comparisons are purely lexical and done component-by-component, so trailing slashes don't matter and `a/bc` isn't considered
+/// to be within `a/b`. Callers are expected to have resolved symlinks in both paths beforehand.
+pub(crate) fn is_within(path: &Path, prefix: &Path, ignore_case: bool) -> bool {
+    let mut path = path.components().filter(|c| !matches!(c, Component::CurDir));
+    for prefix_component in prefix.components().filter(|c| !matches!(c, Component::CurDir)) {
+        match path.next() {
+            Some(component) => {
+                let (lhs, rhs) = (component.as_os_str(), prefix_component.as_os_str());
+                let is_equal = if ignore_case {
+                    match (lhs.to_str(), rhs.to_str()) {
+                        (Some(lhs), Some(rhs)) => lhs.to_lowercase() == rhs.to_lowercase(),
+                        _ => lhs == rhs,
+                    }
+                } else {
+                    lhs == rhs
+                };
+                if !is_equal {
+                    return false;
+                }
+            }
+            None => return false,
+        }
+    }
+    true
+}
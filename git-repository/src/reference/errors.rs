@@ -14,6 +14,25 @@ pub mod edit {
     }
 }
 
+///
+pub mod create_branch {
+    /// The error returned by [Repository::create_branch(…)][crate::Repository::create_branch()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The branch '{name}' already exists")]
+        AlreadyExists { name: git_ref::FullName },
+        #[error("Cannot force-update the branch '{name}' as it is currently checked out")]
+        CheckedOut { name: git_ref::FullName },
+        #[error(transparent)]
+        Find(#[from] super::find::Error),
+        #[error(transparent)]
+        FindHead(#[from] super::find::existing::Error),
+        #[error(transparent)]
+        Edit(#[from] super::edit::Error),
+    }
+}
+
 ///
 pub mod peel {
     /// The error returned by [Reference::peel_to_id_in_place(…)][crate::Reference::peel_to_id_in_place()] and
@@ -28,6 +47,42 @@ pub mod peel {
     }
 }
 
+///
+pub mod peel_to_commit {
+    /// The error returned by [Reference::peel_to_commit(…)][crate::Reference::peel_to_commit()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Peel(#[from] super::peel::Error),
+        #[error(transparent)]
+        FindExistingObject(#[from] crate::object::find::existing::Error),
+        #[error("The reference peeled to {kind} object {id}, which is not a commit")]
+        NotACommit {
+            kind: git_object::Kind,
+            id: git_hash::ObjectId,
+        },
+    }
+}
+
+///
+pub mod peel_to_tag {
+    /// The error returned by [Reference::peel_to_tag(…)][crate::Reference::peel_to_tag()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FollowSymbolic(#[from] git_ref::peel::to_id::Error),
+        #[error(transparent)]
+        FindExistingObject(#[from] crate::object::find::existing::Error),
+        #[error("The reference points to {kind} object {id}, which is not an annotated tag")]
+        NotATag {
+            kind: git_object::Kind,
+            id: git_hash::ObjectId,
+        },
+    }
+}
+
 ///
 pub mod head_id {
     /// The error returned by [Repository::head_id(…)][crate::Repository::head_id()].
@@ -3,14 +3,14 @@
 use git_odb::pack::Find;
 use git_ref::file::ReferenceExt;
 
-use crate::{Id, Reference};
+use crate::{bstr::BStr, Id, Reference};
 
 pub mod iter;
 ///
 pub mod remote;
 
 mod errors;
-pub use errors::{edit, find, head_commit, head_id, peel};
+pub use errors::{create_branch, edit, find, head_commit, head_id, peel, peel_to_commit, peel_to_tag};
 
 use crate::ext::ObjectIdExt;
 
@@ -44,6 +44,20 @@ impl<'repo> Reference<'repo> {
         self.inner.name.as_ref()
     }
 
+    /// Return the reference's name without its well-known prefix, like `main` for `refs/heads/main`
+    /// or `origin/main` for `refs/remotes/origin/main`.
+    ///
+    /// Names without a known prefix, like `HEAD`, are returned unchanged.
+    pub fn short_name(&self) -> &BStr {
+        self.inner.name.as_ref().shorten()
+    }
+
+    /// Classify this reference by the kind of prefix it carries, or `None` if the name doesn't
+    /// fall into any well-known category, like `refs/stash`.
+    pub fn category(&self) -> Option<Category<'_>> {
+        self.inner.name.as_ref().category()
+    }
+
     /// Turn this instances into a stand-alone reference.
     pub fn detach(self) -> git_ref::Reference {
         self.inner
@@ -81,6 +95,33 @@ impl<'repo> Reference<'repo> {
     pub fn into_fully_peeled_id(mut self) -> Result<Id<'repo>, peel::Error> {
         self.peel_to_id_in_place()
     }
+
+    /// Follow all symbolic targets and peel the underlying object like [`peel_to_id_in_place()`][Reference::peel_to_id_in_place()],
+    /// and return it as commit.
+    ///
+    /// Returns an error if the object at the end of the chain isn't a commit, for instance if a tag points to a tree.
+    pub fn peel_to_commit(&mut self) -> Result<crate::Commit<'repo>, peel_to_commit::Error> {
+        let object = self.peel_to_id_in_place()?.object()?;
+        match object.kind {
+            git_object::Kind::Commit => Ok(object.into_commit()),
+            kind => Err(peel_to_commit::Error::NotACommit { kind, id: object.id }),
+        }
+    }
+
+    /// Follow all symbolic targets and return the annotated tag this reference points to.
+    ///
+    /// Unlike [`peel_to_commit()`][Reference::peel_to_commit()], tag objects aren't followed, and it's an error if the
+    /// reference points to any other kind of object, as lightweight tags do.
+    pub fn peel_to_tag(&self) -> Result<crate::Tag<'repo>, peel_to_tag::Error> {
+        let mut reference = self.inner.clone();
+        // The peeled id of packed tags is the object the tag points to, which must be skipped.
+        reference.peeled = None;
+        let id = reference.peel_to_id_in_place(&self.repo.refs, git_ref::peel::none)?;
+        self.repo
+            .find_object(id)?
+            .try_into_tag()
+            .map_err(|err| peel_to_tag::Error::NotATag { kind: err.actual, id })
+    }
 }
 
 mod edits;
@@ -0,0 +1,234 @@
+use std::{collections::BTreeSet, sync::atomic::AtomicBool};
+
+use git_odb::{Find, FindExt};
+use git_protocol::transport::client::Transport;
+
+use crate::{
+    remote,
+    remote::{fetch, fetch::Error, Connection},
+    Progress,
+};
+
+impl<'remote, 'repo, T, P> Connection<'remote, 'repo, T, P>
+where
+    T: Transport,
+    P: Progress,
+{
+    /// Perform a complete fetch: list all references on the remote, obtain all objects our remote-tracking references don't yet
+    /// know and write them into a new pack in the object database, before updating the local references as
+    /// per our remote's [`refspecs`][crate::Remote::refspecs()] for _fetching_.
+    ///
+    /// `should_interrupt` is checked while receiving the pack and may be set to abort the operation.
+    ///
+    /// Note that updates to local references never happen if the pack couldn't be received in its entirety, and that
+    /// all references are updated in a single transaction.
+    ///
+    /// If the server supports the `ref-in-want` feature of protocol V2, remote references are requested by name instead of
+    /// by the id they were advertised with. The ids the server responds with are authoritative and replace the advertised
+    /// ones in the [mappings][fetch::RefMap::mappings] before local references are updated, which avoids races with
+    /// references that change on the remote between listing and fetching them.
+    ///
+    /// The objects we have are negotiated with the server as described in [`Negotiation`][git_protocol::fetch::Negotiation],
+    /// in as many rounds as needed for the server to send only what we don't have.
+    ///
+    /// ### Limitations
+    ///
+    /// * Only blocking transports are supported.
+    /// * With protocol V1, there is only one round of negotiation in which up to
+    ///   [`MAX_IN_VAIN`][git_protocol::fetch::negotiate::MAX_IN_VAIN] `have`s are sent.
+    /// * No pruning of local references that were removed on the remote is performed.
+    /// * Shallow repositories are not supported.
+    pub fn fetch(mut self, should_interrupt: &AtomicBool) -> Result<fetch::Outcome<'remote>, Error> {
+        let mut ref_map = match self.ref_map_inner() {
+            Ok(ref_map) => ref_map,
+            Err(err) => {
+                git_protocol::fetch::indicate_end_of_interaction(&mut self.transport)?;
+                return Err(err.into());
+            }
+        };
+        let repo = self.remote.repo;
+        let protocol_version = ref_map.handshake.server_protocol_version;
+
+        let local_refs: Vec<_> = repo
+            .references()?
+            .all()?
+            .filter_map(Result::ok)
+            .filter_map(|r| r.try_id().map(|id| id.detach()))
+            .collect();
+        let remote_refs: Vec<_> = ref_map
+            .mappings
+            .iter()
+            .filter_map(|m| match &m.remote {
+                fetch::Source::Ref(r) => Some(r.clone()),
+                fetch::Source::ObjectId(_) => None,
+            })
+            .collect();
+        let mut negotiation = git_protocol::fetch::Negotiation::new(
+            &local_refs,
+            &remote_refs,
+            |id, buf| repo.objects.try_find(id, buf).ok().flatten(),
+            Default::default(),
+        )?;
+        let mut wanted_ids: Vec<_> = ref_map
+            .mappings
+            .iter()
+            .filter_map(|m| match &m.remote {
+                fetch::Source::ObjectId(id) => Some(*id),
+                fetch::Source::Ref(_) => None,
+            })
+            .collect();
+        wanted_ids.sort();
+        wanted_ids.dedup();
+        let known = repo.objects.filter_known(&wanted_ids)?;
+        let mut is_known = known.into_iter();
+        wanted_ids.retain(|_| !is_known.next().expect("one flag per id"));
+        if negotiation.wants().is_empty() && wanted_ids.is_empty() {
+            git_protocol::fetch::indicate_end_of_interaction(&mut self.transport)?;
+            let update_refs =
+                fetch::refs::update(repo, &ref_map.mappings, self.remote.refspecs(remote::Direction::Fetch))?;
+            return Ok(fetch::Outcome {
+                ref_map,
+                status: fetch::Status::NoPackReceived { update_refs },
+            });
+        }
+
+        let fetch = git_protocol::fetch::Command::Fetch;
+        let features = fetch.default_features(protocol_version, &ref_map.handshake.capabilities);
+        git_protocol::fetch::Response::check_required_features(protocol_version, &features)?;
+        let sideband_all = features.iter().any(|(n, _)| *n == "sideband-all");
+        let mut arguments = git_protocol::fetch::Arguments::new(protocol_version, features);
+        if arguments.can_use_ref_in_want() {
+            let mut seen = BTreeSet::new();
+            for r in &remote_refs {
+                let (name, id, _) = r.unpack();
+                if negotiation.wants().binary_search(&id.to_owned()).is_ok() && seen.insert(name) {
+                    arguments.want_ref(name);
+                }
+            }
+        } else {
+            for id in negotiation.wants() {
+                arguments.want(id);
+            }
+        }
+        for id in &wanted_ids {
+            arguments.want(id);
+        }
+
+        let is_stateless_v2 = matches!(protocol_version, git_protocol::transport::Protocol::V2);
+        let mut round = 1;
+        let write_pack_bundle = loop {
+            self.progress.set_name(format!("negotiate (round {})", round));
+            round += 1;
+            let mut haves = negotiation.next_haves()?;
+            if !is_stateless_v2 {
+                // Reading acknowledgements of intermediate rounds isn't reliable in V1, so send what's worth sending at once.
+                while haves.len() < git_protocol::fetch::negotiate::MAX_IN_VAIN {
+                    let more = negotiation.next_haves()?;
+                    if more.is_empty() {
+                        break;
+                    }
+                    haves.extend(more);
+                }
+            }
+            let is_done = haves.is_empty() || !is_stateless_v2;
+            if is_stateless_v2 {
+                for id in negotiation.common() {
+                    arguments.have(id);
+                }
+            }
+            for id in &haves {
+                arguments.have(id);
+            }
+
+            let mut reader = arguments.send(&mut self.transport, is_done)?;
+            if sideband_all {
+                setup_remote_progress(&mut self.progress, &mut reader);
+            }
+            let response = git_protocol::fetch::Response::from_line_reader(protocol_version, &mut reader)?;
+            if !response.has_pack() {
+                if is_done {
+                    return Err(Error::MissingPack);
+                }
+                negotiation.acknowledged(response.acknowledgements())?;
+                continue;
+            }
+            update_mappings_with_wanted_refs(&mut ref_map.mappings, response.wanted_refs());
+            if !sideband_all {
+                setup_remote_progress(&mut self.progress, &mut reader);
+            }
+            let options = git_pack::bundle::write::Options {
+                thread_limit: None,
+                index_kind: git_pack::index::Version::V2,
+                iteration_mode: git_pack::data::input::Mode::Verify,
+                object_hash: repo.object_hash(),
+            };
+            break git_pack::Bundle::write_to_directory(
+                reader,
+                Some(repo.objects.store_ref().path().join("pack")),
+                self.progress.add_child("receiving pack"),
+                should_interrupt,
+                Some(Box::new({
+                    let objects = repo.objects.clone();
+                    move |oid, buf| objects.find(oid, buf).ok()
+                })),
+                options,
+            )?;
+        };
+
+        if matches!(protocol_version, git_protocol::transport::Protocol::V2) {
+            git_protocol::fetch::indicate_end_of_interaction(&mut self.transport)?;
+        }
+
+        let update_refs = fetch::refs::update(repo, &ref_map.mappings, self.remote.refspecs(remote::Direction::Fetch))?;
+
+        Ok(fetch::Outcome {
+            ref_map,
+            status: fetch::Status::Change {
+                write_pack_bundle,
+                update_refs,
+            },
+        })
+    }
+}
+
+/// Set the ids of remote references in `mappings` to the ones the server sent for them in response to `want-ref`,
+/// as these are what the received pack contains.
+fn update_mappings_with_wanted_refs(
+    mappings: &mut [fetch::Mapping],
+    wanted_refs: &[git_protocol::fetch::response::WantedRef],
+) {
+    use git_protocol::fetch::Ref;
+    for mapping in mappings {
+        let r = match &mut mapping.remote {
+            fetch::Source::Ref(r) => r,
+            fetch::Source::ObjectId(_) => continue,
+        };
+        let wanted = match wanted_refs.iter().find(|w| w.path == r.unpack().0) {
+            Some(wanted) => wanted,
+            None => continue,
+        };
+        match r {
+            Ref::Direct { object, .. } | Ref::Symbolic { object, .. } => *object = wanted.id,
+            Ref::Peeled { tag, .. } if *tag == wanted.id => {}
+            Ref::Peeled { full_ref_name, .. } => {
+                // The tag moved, and we don't know what the new one points to.
+                *r = Ref::Direct {
+                    full_ref_name: std::mem::take(full_ref_name),
+                    object: wanted.id,
+                }
+            }
+        }
+    }
+}
+
+fn setup_remote_progress(
+    progress: &mut impl Progress,
+    reader: &mut Box<dyn git_protocol::transport::client::ExtendedBufRead + Unpin + '_>,
+) {
+    reader.set_progress_handler(Some(Box::new({
+        let mut remote_progress = progress.add_child("remote");
+        move |is_err: bool, data: &[u8]| {
+            git_protocol::RemoteProgress::translate_to_progress(is_err, data, &mut remote_progress)
+        }
+    }) as git_protocol::transport::client::HandleProgress));
+}
@@ -67,3 +67,6 @@ mod access {
 
 ///
 pub mod ref_map;
+
+#[cfg(feature = "blocking-network-client")]
+mod fetch;
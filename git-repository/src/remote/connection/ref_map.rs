@@ -39,7 +39,7 @@ where
     }
 
     #[git_protocol::maybe_async::maybe_async]
-    async fn ref_map_inner(&mut self) -> Result<fetch::RefMap<'remote>, Error> {
+    pub(crate) async fn ref_map_inner(&mut self) -> Result<fetch::RefMap<'remote>, Error> {
         let remote = self.fetch_refs().await?;
         let group = git_refspec::MatchGroup::from_fetch_specs(self.remote.fetch_specs.iter().map(|s| s.to_ref()));
         let (res, fixes) = group
@@ -0,0 +1,111 @@
+use crate::bstr::BString;
+
+/// Information about the relationship between our refspecs, and remote references with their local counterparts.
+#[derive(Debug, Clone)]
+pub struct RefMap<'spec> {
+    /// A mapping between a remote reference and a local tracking branch.
+    pub mappings: Vec<Mapping>,
+    /// Information about the fixes applied to the `mapping` due to validation and sanitization.
+    pub fixes: Vec<git_refspec::match_group::validate::Fix<'spec>>,
+    /// All refs advertised by the remote.
+    pub remote_refs: Vec<git_protocol::fetch::Ref>,
+    /// Additional information provided by the server as part of the handshake.
+    ///
+    /// Note that the `refs` field is always `None` as the refs are placed in `remote_refs`.
+    pub handshake: git_protocol::fetch::handshake::Outcome,
+}
+
+/// Either an object id that the remote has or the matched remote ref itself.
+#[derive(Debug, Clone)]
+pub enum Source {
+    /// An object id, as the matched ref-spec was an object id itself.
+    ObjectId(git_hash::ObjectId),
+    /// The remote reference that matched the ref-specs name.
+    Ref(git_protocol::fetch::Ref),
+}
+
+impl Source {
+    /// Return the id of the object the remote has, which is the tag object itself in case of peeled refs.
+    pub fn as_id(&self) -> &git_hash::oid {
+        match self {
+            Source::ObjectId(id) => id,
+            Source::Ref(r) => r.unpack().1,
+        }
+    }
+}
+
+/// A mapping between a single remote reference and its advertised objects to a local destination which may or may not exist.
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    /// The reference on the remote side, along with information about the objects they point to as advertised by the server.
+    pub remote: Source,
+    /// The local tracking reference to update after fetching the object visible via `remote`.
+    pub local: Option<BString>,
+    /// The index into the fetch ref-specs used to produce the mapping, allowing it to be recovered.   
+    pub spec_index: usize,
+}
+
+#[cfg(feature = "blocking-network-client")]
+mod error {
+    /// The error returned by [`Connection::fetch()`][crate::remote::Connection::fetch()] and [`Repository::fetch()`][crate::Repository::fetch()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        RefMap(#[from] crate::remote::ref_map::Error),
+        #[error(transparent)]
+        Transport(#[from] git_protocol::transport::client::Error),
+        #[error(transparent)]
+        Response(#[from] git_protocol::fetch::response::Error),
+        #[error("Could not iterate local references to tell the remote what we have")]
+        IterReferences(#[from] crate::reference::iter::Error),
+        #[error("Could not start iterating local references to tell the remote what we have")]
+        IterReferencesInit(#[from] crate::reference::iter::init::Error),
+        #[error("Could not determine the objects to fetch and the ones we have")]
+        Negotiate(#[from] git_protocol::fetch::negotiate::Error),
+        #[error("Could not determine which of the objects to fetch by id we have already")]
+        FilterKnown(#[from] git_odb::store::filter_known::Error),
+        #[error("The remote didn't send a pack even though we asked for objects")]
+        MissingPack,
+        #[error("Failed to write the received pack into the object database")]
+        WritePack(#[from] git_pack::bundle::write::Error),
+        #[error(transparent)]
+        UpdateRefs(#[from] crate::remote::fetch::refs::update::Error),
+    }
+}
+#[cfg(feature = "blocking-network-client")]
+pub use error::Error;
+
+/// The outcome of a [`Connection::fetch()`][crate::remote::Connection::fetch()] operation.
+#[cfg(feature = "blocking-network-client")]
+#[derive(Debug, Clone)]
+pub struct Outcome<'spec> {
+    /// The result of the initial mapping of references, the prerequisite for any fetch.
+    pub ref_map: RefMap<'spec>,
+    /// The status of the operation to indicate what happened.
+    pub status: Status,
+}
+
+/// The status of the repository after the fetch operation.
+#[cfg(feature = "blocking-network-client")]
+#[derive(Debug, Clone)]
+pub enum Status {
+    /// No pack was received as we already had all objects the remote refers to, but local references may still have been updated.
+    NoPackReceived {
+        /// Information collected while updating references.
+        update_refs: refs::update::Outcome,
+    },
+    /// There was at least one tip with a new object which we received.
+    Change {
+        /// Information collected while writing the pack and its index.
+        write_pack_bundle: git_pack::bundle::write::Outcome,
+        /// Information collected while updating references.
+        update_refs: refs::update::Outcome,
+    },
+}
+
+///
+#[cfg(feature = "blocking-network-client")]
+pub mod refs;
@@ -0,0 +1,137 @@
+use std::convert::TryFrom;
+
+use git_odb::{Find, FindExt};
+use git_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target, TargetRef,
+};
+
+use crate::remote::fetch;
+
+///
+pub mod update;
+
+/// Information about the update of a single reference, corresponding the respective entry in [`RefMap::mappings`][crate::remote::fetch::RefMap::mappings].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    /// The way the update was performed.
+    pub mode: update::Mode,
+    /// The index to the edit that was created from the corresponding mapping, or `None` if there was no local ref.
+    pub edit_index: Option<usize>,
+}
+
+impl From<update::Mode> for Update {
+    fn from(mode: update::Mode) -> Self {
+        Update { mode, edit_index: None }
+    }
+}
+
+/// Update all refs as derived from `mappings` and produce an `Outcome` informing about all applied changes in detail.
+/// `fetch_specs` are the refspecs the `mappings` were created from, and are used to determine if forced updates are allowed.
+///
+/// It can be used to produce typical information that one is used to from `git fetch`.
+pub(crate) fn update(
+    repo: &crate::Repository,
+    mappings: &[fetch::Mapping],
+    fetch_specs: &[git_refspec::RefSpec],
+) -> Result<update::Outcome, update::Error> {
+    let mut edits = Vec::new();
+    let mut updates = Vec::new();
+
+    for fetch::Mapping {
+        remote,
+        local,
+        spec_index,
+    } in mappings
+    {
+        let local = match local {
+            Some(name) => name,
+            None => {
+                updates.push(update::Mode::NoChangeNeeded.into());
+                continue;
+            }
+        };
+        let remote_id = remote.as_id();
+        if !repo.objects.contains(remote_id) {
+            updates.push(update::Mode::RejectedSourceObjectNotFound { id: remote_id.into() }.into());
+            continue;
+        }
+        let name = git_ref::FullName::try_from(local.clone())?;
+        let (mode, reflog_message, previous_value) = match repo.try_find_reference(&name)? {
+            Some(existing) => match existing.target() {
+                TargetRef::Symbolic(_) => {
+                    updates.push(update::Mode::RejectedSymbolic.into());
+                    continue;
+                }
+                TargetRef::Peeled(local_id) => {
+                    let previous_value = PreviousValue::MustExistAndMatch(Target::Peeled(local_id.to_owned()));
+                    if local_id == remote_id {
+                        updates.push(update::Mode::NoChangeNeeded.into());
+                        continue;
+                    }
+                    let allow_non_fast_forward = matches!(
+                        fetch_specs[*spec_index].to_ref().instruction(),
+                        git_refspec::Instruction::Fetch(git_refspec::instruction::Fetch::AndUpdate {
+                            allow_non_fast_forward: true,
+                            ..
+                        })
+                    );
+                    if !allow_non_fast_forward && name.as_bstr().starts_with(b"refs/tags/") {
+                        updates.push(update::Mode::RejectedTagUpdate.into());
+                        continue;
+                    }
+                    if is_fast_forward(repo, local_id, remote_id)? {
+                        (update::Mode::FastForward, "fast-forward", previous_value)
+                    } else if allow_non_fast_forward {
+                        (update::Mode::Forced, "forced-update", previous_value)
+                    } else {
+                        updates.push(update::Mode::RejectedNonFastForward.into());
+                        continue;
+                    }
+                }
+            },
+            None => (update::Mode::New, "storing head", PreviousValue::MustNotExist),
+        };
+        let edit = RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("fetch: {}", reflog_message).into(),
+                },
+                expected: previous_value,
+                new: Target::Peeled(remote_id.into()),
+            },
+            name,
+            deref: false,
+        };
+        updates.push(Update {
+            mode,
+            edit_index: Some(edits.len()),
+        });
+        edits.push(edit);
+    }
+
+    let edits = repo.edit_references(edits, git_lock::acquire::Fail::Immediately, repo.committer_or_default())?;
+    Ok(update::Outcome { edits, updates })
+}
+
+/// Return true if `ancestor` can be reached from `descendant` by following commit parents, which is only possible if both are commits.
+fn is_fast_forward(
+    repo: &crate::Repository,
+    ancestor: &git_hash::oid,
+    descendant: &git_hash::oid,
+) -> Result<bool, update::Error> {
+    let mut buf = Vec::new();
+    for id in [ancestor, descendant] {
+        if repo.objects.find(id, &mut buf)?.kind != git_object::Kind::Commit {
+            return Ok(false);
+        }
+    }
+    for commit in crate::Id::from_id(descendant, repo).ancestors().all()? {
+        if commit? == *ancestor {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,96 @@
+use crate::remote::fetch;
+
+mod error {
+    /// The error returned when updating references.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        InvalidRefName(#[from] git_validate::refname::Error),
+        #[error("Failed to update references to their new position to match their remote locations")]
+        EditReferences(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+        #[error("Could not find an object to determine if the update is a fast-forward")]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        WalkAncestors(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        TraverseAncestors(#[from] git_traverse::commit::ancestors::Error),
+    }
+}
+
+pub use error::Error;
+
+/// The outcome of the refs-update operation at the end of a fetch.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// All edits that were performed to update local refs.
+    pub edits: Vec<git_ref::transaction::RefEdit>,
+    /// Each update provides more information about what happened to the corresponding mapping.
+    /// Use [`iter_mapping_updates()`][Self::iter_mapping_updates()] to recombine the update information with ref-edits and their
+    /// mapping.
+    pub updates: Vec<super::Update>,
+}
+
+/// Describe the way a ref was updated
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// No change was attempted as the remote ref didn't change compared to the current ref, or because no remote ref was specified
+    /// in the ref-spec.
+    NoChangeNeeded,
+    /// The old ref's commit was an ancestor of the new one, allowing for a fast-forward without a merge.
+    FastForward,
+    /// The ref was set to point to the new commit from the remote without taking into consideration its ancestry.
+    Forced,
+    /// A new ref has been created as there was none before.
+    New,
+    /// The object id to set the target reference to could not be found.
+    RejectedSourceObjectNotFound {
+        /// The id of the object that didn't exist in the object database, even though it should since it should be part of the pack.
+        id: git_hash::ObjectId,
+    },
+    /// Tags can never be overwritten (whether the new object would be a fast-forward or not, or unchanged), unless the refspec
+    /// specifies force.
+    RejectedTagUpdate,
+    /// The reference update would not have been a fast-forward, and force is not specified in the ref-spec.
+    RejectedNonFastForward,
+    /// The update of a local symbolic reference was rejected.
+    RejectedSymbolic,
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::NoChangeNeeded => "up-to-date",
+            Mode::FastForward => "fast-forward",
+            Mode::Forced => "forced-update",
+            Mode::New => "new",
+            Mode::RejectedSourceObjectNotFound { .. } => "rejected (source object not found)",
+            Mode::RejectedTagUpdate => "rejected (would overwrite existing tag)",
+            Mode::RejectedNonFastForward => "rejected (non-fast-forward)",
+            Mode::RejectedSymbolic => "rejected (refusing to write symbolic refs)",
+        }
+        .fmt(f)
+    }
+}
+
+impl Outcome {
+    /// Produce an iterator over all information used to produce the this outcome, ref-update by ref-update, using the `mappings`
+    /// used when producing the ref update.
+    pub fn iter_mapping_updates<'a>(
+        &self,
+        mappings: &'a [fetch::Mapping],
+    ) -> impl Iterator<
+        Item = (
+            &super::Update,
+            &'a fetch::Mapping,
+            Option<&git_ref::transaction::RefEdit>,
+        ),
+    > {
+        self.updates
+            .iter()
+            .zip(mappings.iter())
+            .map(move |(update, mapping)| (update, mapping, update.edit_index.and_then(|idx| self.edits.get(idx))))
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use git_hash::ObjectId;
+use git_object::tree::{EntryMode, EntryRef};
+use git_traverse::tree::{visit::Action, Visit};
+
+use crate::{
+    archive::{Error, Format, Options},
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+};
+
+impl crate::Repository {
+    /// Write the tree of `id`, which may also be a commit or a tag pointing to one, to `out` as archive in the format
+    /// configured in `options`, similar to `git archive`.
+    ///
+    /// Each blob is written as soon as it was read from the object database, so only one of them is held in memory at a time.
+    /// All entries have the time of the commit as modification time, or the current time if `id` points to a tree.
+    /// Executable files have mode `0755` and all other files mode `0644`, symbolic links are written as such, and submodules
+    /// become empty directories.
+    pub fn archive(&self, id: impl Into<ObjectId>, out: impl Write, options: Options) -> Result<(), Error> {
+        let object = self.find_object(id)?.peel_tags_to_end()?;
+        let mtime = match object.kind {
+            git_object::Kind::Commit => object.to_commit_ref_iter().committer()?.time.seconds_since_unix_epoch as u64,
+            _ => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        };
+        let tree = object.peel_to_tree()?;
+        match options.format {
+            Format::Tar => {
+                write_tar(&tree, out, options.prefix.as_ref(), mtime)?;
+            }
+            Format::TarGz => {
+                write_tar(
+                    &tree,
+                    flate2::write::GzEncoder::new(out, flate2::Compression::default()),
+                    options.prefix.as_ref(),
+                    mtime,
+                )?
+                .finish()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write all entries of `tree` as `tar` archive into `out` and return it once the archive is complete.
+fn write_tar<W: Write>(tree: &crate::Tree<'_>, out: W, prefix: &BStr, mtime: u64) -> Result<W, Error> {
+    let mut writer = Writer {
+        repo: tree.repo,
+        builder: tar::Builder::new(out),
+        mtime,
+        path_deque: VecDeque::new(),
+        path: BString::default(),
+        prefix,
+        err: None,
+    };
+    if prefix.ends_with(b"/") {
+        writer.append_directory(prefix)?;
+    }
+    let res = tree.traverse().breadthfirst(&mut writer);
+    if let Some(err) = writer.err.take() {
+        return Err(err);
+    }
+    res?;
+    Ok(writer.builder.into_inner()?)
+}
+
+/// A delegate to write each entry of a tree to a `tar` archive as it's encountered during traversal.
+struct Writer<'a, 'repo, W: Write> {
+    repo: &'repo crate::Repository,
+    builder: tar::Builder<W>,
+    mtime: u64,
+    path_deque: VecDeque<BString>,
+    path: BString,
+    prefix: &'a BStr,
+    /// The first error that occurred, which also cancels the traversal.
+    err: Option<Error>,
+}
+
+impl<'a, 'repo, W: Write> Writer<'a, 'repo, W> {
+    fn pop_element(&mut self) {
+        if let Some(pos) = self.path.rfind_byte(b'/') {
+            self.path.resize(pos, 0);
+        } else {
+            self.path.clear();
+        }
+    }
+
+    fn push_element(&mut self, name: &BStr) {
+        if !self.path.is_empty() {
+            self.path.push(b'/');
+        }
+        self.path.push_str(name);
+    }
+
+    fn entry_path(&self) -> BString {
+        let mut path = BString::from(self.prefix);
+        path.push_str(&self.path);
+        path
+    }
+
+    fn header(&self, entry_type: tar::EntryType, mode: u32, size: u64) -> tar::Header {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(entry_type);
+        header.set_mode(mode);
+        header.set_size(size);
+        header.set_mtime(self.mtime);
+        header
+    }
+
+    fn append_directory(&mut self, path: &BStr) -> Result<(), Error> {
+        let mut header = self.header(tar::EntryType::Directory, 0o755, 0);
+        self.builder
+            .append_data(&mut header, to_path(path)?, std::io::empty())
+            .map_err(|err| Error::WriteEntry {
+                source: err,
+                path: path.to_owned(),
+            })
+    }
+
+    fn append_entry(&mut self, entry: &EntryRef<'_>) -> Result<(), Error> {
+        let path = self.entry_path();
+        let (mode, is_link) = match entry.mode {
+            EntryMode::Tree | EntryMode::Commit => return self.append_directory(path.as_ref()),
+            EntryMode::Blob => (0o644, false),
+            EntryMode::BlobExecutable => (0o755, false),
+            EntryMode::Link => (0o777, true),
+        };
+        let blob = self.repo.find_object(entry.oid)?;
+        let res = if is_link {
+            let mut header = self.header(tar::EntryType::Symlink, mode, 0);
+            self.builder
+                .append_link(&mut header, to_path(path.as_ref())?, to_path(blob.data.as_bstr())?)
+        } else {
+            let mut header = self.header(tar::EntryType::Regular, mode, blob.data.len() as u64);
+            self.builder
+                .append_data(&mut header, to_path(path.as_ref())?, blob.data.as_slice())
+        };
+        res.map_err(|err| Error::WriteEntry { source: err, path })
+    }
+
+    fn visit(&mut self, entry: &EntryRef<'_>) -> Action {
+        match self.append_entry(entry) {
+            Ok(()) => Action::Continue,
+            Err(err) => {
+                self.err = Some(err);
+                Action::Cancel
+            }
+        }
+    }
+}
+
+impl<'a, 'repo, W: Write> Visit for Writer<'a, 'repo, W> {
+    fn pop_front_tracked_path_and_set_current(&mut self) {
+        self.path = self
+            .path_deque
+            .pop_front()
+            .expect("every call is matched with push_tracked_path_component");
+    }
+
+    fn push_back_tracked_path_component(&mut self, component: &BStr) {
+        self.push_element(component);
+        self.path_deque.push_back(self.path.clone());
+    }
+
+    fn push_path_component(&mut self, component: &BStr) {
+        self.push_element(component);
+    }
+
+    fn pop_path_component(&mut self) {
+        self.pop_element();
+    }
+
+    fn visit_tree(&mut self, entry: &EntryRef<'_>) -> Action {
+        self.visit(entry)
+    }
+
+    fn visit_nontree(&mut self, entry: &EntryRef<'_>) -> Action {
+        self.visit(entry)
+    }
+}
+
+fn to_path(path: &BStr) -> Result<std::borrow::Cow<'_, Path>, Error> {
+    git_path::try_from_bstr(path).map_err(|_| Error::IllformedPath { path: path.to_owned() })
+}
@@ -0,0 +1,248 @@
+use std::collections::{hash_map, BinaryHeap, HashMap};
+
+use git_diff::lines::similar::DiffOp;
+use git_hash::ObjectId;
+use git_object::tree::EntryMode;
+
+use crate::{
+    blame::{Entry, Error, Options},
+    bstr::{BStr, BString, ByteSlice},
+};
+
+/// Lines of a version of the blamed file which still have to be attributed to a commit.
+#[derive(Debug, Clone, Copy)]
+struct UnblamedHunk {
+    /// The first line in the blamed file.
+    final_start: usize,
+    /// The first line in the version of the file of the suspected commit.
+    suspect_start: usize,
+    len: usize,
+}
+
+/// The version of the file in a commit suspected of having introduced `hunks`.
+struct Suspect {
+    blob: ObjectId,
+    hunks: Vec<UnblamedHunk>,
+}
+
+impl crate::Repository {
+    /// Attribute each line of the file at `path`, like `dir/file`, as seen in the commit `commit_id` to the commit that
+    /// introduced it, similar to `git blame`, and return the ranges of lines sorted by their position in the file.
+    ///
+    /// Commits are visited from the most recent to the oldest one. Lines which are unchanged compared to a parent are passed
+    /// on to it, and all lines that remain are attributed to the commit itself. Lines of merge commits are passed to the first
+    /// parent that has them, unless only the [first parent][Options::first_parent] should be followed.
+    ///
+    /// # Deviation
+    ///
+    /// - Renames are only followed if the file in the parent commit is unchanged, as there is no similarity-based rename
+    ///   detection yet.
+    /// - Lines aren't searched for in other files, as `git blame -C` would do.
+    /// - The line-diff may align ambiguous changes differently than `git` does, attributing such lines to another commit.
+    pub fn blame(
+        &self,
+        path: impl AsRef<BStr>,
+        commit_id: impl Into<ObjectId>,
+        options: Options,
+    ) -> Result<Vec<Entry>, Error> {
+        let path = path.as_ref();
+        let commit_id = commit_id.into();
+        let commit = self.find_object(commit_id)?.try_into_commit()?;
+        let blob = self
+            .file_in_tree(commit.tree_id()?.detach(), path)?
+            .ok_or_else(|| Error::FileNotFound {
+                path: path.to_owned(),
+                commit_id,
+            })?;
+        let num_lines = self.find_object(blob)?.data.lines_with_terminator().count();
+        let mut entries = Vec::new();
+        if num_lines == 0 {
+            return Ok(entries);
+        }
+
+        let line_options = git_diff::lines::Options {
+            ignore_all_space: options.ignore_whitespace,
+            ..Default::default()
+        };
+        let mut suspects = HashMap::<(ObjectId, BString), Suspect>::new();
+        let mut queue = BinaryHeap::<(u32, ObjectId, BString)>::new();
+        suspects.insert(
+            (commit_id, path.to_owned()),
+            Suspect {
+                blob,
+                hunks: vec![UnblamedHunk {
+                    final_start: 0,
+                    suspect_start: 0,
+                    len: num_lines,
+                }],
+            },
+        );
+        queue.push((
+            commit.decode()?.committer.time.seconds_since_unix_epoch,
+            commit_id,
+            path.to_owned(),
+        ));
+
+        while let Some((_time, commit_id, path)) = queue.pop() {
+            let Suspect { blob, mut hunks } = match suspects.remove(&(commit_id, path.clone())) {
+                Some(suspect) => suspect,
+                None => continue,
+            };
+            let commit = self.find_object(commit_id)?.try_into_commit()?;
+            let (author, mut parent_ids) = {
+                let commit = commit.decode()?;
+                (commit.author.to_owned(), commit.parents().collect::<Vec<_>>())
+            };
+            if options.first_parent {
+                parent_ids.truncate(1);
+            }
+            let data = self.find_object(blob)?.detach().data;
+
+            for parent_id in parent_ids {
+                if hunks.is_empty() {
+                    break;
+                }
+                let parent = self.find_object(parent_id)?.try_into_commit()?;
+                let parent_tree = parent.tree_id()?.detach();
+                let (parent_path, parent_blob) = match self.file_in_tree(parent_tree, path.as_ref())? {
+                    Some(parent_blob) => (path.clone(), parent_blob),
+                    None => match self.renamed_file_in_tree(parent_tree, blob)? {
+                        Some(renamed) => renamed,
+                        None => continue,
+                    },
+                };
+
+                let passed = if parent_blob == blob {
+                    std::mem::take(&mut hunks)
+                } else {
+                    let parent_data = self.find_object(parent_blob)?;
+                    let diff = git_diff::lines::with_options(
+                        parent_data.data.as_bstr(),
+                        data.as_bstr(),
+                        git_diff::lines::Algorithm::Myers,
+                        line_options,
+                    );
+                    let (passed, remaining) = pass_unchanged_lines(hunks, &diff.ops);
+                    hunks = remaining;
+                    passed
+                };
+                if passed.is_empty() {
+                    continue;
+                }
+                match suspects.entry((parent_id, parent_path)) {
+                    hash_map::Entry::Occupied(mut entry) => entry.get_mut().hunks.extend(passed),
+                    hash_map::Entry::Vacant(entry) => {
+                        queue.push((
+                            parent.decode()?.committer.time.seconds_since_unix_epoch,
+                            parent_id,
+                            entry.key().1.clone(),
+                        ));
+                        entry.insert(Suspect {
+                            blob: parent_blob,
+                            hunks: passed,
+                        });
+                    }
+                }
+            }
+
+            entries.extend(hunks.into_iter().map(|hunk| Entry {
+                commit_id,
+                author: author.clone(),
+                lines: hunk.final_start..hunk.final_start + hunk.len,
+                original_lines: hunk.suspect_start..hunk.suspect_start + hunk.len,
+                original_path: path.clone(),
+            }));
+        }
+
+        entries.sort_by_key(|entry| entry.lines.start);
+        Ok(coalesce(entries))
+    }
+
+    fn file_in_tree(&self, tree: ObjectId, path: &BStr) -> Result<Option<ObjectId>, Error> {
+        Ok(self
+            .find_object(tree)?
+            .try_into_tree()?
+            .lookup_entry(path.split_str("/"))?
+            .filter(|entry| is_file(entry.mode))
+            .map(|entry| entry.oid))
+    }
+
+    /// Find a file with the exact content `blob` in `tree`, returning its path and id.
+    fn renamed_file_in_tree(&self, tree: ObjectId, blob: ObjectId) -> Result<Option<(BString, ObjectId)>, Error> {
+        Ok(self
+            .find_object(tree)?
+            .try_into_tree()?
+            .traverse()
+            .breadthfirst
+            .files()?
+            .into_iter()
+            .filter(|entry| entry.oid == blob && is_file(entry.mode))
+            .map(|entry| entry.filepath)
+            .min()
+            .map(|path| (path, blob)))
+    }
+}
+
+fn is_file(mode: EntryMode) -> bool {
+    matches!(mode, EntryMode::Blob | EntryMode::BlobExecutable | EntryMode::Link)
+}
+
+/// Split `hunks` into those that are unchanged according to `ops`, translated to the line numbers of the old version of the file,
+/// and those that remain.
+fn pass_unchanged_lines(hunks: Vec<UnblamedHunk>, ops: &[DiffOp]) -> (Vec<UnblamedHunk>, Vec<UnblamedHunk>) {
+    let (mut passed, mut remaining) = (Vec::new(), Vec::new());
+    for hunk in hunks {
+        let end = hunk.suspect_start + hunk.len;
+        let sub_hunk = |start: usize, end: usize| UnblamedHunk {
+            final_start: hunk.final_start + (start - hunk.suspect_start),
+            suspect_start: start,
+            len: end - start,
+        };
+        let mut cursor = hunk.suspect_start;
+        for op in ops {
+            if let DiffOp::Equal {
+                old_index,
+                new_index,
+                len,
+            } = *op
+            {
+                let (start, stop) = (new_index.max(cursor), (new_index + len).min(end));
+                if start >= stop {
+                    continue;
+                }
+                if start > cursor {
+                    remaining.push(sub_hunk(cursor, start));
+                }
+                passed.push(UnblamedHunk {
+                    suspect_start: old_index + (start - new_index),
+                    ..sub_hunk(start, stop)
+                });
+                cursor = stop;
+            }
+        }
+        if cursor < end {
+            remaining.push(sub_hunk(cursor, end));
+        }
+    }
+    (passed, remaining)
+}
+
+/// Merge adjacent entries of the same commit and file whose lines are contiguous in both versions of the file.
+fn coalesce(entries: Vec<Entry>) -> Vec<Entry> {
+    let mut out: Vec<Entry> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match out.last_mut() {
+            Some(last)
+                if last.commit_id == entry.commit_id
+                    && last.original_path == entry.original_path
+                    && last.lines.end == entry.lines.start
+                    && last.original_lines.end == entry.original_lines.start =>
+            {
+                last.lines.end = entry.lines.end;
+                last.original_lines.end = entry.original_lines.end;
+            }
+            _ => out.push(entry),
+        }
+    }
+    out
+}
@@ -18,6 +18,27 @@ impl crate::Repository {
         }
     }
 
+    /// Sets the amount of space used at most for caching delta bases of packed objects to `Some(bytes)`, or `None` to
+    /// deactivate it entirely.
+    ///
+    /// By default, a small cache limited to the amount of bytes configured in `core.deltaBaseCacheLimit` (or 96MB) is used,
+    /// which is cleared entirely once it is full. The cache set here keeps the most recently used delta bases instead and
+    /// is more effective for large values.
+    ///
+    /// Note that the pack cache is only available with the `max-performance-safe` feature, and this method does nothing
+    /// without it. `Some(0)` is treated like `None`.
+    pub fn pack_cache_size(&mut self, bytes: impl Into<Option<usize>>) {
+        #[cfg(feature = "max-performance-safe")]
+        match bytes.into() {
+            Some(bytes) if bytes != 0 => self
+                .objects
+                .set_pack_cache(move || Box::new(git_pack::cache::lru::MemoryCappedHashmap::new(bytes))),
+            _ => self.objects.unset_pack_cache(),
+        }
+        #[cfg(not(feature = "max-performance-safe"))]
+        let _ = bytes;
+    }
+
     /// Set an object cache of size `bytes` if none is set.
     ///
     /// Use this method to avoid overwriting any existing value while assuring better performance in case no value is set.
@@ -48,14 +69,10 @@ impl crate::Repository {
             let pack_cache_disabled = std::env::var_os("GITOXIDE_DISABLE_PACK_CACHE").is_some();
             let mut this = self;
             if !pack_cache_disabled {
-                let bytes = parse_bytes_from_var("GITOXIDE_PACK_CACHE_MEMORY");
-                let new_pack_cache = move || -> Box<git_odb::cache::PackCache> {
-                    match bytes {
-                        Some(bytes) => Box::new(git_pack::cache::lru::MemoryCappedHashmap::new(bytes)),
-                        None => Box::new(git_pack::cache::lru::StaticLinkedList::<64>::default()),
-                    }
-                };
-                this.objects.set_pack_cache(new_pack_cache);
+                match parse_bytes_from_var("GITOXIDE_PACK_CACHE_MEMORY") {
+                    Some(bytes) => this.pack_cache_size(bytes),
+                    None => set_default_pack_cache(&mut this.objects, this.config.pack_cache_bytes),
+                }
             } else {
                 this.objects.unset_pack_cache();
             }
@@ -69,6 +86,16 @@ impl crate::Repository {
     }
 }
 
+/// Set the pack cache used by default on `objects`, holding up to 64 delta bases in at most `bytes`, or remove it if `bytes` is 0.
+#[cfg(feature = "max-performance-safe")]
+pub(crate) fn set_default_pack_cache(objects: &mut crate::OdbHandle, bytes: usize) {
+    if bytes == 0 {
+        objects.unset_pack_cache();
+    } else {
+        objects.set_pack_cache(move || Box::new(git_pack::cache::lru::StaticLinkedList::<64>::new(bytes)));
+    }
+}
+
 #[cfg(feature = "max-performance-safe")]
 fn parse_bytes_from_var(name: &str) -> Option<usize> {
     std::env::var(name)
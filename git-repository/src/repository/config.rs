@@ -20,10 +20,42 @@ impl crate::Repository {
         &self.options
     }
 
-    /// The kind of object hash the repository is configured to use.
+    /// The kind of object hash the repository is configured to use, as defined by `extensions.objectFormat`.
     pub fn object_hash(&self) -> git_hash::Kind {
         self.config.object_hash
     }
+
+    /// Return the filesystem capabilities configured through `core.precomposeUnicode`, `core.ignoreCase`, `core.fileMode`
+    /// and `core.symlinks`, which take precedence over probed capabilities. Invalid values are ignored.
+    pub fn fs_capability_overrides(&self) -> git_worktree::fs::capabilities::Overrides {
+        let config = self.config_snapshot();
+        git_worktree::fs::capabilities::Overrides {
+            precompose_unicode: config.boolean("core.precomposeUnicode"),
+            ignore_case: config.boolean("core.ignoreCase"),
+            executable_bit: config.boolean("core.fileMode"),
+            symlink: config.boolean("core.symlinks"),
+        }
+    }
+
+    /// Return true if the creation time of files should be compared when checking if they changed, as configured by
+    /// `core.trustCTime`. Defaults to true.
+    pub fn trust_ctime(&self) -> bool {
+        self.config_snapshot().boolean("core.trustCTime").unwrap_or(true)
+    }
+
+    /// Return true if all stat fields should be compared when checking if files changed, or false if only the whole-second part
+    /// of their modification time and their size should be compared, which is the case if `core.checkStat` is `minimal`.
+    pub fn check_stat(&self) -> bool {
+        self.config_snapshot()
+            .string("core.checkStat")
+            .map_or(true, |value| !value.eq_ignore_ascii_case(b"minimal"))
+    }
+
+    /// Probe the filesystem at `dir` for its capabilities and let those [set in configuration][Self::fs_capability_overrides()]
+    /// take precedence, which is what should be used for checkouts into `dir`.
+    pub fn fs_capabilities(&self, dir: impl AsRef<std::path::Path>) -> git_worktree::fs::Capabilities {
+        git_worktree::fs::Capabilities::probe(dir).with_config_overrides(self.fs_capability_overrides())
+    }
 }
 
 mod remote {
@@ -0,0 +1,6 @@
+impl crate::Repository {
+    /// Return a platform to find and run the hooks of this repository, like `pre-commit` or `post-checkout`.
+    pub fn hooks(&self) -> crate::hooks::Platform<'_> {
+        crate::hooks::Platform { repo: self }
+    }
+}
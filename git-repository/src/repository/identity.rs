@@ -1,6 +1,6 @@
 use std::{borrow::Cow, time::SystemTime};
 
-use crate::bstr::BString;
+use crate::bstr::{BString, ByteSlice};
 
 /// Identity handling.
 impl crate::Repository {
@@ -29,6 +29,9 @@ impl crate::Repository {
     /// …and in that order, or `None` if there was nothing configured. In that case, one may use the
     /// [`committer_or_default()`][Self::committer_or_default()] method.
     ///
+    /// The time is taken from `gitoxide.commit.committerDate` if set, which allows pinning it for reproducible results,
+    /// or from `GIT_COMMITTER_DATE`, both in any format understood by git, or it's the current time.
+    ///
     /// # Note
     ///
     /// The values are cached when the repository is instantiated.
@@ -53,6 +56,13 @@ impl crate::Repository {
         self.committer().unwrap_or_else(|| self.user_default())
     }
 
+    /// Like [`committer()`][Self::committer()], but fails with an error listing all places that were searched
+    /// if no name or email is configured, similar to what `git commit` does.
+    pub fn committer_or_err(&self) -> Result<git_actor::SignatureRef<'_>, crate::identity::Error> {
+        self.committer()
+            .ok_or_else(|| self.config.personas().missing(Role::Committer))
+    }
+
     /// Return the author as configured by this repository, which is determined by…
     ///
     /// * …the git configuration `author.name|email`…
@@ -62,6 +72,9 @@ impl crate::Repository {
     /// …and in that order, or `None` if there was nothing configured. In that case, one may use the
     /// [`author_or_default()`][Self::author_or_default()] method.
     ///
+    /// The time is taken from `gitoxide.commit.authorDate` if set, which allows pinning it for reproducible results,
+    /// or from `GIT_AUTHOR_DATE`, both in any format understood by git, or it's the current time.
+    ///
     /// # Note
     ///
     /// The values are cached when the repository is instantiated.
@@ -80,6 +93,13 @@ impl crate::Repository {
     pub fn author_or_default(&self) -> git_actor::SignatureRef<'_> {
         self.author().unwrap_or_else(|| self.user_default())
     }
+
+    /// Like [`author()`][Self::author()], but fails with an error listing all places that were searched
+    /// if no name or email is configured, similar to what `git commit` does.
+    pub fn author_or_err(&self) -> Result<git_actor::SignatureRef<'_>, crate::identity::Error> {
+        self.author()
+            .ok_or_else(|| self.config.personas().missing(Role::Author))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -95,6 +115,14 @@ pub(crate) struct Personas {
     user: Entity,
     committer: Entity,
     author: Entity,
+    /// If true, we were allowed to read identities from `GIT_*` environment variables.
+    git_env: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Role {
+    Committer,
+    Author,
 }
 
 impl Personas {
@@ -120,21 +148,29 @@ impl Personas {
         let mut author_date = None;
         let (user_name, mut user_email) = entity_in_section("user", config);
 
-        if git_env.eq(&git_sec::Permission::Allow) {
+        let git_env = git_env.eq(&git_sec::Permission::Allow);
+        if git_env {
             committer_name = committer_name.or_else(|| env_var("GIT_COMMITTER_NAME"));
             committer_email = committer_email.or_else(|| env_var("GIT_COMMITTER_EMAIL"));
             committer_date = std::env::var("GIT_COMMITTER_DATE")
                 .ok()
-                .and_then(|date| git_date::parse(&date, Some(SystemTime::now())).ok());
+                .and_then(|date| parse_date(&date));
 
             author_name = author_name.or_else(|| env_var("GIT_AUTHOR_NAME"));
             author_email = author_email.or_else(|| env_var("GIT_AUTHOR_EMAIL"));
-            author_date = std::env::var("GIT_AUTHOR_DATE")
-                .ok()
-                .and_then(|date| git_date::parse(&date, Some(SystemTime::now())).ok());
+            author_date = std::env::var("GIT_AUTHOR_DATE").ok().and_then(|date| parse_date(&date));
 
             user_email = user_email.or_else(|| env_var("EMAIL")); // NOTE: we don't have permission for this specific one…
         }
+
+        // Allow pinning times independently of the environment, which is useful for reproducible results.
+        let date_in_config = |key: &str| {
+            config
+                .string("gitoxide", Some("commit"), key)
+                .and_then(|date| parse_date(date.to_str().ok()?))
+        };
+        committer_date = date_in_config("committerDate").or(committer_date);
+        author_date = date_in_config("authorDate").or(author_date);
         Personas {
             user: Entity {
                 name: user_name,
@@ -151,6 +187,46 @@ impl Personas {
                 email: author_email,
                 time: author_date,
             },
+            git_env,
+        }
+    }
+
+    pub fn missing(&self, role: Role) -> crate::identity::Error {
+        let (entity, role, keys, env_vars): (_, _, [&'static str; 2], [&'static str; 2]) = match role {
+            Role::Committer => (
+                &self.committer,
+                "committer",
+                ["committer.name", "committer.email"],
+                ["GIT_COMMITTER_NAME", "GIT_COMMITTER_EMAIL"],
+            ),
+            Role::Author => (
+                &self.author,
+                "author",
+                ["author.name", "author.email"],
+                ["GIT_AUTHOR_NAME", "GIT_AUTHOR_EMAIL"],
+            ),
+        };
+        let mut searched = Vec::new();
+        if entity.name.is_none() && self.user.name.is_none() {
+            searched.push(keys[0]);
+            if self.git_env {
+                searched.push(env_vars[0]);
+            }
+            searched.push("user.name");
+        }
+        if entity.email.is_none() && self.user.email.is_none() {
+            searched.push(keys[1]);
+            if self.git_env {
+                searched.extend([env_vars[1], "EMAIL"]);
+            }
+            searched.push("user.email");
         }
+        crate::identity::Error::MissingIdentity { role, searched }
     }
 }
+
+/// Parse `date` in any of the formats git understands for its `GIT_(COMMITTER|AUTHOR)_DATE` variables,
+/// like `<unix-seconds> <offset>` or RFC-2822.
+fn parse_date(date: &str) -> Option<git_actor::Time> {
+    git_date::parse(date, Some(SystemTime::now())).ok()
+}
@@ -9,6 +9,7 @@ impl crate::Repository {
         config: crate::config::Cache,
         linked_worktree_options: crate::open::Options,
         index: crate::worktree::IndexStorage,
+        shallow_commits: crate::shallow::CommitsStorage,
     ) -> Self {
         crate::Repository {
             bufs: RefCell::new(Vec::with_capacity(4)),
@@ -17,7 +18,9 @@ impl crate::Repository {
             objects: {
                 #[cfg(feature = "max-performance-safe")]
                 {
-                    objects.with_pack_cache(|| Box::new(git_pack::cache::lru::StaticLinkedList::<64>::default()))
+                    let mut objects = objects;
+                    super::cache::set_default_pack_cache(&mut objects, config.pack_cache_bytes);
+                    objects
                 }
                 #[cfg(not(feature = "max-performance-safe"))]
                 {
@@ -28,6 +31,7 @@ impl crate::Repository {
             config,
             options: linked_worktree_options,
             index,
+            shallow_commits,
         }
     }
 
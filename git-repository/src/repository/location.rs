@@ -35,6 +35,27 @@ impl crate::Repository {
         self.work_tree.as_deref()
     }
 
+    /// Return `true` if `path` is located inside of the [work tree][crate::Repository::work_dir()] but not inside of the
+    /// [`git_dir()`][crate::Repository::git_dir()], or `false` if it lies elsewhere or if this repository is bare.
+    ///
+    /// Relative paths are interpreted relative to the current working directory, and symlinks in both `path` and the work tree
+    /// are resolved before comparison. The work tree root itself is considered to be inside of the work tree.
+    /// Comparisons are case-insensitive if `core.ignoreCase` is set.
+    pub fn is_inside_work_tree(&self, path: impl AsRef<std::path::Path>) -> Result<bool, git_path::realpath::Error> {
+        let work_dir = match self.work_dir() {
+            Some(dir) => dir,
+            None => return Ok(false),
+        };
+        let cwd = std::env::current_dir().map_err(git_path::realpath::Error::CurrentWorkingDir)?;
+        let path = git_path::realpath_opts(path, &cwd, MAX_SYMLINKS)?;
+        let work_dir = git_path::realpath_opts(work_dir, &cwd, MAX_SYMLINKS)?;
+        let git_dir = git_path::realpath_opts(self.git_dir(), &cwd, MAX_SYMLINKS)?;
+
+        let ignore_case = self.config.ignore_case;
+        Ok(crate::path::is_within(&path, &work_dir, ignore_case)
+            && !crate::path::is_within(&path, &git_dir, ignore_case))
+    }
+
     // TODO: tests, respect precomposeUnicode
     /// The directory of the binary path of the current process.
     pub fn install_dir(&self) -> std::io::Result<PathBuf> {
@@ -0,0 +1,361 @@
+use std::convert::TryFrom;
+
+use git_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    merge::message::Error,
+    object::Kind,
+};
+
+/// The amount of commits to list per merged head if `merge.log` is `true`.
+const DEFAULT_MERGE_LOG_LEN: usize = 20;
+
+impl crate::Repository {
+    /// Produce the default message for merging all heads marked for merge in `FETCH_HEAD` into `HEAD`, similar to what
+    /// `git fmt-merge-msg` produces, or `None` if there is no `FETCH_HEAD` or nothing in it that is left to merge.
+    ///
+    /// Heads are grouped by the url they were fetched from and described as branch, remote-tracking branch, tag or commit.
+    /// Heads which are already contained in `HEAD` or in another head to merge are skipped.
+    /// Messages of annotated tags are added, and if `merge.log` is set, the subjects of the commits that will be
+    /// merged are listed as well.
+    ///
+    /// # Deviation
+    ///
+    /// - Authors and committers of merged commits are not credited in comment lines as `git fmt-merge-msg` does,
+    ///   matching the message `git merge` produces when not editing it.
+    /// - Signatures of signed tags are not verified and thus not added as comment.
+    /// - Branch descriptions aren't added even if `merge.branchdesc` is set.
+    pub fn merge_message_from_fetch_head(&self) -> Result<Option<BString>, Error> {
+        let entries = self.refs.read_fetch_head()?;
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        let head = self.head()?;
+        let head_id = head.id().map(crate::Id::detach);
+        let current_branch: BString = match head.referent_name() {
+            Some(name) => name.shorten().into(),
+            None => "HEAD".into(),
+        };
+
+        let heads: Vec<_> = entries.into_iter().filter(|entry| !entry.not_for_merge).collect();
+
+        let mut merge_parents = Vec::with_capacity(heads.len());
+        for head in &heads {
+            if let Some(commit_id) = self.peel_to_commit_id(head.id)? {
+                merge_parents.push((head.id, commit_id));
+            }
+        }
+        let independent = self.independent_commits(merge_parents.iter().map(|(_, commit)| *commit).chain(head_id))?;
+        merge_parents.retain(|(_, commit)| independent.contains(commit));
+
+        let mut srcs = Vec::<Src>::new();
+        let mut origins = Vec::<(BString, ObjectId)>::new();
+        for head in heads {
+            let id = head.id;
+            if !merge_parents.iter().any(|(given, _)| *given == id) {
+                continue;
+            }
+            let note = head.note();
+            let (line, src) = match &note {
+                Some(note) => (note.as_bstr(), Some(head.remote_url.as_bstr())),
+                None => (head.remote_url.as_bstr(), None),
+            };
+            let src_name = src.unwrap_or(line);
+            let src_data = match srcs.iter().position(|s| s.name == src_name) {
+                Some(pos) => &mut srcs[pos],
+                None => {
+                    srcs.push(Src::new(src_name.into()));
+                    srcs.last_mut().expect("just pushed")
+                }
+            };
+
+            let origin = if src.is_none() {
+                src_data.head_status |= 1;
+                src_name
+            } else {
+                src_data.head_status |= 2;
+                if let Some(branch) = line.strip_prefix(b"branch ") {
+                    src_data.branch.push(branch.into());
+                    branch
+                } else if let Some(tag) = line.strip_prefix(b"tag ") {
+                    src_data.tag.push(tag.into());
+                    line
+                } else if let Some(branch) = line.strip_prefix(b"remote-tracking branch ") {
+                    src_data.r_branch.push(branch.into());
+                    branch
+                } else {
+                    src_data.generic.push(line.into());
+                    line
+                }
+            };
+            let origin: BString = if src_name == "." || src_name == origin {
+                match origin.strip_prefix(b"'").and_then(|o| o.strip_suffix(b"'")) {
+                    Some(unquoted) => unquoted.into(),
+                    None => origin.into(),
+                }
+            } else {
+                let mut out = BString::from(origin);
+                out.push_str(" of ");
+                out.push_str(src_name);
+                out
+            };
+            origins.push((origin, id));
+        }
+
+        if srcs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut out = BString::from("Merge ");
+        for (idx, src) in srcs.iter().enumerate() {
+            if idx != 0 {
+                out.push_str("; ");
+            }
+            if src.head_status == 1 {
+                out.push_str(&src.name);
+                continue;
+            }
+            let mut subsep = "";
+            if src.head_status == 3 {
+                subsep = ", ";
+                out.push_str("HEAD");
+            }
+            for (items, singular, plural) in [
+                (&src.branch, "branch ", "branches "),
+                (&src.r_branch, "remote-tracking branch ", "remote-tracking branches "),
+                (&src.tag, "tag ", "tags "),
+                (&src.generic, "commit ", "commits "),
+            ] {
+                if items.is_empty() {
+                    continue;
+                }
+                out.push_str(subsep);
+                subsep = ", ";
+                push_joined(&mut out, singular, plural, items);
+            }
+            if src.name != "." {
+                out.push_str(" of ");
+                out.push_str(&src.name);
+            }
+        }
+        if !self.suppresses_merge_destination(current_branch.as_ref()) {
+            out.push_str(" into ");
+            out.push_str(&current_branch);
+        }
+        out.push_byte(b'\n');
+
+        self.push_tag_messages(&mut out, &origins)?;
+
+        let limit = self.merge_log_limit()?;
+        if limit > 0 {
+            for (name, id) in &origins {
+                if let Some(tip) = self.peel_to_commit_id(*id)? {
+                    self.push_shortlog(&mut out, name.as_ref(), tip, head_id, limit)?;
+                }
+            }
+        }
+        Ok(Some(out))
+    }
+}
+
+struct Src {
+    name: BString,
+    /// `1` if a head was fetched without a name, and `2` if named heads were fetched.
+    head_status: u8,
+    branch: Vec<BString>,
+    r_branch: Vec<BString>,
+    tag: Vec<BString>,
+    generic: Vec<BString>,
+}
+
+impl Src {
+    fn new(name: BString) -> Self {
+        Src {
+            name,
+            head_status: 0,
+            branch: Vec::new(),
+            r_branch: Vec::new(),
+            tag: Vec::new(),
+            generic: Vec::new(),
+        }
+    }
+}
+
+fn push_joined(out: &mut BString, singular: &str, plural: &str, items: &[BString]) {
+    match items {
+        [item] => {
+            out.push_str(singular);
+            out.push_str(item);
+        }
+        [head @ .., last] => {
+            out.push_str(plural);
+            for (idx, item) in head.iter().enumerate() {
+                if idx != 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(item);
+            }
+            out.push_str(" and ");
+            out.push_str(last);
+        }
+        [] => {}
+    }
+}
+
+fn push_commented_lines(out: &mut BString, text: &BStr) {
+    for line in text.lines() {
+        if line.is_empty() {
+            out.push_str("#\n");
+        } else {
+            out.push_str("# ");
+            out.push_str(line);
+            out.push_byte(b'\n');
+        }
+    }
+}
+
+fn complete_line(out: &mut BString) {
+    if !out.is_empty() && !out.ends_with(b"\n") {
+        out.push_byte(b'\n');
+    }
+}
+
+/// Utilities
+impl crate::Repository {
+    fn peel_to_commit_id(&self, mut id: ObjectId) -> Result<Option<ObjectId>, Error> {
+        loop {
+            let object = self.find_object(id)?;
+            match object.kind {
+                Kind::Commit => return Ok(Some(id)),
+                Kind::Tag => id = object.to_tag_ref_iter().target_id()?,
+                Kind::Tree | Kind::Blob => return Ok(None),
+            }
+        }
+    }
+
+    /// Return all unique `commits` which aren't reachable from any of the other `commits`.
+    fn independent_commits(&self, commits: impl IntoIterator<Item = ObjectId>) -> Result<Vec<ObjectId>, Error> {
+        let commits: Vec<_> = commits.into_iter().collect();
+        Ok(git_revision::independent(&commits, &mut self.graph())?)
+    }
+
+    fn suppresses_merge_destination(&self, branch: &BStr) -> bool {
+        let patterns = self.config.resolved.strings("merge", None, "suppressDest");
+        let mut patterns: Vec<_> = match patterns {
+            None => return branch == "main" || branch == "master",
+            Some(patterns) => patterns,
+        };
+        if let Some(pos) = patterns.iter().rposition(|p| p.is_empty()) {
+            patterns.drain(..=pos);
+        }
+        patterns.iter().any(|pattern| {
+            git_glob::wildmatch(
+                pattern.as_ref(),
+                branch,
+                git_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+            )
+        })
+    }
+
+    fn merge_log_limit(&self) -> Result<usize, Error> {
+        let config = self.config_snapshot();
+        for key in ["merge.log", "merge.summary"] {
+            match config.try_integer(key) {
+                Some(Ok(len)) => {
+                    return usize::try_from(len).map_err(|_| Error::InvalidLogLength { key, source: None });
+                }
+                Some(Err(_)) => {
+                    return match config.try_boolean(key).expect("value exists") {
+                        Ok(true) => Ok(DEFAULT_MERGE_LOG_LEN),
+                        Ok(false) => Ok(0),
+                        Err(err) => Err(Error::InvalidLogLength { key, source: Some(err) }),
+                    };
+                }
+                None => continue,
+            }
+        }
+        Ok(0)
+    }
+
+    fn push_tag_messages(&self, out: &mut BString, origins: &[(BString, ObjectId)]) -> Result<(), Error> {
+        let mut tags = BString::default();
+        let mut num_tags = 0;
+        let mut first_tag = 0;
+        for (idx, (name, id)) in origins.iter().enumerate() {
+            let object = self.find_object(*id)?;
+            if object.kind != Kind::Tag {
+                continue;
+            }
+            num_tags += 1;
+            if num_tags == 1 {
+                first_tag = idx;
+            } else {
+                if num_tags == 2 {
+                    let mut header = BString::from("\n");
+                    push_commented_lines(&mut header, origins[first_tag].0.as_ref());
+                    tags.insert_str(0, header);
+                }
+                tags.push_byte(b'\n');
+                push_commented_lines(&mut tags, name.as_ref());
+            }
+            tags.push_str(git_object::TagRef::from_bytes(&object.data)?.message);
+            complete_line(&mut tags);
+        }
+        if !tags.is_empty() {
+            out.push_byte(b'\n');
+            out.push_str(tags);
+        }
+        Ok(())
+    }
+
+    fn push_shortlog(
+        &self,
+        out: &mut BString,
+        name: &BStr,
+        tip: ObjectId,
+        head: Option<ObjectId>,
+        limit: usize,
+    ) -> Result<(), Error> {
+        let mut count = 0;
+        let mut subjects = Vec::new();
+        for id in
+            git_revision::commits_not_in(&tip, head.as_ref().map_or(&[], std::slice::from_ref), &mut self.graph())?
+        {
+            let commit = self.find_object(id)?.try_into_commit()?;
+            if commit.parent_ids().nth(1).is_some() {
+                continue;
+            }
+            count += 1;
+            if subjects.len() > limit {
+                continue;
+            }
+            let subject = commit.message()?.summary().into_owned();
+            subjects.push(if subject.is_empty() {
+                id.to_hex().to_string().into()
+            } else {
+                subject
+            });
+        }
+
+        complete_line(out);
+        out.push_str("\n* ");
+        out.push_str(name);
+        if count > limit {
+            out.push_str(format!(": ({} commits)\n", count));
+        } else {
+            out.push_str(":\n");
+        }
+        for (idx, subject) in subjects.iter().enumerate() {
+            if idx >= limit {
+                out.push_str("  ...\n");
+            } else {
+                out.push_str("  ");
+                out.push_str(subject);
+                out.push_byte(b'\n');
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,18 @@
 //!
 
+/// Buffers
+impl crate::Repository {
+    /// Return an empty buffer from the free-list of reusable buffers, which is returned to it once the buffer is dropped.
+    ///
+    /// Each call yields a buffer of its own, so multiple buffers may be in use at the same time, for instance while decoding
+    /// one object and loading another.
+    pub fn buffer(&self) -> crate::Buffer<'_> {
+        let mut inner = self.free_buf();
+        inner.clear();
+        crate::Buffer { inner, repo: self }
+    }
+}
+
 /// Internal
 impl crate::Repository {
     #[inline]
@@ -19,18 +32,26 @@ impl crate::Repository {
     }
 }
 
+#[cfg(feature = "archive")]
+mod archive;
+mod blame;
 mod cache;
 mod config;
+mod hooks;
 pub(crate) mod identity;
 mod impls;
 mod init;
 mod location;
+mod merge;
 mod object;
 pub(crate) mod permissions;
 mod reference;
 mod remote;
 mod revision;
+mod shallow;
 mod snapshots;
 mod state;
+mod submodule;
+mod switch;
 mod thread_safe;
 mod worktree;
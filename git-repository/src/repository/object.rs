@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{collections::HashSet, convert::TryInto};
 
 use git_hash::{oid, ObjectId};
 use git_odb::{Find, FindExt, Write};
@@ -7,7 +7,12 @@ use git_ref::{
     FullName,
 };
 
-use crate::{commit, ext::ObjectIdExt, object, tag, Id, Object, Reference};
+use crate::{
+    bstr::{BString, ByteVec},
+    commit,
+    ext::ObjectIdExt,
+    object, tag, Id, Object, Reference,
+};
 
 /// Methods related to object creation.
 impl crate::Repository {
@@ -16,11 +21,8 @@ impl crate::Repository {
     /// There are various legitimate reasons for an object to not be present, which is why
     /// [`try_find_object(…)`][crate::Repository::try_find_object()] might be preferable instead.
     ///
-    /// # Important
-    ///
-    /// As a shared buffer is written to back the object data, the returned `ObjectRef` will prevent other
-    /// `find_object()` operations from succeeding while alive.
-    /// To bypass this limit, clone this `sync::Handle` instance.
+    /// The object data is loaded right away into a buffer from the free-list of this instance, which is returned to it once
+    /// the object is dropped. Hence any amount of objects can be held at the same time.
     ///
     /// # Performance Note
     ///
@@ -43,11 +45,7 @@ impl crate::Repository {
 
     /// Try to find the object with `id` or return `None` it it wasn't found.
     ///
-    /// # Important
-    ///
-    /// As a shared buffer is written to back the object data, the returned `ObjectRef` will prevent other
-    /// `try_find_object()` operations from succeeding while alive.
-    /// To bypass this limit, clone this `sync::Handle` instance.
+    /// Like with [`find_object()`][Self::find_object()], the object data is loaded right away.
     pub fn try_find_object(&self, id: impl Into<ObjectId>) -> Result<Option<Object<'_>>, object::find::Error> {
         let id = id.into();
         if id == git_hash::ObjectId::empty_tree(self.object_hash()) {
@@ -69,22 +67,111 @@ impl crate::Repository {
         }
     }
 
+    /// Return true if the object with `id` exists in the object database, without decoding or even reading its data.
+    ///
+    /// Loose objects are checked by their path and packed objects by a lookup in the pack indices, which makes this much
+    /// cheaper than [`try_find_object()`][Self::try_find_object()].
+    pub fn object_exists(&self, id: impl AsRef<oid>) -> bool {
+        let id = id.as_ref();
+        id == git_hash::ObjectId::empty_tree(self.object_hash()) || self.objects.contains(id)
+    }
+
+    /// Like [`object_exists()`][Self::object_exists()], but checks all `ids` at once and returns one flag per id in the
+    /// order they were given, with `true` indicating that the respective object exists.
+    pub fn objects_exist(&self, ids: impl IntoIterator<Item = impl AsRef<oid>>) -> Vec<bool> {
+        ids.into_iter().map(|id| self.object_exists(id)).collect()
+    }
+
+    /// Find the single object whose id starts with `prefix` and return its id along with its kind, or `None` if there is no such object.
+    ///
+    /// The object's data is decoded into `buf`. If more than one object matches `prefix`, an
+    /// [ambiguity error][object::find::by_prefix::Error::Ambiguous] listing all candidates is returned.
+    ///
+    /// Use [`git_hash::Prefix::from_hex()`] to obtain a validated `prefix` of at least [`git_hash::Prefix::MIN_HEX_LEN`] characters.
+    pub fn find_object_by_prefix(
+        &self,
+        prefix: &git_hash::Prefix,
+        buf: &mut Vec<u8>,
+    ) -> Result<Option<(ObjectId, git_object::Kind)>, object::find::by_prefix::Error> {
+        match self.unique_id_by_prefix(prefix)? {
+            Some(id) => Ok(self.objects.try_find(id, buf)?.map(|obj| (id, obj.kind))),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve the abbreviated hexadecimal object id `prefix` to the full id of the only object starting with it, or return
+    /// `None` if there is no such object.
+    ///
+    /// Loose objects and objects in packs are considered alike, and if more than one object matches `prefix`, an
+    /// [ambiguity error][object::find::by_prefix::Error::Ambiguous] listing all candidates is returned.
+    /// This is the inverse of [`shorten_id()`][crate::Repository::shorten_id()].
+    pub fn disambiguate_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Option<ObjectId>, object::find::disambiguate_prefix::Error> {
+        let prefix = git_hash::Prefix::from_hex(prefix)?;
+        Ok(self.unique_id_by_prefix(&prefix)?)
+    }
+
+    /// Return the shortest hexadecimal abbreviation of `id` that is at least as long as configured by `core.abbrev` and
+    /// that no other object in the object database starts with, similar to `git rev-parse --short`.
+    ///
+    /// If `core.abbrev` isn't set, the length is derived from the amount of packed objects, but is at least 7.
+    /// It's an error if `id` doesn't exist.
+    pub fn shorten_id(&self, id: impl Into<ObjectId>) -> Result<String, crate::id::shorten::Error> {
+        Id::from_id(id, self).shorten().map(|prefix| prefix.to_string())
+    }
+
+    fn unique_id_by_prefix(
+        &self,
+        prefix: &git_hash::Prefix,
+    ) -> Result<Option<ObjectId>, object::find::by_prefix::Error> {
+        let mut candidates = HashSet::default();
+        self.objects.lookup_prefix(*prefix, Some(&mut candidates))?;
+        match candidates.len() {
+            0 => Ok(None),
+            1 => Ok(candidates.into_iter().next()),
+            _ => {
+                let mut candidates: Vec<_> = candidates.into_iter().collect();
+                candidates.sort();
+                Err(object::find::by_prefix::Error::Ambiguous {
+                    prefix: *prefix,
+                    candidates,
+                })
+            }
+        }
+    }
+
+    /// Re-scan the object database for packs that were added, changed or removed by other processes, for instance by a
+    /// `git fetch` or `git repack`, so lookups see them right away.
+    ///
+    /// Note that this isn't usually necessary as a lookup that misses already triggers such a re-scan once before reporting
+    /// the object as missing, unless [refreshes were disabled][git_odb::store::Handle::refresh_never()].
+    /// As the object database is shared, this affects all clones of this instance.
+    pub fn refresh_object_database(&self) -> Result<(), git_odb::store::load_index::Error> {
+        self.objects.store_ref().refresh()
+    }
+
     /// Write the given object into the object database and return its object id.
+    ///
+    /// Note that the object is hashed in memory first and only written if it doesn't exist yet, which avoids
+    /// writing duplicate loose objects that would eventually have to be garbage collected.
     pub fn write_object(&self, object: impl git_object::WriteTo) -> Result<Id<'_>, object::write::Error> {
-        self.objects
-            .write(object)
-            .map(|oid| oid.attach(self))
-            .map_err(Into::into)
+        let mut buf = self.buffer();
+        object.write_to(&mut *buf)?;
+        self.write_buf_if_missing(object.kind(), &buf)
     }
 
     /// Write a blob from the given `bytes`.
+    ///
+    /// Like with [`write_object()`][Self::write_object()], nothing is written if the blob already exists.
     pub fn write_blob(&self, bytes: impl AsRef<[u8]>) -> Result<Id<'_>, object::write::Error> {
-        self.objects
-            .write_buf(git_object::Kind::Blob, bytes.as_ref())
-            .map(|oid| oid.attach(self))
+        self.write_buf_if_missing(git_object::Kind::Blob, bytes.as_ref())
     }
 
     /// Write a blob from the given `Read` implementation.
+    ///
+    /// The stream is read twice, once to compute the id of the blob and once to write it if it doesn't exist yet.
     pub fn write_blob_stream(
         &self,
         mut bytes: impl std::io::Read + std::io::Seek,
@@ -93,11 +180,25 @@ impl crate::Repository {
         let len = bytes.seek(std::io::SeekFrom::End(0))? - current;
         bytes.seek(std::io::SeekFrom::Start(current))?;
 
+        let id = git_odb::sink(self.object_hash()).write_stream(git_object::Kind::Blob, len, &mut bytes)?;
+        if self.objects.contains(id) {
+            return Ok(id.attach(self));
+        }
+        bytes.seek(std::io::SeekFrom::Start(current))?;
+
         self.objects
             .write_stream(git_object::Kind::Blob, len, bytes)
             .map(|oid| oid.attach(self))
     }
 
+    fn write_buf_if_missing(&self, kind: git_object::Kind, buf: &[u8]) -> Result<Id<'_>, object::write::Error> {
+        let id = git_odb::sink(self.object_hash()).write_buf(kind, buf)?;
+        if self.objects.contains(id) {
+            return Ok(id.attach(self));
+        }
+        self.objects.write_buf(kind, buf).map(|oid| oid.attach(self))
+    }
+
     /// Create a tag reference named `name` (without `refs/tags/` prefix) pointing to a newly created tag object
     /// which in turn points to `target` and return the newly created reference.
     ///
@@ -121,8 +222,23 @@ impl crate::Repository {
             message: message.as_ref().into(),
             pgp_signature: None,
         };
-        let tag_id = self.write_object(&tag)?;
-        self.tag_reference(name, tag_id, constraint).map_err(Into::into)
+        self.tag_object(&tag, constraint)
+    }
+
+    /// Write the annotated `tag` object and create a tag reference named after its [`name`][git_object::Tag::name]
+    /// (without `refs/tags/` prefix) pointing to it, and return the newly created reference.
+    ///
+    /// Use this instead of [`tag()`][Self::tag()] to control all fields of the tag object, for instance to write a signed tag
+    /// with a `pgp_signature`.
+    ///
+    /// If `constraint` is [`PreviousValue::MustNotExist`], an existing tag of the same name pointing to a different object is
+    /// an [error][tag::Error::AlreadyExists].
+    pub fn tag_object(&self, tag: &git_object::Tag, constraint: PreviousValue) -> Result<Reference<'_>, tag::Error> {
+        let mut name = BString::from("refs/tags/");
+        name.push_str(&tag.name);
+        let name: FullName = name.try_into().map_err(crate::reference::edit::Error::from)?;
+        let tag_id = self.write_object(tag)?;
+        self.create_tag_reference(name, tag_id.detach(), constraint)
     }
 
     /// Create a new commit object with `author`, `committer` and `message` referring to `tree` with `parents`, and point `reference`
@@ -5,7 +5,7 @@ use git_hash::ObjectId;
 use git_lock as lock;
 use git_ref::{
     transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
-    FullName, PartialNameRef, Target,
+    FullName, FullNameRef, PartialNameRef, Target,
 };
 
 use crate::{bstr::BString, ext::ReferenceExt, reference, Reference};
@@ -24,7 +24,57 @@ impl crate::Repository {
         target: impl Into<ObjectId>,
         constraint: PreviousValue,
     ) -> Result<Reference<'_>, reference::edit::Error> {
-        let id = target.into();
+        self.edit_tag_reference(
+            format!("refs/tags/{}", name.as_ref()).try_into()?,
+            target.into(),
+            constraint,
+        )
+    }
+
+    /// Create a lightweight tag with the given `name` (and without `refs/tags/` prefix) pointing to `target`, and return it
+    /// as reference, similar to `git tag <name> <target>`.
+    ///
+    /// If the tag already exists and points elsewhere, it's an [error][crate::tag::Error::AlreadyExists] unless `force` is `true`, in which case
+    /// it's overwritten, similar to `git tag --force`.
+    pub fn tag_lightweight(
+        &self,
+        name: impl AsRef<str>,
+        target: impl Into<ObjectId>,
+        force: bool,
+    ) -> Result<Reference<'_>, crate::tag::Error> {
+        let name: FullName = format!("refs/tags/{}", name.as_ref())
+            .try_into()
+            .map_err(reference::edit::Error::from)?;
+        let constraint = if force {
+            PreviousValue::Any
+        } else {
+            PreviousValue::MustNotExist
+        };
+        self.create_tag_reference(name, target.into(), constraint)
+    }
+
+    /// Like [`edit_tag_reference()`][Self::edit_tag_reference()], but turns a violated `constraint` into a typed error
+    /// if the tag must not exist.
+    pub(crate) fn create_tag_reference(
+        &self,
+        name: FullName,
+        id: ObjectId,
+        constraint: PreviousValue,
+    ) -> Result<Reference<'_>, crate::tag::Error> {
+        match self.edit_tag_reference(name.clone(), id, constraint) {
+            Err(reference::edit::Error::FileTransactionPrepare(
+                git_ref::file::transaction::prepare::Error::MustNotExist { .. },
+            )) => Err(crate::tag::Error::AlreadyExists { name }),
+            res => Ok(res?),
+        }
+    }
+
+    fn edit_tag_reference(
+        &self,
+        name: FullName,
+        id: ObjectId,
+        constraint: PreviousValue,
+    ) -> Result<Reference<'_>, reference::edit::Error> {
         let mut edits = self.edit_reference(
             RefEdit {
                 change: Change::Update {
@@ -32,7 +82,7 @@ impl crate::Repository {
                     expected: constraint,
                     new: Target::Peeled(id),
                 },
-                name: format!("refs/tags/{}", name.as_ref()).try_into()?,
+                name,
                 deref: false,
             },
             DEFAULT_LOCK_MODE,
@@ -50,6 +100,48 @@ impl crate::Repository {
         })
     }
 
+    /// Create a branch with the given `name` (and without `refs/heads/` prefix) pointing to the given `target`, and return it
+    /// as reference, similar to `git branch <name> <target>`.
+    ///
+    /// If the branch already exists, it's an error unless `force` is `true`, in which case it's reset to `target`, similar to
+    /// `git branch --force`. The reference log records the creation or the reset.
+    /// Like `git`, the branch that is currently checked out can't be reset, and the reset fails if the branch changes
+    /// concurrently.
+    pub fn create_branch(
+        &self,
+        name: impl AsRef<str>,
+        target: impl Into<ObjectId>,
+        force: bool,
+    ) -> Result<Reference<'_>, reference::create_branch::Error> {
+        let name: FullName = format!("refs/heads/{}", name.as_ref())
+            .try_into()
+            .map_err(reference::edit::Error::from)?;
+        let id = target.into();
+        let existing = if force {
+            self.try_find_reference(name.as_ref())?
+        } else {
+            None
+        };
+        let (constraint, message) = match existing {
+            Some(existing) => {
+                if self.head_name()?.as_ref() == Some(&name) {
+                    return Err(reference::create_branch::Error::CheckedOut { name });
+                }
+                (
+                    PreviousValue::MustExistAndMatch(existing.inner.target),
+                    format!("branch: Reset to {}", id),
+                )
+            }
+            None => (PreviousValue::MustNotExist, format!("branch: Created from {}", id)),
+        };
+        match self.reference(name.clone(), id, constraint, message) {
+            Err(reference::edit::Error::FileTransactionPrepare(
+                git_ref::file::transaction::prepare::Error::MustNotExist { .. },
+            )) => Err(reference::create_branch::Error::AlreadyExists { name }),
+            res => Ok(res?),
+        }
+    }
+
     /// Returns the currently set namespace for references, or `None` if it is not set.
     ///
     /// Namespaces allow to partition references, and is configured per `Easy`.
@@ -77,6 +169,25 @@ impl crate::Repository {
         Ok(self.refs.namespace.replace(namespace))
     }
 
+    /// Return this instance with the reference namespace `namespace`, like `"foo"` or `"foo/bar"`, nested within the currently
+    /// set namespace, if there is one.
+    ///
+    /// All reference lookups, iterations and edits through the returned instance are transparently prefixed, so that
+    /// `find_reference("refs/heads/main")` reads `refs/namespaces/foo/refs/heads/main`.
+    /// Use `repo.clone().with_namespace(…)` to keep the original instance unaffected.
+    pub fn with_namespace<'a, Name, E>(mut self, namespace: Name) -> Result<Self, git_validate::refname::Error>
+    where
+        Name: TryInto<&'a PartialNameRef, Error = E>,
+        git_validate::refname::Error: From<E>,
+    {
+        let namespace = git_ref::namespace::expand(namespace)?;
+        self.refs.namespace = Some(match self.refs.namespace.take() {
+            Some(current) => current.join(&namespace),
+            None => namespace,
+        });
+        Ok(self)
+    }
+
     // TODO: more tests or usage
     /// Create a new reference with `name`, like `refs/heads/branch`, pointing to `target`, adhering to `constraint`
     /// during creation and writing `log_message` into the reflog. Note that a ref-log will be written even if `log_message` is empty.
@@ -257,4 +368,16 @@ impl crate::Repository {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Delete the log of the reference with the full `name`, like `refs/heads/main` or `HEAD`, returning `true` if there was one.
+    ///
+    /// The reference itself is left untouched, and directories which became empty are removed as well, with the exception of
+    /// category directories like `logs/refs/heads`.
+    pub fn remove_reference_log<'a, Name, E>(&self, name: Name) -> Result<bool, git_ref::file::log::Error>
+    where
+        Name: TryInto<&'a FullNameRef, Error = E>,
+        git_ref::name::Error: From<E>,
+    {
+        self.refs.remove_reflog(name)
+    }
 }
@@ -23,6 +23,34 @@ impl crate::Repository {
         Remote::from_fetch_url(url, false, self)
     }
 
+    /// Fetch all objects that `remote` advertises and that we don't have yet, and update local references as per the
+    /// remote's [fetch refspecs][Remote::refspecs()], returning information about what changed.
+    ///
+    /// Use [`remote_at()`][Self::remote_at()] or [`find_remote()`][Self::find_remote()] to obtain a remote, along with
+    /// [`Remote::with_refspec()`] to fetch with refspecs that aren't configured.
+    /// `progress` is used to indicate what's happening, and `should_interrupt` can be set to abort receiving the pack.
+    ///
+    /// This is a shortcut for [connecting][Remote::connect()] and [fetching][crate::remote::Connection::fetch()], hence `remote`
+    /// must have been obtained from this repository.
+    #[cfg(feature = "blocking-network-client")]
+    pub fn fetch<'remote, P>(
+        &self,
+        remote: &'remote Remote<'_>,
+        progress: P,
+        should_interrupt: &std::sync::atomic::AtomicBool,
+    ) -> Result<remote::fetch::Outcome<'remote>, remote::fetch::Error>
+    where
+        P: crate::Progress,
+    {
+        debug_assert!(
+            std::ptr::eq(self, remote.repo),
+            "BUG: the remote must belong to this repository"
+        );
+        remote
+            .connect(remote::Direction::Fetch, progress)?
+            .fetch(should_interrupt)
+    }
+
     /// Find the remote with the given `name` or report an error, similar to [`try_find_remote(…)`][Self::try_find_remote()].
     ///
     /// Note that we will include remotes only if we deem them [trustworthy][crate::open::Options::filter_config_section()].
@@ -91,11 +119,6 @@ impl crate::Repository {
                                 })
                         })
                         .collect::<Result<Vec<_>, _>>()
-                        .map(|mut specs| {
-                            specs.sort();
-                            specs.dedup();
-                            specs
-                        })
                 })
         };
         let fetch_specs = config_spec(git_refspec::parse::Operation::Fetch);
@@ -1,3 +1,6 @@
+use git_hash::ObjectId;
+use git_odb::Find;
+
 use crate::{bstr::BStr, revision, Id};
 
 /// Methods for resolving revisions by spec or working with the commit graph.
@@ -30,6 +33,23 @@ impl crate::Repository {
             .ok_or(revision::spec::parse::single::Error::RangedRev { spec: spec.into() })
     }
 
+    /// Create a platform to further configure a `git describe` operation to find a name for the commit with `id` by looking
+    /// at the closest annotated tags (by default) in its past.
+    ///
+    /// Note that `id` is expected to point to a commit, use [`Commit::describe()`][crate::Commit::describe()] to be sure.
+    pub fn describe(&self, id: impl Into<ObjectId>) -> crate::commit::describe::Platform<'_> {
+        crate::commit::describe::Platform {
+            id: id.into(),
+            repo: self,
+            select: Default::default(),
+            first_parent: false,
+            id_as_fallback: false,
+            long_format: false,
+            max_candidates: 10,
+            patterns: Vec::new(),
+        }
+    }
+
     /// Create the baseline for a revision walk by initializing it with the `tips` to start iterating on.
     ///
     /// It can be configured further before starting the actual walk.
@@ -39,4 +59,60 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Return a cache of commits for use with graph algorithms, which parses each commit at most once and uses the
+    /// commit-graph file if there is one and `core.commitGraph` isn't disabled.
+    ///
+    /// Keep the returned instance for as long as multiple graph algorithms are run on the same commits, as each call to
+    /// methods like [`merge_base()`][Self::merge_base()] creates a new one.
+    pub fn graph(&self) -> revision::Graph<'_> {
+        revision::Graph::new(
+            move |id, buf| Ok(self.objects.try_find(id, buf)?.and_then(|d| d.try_into_commit_iter())),
+            self.commit_graph_if_enabled(),
+        )
+    }
+
+    /// Return the commit-graph if there is one and `core.commitGraph` isn't disabled.
+    pub(crate) fn commit_graph_if_enabled(&self) -> Option<git_commitgraph::Graph> {
+        let use_commit_graph = self
+            .config
+            .resolved
+            .boolean("core", None, "commitGraph")
+            .and_then(Result::ok)
+            .unwrap_or(true);
+        use_commit_graph
+            .then(|| git_commitgraph::Graph::from_info_dir(self.objects.store_ref().path().join("info")).ok())
+            .flatten()
+    }
+
+    /// Return all best common ancestors of the commits `one` and `two`, similar to `git merge-base --all`, sorted by
+    /// commit time with the most recent one first.
+    ///
+    /// There can be more than one merge base in case of criss-cross merges, and none if the histories are unrelated.
+    pub fn merge_base(
+        &self,
+        one: impl Into<ObjectId>,
+        two: impl Into<ObjectId>,
+    ) -> Result<Vec<ObjectId>, revision::merge_base::Error> {
+        git_revision::merge_base(&one.into(), &[two.into()], &mut self.graph())
+    }
+
+    /// Return the best common ancestors of all `tips`, similar to `git merge-base --octopus`.
+    pub fn merge_base_octopus(
+        &self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<ObjectId>, revision::merge_base::Error> {
+        let tips: Vec<_> = tips.into_iter().map(Into::into).collect();
+        git_revision::merge_base_octopus(&tips, &mut self.graph())
+    }
+
+    /// Return `(ahead, behind)`, the amount of commits reachable from `one` but not from `two`, and the amount of commits reachable
+    /// from `two` but not from `one`, similar to `git rev-list --left-right --count one...two`.
+    pub fn ahead_behind(
+        &self,
+        one: impl Into<ObjectId>,
+        two: impl Into<ObjectId>,
+    ) -> Result<(usize, usize), revision::merge_base::Error> {
+        git_revision::ahead_behind(&one.into(), &two.into(), &mut self.graph())
+    }
 }
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use crate::shallow::{self, Commits};
+
+impl crate::Repository {
+    /// Return `true` if the repository is a shallow clone, i.e. contains history only up to a certain depth.
+    pub fn is_shallow(&self) -> bool {
+        self.shallow_file()
+            .metadata()
+            .map_or(false, |m| m.is_file() && m.len() > 0)
+    }
+
+    /// Return a shared list of shallow commits which is updated automatically if the in-memory snapshot has become stale
+    /// as the underlying file on disk has changed.
+    ///
+    /// The parents of these commits aren't available locally, so traversals have to stop at them.
+    /// It's `None` if the repository isn't shallow.
+    ///
+    /// To change the shallow boundary, convert the commits into a [`shallow::File`][crate::shallow::File] and
+    /// [write it][crate::shallow::File::write_to()] to the [`shallow_file()`][Self::shallow_file()].
+    pub fn shallow_commits(&self) -> Result<Option<Commits>, shallow::open::Error> {
+        self.shallow_commits.recent_snapshot(
+            || git_features::fs::Stamp::at(self.shallow_file()),
+            || {
+                let path = self.shallow_file();
+                let buf = match std::fs::read(&path) {
+                    Ok(buf) => buf,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(source) => return Err(shallow::open::Error::Io { source, path }),
+                };
+
+                let commits: Vec<_> = shallow::File::from_bytes(&buf)?.into();
+                if commits.is_empty() {
+                    Ok(None)
+                } else {
+                    Ok(Some(commits))
+                }
+            },
+        )
+    }
+
+    /// Return the path to the file containing the shallow commits, which may not exist if the repository isn't shallow.
+    pub fn shallow_file(&self) -> PathBuf {
+        self.common_dir().join("shallow")
+    }
+}
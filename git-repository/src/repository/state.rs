@@ -1,4 +1,12 @@
-use crate::state;
+use std::convert::TryInto;
+
+use git_hash::ObjectId;
+use git_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    Target,
+};
+
+use crate::{bstr::ByteSlice, reference, state};
 
 impl crate::Repository {
     /// Returns the status of an in progress operation on a repository or [`None`]
@@ -41,4 +49,108 @@ impl crate::Repository {
             None
         }
     }
+
+    /// Return the ids of the commits being merged into `HEAD` as recorded in `MERGE_HEAD`, or `None` if no merge is in progress.
+    ///
+    /// There is more than one id if an octopus merge stopped due to conflicts.
+    pub fn merge_heads(&self) -> Result<Option<Vec<ObjectId>>, state::head::Error> {
+        self.read_head_file("MERGE_HEAD")
+    }
+
+    /// Return the id of the commit being cherry-picked as recorded in `CHERRY_PICK_HEAD`,
+    /// or `None` if no cherry-pick is in progress.
+    pub fn cherry_pick_head(&self) -> Result<Option<ObjectId>, state::head::Error> {
+        Ok(self
+            .read_head_file("CHERRY_PICK_HEAD")?
+            .and_then(|ids| ids.into_iter().next()))
+    }
+
+    /// Return the id of the commit being reverted as recorded in `REVERT_HEAD`,
+    /// or `None` if no revert is in progress.
+    pub fn revert_head(&self) -> Result<Option<ObjectId>, state::head::Error> {
+        Ok(self
+            .read_head_file("REVERT_HEAD")?
+            .and_then(|ids| ids.into_iter().next()))
+    }
+
+    /// Record `id` as `ORIG_HEAD`, the commit `HEAD` pointed to before a merge, reset or rebase changed it.
+    ///
+    /// The change is made in a transaction, failing if `ORIG_HEAD` is currently locked by another process.
+    /// A reflog entry with `log_message` is only written if `ORIG_HEAD` has a reflog already.
+    /// The previous id is returned, if there was one.
+    pub fn set_orig_head(
+        &self,
+        id: impl Into<ObjectId>,
+        log_message: &str,
+    ) -> Result<Option<ObjectId>, reference::edit::Error> {
+        self.write_head_file("ORIG_HEAD", id.into(), log_message)
+    }
+
+    /// Record `id` as the commit being merged into `HEAD` in `MERGE_HEAD`, marking a merge as in progress.
+    ///
+    /// The previous id is returned, if there was one. Like [`set_orig_head()`][Self::set_orig_head()], the change is
+    /// made in a transaction.
+    pub fn set_merge_head(&self, id: impl Into<ObjectId>) -> Result<Option<ObjectId>, reference::edit::Error> {
+        self.write_head_file("MERGE_HEAD", id.into(), "")
+    }
+
+    /// Record `id` as the commit being cherry-picked in `CHERRY_PICK_HEAD`, marking a cherry-pick as in progress.
+    ///
+    /// The previous id is returned, if there was one. Like [`set_orig_head()`][Self::set_orig_head()], the change is
+    /// made in a transaction.
+    pub fn set_cherry_pick_head(&self, id: impl Into<ObjectId>) -> Result<Option<ObjectId>, reference::edit::Error> {
+        self.write_head_file("CHERRY_PICK_HEAD", id.into(), "")
+    }
+
+    fn write_head_file(
+        &self,
+        name: &str,
+        id: ObjectId,
+        log_message: &str,
+    ) -> Result<Option<ObjectId>, reference::edit::Error> {
+        let edits = self.edit_reference(
+            RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        message: log_message.into(),
+                        ..Default::default()
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(id),
+                },
+                name: name.try_into()?,
+                deref: false,
+            },
+            git_lock::acquire::Fail::Immediately,
+            self.committer_or_default(),
+        )?;
+        Ok(edits.into_iter().next().and_then(|edit| {
+            edit.change
+                .previous_value()
+                .and_then(|v| v.try_id().map(ToOwned::to_owned))
+        }))
+    }
+
+    fn read_head_file(&self, name: &str) -> Result<Option<Vec<ObjectId>>, state::head::Error> {
+        let path = self.path().join(name);
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(state::head::Error::Io { source, path }),
+        };
+        content
+            .lines()
+            .map(|line| line.trim())
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_index, line)| {
+                ObjectId::from_hex(line).map_err(|source| state::head::Error::Decode {
+                    source,
+                    path: path.clone(),
+                    line_number: line_index + 1,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
 }
@@ -0,0 +1,117 @@
+use git_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    submodule::{Error, State, Submodule},
+};
+
+impl crate::Repository {
+    /// Return all submodules configured in `.gitmodules`, in the order they appear in it, similar to `git submodule status`.
+    ///
+    /// `.gitmodules` is read from the worktree, or from the tree of `HEAD` in bare repositories, and submodules without a `path`
+    /// are ignored. Their [`head_id`][Submodule::head_id] is the commit recorded for their path in the index, or in the tree of `HEAD`
+    /// in bare repositories.
+    ///
+    /// Note that this is read-only and doesn't open the repositories of submodules.
+    pub fn submodules(&self) -> Result<Vec<Submodule>, Error> {
+        let head_tree = match self.work_dir() {
+            Some(_) => None,
+            None => {
+                let mut head = self.head()?;
+                if head.id().is_none() {
+                    return Ok(Vec::new());
+                }
+                Some(head.peel_to_commit_in_place()?.tree_id()?.detach())
+            }
+        };
+        let gitmodules = match (self.work_dir(), head_tree) {
+            (Some(root), _) => match std::fs::read(root.join(".gitmodules")) {
+                Ok(data) => data,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(err) => return Err(Error::ReadWorktreeFile(err)),
+            },
+            (None, Some(tree)) => match self
+                .find_object(tree)?
+                .into_tree()
+                .lookup_entry_by_path(".gitmodules")?
+            {
+                Some(entry) => self.find_object(entry.oid)?.detach().data,
+                None => return Ok(Vec::new()),
+            },
+            (None, None) => unreachable!("bare repositories have a tree"),
+        };
+        let config = git_config::File::from_bytes_no_includes(
+            &gitmodules,
+            git_config::file::Metadata::api(),
+            Default::default(),
+        )?;
+
+        let index = match head_tree {
+            Some(_) => None,
+            None => Some(self.index_or_empty()?),
+        };
+        let mut out = Vec::<Submodule>::new();
+        for section in config.sections_by_name("submodule").into_iter().flatten() {
+            let name = match section.header().subsection_name() {
+                Some(name) => name,
+                None => continue,
+            };
+            // Like everywhere in configuration, the last value wins even if the section appears multiple times.
+            let submodule = match out.iter_mut().position(|s| s.name == name) {
+                Some(pos) => &mut out[pos],
+                None => {
+                    out.push(Submodule {
+                        name: name.to_owned(),
+                        path: Default::default(),
+                        url: None,
+                        head_id: None,
+                        state: Default::default(),
+                    });
+                    out.last_mut().expect("just pushed")
+                }
+            };
+            if let Some(path) = section.value("path") {
+                submodule.path = path.into_owned();
+            }
+            if let Some(url) = section.value("url") {
+                submodule.url = Some(url.into_owned());
+            }
+        }
+        out.retain(|s| !s.path.is_empty());
+
+        for submodule in &mut out {
+            let path = submodule.path.as_bstr();
+            submodule.head_id = match (&index, head_tree) {
+                (Some(index), _) => index
+                    .entry_by_path_and_stage(path, 0)
+                    .filter(|entry| entry.mode == git_index::entry::Mode::COMMIT)
+                    .map(|entry| entry.id),
+                (None, Some(tree)) => self.gitlink_in_tree(tree, path)?,
+                (None, None) => None,
+            };
+            submodule.state = State {
+                is_initialized: self
+                    .config
+                    .resolved
+                    .sections_by_name("submodule")
+                    .into_iter()
+                    .flatten()
+                    .filter(|section| section.header().subsection_name() == Some(submodule.name.as_bstr()))
+                    .any(|section| section.value("url").is_some()),
+                is_checked_out: self
+                    .work_dir()
+                    .map_or(false, |root| root.join(git_path::from_bstr(path)).join(".git").exists()),
+            };
+        }
+        Ok(out)
+    }
+
+    fn gitlink_in_tree(&self, tree: ObjectId, path: &BStr) -> Result<Option<ObjectId>, Error> {
+        Ok(self
+            .find_object(tree)?
+            .into_tree()
+            .lookup_entry(path.split_str("/"))?
+            .filter(|entry| entry.mode == git_object::tree::EntryMode::Commit)
+            .map(|entry| entry.oid))
+    }
+}
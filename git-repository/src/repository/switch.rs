@@ -0,0 +1,389 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    path::Path,
+    sync::atomic::AtomicBool,
+};
+
+use git_hash::ObjectId;
+use git_odb::FindExt;
+use git_ref::{
+    transaction::{Change as RefChange, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    status,
+    switch::{Change, ChangeKind, Error, Options, Outcome},
+};
+
+/// The object id and mode of each path in a tree or index.
+type EntriesByPath = BTreeMap<BString, (ObjectId, git_index::entry::Mode)>;
+
+impl crate::Repository {
+    /// Switch to the local branch `branch_name`, given as short name like `main` or as full reference name like `refs/heads/main`,
+    /// by updating the worktree and the index to the tree of its commit and pointing `HEAD` to it, similar to `git checkout <branch>`.
+    ///
+    /// Only paths which differ between the tree of the current `HEAD` commit and the tree of the branch are written to or removed
+    /// from the worktree. Local changes to all other paths are kept, both in the worktree and in the index.
+    /// Unless `opts.force` is set, nothing is changed and an error is returned if local changes or untracked files would be
+    /// overwritten, or if the index contains unresolved conflicts.
+    /// With `opts.run_hooks`, the `post-checkout` hook is run once `HEAD` was updated.
+    ///
+    /// See [`checkout_ref()`][Self::checkout_ref()] for a way to detach `HEAD` and for what happens on failure.
+    pub fn switch_branch(&self, branch_name: &BStr, opts: Options) -> Result<Outcome, Error> {
+        let name: BString = if branch_name.starts_with(b"refs/") {
+            branch_name.into()
+        } else {
+            let mut name = BString::from("refs/heads/");
+            name.push_str(branch_name);
+            name
+        };
+        let branch = self
+            .find_reference(name.as_bstr())
+            .map_err(|source| Error::FindBranch {
+                name: name.clone(),
+                source,
+            })?;
+        self.checkout_reference(branch, branch_name, opts)
+    }
+
+    /// Check out the reference `name`, like `main`, `v1.0` or `refs/remotes/origin/main`, by updating the worktree and the
+    /// index to the tree of the commit it points to, similar to `git checkout <name>`.
+    ///
+    /// If `name` is a local branch, `HEAD` will point to it. Otherwise `HEAD` is detached and points to the commit directly.
+    /// Short names are looked up as local branch first, and with the usual lookup rules otherwise.
+    /// Like `git`, a reflog entry `checkout: moving from <previous> to <name>` is written for `HEAD`.
+    /// The worktree is updated just like with [`switch_branch()`][Self::switch_branch()], and `opts.force` has the same meaning.
+    ///
+    /// # On failure
+    ///
+    /// The index is locked for the duration of the operation, and `HEAD` is only changed after the index was written.
+    /// If updating the worktree fails, some of its files may have already been changed, but neither the index nor `HEAD`
+    /// are touched, so these show up as local modifications relative to the previous commit. If updating `HEAD` fails,
+    /// the index and the worktree match the new commit, which shows up as staged changes relative to the previous commit.
+    pub fn checkout_ref(&self, name: &BStr, opts: Options) -> Result<Outcome, Error> {
+        let branch = if name.starts_with(b"refs/") {
+            None
+        } else {
+            let mut branch_name = BString::from("refs/heads/");
+            branch_name.push_str(name);
+            self.try_find_reference(branch_name.as_bstr())
+                .map_err(|source| Error::FindReference {
+                    name: name.into(),
+                    source: source.into(),
+                })?
+        };
+        let reference = match branch {
+            Some(branch) => branch,
+            None => self.find_reference(name).map_err(|source| Error::FindReference {
+                name: name.into(),
+                source,
+            })?,
+        };
+        self.checkout_reference(reference, name, opts)
+    }
+
+    /// Check out `reference`, given by the user as `name`.
+    fn checkout_reference(
+        &self,
+        mut reference: crate::Reference<'_>,
+        name: &BStr,
+        opts: Options,
+    ) -> Result<Outcome, Error> {
+        let work_dir = self.work_dir().ok_or(Error::MissingWorkDir)?.to_owned();
+        let is_branch = reference.name().as_bstr().starts_with(b"refs/heads/");
+        let moving_to: BString = if is_branch {
+            reference.name().shorten().into()
+        } else {
+            name.into()
+        };
+        let commit = reference.peel_to_id_in_place()?.detach();
+        let new_tree = self.commit_tree_id(commit)?;
+
+        let head = self.head()?;
+        let previous_commit = head.id().map(crate::Id::detach);
+        let moving_from: BString = match head.referent_name() {
+            Some(name) => name.shorten().into(),
+            None => previous_commit
+                .map(|id| id.to_hex().to_string())
+                .unwrap_or_default()
+                .into(),
+        };
+        let old_tree = previous_commit.map(|id| self.commit_tree_id(id)).transpose()?;
+
+        let old_tree = self.index_from_tree(old_tree)?;
+        let new_tree = self.index_from_tree(Some(new_tree))?;
+        let index = match self.open_index() {
+            Ok(index) => index.state,
+            Err(crate::worktree::open_index::Error::NotFound { .. }) => old_tree.clone(),
+            Err(err) => return Err(err.into()),
+        };
+        let old_entries = entries_by_path(&old_tree);
+        let new_entries = entries_by_path(&new_tree);
+        let index_entries = entries_by_path(&index);
+
+        let mut modified = BTreeSet::new();
+        let mut conflicts = Vec::new();
+        let mut untracked = Vec::new();
+        for item in self.status(&index)? {
+            let status::Item { mut rela_path, status } = item?;
+            match status {
+                status::Status::Change(_) => {
+                    modified.insert(rela_path);
+                }
+                status::Status::Conflict => conflicts.push(rela_path),
+                status::Status::Untracked => {
+                    if rela_path.ends_with(b"/") {
+                        rela_path.pop();
+                    }
+                    untracked.push(rela_path)
+                }
+            }
+        }
+        let staged: BTreeSet<_> = changed_paths(&old_entries, &index_entries).collect();
+        let changed: BTreeSet<_> = changed_paths(&old_entries, &new_entries).collect();
+
+        if !opts.force {
+            if !conflicts.is_empty() {
+                return Err(Error::UnresolvedConflicts { paths: conflicts });
+            }
+            let local_changes: Vec<_> = changed
+                .iter()
+                .filter(|path| {
+                    modified.contains(*path)
+                        || (staged.contains(*path) && index_entries.get(*path) != new_entries.get(*path))
+                })
+                .cloned()
+                .collect();
+            if !local_changes.is_empty() {
+                return Err(Error::LocalChangesWouldBeOverwritten { paths: local_changes });
+            }
+            let untracked_in_the_way: Vec<_> = untracked
+                .into_iter()
+                .filter(|untracked| {
+                    changed
+                        .iter()
+                        .filter(|path| new_entries.contains_key(*path))
+                        .any(|path| {
+                            is_same_or_parent(untracked.as_bstr(), path.as_bstr())
+                                || is_same_or_parent(path.as_bstr(), untracked.as_bstr())
+                        })
+                })
+                .collect();
+            if !untracked_in_the_way.is_empty() {
+                return Err(Error::UntrackedFilesWouldBeOverwritten {
+                    paths: untracked_in_the_way,
+                });
+            }
+        }
+
+        let mut lock =
+            git_lock::File::acquire_to_update_resource(self.index_path(), git_lock::acquire::Fail::Immediately, None)?;
+
+        let mut changes = Vec::new();
+        for path in changed.iter().filter(|path| !new_entries.contains_key(*path)) {
+            remove_file(&work_dir, path.as_bstr())?;
+            changes.push(Change {
+                rela_path: path.clone(),
+                kind: ChangeKind::Deleted,
+            });
+        }
+
+        let mut checkout = new_tree.clone();
+        checkout.remove_entries(|_, path, _| {
+            let is_dirty = || modified.contains(path) || staged.contains(path);
+            !(changed.contains(path) || (opts.force && is_dirty()))
+        });
+        // Existing files are neither truncated nor have their executable bit removed when overwritten, so remove them first.
+        // Everything else that is in the way is handled by the checkout itself.
+        for entry in checkout.entries() {
+            let rela_path = entry.path(&checkout);
+            let is_file = std::fs::symlink_metadata(work_dir.join(git_path::from_bstr(rela_path)))
+                .map_or(false, |meta| !meta.is_dir());
+            if is_file {
+                remove_file(&work_dir, rela_path)?;
+            }
+        }
+        let objects = self.objects.clone().into_arc().map_err(Error::ObjectDatabaseHandle)?;
+        let outcome = git_worktree::index::checkout(
+            &mut checkout,
+            &work_dir,
+            move |oid, buf| objects.find_blob(oid, buf),
+            &mut git_features::progress::Discard,
+            &mut git_features::progress::Discard,
+            &AtomicBool::default(),
+            git_worktree::index::checkout::Options {
+                overwrite_existing: true,
+                ..self.checkout_options(self.git_dir())?
+            },
+        )?;
+        if !outcome.collisions.is_empty() {
+            return Err(Error::UntrackedFilesWouldBeOverwritten {
+                paths: outcome.collisions.into_iter().map(|c| c.path).collect(),
+            });
+        }
+        for entry in checkout.entries() {
+            let path = entry.path(&checkout);
+            changes.push(Change {
+                rela_path: path.into(),
+                kind: if old_entries.contains_key(path) {
+                    ChangeKind::Modified
+                } else {
+                    ChangeKind::Added
+                },
+            });
+        }
+        changes.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+
+        let mut new_index = if opts.force {
+            new_tree
+        } else {
+            let mut index = index.clone();
+            index.smudge_racily_clean_entries();
+            index
+        };
+        new_index
+            .remove_entries(|_, path, _| changed.contains(path) || checkout.entry_by_path_and_stage(path, 0).is_some());
+        for entry in checkout.entries() {
+            new_index.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, entry.path(&checkout));
+        }
+        new_index.sort_entries();
+        if opts.force {
+            for (entry, path) in new_index.entries_mut_with_paths() {
+                if let Some(previous) = index.entry_by_path_and_stage(path, 0) {
+                    if entry.stat == Default::default() && previous.id == entry.id && previous.mode == entry.mode {
+                        entry.stat = previous.stat;
+                        if previous.is_racy(index.timestamp()) {
+                            entry.stat.size = 0;
+                        }
+                    }
+                }
+            }
+        }
+        git_index::File {
+            state: new_index,
+            path: self.index_path(),
+            checksum: ObjectId::null(self.object_hash()),
+        }
+        .write_to(
+            &mut lock,
+            git_index::write::Options {
+                hash_kind: self.object_hash(),
+                extensions: git_index::write::Extensions::Given {
+                    tree_cache: false,
+                    end_of_index_entry: true,
+                },
+            },
+        )
+        .map_err(Error::WriteIndex)?;
+        lock.commit()?;
+
+        self.edit_reference(
+            RefEdit {
+                change: RefChange::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: format!("checkout: moving from {} to {}", moving_from, moving_to).into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: if is_branch {
+                        Target::Symbolic(reference.name().to_owned())
+                    } else {
+                        Target::Peeled(commit)
+                    },
+                },
+                name: "HEAD".try_into().expect("HEAD is always valid"),
+                deref: false,
+            },
+            git_lock::acquire::Fail::Immediately,
+            self.committer_or_default(),
+        )?;
+
+        let post_checkout_hook = if opts.run_hooks {
+            let previous = previous_commit.unwrap_or_else(|| ObjectId::null(self.object_hash()));
+            self.hooks()
+                .run(
+                    "post-checkout",
+                    [previous.to_hex().to_string(), commit.to_hex().to_string(), "1".into()],
+                    None,
+                )
+                .transpose()
+        } else {
+            None
+        };
+
+        Ok(Outcome {
+            previous_commit,
+            commit,
+            changes,
+            post_checkout_hook,
+        })
+    }
+
+    fn commit_tree_id(&self, commit: ObjectId) -> Result<ObjectId, Error> {
+        Ok(self
+            .find_object(commit)?
+            .peel_to_kind(git_object::Kind::Commit)?
+            .into_commit()
+            .tree_id()?
+            .detach())
+    }
+
+    /// Turn `tree` into an index without stat information, or return an empty index if there is no tree.
+    fn index_from_tree(&self, tree: Option<ObjectId>) -> Result<git_index::State, Error> {
+        Ok(match tree {
+            Some(tree) => git_index::State::from_tree(&tree, |oid, buf| self.objects.find_tree_iter(oid, buf).ok())?,
+            None => git_index::State::from_tree(&ObjectId::empty_tree(self.object_hash()), |_, _| {
+                Some(git_object::TreeRefIter::from_bytes(&[]))
+            })?,
+        })
+    }
+}
+
+fn entries_by_path(state: &git_index::State) -> EntriesByPath {
+    state
+        .entries()
+        .iter()
+        .filter(|entry| entry.stage() == 0)
+        .map(|entry| (entry.path(state).into(), (entry.id, entry.mode)))
+        .collect()
+}
+
+/// Return all paths which are only in one of `a` and `b`, or whose id or mode differ.
+fn changed_paths<'a>(a: &'a EntriesByPath, b: &'a EntriesByPath) -> impl Iterator<Item = BString> + 'a {
+    a.iter()
+        .filter(move |(path, entry)| b.get(*path) != Some(entry))
+        .map(|(path, _)| path.clone())
+        .chain(b.keys().filter(move |path| !a.contains_key(*path)).cloned())
+}
+
+fn is_same_or_parent(parent: &BStr, path: &BStr) -> bool {
+    path.strip_prefix(parent.as_bytes())
+        .map_or(false, |rest| rest.is_empty() || rest[0] == b'/')
+}
+
+/// Remove the file at `rela_path` in `work_dir` if it exists, along with all directories it leaves empty.
+fn remove_file(work_dir: &Path, rela_path: &BStr) -> Result<(), Error> {
+    let path = work_dir.join(git_path::from_bstr(rela_path));
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(source) => {
+            return Err(Error::RemoveFile {
+                rela_path: rela_path.into(),
+                source,
+            })
+        }
+    }
+    let mut dir = path.parent();
+    while let Some(parent) = dir.filter(|dir| *dir != work_dir) {
+        if std::fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.parent();
+    }
+    Ok(())
+}
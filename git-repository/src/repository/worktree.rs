@@ -66,8 +66,10 @@ impl crate::Repository {
     /// Open a new copy of the index file and decode it entirely.
     ///
     /// It will use the `index.threads` configuration key to learn how many threads to use.
-    /// Note that it may fail if there is no index.
-    // TODO: test
+    /// Note that it fails with [`NotFound`][worktree::open_index::Error::NotFound] if there is no index, which is distinct
+    /// from the errors returned if it can't be read or is corrupt.
+    /// Split indices, which only store part of their entries, fail with
+    /// [`UnsupportedExtension`][worktree::open_index::Error::UnsupportedExtension] as the shared index isn't read yet.
     pub fn open_index(&self) -> Result<git_index::File, worktree::open_index::Error> {
         use std::convert::{TryFrom, TryInto};
         let thread_limit = self
@@ -85,44 +87,147 @@ impl crate::Repository {
                 })
             })
             .transpose()?;
-        git_index::File::at(
-            self.index_path(),
+        let path = self.index_path();
+        let index = git_index::File::at(
+            &path,
             git_index::decode::Options {
                 object_hash: self.object_hash(),
                 thread_limit,
                 min_extension_block_in_bytes_for_threading: 0,
             },
         )
-        .map_err(Into::into)
+        .map_err(|err| match err {
+            git_index::file::init::Error::Io(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                worktree::open_index::Error::NotFound { path: path.clone() }
+            }
+            err => err.into(),
+        })?;
+        if index.link().is_some() {
+            return Err(worktree::open_index::Error::UnsupportedExtension {
+                path,
+                signature: git_index::extension::link::SIGNATURE,
+            });
+        }
+        Ok(index)
     }
 
     /// Return a shared worktree index which is updated automatically if the in-memory snapshot has become stale as the underlying file
-    /// on disk has changed.
+    /// on disk has changed, or fail with [`NotFound`][worktree::open_index::Error::NotFound] if there is no index file.
     ///
     /// The index file is shared across all clones of this repository.
     pub fn index(&self) -> Result<worktree::Index, worktree::open_index::Error> {
-        self.index
-            .recent_snapshot(
-                || self.index_path().metadata().and_then(|m| m.modified()).ok(),
-                || {
-                    self.open_index().map(Some).or_else(|err| match err {
-                        worktree::open_index::Error::IndexFile(git_index::file::init::Error::Io(err))
-                            if err.kind() == std::io::ErrorKind::NotFound =>
-                        {
-                            Ok(None)
-                        }
-                        err => Err(err),
-                    })
-                },
-            )
-            .and_then(|opt| match opt {
-                Some(index) => Ok(index),
-                None => Err(worktree::open_index::Error::IndexFile(
-                    git_index::file::init::Error::Io(std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        format!("Could not find index file at {:?} for opening.", self.index_path()),
-                    )),
-                )),
-            })
+        self.try_index()?.ok_or_else(|| worktree::open_index::Error::NotFound {
+            path: self.index_path(),
+        })
+    }
+
+    /// Like [`index()`][Self::index()], but returns `None` if there is no index file.
+    pub fn try_index(&self) -> Result<Option<worktree::Index>, worktree::open_index::Error> {
+        self.index.recent_snapshot(
+            || git_features::fs::Stamp::at(self.index_path()),
+            || {
+                self.open_index().map(Some).or_else(|err| match err {
+                    worktree::open_index::Error::NotFound { .. } => Ok(None),
+                    err => Err(err),
+                })
+            },
+        )
+    }
+
+    /// Like [`index()`][Self::index()], but returns an empty in-memory index if there is no index file yet, as it is the case
+    /// in freshly initialized repositories.
+    pub fn index_or_empty(&self) -> Result<worktree::IndexPersistedOrInMemory, worktree::open_index::Error> {
+        Ok(match self.try_index()? {
+            Some(index) => worktree::IndexPersistedOrInMemory::Persisted(index),
+            None => worktree::IndexPersistedOrInMemory::InMemory(Box::new(git_index::File {
+                state: git_index::State::from_tree(&git_hash::ObjectId::empty_tree(self.object_hash()), |_, _| {
+                    Some(git_object::TreeRefIter::from_bytes(&[]))
+                })
+                .expect("the empty tree can always be traversed"),
+                path: self.index_path(),
+                checksum: git_hash::ObjectId::null(self.object_hash()),
+            })),
+        })
+    }
+
+    /// Return options for checking out files into `dir` as configured for this repository, to be adjusted for the
+    /// operation at hand.
+    ///
+    /// The following configuration is used:
+    ///
+    /// * filesystem capabilities are [probed in `dir`][Self::fs_capabilities()], with `core.symlinks` and related keys taking precedence.
+    /// * `checkout.workers` limits the amount of threads, with values below 1 meaning one thread per logical core.
+    ///   Unlike `git`, which checks out with a single thread by default, all cores are used if it is unset.
+    /// * `checkout.thresholdForParallelism`, defaulting to 100 like in `git`.
+    /// * `core.trustCTime` and `core.checkStat`, see [`trust_ctime()`][Self::trust_ctime()] and [`check_stat()`][Self::check_stat()].
+    /// * `core.attributesFile`, or `$XDG_CONFIG_HOME/git/attributes` if unset, for global attributes.
+    ///
+    /// Invalid numbers are ignored. Note that line ending conversions configured by `core.autocrlf` and `core.eol` are
+    /// not yet supported by checkouts.
+    pub fn checkout_options(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+    ) -> Result<git_worktree::index::checkout::Options, worktree::checkout_options::Error> {
+        let config = self.config_snapshot();
+        let thread_limit = config
+            .integer("checkout.workers")
+            .map(|workers| if workers < 1 { 0 } else { workers as usize });
+        let thread_threshold = config
+            .integer("checkout.thresholdForParallelism")
+            .and_then(|threshold| std::convert::TryFrom::try_from(threshold).ok())
+            .unwrap_or(100);
+        let attributes_file = match config.trusted_path("core.attributesFile").transpose()? {
+            Some(path) => Some(path.into_owned()),
+            None => self.config.xdg_config_path("attributes")?,
+        };
+        let mut attribute_globals = git_attributes::MatchGroup::<git_attributes::Attributes>::default();
+        attribute_globals.patterns.extend(
+            attributes_file
+                .map(|path| git_attributes::PatternList::from_file(path, None, true, &mut Vec::new()))
+                .transpose()?
+                .flatten(),
+        );
+        Ok(git_worktree::index::checkout::Options {
+            fs: self.fs_capabilities(dir),
+            thread_limit,
+            thread_threshold,
+            trust_ctime: self.trust_ctime(),
+            check_stat: self.check_stat(),
+            attribute_globals,
+            ..Default::default()
+        })
+    }
+
+    /// Compare `index` to the worktree and return an iterator over all paths whose status differs, that is tracked paths
+    /// which were modified, removed or changed their type, paths with merge conflicts, and untracked paths which aren't
+    /// excluded by ignore rules.
+    ///
+    /// `index` is typically obtained with [`index()`][Self::index()]. Stat information of its entries is compared first,
+    /// falling back to hashing the worktree file only if it doesn't match or if the entry is racy.
+    /// The executable bit is only compared if the filesystem supports it, as determined by [`fs_capabilities()`][Self::fs_capabilities()],
+    /// and the stat fields to compare are configured by `core.checkStat` and `core.trustCTime`.
+    pub fn status<'index>(
+        &self,
+        index: &'index git_index::State,
+    ) -> Result<crate::status::Iter<'_, 'index>, crate::status::Error> {
+        let worktree = self.worktree().ok_or(crate::status::Error::MissingWorkDir)?;
+        let excludes = worktree.excludes(index, None)?;
+        Ok(crate::status::Iter {
+            repo: self,
+            index,
+            work_dir: worktree.base().to_owned(),
+            excludes,
+            options: git_worktree::status::Options {
+                fs: self.fs_capabilities(self.git_dir()),
+                object_hash: self.object_hash(),
+                index_timestamp: index.timestamp(),
+                trust_ctime: self.trust_ctime(),
+                check_stat: self.check_stat(),
+            },
+            next_entry: 0,
+            untracked_candidates: None,
+            detect_nested_repositories: true,
+            buf: Vec::new(),
+        })
     }
 }
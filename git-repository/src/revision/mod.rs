@@ -11,6 +11,20 @@ pub use walk::iter::Walk;
 ///
 pub mod spec;
 
+///
+pub mod merge_base {
+    /// The error returned by [`Repository::merge_base()`][crate::Repository::merge_base()],
+    /// [`Repository::merge_base_octopus()`][crate::Repository::merge_base_octopus()] and
+    /// [`Repository::ahead_behind()`][crate::Repository::ahead_behind()].
+    pub type Error = git_revision::merge_base::Error<git_odb::store::find::Error>;
+}
+
+/// A cache of commits for fast access to their parents and commit times, as returned by [`Repository::graph()`][crate::Repository::graph()].
+///
+/// Pass it to graph algorithms like [`merge_base()`][git_revision::merge_base()] or [`ahead_behind()`][git_revision::ahead_behind()]
+/// to avoid parsing the same commits multiple times.
+pub type Graph<'repo> = git_revision::Graph<'repo, git_odb::store::find::Error>;
+
 /// The specification of a revision as parsed from a revision specification like `HEAD@{1}` or `v1.2.3...main`.
 /// It's typically created by [`repo.rev_parse()`][crate::Repository::rev_parse()].
 ///
@@ -60,13 +60,10 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
                         .ancestors()
                         .first_parent_only()
                         .all()
-                        .expect("cannot fail without sorting")
-                        .skip(num)
-                        .filter_map(Result::ok)
-                        .next()
+                        .map(|walk| walk.skip(num).filter_map(Result::ok).next())
                     {
-                        Some(id) => replacements.push((*obj, id.detach())),
-                        None => errors.push((
+                        Ok(Some(id)) => replacements.push((*obj, id.detach())),
+                        Ok(None) => errors.push((
                             *obj,
                             Error::AncestorOutOfRange {
                                 oid: id.shorten_or_id(),
@@ -75,11 +72,11 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
                                     .ancestors()
                                     .first_parent_only()
                                     .all()
-                                    .expect("cannot fail without sorting")
-                                    .skip(1)
-                                    .count(),
+                                    .map(|walk| walk.skip(1).count())
+                                    .unwrap_or_default(),
                             },
                         )),
+                        Err(err) => errors.push((*obj, err.into())),
                     }
                 }
             }
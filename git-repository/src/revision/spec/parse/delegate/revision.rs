@@ -168,9 +168,7 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         ) -> Result<impl Iterator<Item = (BString, ObjectId)> + 'a, Error> {
             match platform.rev().ok().flatten() {
                 Some(log) => Ok(log.filter_map(Result::ok).filter_map(|line| {
-                    line.message
-                        .strip_prefix(b"checkout: moving from ")
-                        .and_then(|from_to| from_to.find(" to ").map(|pos| &from_to[..pos]))
+                    crate::head::log::checked_out_from(line.message.as_ref())
                         .map(|from_branch| (from_branch.into(), line.previous_oid))
                 })),
                 None => Err(Error::MissingRefLog {
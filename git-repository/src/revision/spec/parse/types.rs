@@ -173,6 +173,8 @@ pub enum Error {
     },
     #[error(transparent)]
     Traverse(#[from] git_traverse::commit::ancestors::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
     #[error("Spec does not contain a single object id")]
     SingleNotFound,
 }
@@ -1,7 +1,17 @@
 use git_hash::ObjectId;
 use git_odb::FindExt;
 
-use crate::{revision, Repository};
+use crate::{bstr::BString, revision, Repository};
+
+/// The error returned by [`Platform::all()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    AncestorIter(#[from] git_traverse::commit::ancestors::Error),
+    #[error(transparent)]
+    ShallowCommits(#[from] crate::shallow::open::Error),
+}
 
 /// A platform to traverse the revision graph by adding starting points as well as points which shouldn't be crossed,
 /// returned by [`Repository::rev_walk()`].
@@ -10,6 +20,7 @@ pub struct Platform<'repo> {
     pub(crate) tips: Vec<ObjectId>,
     pub(crate) sorting: git_traverse::commit::Sorting,
     pub(crate) parents: git_traverse::commit::Parents,
+    pub(crate) paths: Vec<BString>,
 }
 
 impl<'repo> Platform<'repo> {
@@ -19,13 +30,14 @@ impl<'repo> Platform<'repo> {
             tips: tips.into_iter().map(Into::into).collect(),
             sorting: Default::default(),
             parents: Default::default(),
+            paths: Vec::new(),
         }
     }
 }
 
 /// Create-time builder methods
 impl<'repo> Platform<'repo> {
-    /// Set the sort mode for commits to the given value. The default is to order by topology.
+    /// Set the sort mode for commits to the given value. The default is to order breadth first.
     pub fn sorting(mut self, sorting: git_traverse::commit::Sorting) -> Self {
         self.sorting = sorting;
         self
@@ -36,6 +48,27 @@ impl<'repo> Platform<'repo> {
         self.parents = git_traverse::commit::Parents::First;
         self
     }
+
+    /// Only return commits which change any of the given `paths` compared to their parents, similar to `git log -- <paths>`.
+    ///
+    /// Paths are relative to the root of the repository, like `dir/file`, and may also be directories to match everything in them.
+    ///
+    /// History is simplified like `git` does by default: a commit which doesn't change the paths compared to one of its parents
+    /// is only followed through that parent, so side branches whose changes didn't make it into a merge aren't traversed.
+    /// The commits are always sorted by commit time, newest first, ignoring the configured [sorting][Self::sorting()].
+    pub fn paths(mut self, paths: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+        self.paths = paths
+            .into_iter()
+            .map(|path| {
+                let mut path = path.into();
+                while path.ends_with(b"/") {
+                    path.pop();
+                }
+                path
+            })
+            .collect();
+        self
+    }
 }
 
 /// Produce the iterator
@@ -46,13 +79,39 @@ impl<'repo> Platform<'repo> {
     ///
     /// It's highly recommended to set an [`object cache`][Repository::object_cache_size()] on the parent repo
     /// to greatly speed up performance if the returned id is supposed to be looked up right after.
-    pub fn all(self) -> Result<revision::Walk<'repo>, git_traverse::commit::ancestors::Error> {
+    ///
+    /// The commit-graph is used to avoid decoding commits if there is one and `core.commitGraph` isn't disabled.
+    ///
+    /// # Shallow Repositories
+    ///
+    /// In [shallow repositories][Repository::is_shallow()], the [shallow commits][Repository::shallow_commits()] are treated as if
+    /// they had no parents, so the traversal ends there without error.
+    pub fn all(self) -> Result<revision::Walk<'repo>, Error> {
         let Platform {
             repo,
             tips,
             sorting,
             parents,
+            paths,
         } = self;
+        let shallow_commits = repo.shallow_commits()?;
+        if !paths.is_empty() {
+            return Ok(revision::Walk {
+                repo,
+                inner: Box::new(path_limited::Iter::new(
+                    repo,
+                    tips,
+                    paths,
+                    matches!(parents, git_traverse::commit::Parents::First),
+                    shallow_commits
+                        .iter()
+                        .flat_map(|commits| commits.iter().copied())
+                        .collect(),
+                )),
+                is_shallow: None,
+                error_on_missing_commit: false,
+            });
+        }
         Ok(revision::Walk {
             repo,
             inner: Box::new(
@@ -61,6 +120,8 @@ impl<'repo> Platform<'repo> {
                     git_traverse::commit::ancestors::State::default(),
                     move |oid, buf| repo.objects.find_commit_iter(oid, buf),
                 )
+                .with_shallow_commits(shallow_commits.iter().flat_map(|commits| commits.iter().copied()))
+                .commit_graph(repo.commit_graph_if_enabled())
                 .sorting(sorting)?
                 .parents(parents),
             ),
@@ -70,6 +131,179 @@ impl<'repo> Platform<'repo> {
     }
 }
 
+mod path_limited {
+    use std::collections::{BinaryHeap, HashMap, HashSet};
+
+    use git_hash::ObjectId;
+    use git_object::tree::EntryMode;
+    use git_traverse::commit::ancestors::Error;
+
+    use crate::{
+        bstr::{BString, ByteSlice},
+        Repository,
+    };
+
+    /// The tree entries at each of the paths of interest, or `None` if there is no such entry.
+    type PathState = Vec<Option<(EntryMode, ObjectId)>>;
+
+    /// A traversal by commit time that only yields commits which change any of the given paths compared to all of their parents,
+    /// and which follows only a parent that's the same for all paths if there is one, like `git`'s default history simplification.
+    pub(super) struct Iter<'repo> {
+        repo: &'repo Repository,
+        paths: Vec<BString>,
+        first_parent_only: bool,
+        shallow: HashSet<ObjectId>,
+        /// Commits to look at, ordered by commit time and by order of insertion for commits with the same time.
+        queue: BinaryHeap<(u32, std::cmp::Reverse<usize>, ObjectId)>,
+        num_queued: usize,
+        seen: HashSet<ObjectId>,
+        /// The states of parents which were compared to their children, to avoid computing them again once they are traversed.
+        states: HashMap<ObjectId, PathState>,
+        /// An error that occurred when queuing the tips, to be returned on first iteration.
+        err: Option<Error>,
+    }
+
+    impl<'repo> Iter<'repo> {
+        pub(super) fn new(
+            repo: &'repo Repository,
+            tips: Vec<ObjectId>,
+            paths: Vec<BString>,
+            first_parent_only: bool,
+            shallow: HashSet<ObjectId>,
+        ) -> Self {
+            let mut iter = Iter {
+                repo,
+                paths,
+                first_parent_only,
+                shallow,
+                queue: Default::default(),
+                num_queued: 0,
+                seen: Default::default(),
+                states: Default::default(),
+                err: None,
+            };
+            for tip in tips {
+                if let Err(err) = iter.enqueue(tip) {
+                    iter.err = Some(err);
+                    break;
+                }
+            }
+            iter
+        }
+
+        fn enqueue(&mut self, id: ObjectId) -> Result<(), Error> {
+            if !self.seen.insert(id) {
+                return Ok(());
+            }
+            let commit = self.find_commit(id)?;
+            let time = commit.committer()?.time.seconds_since_unix_epoch;
+            self.num_queued += 1;
+            self.queue.push((time, std::cmp::Reverse(self.num_queued), id));
+            Ok(())
+        }
+
+        fn find_commit(&self, id: ObjectId) -> Result<crate::Commit<'repo>, Error> {
+            self.repo
+                .find_object(id)
+                .map_err(|err| Error::FindExisting {
+                    oid: id,
+                    source: err.into(),
+                })?
+                .try_into_commit()
+                .map_err(|err| Error::ObjectKind {
+                    oid: id,
+                    expected: err.expected,
+                    actual: err.actual,
+                })
+        }
+
+        fn find_tree(&self, id: ObjectId) -> Result<crate::Tree<'repo>, Error> {
+            self.repo
+                .find_object(id)
+                .map_err(|err| Error::FindExisting {
+                    oid: id,
+                    source: err.into(),
+                })?
+                .try_into_tree()
+                .map_err(|err| Error::ObjectKind {
+                    oid: id,
+                    expected: err.expected,
+                    actual: err.actual,
+                })
+        }
+
+        /// Return the state of the paths in `commit_id`, and keep it for later if `keep` is `true`.
+        fn state(&mut self, commit_id: ObjectId, keep: bool) -> Result<PathState, Error> {
+            if let Some(state) = self.states.get(&commit_id) {
+                return Ok(state.clone());
+            }
+            let tree_id = self.find_commit(commit_id)?.tree_id()?.detach();
+            let mut state = PathState::with_capacity(self.paths.len());
+            for path in &self.paths {
+                let entry = self
+                    .find_tree(tree_id)?
+                    .lookup_entry(path.split_str("/"))
+                    .map_err(|err| Error::TreeEntryLookup {
+                        oid: tree_id,
+                        source: err.into(),
+                    })?;
+                state.push(entry.map(|entry| (entry.mode, entry.oid)));
+            }
+            if keep {
+                self.states.insert(commit_id, state.clone());
+            }
+            Ok(state)
+        }
+
+        /// Return `true` if `id` changes the paths compared to all of its parents, and queue the parents to follow.
+        fn simplify(&mut self, id: ObjectId) -> Result<bool, Error> {
+            let mut parent_ids: Vec<_> = if self.shallow.contains(&id) {
+                Vec::new()
+            } else {
+                self.find_commit(id)?.parent_ids().map(|id| id.detach()).collect()
+            };
+            if self.first_parent_only {
+                parent_ids.truncate(1);
+            }
+            let state = match self.states.remove(&id) {
+                Some(state) => state,
+                None => self.state(id, false)?,
+            };
+            if parent_ids.is_empty() {
+                return Ok(state.iter().any(Option::is_some));
+            }
+            for parent_id in &parent_ids {
+                if self.state(*parent_id, true)? == state {
+                    self.enqueue(*parent_id)?;
+                    return Ok(false);
+                }
+            }
+            for parent_id in parent_ids {
+                self.enqueue(parent_id)?;
+            }
+            Ok(true)
+        }
+    }
+
+    impl<'repo> Iterator for Iter<'repo> {
+        type Item = Result<ObjectId, Error>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if let Some(err) = self.err.take() {
+                return Some(Err(err));
+            }
+            while let Some((_time, _order, id)) = self.queue.pop() {
+                match self.simplify(id) {
+                    Ok(true) => return Some(Ok(id)),
+                    Ok(false) => continue,
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            None
+        }
+    }
+}
+
 pub(crate) mod iter {
     use crate::{ext::ObjectIdExt, Id};
 
@@ -0,0 +1,127 @@
+use std::{io::Write, path::Path};
+
+use git_hash::{oid, ObjectId};
+
+use crate::bstr::ByteSlice;
+
+pub(crate) type CommitsStorage =
+    git_features::threading::OwnShared<git_features::fs::MutableSnapshot<Vec<git_hash::ObjectId>>>;
+/// A lazily loaded and auto-updated list of commits which are at the shallow boundary (behind which there are no commits available),
+/// sorted to allow bisecting.
+pub type Commits = git_features::fs::SharedSnapshot<Vec<git_hash::ObjectId>>;
+
+/// The editable content of a `shallow` file, a sorted list of the commits at the shallow boundary.
+///
+/// Use it to change the shallow boundary of a repository, as obtained with [`Repository::shallow_commits()`][crate::Repository::shallow_commits()],
+/// and [write it][File::write_to()] to [`Repository::shallow_file()`][crate::Repository::shallow_file()].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    commits: Vec<ObjectId>,
+}
+
+/// Initialization
+impl File {
+    /// Parse `data` in the format of the `shallow` file, one hexadecimal object id per line.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, git_hash::decode::Error> {
+        data.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(ObjectId::from_hex)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Into::into)
+    }
+}
+
+impl From<Vec<ObjectId>> for File {
+    fn from(mut commits: Vec<ObjectId>) -> Self {
+        commits.sort();
+        commits.dedup();
+        File { commits }
+    }
+}
+
+impl From<File> for Vec<ObjectId> {
+    fn from(file: File) -> Self {
+        file.commits
+    }
+}
+
+/// Access and mutation
+impl File {
+    /// Return all shallow commits, sorted.
+    pub fn commits(&self) -> &[ObjectId] {
+        &self.commits
+    }
+
+    /// Return true if `id` is a commit at the shallow boundary, whose parents aren't available.
+    pub fn contains(&self, id: impl AsRef<oid>) -> bool {
+        let id = id.as_ref();
+        self.commits.binary_search_by(|c| c.as_ref().cmp(id)).is_ok()
+    }
+
+    /// Add `id` to the shallow boundary, returning `true` if it wasn't present yet.
+    pub fn add(&mut self, id: impl Into<ObjectId>) -> bool {
+        let id = id.into();
+        match self.commits.binary_search(&id) {
+            Ok(_) => false,
+            Err(pos) => {
+                self.commits.insert(pos, id);
+                true
+            }
+        }
+    }
+
+    /// Return true if there are no shallow commits, which makes the repository a complete one.
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty()
+    }
+
+    /// Write all commits to the file at `path` while holding a lock on it, or remove it if there are no commits
+    /// as the repository is no longer shallow then.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), write::Error> {
+        let path = path.as_ref();
+        let mut lock = git_lock::File::acquire_to_update_resource(path, git_lock::acquire::Fail::Immediately, None)?;
+        if self.commits.is_empty() {
+            drop(lock);
+            return match std::fs::remove_file(path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            };
+        }
+        for id in &self.commits {
+            writeln!(lock, "{}", id)?;
+        }
+        lock.commit()?;
+        Ok(())
+    }
+}
+
+///
+pub mod open {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Repository::shallow_commits()`][crate::Repository::shallow_commits()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not open shallow file at '{}' for reading", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        DecodeHash(#[from] git_hash::decode::Error),
+    }
+}
+
+///
+pub mod write {
+    /// The error returned by [`File::write_to()`][super::File::write_to()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not lock the shallow file for writing")]
+        Lock(#[from] git_lock::acquire::Error),
+        #[error("Could not write or remove the shallow file")]
+        Io(#[from] std::io::Error),
+        #[error("Could not move the updated shallow file into place")]
+        Commit(#[from] git_lock::commit::Error<git_lock::File>),
+    }
+}
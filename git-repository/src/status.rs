@@ -0,0 +1,278 @@
+use std::path::PathBuf;
+
+use git_odb::FindExt;
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// The error returned by [`Repository::status()`][crate::Repository::status()] and its [iterator][Iter].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A status can only be obtained for repositories with a worktree")]
+    MissingWorkDir,
+    #[error(transparent)]
+    Excludes(#[from] crate::worktree::excludes::Error),
+    #[error("Could not compare '{rela_path}' to its counterpart in the worktree")]
+    Io { rela_path: BString, source: std::io::Error },
+}
+
+/// The status of a path in the worktree compared to the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The path is tracked in the index and changed in the worktree.
+    Change(git_worktree::status::Change),
+    /// The path has unresolved merge conflicts and thus is tracked in more than one stage of the index.
+    Conflict,
+    /// The path isn't tracked in the index and isn't ignored.
+    ///
+    /// Untracked directories which don't contain any tracked files are reported once, with their path ending in a slash.
+    /// The same is true for nested repositories, which are never descended into.
+    Untracked,
+}
+
+/// A path in the worktree along with its status.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The path relative to the root of the worktree, with slashes as separator.
+    pub rela_path: BString,
+    /// The status of the path.
+    pub status: Status,
+}
+
+/// An iterator over all paths which differ between the index and the worktree, created by
+/// [`Repository::status()`][crate::Repository::status()].
+///
+/// All index entries are compared to the worktree first, in the order of the index, and then the worktree is
+/// traversed to find untracked files, which are returned sorted by path within each directory.
+pub struct Iter<'repo, 'index> {
+    pub(crate) repo: &'repo crate::Repository,
+    pub(crate) index: &'index git_index::State,
+    pub(crate) work_dir: PathBuf,
+    pub(crate) excludes: git_worktree::fs::Cache<'index>,
+    pub(crate) options: git_worktree::status::Options,
+    pub(crate) next_entry: usize,
+    /// Directory entries yet to be visited in search for untracked files, with the next one to visit last.
+    /// It's `None` until all index entries are processed.
+    pub(crate) untracked_candidates: Option<Vec<(BString, bool)>>,
+    pub(crate) detect_nested_repositories: bool,
+    pub(crate) buf: Vec<u8>,
+}
+
+/// Builder
+impl<'repo, 'index> Iter<'repo, 'index> {
+    /// If `toggle` is true, the default, untracked directories which contain a `.git` directory or file are treated
+    /// as nested repositories and reported as a single untracked entry without looking at their content, just like git does.
+    ///
+    /// Tools which intentionally manage embedded repositories may turn this off to treat them like any other directory,
+    /// ignoring only their `.git` entry.
+    pub fn detect_nested_repositories(mut self, toggle: bool) -> Self {
+        self.detect_nested_repositories = toggle;
+        self
+    }
+}
+
+impl<'repo, 'index> Iterator for Iter<'repo, 'index> {
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_tracked()
+            .or_else(|| self.next_untracked())
+            .map(|res| res.map_err(|(rela_path, source)| Error::Io { rela_path, source }))
+    }
+}
+
+type IoResult<T> = Result<T, (BString, std::io::Error)>;
+
+impl<'repo, 'index> Iter<'repo, 'index> {
+    fn next_tracked(&mut self) -> Option<IoResult<Item>> {
+        use git_index::entry::Flags;
+        let index = self.index;
+        while let Some(entry) = index.entries().get(self.next_entry) {
+            self.next_entry += 1;
+            let rela_path = entry.path(index);
+            if entry.stage() != 0 {
+                while index
+                    .entries()
+                    .get(self.next_entry)
+                    .map_or(false, |next| next.path(index) == rela_path)
+                {
+                    self.next_entry += 1;
+                }
+                return Some(Ok(Item {
+                    rela_path: rela_path.into(),
+                    status: Status::Conflict,
+                }));
+            }
+            if entry.flags.intersects(Flags::SKIP_WORKTREE | Flags::ASSUME_VALID) {
+                continue;
+            }
+            let path = self.work_dir.join(git_path::from_bstr(rela_path));
+            match git_worktree::status::compare_to_worktree(entry, &path, &self.options, &mut self.buf) {
+                Ok(Some(change)) => {
+                    return Some(Ok(Item {
+                        rela_path: rela_path.into(),
+                        status: Status::Change(change),
+                    }))
+                }
+                Ok(None) => continue,
+                Err(err) => return Some(Err((rela_path.into(), err))),
+            }
+        }
+        None
+    }
+
+    fn next_untracked(&mut self) -> Option<IoResult<Item>> {
+        let mut candidates = match self.untracked_candidates.take() {
+            Some(candidates) => candidates,
+            None => {
+                let mut candidates = Vec::new();
+                if let Err(err) = push_sorted_dir_entries(&self.work_dir, BStr::new(""), &mut candidates) {
+                    return Some(Err((BString::default(), err)));
+                }
+                candidates
+            }
+        };
+        let mut res = None;
+        while let Some((rela_path, is_dir)) = candidates.pop() {
+            match self.untracked_item(rela_path.as_bstr(), is_dir, &mut candidates) {
+                Ok(Some(status)) => {
+                    let mut rela_path = rela_path;
+                    if is_dir {
+                        rela_path.push_byte(b'/');
+                    }
+                    res = Some(Ok(Item { rela_path, status }));
+                    break;
+                }
+                Ok(None) => continue,
+                Err(err) => {
+                    res = Some(Err((rela_path, err)));
+                    break;
+                }
+            }
+        }
+        self.untracked_candidates = Some(candidates);
+        res
+    }
+
+    /// Return the status of the untracked file or directory at `rela_path`, or `None` if it's tracked, ignored or empty.
+    /// Directories which contain tracked files have their entries added to `candidates` instead.
+    fn untracked_item(
+        &mut self,
+        rela_path: &BStr,
+        is_dir: bool,
+        candidates: &mut Vec<(BString, bool)>,
+    ) -> std::io::Result<Option<Status>> {
+        if rela_path == ".git" || rela_path.ends_with(b"/.git") {
+            return Ok(None);
+        }
+        let repo = self.repo;
+        if self
+            .excludes
+            .at_entry(rela_path, Some(is_dir), |oid, buf| repo.objects.find_blob(oid, buf))?
+            .is_excluded()
+        {
+            return Ok(None);
+        }
+        if is_tracked(self.index, rela_path) {
+            // Tracked paths which are directories now, like submodules, are reported by their index entry.
+            return Ok(None);
+        }
+        if !is_dir {
+            return Ok(Some(Status::Untracked));
+        }
+
+        if contains_tracked(self.index, rela_path) {
+            push_sorted_dir_entries(
+                &self.work_dir.join(git_path::from_bstr(rela_path)),
+                rela_path,
+                candidates,
+            )?;
+            return Ok(None);
+        }
+        if self.is_nested_repository(rela_path) {
+            return Ok(Some(Status::Untracked));
+        }
+        Ok(self.contains_untracked_file(rela_path)?.then(|| Status::Untracked))
+    }
+
+    fn is_nested_repository(&self, rela_dir: &BStr) -> bool {
+        self.detect_nested_repositories
+            && git_worktree::fs::is_nested_repository(&self.work_dir.join(git_path::from_bstr(rela_dir)))
+    }
+
+    /// Return true if the directory at `rela_dir` contains a file that isn't excluded, or a nested repository.
+    fn contains_untracked_file(&mut self, rela_dir: &BStr) -> std::io::Result<bool> {
+        let mut candidates = Vec::new();
+        push_sorted_dir_entries(
+            &self.work_dir.join(git_path::from_bstr(rela_dir)),
+            rela_dir,
+            &mut candidates,
+        )?;
+        let repo = self.repo;
+        while let Some((rela_path, is_dir)) = candidates.pop() {
+            if rela_path.ends_with(b"/.git") {
+                continue;
+            }
+            if self
+                .excludes
+                .at_entry(rela_path.as_bstr(), Some(is_dir), |oid, buf| {
+                    repo.objects.find_blob(oid, buf)
+                })?
+                .is_excluded()
+            {
+                continue;
+            }
+            if !is_dir || self.is_nested_repository(rela_path.as_bstr()) {
+                return Ok(true);
+            }
+            push_sorted_dir_entries(
+                &self.work_dir.join(git_path::from_bstr(rela_path.as_bstr())),
+                rela_path.as_bstr(),
+                &mut candidates,
+            )?;
+        }
+        Ok(false)
+    }
+}
+
+/// Add the entries of `dir`, whose path relative to the worktree is `rela_dir`, to `out` in reverse order so they
+/// can be popped in sorted order, along with a flag to tell if they are a directory.
+fn push_sorted_dir_entries(
+    dir: &std::path::Path,
+    rela_dir: &BStr,
+    out: &mut Vec<(BString, bool)>,
+) -> std::io::Result<()> {
+    let start = out.len();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let mut rela_path = BString::from(rela_dir);
+        if !rela_path.is_empty() {
+            rela_path.push_byte(b'/');
+        }
+        rela_path.push_str(git_path::into_bstr(PathBuf::from(entry.file_name())).as_ref());
+        out.push((rela_path, entry.file_type()?.is_dir()));
+    }
+    // Like git, sort directories as if their name had a trailing slash.
+    fn with_slash((rela_path, is_dir): &(BString, bool)) -> impl Iterator<Item = &u8> {
+        rela_path.iter().chain(is_dir.then(|| &b'/'))
+    }
+    out[start..].sort_by(|a, b| with_slash(a).cmp(with_slash(b)).reverse());
+    Ok(())
+}
+
+fn is_tracked(index: &git_index::State, rela_path: &BStr) -> bool {
+    index
+        .entries()
+        .binary_search_by(|entry| entry.path(index).cmp(rela_path))
+        .is_ok()
+}
+
+fn contains_tracked(index: &git_index::State, rela_dir: &BStr) -> bool {
+    let mut prefix = BString::from(rela_dir);
+    prefix.push_byte(b'/');
+    let entries = index.entries();
+    let first_candidate = entries.partition_point(|entry| entry.path(index) < prefix.as_bstr());
+    entries
+        .get(first_candidate)
+        .map_or(false, |entry| entry.path(index).starts_with(&prefix))
+}
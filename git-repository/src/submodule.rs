@@ -0,0 +1,52 @@
+use git_hash::ObjectId;
+
+use crate::bstr::BString;
+
+/// A submodule as configured in `.gitmodules`, as obtained with [`Repository::submodules()`][crate::Repository::submodules()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submodule {
+    /// The name of the submodule, as the subsection of its `submodule` section in `.gitmodules`.
+    pub name: BString,
+    /// The path of the submodule relative to the root of the worktree, with slashes as separator.
+    pub path: BString,
+    /// The URL to clone the submodule from as written in `.gitmodules`, if set.
+    ///
+    /// It's not interpreted and may be relative to the URL of the remote of the superproject.
+    pub url: Option<BString>,
+    /// The commit recorded for the submodule in the index, or in the tree of `HEAD` in bare repositories,
+    /// or `None` if there is no such entry.
+    pub head_id: Option<ObjectId>,
+    /// Information about the presence of the submodule in this repository.
+    pub state: State,
+}
+
+/// The state of a [`Submodule`] in the superproject.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct State {
+    /// `true` if the submodule was initialized with `git submodule init`, which is the case if `submodule.<name>.url` is set
+    /// in the configuration of the superproject.
+    pub is_initialized: bool,
+    /// `true` if the submodule is checked out, which is the case if its directory in the worktree contains a `.git` file or directory.
+    /// It's always `false` in bare repositories.
+    pub is_checked_out: bool,
+}
+
+/// The error returned by [`Repository::submodules()`][crate::Repository::submodules()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read '.gitmodules' from the worktree")]
+    ReadWorktreeFile(#[source] std::io::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::to_commit::Error),
+    #[error(transparent)]
+    DecodeHeadCommit(#[from] git_object::decode::Error),
+    #[error(transparent)]
+    FindExistingObject(#[from] crate::object::find::existing::Error),
+    #[error("The '.gitmodules' file could not be parsed")]
+    Parse(#[from] git_config::file::init::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+}
@@ -0,0 +1,112 @@
+use crate::bstr::BString;
+
+/// The error returned by [`Repository::switch_branch()`][crate::Repository::switch_branch()] and
+/// [`Repository::checkout_ref()`][crate::Repository::checkout_ref()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Branches can only be switched in repositories with a worktree")]
+    MissingWorkDir,
+    #[error("The branch {name:?} could not be found")]
+    FindBranch {
+        name: BString,
+        source: crate::reference::find::existing::Error,
+    },
+    #[error("The reference {name:?} could not be found")]
+    FindReference {
+        name: BString,
+        source: crate::reference::find::existing::Error,
+    },
+    #[error(transparent)]
+    PeelBranch(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    FindExistingObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] git_object::decode::Error),
+    #[error(transparent)]
+    TreeTraversal(#[from] git_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::worktree::checkout_options::Error),
+    #[error(transparent)]
+    Status(#[from] crate::status::Error),
+    #[error("The index contains unresolved conflicts in {}", paths_to_string(.paths))]
+    UnresolvedConflicts { paths: Vec<BString> },
+    #[error("Local changes to {} would be overwritten by switching branches", paths_to_string(.paths))]
+    LocalChangesWouldBeOverwritten { paths: Vec<BString> },
+    #[error("Untracked files at {} would be overwritten by switching branches", paths_to_string(.paths))]
+    UntrackedFilesWouldBeOverwritten { paths: Vec<BString> },
+    #[error("Could not remove '{rela_path}' from the worktree")]
+    RemoveFile { rela_path: BString, source: std::io::Error },
+    #[error("Could not obtain a thread-safe handle to the object database")]
+    ObjectDatabaseHandle(#[source] std::io::Error),
+    #[error(transparent)]
+    Checkout(
+        #[from]
+        git_worktree::index::checkout::Error<git_odb::find::existing_object::Error<git_odb::store::find::Error>>,
+    ),
+    #[error("Could not write the updated index")]
+    WriteIndex(#[source] std::io::Error),
+    #[error(transparent)]
+    LockIndex(#[from] git_lock::acquire::Error),
+    #[error(transparent)]
+    CommitIndex(#[from] git_lock::commit::Error<git_lock::File>),
+    #[error(transparent)]
+    EditHead(#[from] crate::reference::edit::Error),
+}
+
+fn paths_to_string(paths: &[BString]) -> String {
+    paths.iter().map(|p| format!("'{}'", p)).collect::<Vec<_>>().join(", ")
+}
+
+/// Options for use in [`Repository::switch_branch()`][crate::Repository::switch_branch()] and
+/// [`Repository::checkout_ref()`][crate::Repository::checkout_ref()].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, switch even if local changes or untracked files would be overwritten, and throw away all
+    /// changes to tracked files in the index and in the worktree, similar to `git checkout --force`.
+    pub force: bool,
+    /// If `true`, default `false`, run the `post-checkout` hook after switching, like `git checkout` does.
+    pub run_hooks: bool,
+}
+
+/// The way a path in the worktree was changed by switching branches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The path didn't exist on the previous branch and was written.
+    Added,
+    /// The path existed before and was overwritten with a different version.
+    Modified,
+    /// The path doesn't exist on the new branch and was removed.
+    Deleted,
+}
+
+/// A path in the worktree that was changed by switching branches.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The path relative to the root of the worktree, with slashes as separator.
+    pub rela_path: BString,
+    /// How the path changed.
+    pub kind: ChangeKind,
+}
+
+/// The outcome of [`Repository::switch_branch()`][crate::Repository::switch_branch()] and
+/// [`Repository::checkout_ref()`][crate::Repository::checkout_ref()].
+#[derive(Debug)]
+pub struct Outcome {
+    /// The commit `HEAD` pointed to before switching, or `None` if it was unborn.
+    pub previous_commit: Option<git_hash::ObjectId>,
+    /// The commit `HEAD` points to now.
+    pub commit: git_hash::ObjectId,
+    /// All paths which were written to or removed from the worktree, sorted by path.
+    pub changes: Vec<Change>,
+    /// The outcome of the `post-checkout` hook if [`Options::run_hooks`] was set and the hook exists, or the error if it couldn't be run.
+    ///
+    /// Note that a failing hook doesn't undo the switch, just like with `git checkout`.
+    pub post_checkout_hook: Option<Result<crate::hooks::Outcome, crate::hooks::run::Error>>,
+}
@@ -1,7 +1,8 @@
 //!
 mod error {
 
-    /// The error returned by [`tag(…)`][crate::Repository::tag()].
+    /// The error returned by [`tag(…)`][crate::Repository::tag()], [`tag_object(…)`][crate::Repository::tag_object()]
+    /// and [`tag_lightweight(…)`][crate::Repository::tag_lightweight()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -11,6 +12,8 @@ mod error {
         WriteObject(#[from] crate::object::write::Error),
         #[error(transparent)]
         ReferenceEdit(#[from] crate::reference::edit::Error),
+        #[error("The tag '{name}' already exists")]
+        AlreadyExists { name: git_ref::FullName },
     }
 }
 pub use error::Error;
@@ -105,6 +105,34 @@ impl<'a> Drop for Commit<'a> {
     }
 }
 
+/// An empty buffer taken from the free-list of its owning repository, returned by [`Repository::buffer()`].
+///
+/// It dereferences to a `Vec<u8>` and is returned to the free-list when dropped, so each in-flight buffer is exclusively owned.
+pub struct Buffer<'repo> {
+    pub(crate) inner: Vec<u8>,
+    pub(crate) repo: &'repo Repository,
+}
+
+impl<'a> Drop for Buffer<'a> {
+    fn drop(&mut self) {
+        self.repo.reuse_buffer(&mut self.inner);
+    }
+}
+
+impl<'a> std::ops::Deref for Buffer<'a> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'a> std::ops::DerefMut for Buffer<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 /// A detached, self-contained object, without access to its source repository.
 ///
 /// Use it if an `ObjectRef` should be sent over thread boundaries or stored in collections.
@@ -152,6 +180,7 @@ pub struct Repository {
     /// Particularly useful when following linked worktrees and instantiating new equally configured worktree repositories.
     pub(crate) options: crate::open::Options,
     pub(crate) index: crate::worktree::IndexStorage,
+    pub(crate) shallow_commits: crate::shallow::CommitsStorage,
 }
 
 /// An instance with access to everything a git repository entails, best imagined as container implementing `Sync + Send` for _most_
@@ -175,6 +204,7 @@ pub struct ThreadSafeRepository {
     pub(crate) linked_worktree_options: crate::open::Options,
     /// The index of this instances worktree.
     pub(crate) index: crate::worktree::IndexStorage,
+    pub(crate) shallow_commits: crate::shallow::CommitsStorage,
 }
 
 /// A remote which represents a way to interact with hosts for remote clones of the parent repository.
@@ -11,6 +11,27 @@ pub(crate) type IndexStorage = git_features::threading::OwnShared<git_features::
 /// A lazily loaded and auto-updated worktree index.
 pub type Index = git_features::fs::SharedSnapshot<git_index::File>;
 
+/// A worktree index which was either loaded from disk, or created in memory as there was no index file yet,
+/// as returned by [`Repository::index_or_empty()`][crate::Repository::index_or_empty()].
+#[derive(Debug)]
+pub enum IndexPersistedOrInMemory {
+    /// The index as loaded from disk, which is shared among all clones of the repository.
+    Persisted(Index),
+    /// An empty index which exists only in memory, but would be written to the index file's location.
+    InMemory(Box<git_index::File>),
+}
+
+impl std::ops::Deref for IndexPersistedOrInMemory {
+    type Target = git_index::File;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            IndexPersistedOrInMemory::Persisted(index) => index,
+            IndexPersistedOrInMemory::InMemory(index) => index,
+        }
+    }
+}
+
 /// A stand-in to a worktree as result of a worktree iteration.
 ///
 /// It provides access to typical worktree state, but may not actually point to a valid checkout as the latter has been moved or
@@ -71,7 +92,7 @@ pub mod proxy;
 
 ///
 pub mod open_index {
-    use crate::bstr::BString;
+    use crate::bstr::{BString, ByteSlice};
 
     /// The error returned by [`Worktree::open_index()`][crate::Worktree::open_index()].
     #[derive(Debug, thiserror::Error)]
@@ -83,8 +104,15 @@ pub mod open_index {
             #[source]
             err: git_config::value::Error,
         },
+        #[error("There is no index file at {path:?}")]
+        NotFound { path: std::path::PathBuf },
         #[error(transparent)]
         IndexFile(#[from] git_index::file::init::Error),
+        #[error("The index at {path:?} uses the '{}' extension which isn't supported yet", .signature.as_bstr())]
+        UnsupportedExtension {
+            path: std::path::PathBuf,
+            signature: git_index::extension::Signature,
+        },
     }
 
     impl<'repo> crate::Worktree<'repo> {
@@ -100,6 +128,23 @@ pub mod open_index {
     }
 }
 
+///
+pub mod checkout_options {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Repository::checkout_options()`][crate::Repository::checkout_options()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read the global attributes file")]
+        AttributesFile(#[from] std::io::Error),
+        #[error(transparent)]
+        AttributesFileInterpolation(#[from] git_config::path::interpolate::Error),
+        #[error(transparent)]
+        EnvironmentPermission(#[from] git_sec::permission::Error<PathBuf>),
+    }
+}
+
 ///
 pub mod excludes {
     use std::path::PathBuf;
@@ -1,4 +1,10 @@
-use std::path::{Path, PathBuf};
+use std::{
+    convert::TryInto,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use git_ref::Target;
 
 use crate::{
     bstr::{BStr, BString, ByteSlice},
@@ -23,6 +29,55 @@ pub mod into_repo {
     }
 }
 
+#[allow(missing_docs)]
+pub mod head {
+    /// The error returned by [`Proxy::head()`][super::Proxy::head()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read the HEAD file of the worktree")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Decode(#[from] git_ref::file::loose::reference::decode::Error),
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::existing::Error),
+    }
+}
+
+#[allow(missing_docs)]
+pub mod lock {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Proxy::lock()`][super::Proxy::lock()] and [`Proxy::unlock()`][super::Proxy::unlock()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The worktree is already locked, reason: '{reason}'")]
+        AlreadyLocked { reason: BString },
+        #[error("The worktree is not locked")]
+        NotLocked,
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+#[allow(missing_docs)]
+pub mod prune {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Proxy::prune()`][super::Proxy::prune()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The worktree is locked and can't be pruned, reason: '{reason}'")]
+        Locked { reason: BString },
+        #[error("The worktree still exists and can't be pruned")]
+        NotPrunable,
+        #[error("Could not remove the administrative files of the worktree")]
+        Io(#[from] std::io::Error),
+    }
+}
+
 impl<'repo> Proxy<'repo> {
     pub(crate) fn new(parent: &'repo Repository, git_dir: impl Into<PathBuf>) -> Self {
         Proxy {
@@ -73,6 +128,92 @@ impl<'repo> Proxy<'repo> {
             .map(|contents| contents.trim().into())
     }
 
+    /// Return the `HEAD` reference of the worktree, which is private to it, with symbolic references resolved in the
+    /// references shared with the parent repository.
+    pub fn head(&self) -> Result<crate::Head<'repo>, head::Error> {
+        let content = std::fs::read(self.git_dir.join("HEAD"))?;
+        let head =
+            git_ref::file::loose::Reference::try_from_path("HEAD".try_into().expect("HEAD is always valid"), &content)?;
+        Ok(match head.target {
+            Target::Symbolic(branch) => match self.parent.find_reference(&branch) {
+                Ok(r) => crate::head::Kind::Symbolic(r.detach()),
+                Err(crate::reference::find::existing::Error::NotFound) => crate::head::Kind::Unborn(branch),
+                Err(err) => return Err(err.into()),
+            },
+            Target::Peeled(target) => crate::head::Kind::Detached { target, peeled: None },
+        }
+        .attach(self.parent))
+    }
+
+    /// Return the reason for why this worktree can be pruned, like `git worktree list` does, or `None` if it is still
+    /// present or locked.
+    ///
+    /// A worktree can be pruned if the `.git` file of its checkout it points to doesn't exist anymore.
+    pub fn prunable_reason(&self) -> Option<BString> {
+        if self.is_locked() {
+            return None;
+        }
+        match git_discover::path::from_plain_file(self.git_dir.join("gitdir")) {
+            None => Some("gitdir file does not exist".into()),
+            Some(Err(_)) => Some("invalid gitdir file".into()),
+            Some(Ok(dot_git)) => (!dot_git.exists()).then(|| "gitdir file points to non-existent location".into()),
+        }
+    }
+
+    /// Remove the administrative files of this worktree within the parent repository if its checkout is gone, similar to
+    /// `git worktree prune`.
+    ///
+    /// Locked worktrees and those which aren't [prunable][Proxy::prunable_reason()] aren't removed and cause an error.
+    pub fn prune(self) -> Result<(), prune::Error> {
+        if let Some(reason) = self.lock_reason() {
+            return Err(prune::Error::Locked { reason });
+        }
+        if self.prunable_reason().is_none() {
+            return Err(prune::Error::NotPrunable);
+        }
+        std::fs::remove_dir_all(&self.git_dir)?;
+        if let Some(worktrees) = self.git_dir.parent() {
+            // Like git, remove the `worktrees` directory once it is empty, which fails otherwise.
+            std::fs::remove_dir(worktrees).ok();
+        }
+        Ok(())
+    }
+
+    /// Lock this worktree to prevent it from being pruned, moved or deleted, along with a `reason` which may be empty.
+    ///
+    /// It's an error if the worktree is already locked.
+    pub fn lock(&self, reason: &BStr) -> Result<(), lock::Error> {
+        let mut file = match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(self.git_dir.join("locked"))
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                return Err(lock::Error::AlreadyLocked {
+                    reason: self.lock_reason().unwrap_or_default(),
+                })
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if !reason.is_empty() {
+            file.write_all(reason)?;
+            if !reason.ends_with(b"\n") {
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unlock this worktree, which is an error if it isn't locked.
+    pub fn unlock(&self) -> Result<(), lock::Error> {
+        match std::fs::remove_file(self.git_dir.join("locked")) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(lock::Error::NotLocked),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     /// Transform this proxy into a [`Repository`] while ignoring issues reading `base()` and ignoring that it might not exist.
     ///
     /// Most importantly, the `Repository` might be initialized with a non-existing work tree directory as the checkout
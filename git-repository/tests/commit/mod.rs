@@ -3,13 +3,100 @@ mod describe {
 
     use crate::named_repo;
 
+    fn repo_with_multiple_tags() -> crate::Result<(git_repository::Repository, tempfile::TempDir)> {
+        let mut fixture = git_repository::fixture::Builder::new()?;
+        let c1 = fixture.commit("main", "c1")?;
+        let c2 = fixture.commit("main", "c2")?;
+        fixture.annotated_tag("v0", c1, "tag object 0")?;
+        fixture.annotated_tag("v1", c2, "tag object 1")?;
+        fixture.tag("v1.5", c2)?;
+        fixture
+            .time(1641081600 /* 2022-01-02 */)
+            .annotated_tag("v2", c2, "tag object 2")?;
+        Ok(fixture.finish()?)
+    }
+
     #[test]
     fn tags_are_sorted_by_date_and_lexigraphically() {
-        let repo = named_repo("make_commit_describe_multiple_tags.sh").unwrap();
+        let (repo, _tmp) = repo_with_multiple_tags().unwrap();
         let mut describe = repo.head_commit().unwrap().describe();
         for filter in &[AnnotatedTags, AllTags, AllRefs] {
             describe = describe.names(*filter);
             assert_eq!(describe.format().unwrap().to_string(), "v2", "{:?}", filter);
         }
     }
+
+    #[test]
+    fn tags_can_be_filtered_by_glob_pattern() {
+        let (repo, _tmp) = repo_with_multiple_tags().unwrap();
+        let commit = repo.head_commit().unwrap();
+
+        let format = commit.describe().matching("v1*").format().unwrap();
+        assert_eq!(format.to_string(), "v1", "v2 is more recent, but doesn't match");
+
+        let format = commit.describe().names(AllTags).matching("*.5").format().unwrap();
+        assert_eq!(format.to_string(), "v1.5");
+
+        let format = commit.describe().matching("v0").matching("v9").format().unwrap();
+        assert_eq!(format.name.as_deref(), Some("v0".into()));
+        assert_eq!(format.depth, 1);
+
+        let format = commit.describe().names(AllRefs).matching("main").try_format().unwrap();
+        assert!(format.is_none(), "branches are never used with patterns, like in git");
+    }
+
+    fn baseline(repo: &git_repository::Repository, name: &str) -> String {
+        std::fs::read_to_string(repo.work_dir().expect("non-bare").join(name).with_extension("baseline"))
+            .expect("baseline exists")
+            .trim_end()
+            .to_owned()
+    }
+
+    #[test]
+    fn tags_several_commits_back_are_named_with_their_distance() {
+        let repo = named_repo("make_commit_describe_distance.sh").unwrap();
+        let head = repo.head_id().unwrap();
+
+        let format = repo.describe(head).format().unwrap();
+        assert_eq!(format.name.as_deref(), Some("v1".into()));
+        assert_eq!(format.depth, 3);
+        assert_eq!(format.to_string(), baseline(&repo, "describe"), "v1-3-g<abbrev>");
+
+        assert_eq!(
+            repo.describe(head).names(AllTags).format().unwrap().to_string(),
+            baseline(&repo, "describe-tags"),
+            "lightweight tags are only considered if selected"
+        );
+    }
+
+    #[test]
+    fn exact_matches_are_named_by_the_tag_unless_the_long_format_is_requested() {
+        let repo = named_repo("make_commit_describe_distance.sh").unwrap();
+        let tagged = repo.rev_parse_single("v1^{commit}").unwrap();
+
+        assert_eq!(repo.describe(tagged).format().unwrap().to_string(), "v1");
+        assert_eq!(
+            repo.describe(tagged).long(true).format().unwrap().to_string(),
+            baseline(&repo, "describe-long-exact"),
+            "v1-0-g<abbrev>"
+        );
+    }
+
+    #[test]
+    fn commits_without_tags_in_their_past_are_named_by_id_only_if_requested() {
+        let repo = named_repo("make_commit_describe_distance.sh").unwrap();
+        let untagged = repo.rev_parse_single("untagged").unwrap();
+
+        assert!(repo.describe(untagged).try_format().unwrap().is_none());
+        assert_eq!(
+            repo.describe(untagged)
+                .id_as_fallback(true)
+                .try_format()
+                .unwrap()
+                .expect("always a format")
+                .to_string(),
+            baseline(&repo, "describe-always-untagged"),
+            "the abbreviated id is used"
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use git_repository as git;
+use git_repository::fixture::Builder;
+
+fn build(mut fixture: Builder) -> crate::Result<(Builder, git::ObjectId, git::ObjectId)> {
+    fixture
+        .write_file("a", "a\n")?
+        .write_file("dir/b", "b\n")?
+        .write_file("dir-c", "c\n")?
+        .write_file("dir/sub/d", "d\n")?;
+    let c1 = fixture.commit("main", "c1")?;
+    fixture.reference("refs/heads/other", c1)?;
+    let o1 = fixture.remove_file("dir/b").commit("other", "o1")?;
+    fixture.merge("main", "m1", o1)?;
+    fixture.tag("light", c1)?;
+    let tag = fixture.annotated_tag("v1", o1, "annotated")?;
+    Ok((fixture, c1, tag))
+}
+
+#[test]
+fn object_ids_are_stable_across_runs() -> crate::Result {
+    let (first, c1, tag) = build(Builder::new()?)?;
+    let (second, c1_again, tag_again) = build(Builder::bare()?)?;
+    assert_eq!(c1, c1_again);
+    assert_eq!(tag, tag_again);
+    assert_eq!(
+        c1,
+        git_testtools::hex_to_id("5b0baa14a0469e9d500ab2e3cb2ad22ff77cd1f6"),
+        "ids don't change between runs and machines, and match what git would produce"
+    );
+
+    let (repo, _tmp) = first.finish()?;
+    let head = repo.head_commit()?;
+    assert_eq!(head.parent_ids().count(), 2, "the merge commit is at HEAD");
+    assert_eq!(
+        head.author()?.time,
+        git::date::Time::new(git::fixture::START_TIME + 2 * git::fixture::TICK, 0),
+        "the time advances with each commit"
+    );
+    assert_eq!(
+        head.id,
+        second.repo().head_id()?,
+        "bare and non-bare repositories alike"
+    );
+    Ok(())
+}
+
+#[test]
+fn trees_are_nested_and_sorted_like_git_does() -> crate::Result {
+    let (fixture, c1, _tag) = build(Builder::new()?)?;
+    let (repo, tmp) = fixture.finish()?;
+    let tree = repo.find_object(c1)?.into_commit().tree()?;
+    let names = git::objs::TreeRefIter::from_bytes(&tree.data)
+        .map(|e| e.map(|e| e.filename.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        names,
+        ["a", "dir-c", "dir"],
+        "directories sort as if they had a trailing slash"
+    );
+    assert!(tree.lookup_entry_by_path("dir/sub/d")?.is_some());
+    assert!(git_testtools::run_git(tmp.path(), &["fsck", "--strict"])?.success());
+    Ok(())
+}
+
+#[test]
+fn objects_and_references_can_be_packed() -> crate::Result {
+    let (mut fixture, c1, tag) = build(Builder::bare()?)?;
+    fixture.pack_objects()?.pack_refs()?;
+    let (repo, _tmp) = fixture.finish()?;
+
+    let objects_dir = repo.git_dir().join("objects");
+    let mut loose = git::odb::loose::Store::at(&objects_dir, repo.object_hash()).iter();
+    assert!(loose.next().is_none(), "all loose objects were removed");
+    assert_eq!(
+        std::fs::read_dir(objects_dir.join("pack"))?.count(),
+        2,
+        "a single pack with its index"
+    );
+    assert!(
+        !repo.git_dir().join("refs/tags/v1").is_file(),
+        "loose references were moved into packed-refs"
+    );
+
+    assert_eq!(repo.find_reference("light")?.id(), c1);
+    assert_eq!(repo.find_reference("v1")?.id(), tag);
+    assert_eq!(
+        repo.head_commit()?.ancestors().all()?.count(),
+        3,
+        "objects are found in the pack"
+    );
+    assert!(git_testtools::run_git(repo.git_dir(), &["fsck", "--strict"])?.success());
+    Ok(())
+}
@@ -0,0 +1,16 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+mkdir -p dir/sub
+echo a > a
+echo b > dir/b
+echo c > dir/sub/c
+printf '#!/bin/sh\necho hi\n' > dir/exe
+chmod +x dir/exe
+ln -s dir/b link
+
+git add .
+git commit -q -m c1
+git tag -a -m "annotated" v1
@@ -0,0 +1,41 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+tick=0
+function commit_as() {
+  local name=$1; shift
+  tick=$((tick + 1))
+  GIT_AUTHOR_NAME="$name" GIT_AUTHOR_EMAIL="$name@example.com" \
+  GIT_AUTHOR_DATE="2000-01-01 00:0$tick:00 +0000" GIT_COMMITTER_DATE="2000-01-01 00:0$tick:00 +0000" \
+    git "$@"
+}
+
+printf 'line 1\nline 2\nline 3\nline 4\nline 5\n' > file
+git add file
+commit_as alice commit -q -m "alice adds file"
+
+printf 'line 1\nline two\nline 3\nline 4\nline 5\nline 6\n' > file
+commit_as bob commit -q -am "bob changes line 2 and adds line 6"
+
+git mv file renamed
+commit_as carol commit -q -m "carol renames the file"
+
+git checkout -q -b feature
+printf 'line 1\nline two\nline three\nline 4\nline 5\nline 6\n' > renamed
+commit_as dave commit -q -am "dave changes line 3 on a branch"
+
+git checkout -q main
+printf 'line  1\nline two\nline 3\nline 4\nline five\nline 6\n' > renamed
+commit_as erin commit -q -am "erin changes whitespace in line 1 and changes line 5"
+
+commit_as frank merge -q --no-ff -m "merge feature" feature
+
+printf 'line  1\nline two\nline three\nline 4\ninserted a\ninserted b\nline five\nline 6\n' > renamed
+commit_as grace commit -q -am "grace inserts two lines"
+
+git blame --porcelain renamed > blame.baseline
+git blame --porcelain --first-parent renamed > blame-first-parent.baseline
+git blame --porcelain -w renamed > blame-ignore-whitespace.baseline
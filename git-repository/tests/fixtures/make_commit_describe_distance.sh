@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+git commit --allow-empty -q -m c1
+git tag v1 -m "tag object 1"
+git commit --allow-empty -q -m c2
+git commit --allow-empty -q -m c3
+git tag lightweight
+git commit --allow-empty -q -m c4
+
+git checkout -q --orphan untagged
+git commit --allow-empty -q -m u1
+git checkout -q main
+
+git describe main > describe.baseline
+git describe --tags main > describe-tags.baseline
+git describe --long v1 > describe-long-exact.baseline
+git describe --always untagged > describe-always-untagged.baseline
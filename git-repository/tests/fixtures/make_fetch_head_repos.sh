@@ -0,0 +1,107 @@
+#!/bin/bash
+set -eu -o pipefail
+
+tick=1112911993
+function tick () {
+  tick=$((tick + 60))
+  export GIT_COMMITTER_DATE="$tick -0700"
+  export GIT_AUTHOR_DATE="$tick -0700"
+}
+
+function commit () {
+  tick
+  git commit -q --allow-empty -m "$1"
+}
+
+function baseline () {
+  git fmt-merge-msg < .git/FETCH_HEAD > baseline
+}
+
+git init -q remote
+(cd remote
+  git config merge.ff false
+  git checkout -q -b main
+  commit init
+
+  git checkout -q -b topic
+  commit "topic 1"
+  git checkout -q -b side
+  commit "side 1"
+  git checkout -q topic
+  commit "topic 2"
+  tick
+  git merge -q -m "merge side into topic" side
+  commit "topic 3
+
+with a body"
+
+  git checkout -q -b other main
+  commit "other 1"
+  tick
+  git tag -a -m "the first release" v1
+  git tag light main
+  git checkout -q -b more main
+  commit "more 1"
+  tick
+  git tag -a -m "the second release
+
+with details" v2
+  git checkout -q main
+)
+
+git clone -q remote base
+
+git clone -q remote two-branches
+(cd two-branches
+  git fetch -q origin topic other
+  baseline
+)
+
+git clone -q remote with-log
+(cd with-log
+  git config merge.log 2
+  git fetch -q origin topic other
+  baseline
+)
+
+git clone -q remote local-tags-and-remote-tracking-branch
+(cd local-tags-and-remote-tracking-branch
+  git config merge.log true
+  git fetch -q --no-tags . refs/remotes/origin/topic refs/tags/light refs/tags/v1
+  baseline
+)
+
+git clone -q remote multiple-tags
+(cd multiple-tags
+  git fetch -q --no-tags origin tag v1 tag v2
+  baseline
+)
+
+git clone -q remote commit-into-feature
+(cd commit-into-feature
+  git checkout -q -b feature
+  git fetch -q origin "$(git rev-parse origin/other)"
+  baseline
+)
+
+git clone -q remote detached
+(cd detached
+  git checkout -q --detach
+  git fetch -q origin topic
+  baseline
+)
+
+git clone -q remote suppressed-destination
+(cd suppressed-destination
+  git checkout -q -b feature
+  git config merge.suppressDest 'feat*'
+  git fetch -q origin topic
+  baseline
+)
+
+git clone -q remote already-merged
+(cd already-merged
+  commit "local"
+  git fetch -q origin main
+  baseline
+)
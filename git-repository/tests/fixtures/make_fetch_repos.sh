@@ -0,0 +1,61 @@
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+GIT_AUTHOR_EMAIL=author@example.com
+GIT_AUTHOR_NAME='A U Thor'
+GIT_COMMITTER_EMAIL=committer@example.com
+GIT_COMMITTER_NAME='C O Mitter'
+export GIT_AUTHOR_EMAIL GIT_AUTHOR_NAME GIT_COMMITTER_EMAIL GIT_COMMITTER_NAME
+
+git init -q base
+(cd base
+  tick
+  echo a > file
+  git add file && git commit -q -m a
+  git branch fast-forward
+  git branch forced
+  git tag -m "annotated" moved-tag
+)
+
+git clone -q --no-local base clone
+(cd clone
+  git config --add remote.origin.fetch 'refs/tags/*:refs/tags/*'
+)
+
+git clone -q --no-local base clone-without-force
+(cd clone-without-force
+  git config remote.origin.fetch 'refs/heads/*:refs/remotes/origin/*'
+)
+
+(cd base
+  tick
+  git checkout -q fast-forward
+  echo b >> file
+  git commit -q -am b
+
+  tick
+  git checkout -q forced
+  git commit -q --amend -m "a rewritten"
+
+  tick
+  git checkout -q -b new main
+  echo c >> file
+  git commit -q -am c
+  git tag -m "new annotated" new-tag
+
+  git tag -f -m "moved" moved-tag forced
+  git checkout -q main
+)
+
+git clone -q --no-local base up-to-date
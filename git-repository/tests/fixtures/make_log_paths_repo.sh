@@ -0,0 +1,63 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+tick=0
+function commit() {
+  tick=$((tick + 1))
+  local date
+  date="2000-01-01 00:$(printf %02d $tick):00 +0000"
+  GIT_AUTHOR_DATE="$date" GIT_COMMITTER_DATE="$date" git "$@"
+}
+
+mkdir dir
+echo a > a
+echo b > dir/b
+echo other > other
+git add .
+commit commit -q -m "c1 adds everything"
+
+echo other2 > other
+commit commit -q -am "c2 changes other"
+
+echo a2 > a
+commit commit -q -am "c3 changes a"
+
+git checkout -q -b ignored
+echo ignored > a
+commit commit -q -am "i1 changes a on a branch merged with -s ours"
+echo ignored > dir/b
+commit commit -q -am "i2 changes dir/b on a branch merged with -s ours"
+
+git checkout -q main
+echo c > dir/c
+git add dir/c
+commit commit -q -m "m1 adds dir/c"
+commit merge -q -s ours --no-edit -m "merge ignored changes" ignored
+
+git checkout -q -b side
+echo b-side > dir/b
+commit commit -q -am "s1 changes dir/b"
+
+git checkout -q main
+echo a3 > a
+commit commit -q -am "m2 changes a"
+commit merge -q --no-edit -m "merge side" side
+
+echo both > a
+echo both > dir/b
+commit commit -q -am "m3 changes a and dir/b"
+
+git rm -q a
+commit commit -q -m "m4 removes a"
+
+for spec in "a" "dir" "dir/b" "dir/" "a dir/c" "other" "missing"; do
+  name=${spec//\//_}
+  name=${name// /+}
+  # shellcheck disable=SC2086
+  git log --format=%H -- $spec > "log-${name}.baseline"
+  # shellcheck disable=SC2086
+  git log --first-parent --format=%H -- $spec > "log-first-parent-${name}.baseline"
+done
@@ -0,0 +1,10 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+git commit -q --allow-empty -m c1
+git checkout -q -b a
+git commit -q --allow-empty -m c2
+git checkout -q -b b
+git checkout -q main
@@ -0,0 +1,31 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function repo() {
+  local name=${1:?}
+  local version=${2:?}
+  shift 2
+
+  git init -q "$name"
+  (cd "$name"
+    git config core.repositoryFormatVersion "$version"
+    while [ $# -gt 0 ]; do
+      git config "extensions.$1" "$2"
+      shift 2
+    done
+  )
+  if git -C "$name" rev-parse --git-dir >/dev/null 2>&1; then
+    echo "ok" > "$name.baseline"
+  else
+    echo "refused" > "$name.baseline"
+  fi
+}
+
+repo v0 0
+repo v0-unknown-extension 0 unknownExtension value
+repo v0-worktree-config 0 worktreeConfig true
+repo v0-v1-only-extension 0 objectFormat sha1
+repo v1 1
+repo v1-known-extensions 1 objectFormat sha1 noop-v1 true preciousObjects true
+repo v1-unknown-extension 1 unknownExtension value
+repo v2 2
@@ -0,0 +1,16 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q --object-format=sha256
+
+git checkout -q -b main
+echo content > packed
+git add packed
+git commit -q -m "packed commit"
+git repack -adq
+
+echo content > loose
+git add loose
+git commit -q -m "loose commit"
+
+git rev-parse HEAD HEAD^{tree} HEAD~1 > .git/rev-parse.baseline
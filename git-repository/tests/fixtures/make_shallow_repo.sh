@@ -0,0 +1,22 @@
+#!/bin/bash
+set -eu -o pipefail
+
+mkdir base
+(cd base
+  git init -q
+
+  git checkout -b main
+  touch a && git add a
+  git commit -q -m c1
+  echo b >> a
+  git commit -q -am c2
+  echo c >> a
+  git commit -q -am c3
+
+  git checkout -q -b other main~1
+  echo d >> a
+  git commit -q -am o1
+)
+
+git clone -q --depth 1 --no-single-branch file://$PWD/base shallow
+git clone -q base full
@@ -0,0 +1,43 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config core.symlinks true
+git config core.fileMode true
+
+mkdir -p dir/sub empty-dir
+echo content > unchanged
+echo content > modified-same-size
+echo content > modified
+echo content > removed
+echo content > executable
+echo content > to-symlink
+echo content > dir/unchanged
+echo content > dir/to-dir
+echo "ignored*" > .gitignore
+git add .
+git commit -q -m "initial"
+
+# make sure changes to files of the same size are detected by their modification time, not only when entries are racy
+touch -d "2000-01-01 00:00:00" modified-same-size unchanged
+git update-index --refresh -q >/dev/null
+echo CONTENT > modified-same-size
+
+echo more content >> modified
+rm removed
+chmod +x executable
+rm to-symlink && ln -s unchanged to-symlink
+rm dir/to-dir && mkdir dir/to-dir && echo content > dir/to-dir/file
+
+echo untracked > untracked
+echo untracked > dir/untracked
+mkdir -p untracked-dir/sub && echo untracked > untracked-dir/sub/file
+mkdir -p ignored-only && echo ignored > ignored-only/ignored-file
+echo ignored > ignored
+echo ignored > dir/ignored
+mkdir -p nested && (cd nested && git init -q)
+mkdir -p nested-with-files && (cd nested-with-files && git init -q && echo content > file && git add file && git commit -q -m "nested")
+mkdir -p nested-with-ignored-files && (cd nested-with-ignored-files && git init -q && echo ignored > ignored)
+git init -q --separate-git-dir .git/nested-git-dir nested-with-git-file
+
+git --no-optional-locks status --porcelain > .git/status.baseline
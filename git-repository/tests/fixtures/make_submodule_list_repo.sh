@@ -0,0 +1,31 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q module1
+(cd module1
+  touch this
+  git add .
+  git commit -q -m c1
+)
+
+git init -q super
+(cd super
+  touch file
+  git add file
+  git commit -q -m "init"
+
+  git -c protocol.file.allow=always submodule add -q ../module1 m1
+  git -c protocol.file.allow=always submodule add -q --name other-name ../module1 dir/m2
+  git config -f .gitmodules submodule.no-path.url ../module1
+  git add .gitmodules
+  git commit -q -m "add submodules"
+)
+
+git clone -q super not-initialized
+
+git clone -q super partially-initialized
+(cd partially-initialized
+  git submodule init m1
+)
+
+git clone -q --bare super bare.git
@@ -0,0 +1,26 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config core.fileMode true
+
+mkdir dir
+echo content > unchanged
+echo content > modified-on-other
+echo content > deleted-on-other
+echo content > executable-on-other
+echo content > dir/deleted-on-other
+git add .
+git commit -q -m "main"
+
+git checkout -q -b other
+echo other content > modified-on-other
+git rm -q deleted-on-other dir/deleted-on-other
+chmod +x executable-on-other
+mkdir new-dir
+echo content > added-on-other
+echo content > new-dir/added-on-other
+git add .
+git commit -q -m "other"
+
+git checkout -q main
@@ -0,0 +1,43 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function baseline() {
+  local name=${1:?first argument is the worktree}
+  (cd "$name"
+    git rev-parse --is-bare-repository
+    git config --default unset core.sparseCheckout
+    git config --default unset wt.value
+  ) > "$name.baseline"
+}
+
+git init -q with-extension
+(cd with-extension
+  mkdir a b
+  touch a/f b/f
+  git add .
+  git commit -q -m c1
+  git worktree add -q ../with-extension-wt
+)
+(cd with-extension-wt
+  git sparse-checkout set a
+  git config --worktree wt.value from-worktree
+)
+(cd with-extension
+  git config extensions.worktreeConfig >/dev/null # enabled by sparse-checkout
+  git config wt.value from-common
+  git config core.bare true
+  git config --worktree core.bare false
+)
+baseline with-extension
+baseline with-extension-wt
+
+git init -q without-extension
+(cd without-extension
+  git commit -q --allow-empty -m c1
+  git worktree add -q ../without-extension-wt
+  git config wt.value from-common
+  git config core.bare true
+  git config core.worktree ../non-existing
+  printf '[wt]\n\tvalue = from-worktree\n' > .git/worktrees/without-extension-wt/config.worktree
+)
+baseline without-extension-wt
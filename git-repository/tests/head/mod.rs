@@ -13,3 +13,32 @@ mod remote {
         Ok(())
     }
 }
+
+mod log {
+    #[test]
+    fn prior_checked_out_branches_are_found_newest_first_despite_malformed_lines() -> crate::Result {
+        let (repo, _tmp) = crate::util::repo_rw("make_prior_checkouts_repo.sh")?;
+        let main_id = repo.rev_parse_single("main")?.detach();
+        let a_id = repo.rev_parse_single("a")?.detach();
+        let head = repo.head()?;
+        assert_eq!(head.prior_checked_out_branch(1)?, Some(("b".into(), a_id)));
+        assert_eq!(head.prior_checked_out_branch(2)?, Some(("a".into(), a_id)));
+        assert_eq!(head.prior_checked_out_branch(3)?, Some(("main".into(), main_id)));
+        assert_eq!(head.prior_checked_out_branch(4)?, None);
+
+        let log_path = repo.git_dir().join("logs").join("HEAD");
+        let mut log = std::fs::read(&log_path)?;
+        log.extend_from_slice(b"not a reflog line\n");
+        std::fs::write(&log_path, log)?;
+
+        let entries: Vec<_> = head.log_iter().rev()?.expect("log present").collect();
+        assert!(entries[0].is_err(), "malformed lines yield an error");
+        assert_eq!(entries.iter().filter(|entry| entry.is_ok()).count(), 5);
+        assert_eq!(
+            head.prior_checked_out_branch(1)?,
+            Some(("b".into(), a_id)),
+            "entries that can't be parsed are skipped"
+        );
+        Ok(())
+    }
+}
@@ -2,12 +2,11 @@ use std::cmp::Ordering;
 
 use git_repository as git;
 use git_repository::prelude::ObjectIdExt;
-use git_testtools::hex_to_id;
 
 #[test]
 fn prefix() -> crate::Result {
-    let (repo, worktree_dir) = crate::repo_rw("make_repo_with_fork_and_dates.sh")?;
-    let id = hex_to_id("288e509293165cb5630d08f4185bdf2445bf6170").attach(&repo);
+    let (repo, worktree_dir, ids) = crate::util::repo_with_fork_and_dates()?;
+    let id = ids.m1b1.attach(&repo);
     let prefix = id.shorten()?;
     assert_eq!(prefix.cmp_oid(&id), Ordering::Equal);
     assert_eq!(prefix.hex_len(), 7, "preconfigured via core.abbrev default value");
@@ -49,7 +48,7 @@ mod ancestors {
 
     #[test]
     fn all() -> crate::Result {
-        let repo = crate::repo("make_repo_with_fork_and_dates.sh")?.to_thread_local();
+        let (repo, _tmp, _ids) = crate::util::repo_with_fork_and_dates()?;
         let head = repo.head()?.into_fully_peeled_id().expect("born")?;
         let commits_graph_order = head.ancestors().all()?.collect::<Result<Vec<_>, _>>()?;
         assert_eq!(commits_graph_order.len(), 4, "need a specific amount of commits");
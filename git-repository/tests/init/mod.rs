@@ -59,3 +59,56 @@ mod non_bare {
         Ok(())
     }
 }
+
+mod object_hash {
+    use git_repository as git;
+
+    #[test]
+    fn sha256_is_recorded_in_the_configuration_and_used_for_writing() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = git::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            git::create::Options {
+                bare: true,
+                object_hash: git::hash::Kind::Sha256,
+                ..Default::default()
+            },
+            git::open::Options::isolated(),
+        )?
+        .to_thread_local();
+        assert_eq!(repo.object_hash(), git::hash::Kind::Sha256);
+
+        let config = std::fs::read_to_string(tmp.path().join("config"))?;
+        assert!(config.contains("repositoryformatversion = 1"));
+        assert!(config.contains("objectformat = sha256"));
+
+        assert_eq!(
+            repo.write_blob(b"hello world")?,
+            git::hash::ObjectId::from_hex(b"fee53a18d32820613c0527aa79be5cb30173c823a9b448fa4817767cc84c6f03")?,
+            "the same id as computed by `git hash-object`"
+        );
+        assert_eq!(
+            git::open_opts(tmp.path(), git::open::Options::isolated())?.object_hash(),
+            git::hash::Kind::Sha256,
+            "the object format is picked up when reopening"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_object_formats_are_refused_when_opening() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        git::init_bare(tmp.path())?;
+        let config_path = tmp.path().join("config");
+        let config = std::fs::read_to_string(&config_path)?
+            .replace("repositoryformatversion = 0", "repositoryformatversion = 1");
+        std::fs::write(&config_path, config + "[extensions]\n\tobjectFormat = sha512\n")?;
+
+        let err = git::open_opts(tmp.path(), git::open::Options::isolated()).unwrap_err();
+        assert!(matches!(
+            err,
+            git::open::Error::Config(git::config::Error::UnsupportedObjectFormat { .. })
+        ));
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ mod tree;
 fn object_ref_size_in_memory() {
     assert_eq!(
         std::mem::size_of::<git_repository::Object<'_>>(),
-        56,
+        72,
         "the size of this structure should not changed unexpectedly"
     )
 }
@@ -14,7 +14,7 @@ fn object_ref_size_in_memory() {
 fn oid_size_in_memory() {
     assert_eq!(
         std::mem::size_of::<git_repository::Id<'_>>(),
-        32,
+        48,
         "the size of this structure should not changed unexpectedly"
     )
 }
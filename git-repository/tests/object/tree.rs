@@ -73,6 +73,37 @@ mod diff {
         assert_eq!(expected, Vec::<&str>::new(), "all paths should have been seen");
     }
 
+    #[test]
+    fn stats_to_obtain_tree() -> crate::Result {
+        let repo = named_repo("make_diff_repo.sh")?;
+        let from = tree_named(
+            &repo,
+            &git::hash::ObjectId::empty_tree(git::hash::Kind::Sha1).to_string(),
+        );
+        let files = from.changes().stats_to_obtain_tree(&tree_named(&repo, ":/c3"))?;
+        let mut out = Vec::new();
+        git::diff::stat::write_numstat_to(&files, &mut out)?;
+        assert_eq!(
+            out.as_bstr(),
+            "2\t0\ta\n1\t0\tb\n1\t0\td\n1\t0\tdir/c\n",
+            "directories are skipped and files are listed with their path"
+        );
+
+        let files = tree_named(&repo, ":/c3")
+            .changes()
+            .stats_to_obtain_tree(&tree_named(&repo, "@^{/c3}~1"))?;
+        assert_eq!(
+            files,
+            vec![git::diff::stat::FileStat {
+                name: "a".into(),
+                insertions: 0,
+                deletions: 1,
+                is_binary: false
+            }]
+        );
+        Ok(())
+    }
+
     fn tree_named<'repo>(repo: &'repo git::Repository, rev_spec: &str) -> git::Tree<'repo> {
         repo.rev_parse_single(rev_spec)
             .unwrap()
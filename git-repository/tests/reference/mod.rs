@@ -66,6 +66,134 @@ mod find {
     }
 }
 
+mod name {
+    use git_repository as git;
+
+    #[test]
+    fn short_name_and_category() -> crate::Result {
+        let repo: git::Repository = crate::repo("make_references_repo.sh")?.into();
+        for (name, short_name, category) in [
+            ("refs/heads/main", "main", Some(git::reference::Category::LocalBranch)),
+            (
+                "refs/remotes/origin/main",
+                "origin/main",
+                Some(git::reference::Category::RemoteBranch),
+            ),
+            ("refs/tags/t1", "t1", Some(git::reference::Category::Tag)),
+            ("refs/multi-link", "multi-link", None),
+            ("HEAD", "HEAD", Some(git::reference::Category::PseudoRef)),
+        ] {
+            let reference = repo.find_reference(name)?;
+            assert_eq!(reference.short_name(), short_name, "{}", name);
+            assert_eq!(reference.category(), category, "{}", name);
+        }
+        Ok(())
+    }
+}
+
+mod peel {
+    use git_repository as git;
+
+    struct Fixture {
+        repo: git::Repository,
+        _tmp: tempfile::TempDir,
+        commit: git_hash::ObjectId,
+        tree: git_hash::ObjectId,
+        tag: git_hash::ObjectId,
+    }
+
+    fn fixture() -> crate::Result<Fixture> {
+        let mut fixture = git::fixture::Builder::new()?;
+        let commit = fixture.write_file("a", "a")?.commit("main", "c1")?;
+        let tree = fixture.repo().find_object(commit)?.into_commit().tree_id()?.detach();
+        fixture.tag("lightweight", commit)?;
+        let tag = fixture.annotated_tag("annotated", commit, "a commit")?;
+        fixture.annotated_tag("tree", tree, "a tree")?;
+        let (repo, _tmp) = fixture.finish()?;
+        Ok(Fixture {
+            repo,
+            _tmp,
+            commit,
+            tree,
+            tag,
+        })
+    }
+
+    #[test]
+    fn to_commit_follows_lightweight_and_annotated_tags() -> crate::Result {
+        let fixture = fixture()?;
+        for name in ["main", "lightweight", "annotated"] {
+            let mut reference = fixture.repo.find_reference(name)?;
+            assert_eq!(reference.peel_to_commit()?.id, fixture.commit, "{}", name);
+        }
+
+        let err = fixture.repo.find_reference("tree")?.peel_to_commit().unwrap_err();
+        assert!(
+            matches!(err, git::reference::peel_to_commit::Error::NotACommit { kind: git_object::Kind::Tree, id } if id == fixture.tree),
+            "the peeled object is the tree the tag points to"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_tag_only_works_for_annotated_tags() -> crate::Result {
+        let fixture = fixture()?;
+        let tag = fixture.repo.find_reference("annotated")?.peel_to_tag()?;
+        assert_eq!(tag.id, fixture.tag);
+        assert_eq!(tag.target_id()?, fixture.commit);
+
+        let err = fixture.repo.find_reference("lightweight")?.peel_to_tag().unwrap_err();
+        assert!(
+            matches!(err, git::reference::peel_to_tag::Error::NotATag { kind: git_object::Kind::Commit, id } if id == fixture.commit),
+            "lightweight tags point to commits directly"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_tag_follows_symbolic_refs_and_detects_cycles() -> crate::Result {
+        use std::convert::TryInto;
+
+        let fixture = fixture()?;
+        let refs = &fixture.repo.refs;
+        refs.write_symbolic("refs/heads/sym".try_into()?, "refs/tags/annotated".try_into()?)?;
+        assert_eq!(
+            fixture.repo.find_reference("refs/heads/sym")?.peel_to_tag()?.id,
+            fixture.tag
+        );
+
+        refs.write_symbolic("refs/heads/a".try_into()?, "refs/heads/b".try_into()?)?;
+        refs.write_symbolic("refs/heads/b".try_into()?, "refs/heads/a".try_into()?)?;
+        let err = fixture.repo.find_reference("refs/heads/a")?.peel_to_tag().unwrap_err();
+        assert!(
+            matches!(
+                err,
+                git::reference::peel_to_tag::Error::FollowSymbolic(git::refs::peel::to_id::Error::Cycle { .. })
+            ),
+            "{:?}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn packed_annotated_tags_can_be_peeled_either_way() -> crate::Result {
+        let repo = crate::repo("make_references_repo.sh")?.to_thread_local();
+        let mut reference = repo.find_reference("dt1")?;
+        let tag_id = reference.id();
+        assert_eq!(
+            reference.peel_to_tag()?.id,
+            tag_id,
+            "the reference points to the tag object"
+        );
+        assert_eq!(
+            reference.peel_to_commit()?.id,
+            git_testtools::hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03")
+        );
+        Ok(())
+    }
+}
+
 #[test]
 fn set_target_id() {
     let (repo, _tmp) = repo_rw("make_basic_repo.sh").unwrap();
@@ -0,0 +1,353 @@
+#[cfg(feature = "blocking-network-client")]
+mod blocking_io {
+    use std::sync::atomic::AtomicBool;
+
+    use git_features::progress;
+    use git_repository as git;
+    use git_repository::remote::fetch::{self, refs::update::Mode};
+    use git_testtools::scripted_fixture_repo_writable;
+
+    fn repo_with_origin(
+        tmp: &std::path::Path,
+        name: &str,
+        version: Option<git::protocol::transport::Protocol>,
+    ) -> crate::Result<git::Repository> {
+        let mut repo = git::open_opts(tmp.join(name), git::open::Options::isolated())?;
+        {
+            let mut config = repo.config_snapshot_mut();
+            // The clones refer to the location the fixture was created in, but it may have been copied since.
+            config.set_raw_value(
+                "remote",
+                Some("origin"),
+                "url",
+                tmp.join("base").to_str().expect("valid UTF-8"),
+            )?;
+            if let Some(version) = version {
+                config.set_raw_value("protocol", None, "version", (version as u8).to_string().as_str())?;
+            }
+        }
+        Ok(repo)
+    }
+
+    fn modes_by_local_name(
+        outcome: &fetch::Outcome<'_>,
+        update_refs: &fetch::refs::update::Outcome,
+    ) -> Vec<(String, Mode)> {
+        let mut out: Vec<_> = update_refs
+            .iter_mapping_updates(&outcome.ref_map.mappings)
+            .map(|(update, mapping, _edit)| {
+                (
+                    mapping
+                        .local
+                        .as_ref()
+                        .expect("all mappings have a local ref")
+                        .to_string(),
+                    update.mode,
+                )
+            })
+            .collect();
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out
+    }
+
+    fn id_of(repo: &git::Repository, name: &str) -> git::ObjectId {
+        repo.find_reference(name).expect("reference exists").id().detach()
+    }
+
+    #[test]
+    fn new_fast_forward_forced_and_unchanged_refs() -> crate::Result {
+        for version in [
+            None,
+            Some(git::protocol::transport::Protocol::V2),
+            Some(git::protocol::transport::Protocol::V1),
+        ] {
+            let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+            let repo = repo_with_origin(tmp.path(), "clone", version)?;
+            let packs_before = std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count();
+
+            let remote = repo.find_remote("origin")?;
+            let outcome = repo.fetch(&remote, progress::Discard, &AtomicBool::default())?;
+            let (write_pack_bundle, update_refs) = match &outcome.status {
+                fetch::Status::Change {
+                    write_pack_bundle,
+                    update_refs,
+                } => (write_pack_bundle, update_refs),
+                fetch::Status::NoPackReceived { .. } => unreachable!("objects are missing locally"),
+            };
+
+            assert_eq!(
+                std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count(),
+                packs_before + 2,
+                "a new pack and its index were written"
+            );
+            assert!(write_pack_bundle
+                .index_path
+                .as_ref()
+                .expect("written to disk")
+                .is_file());
+            assert!(write_pack_bundle.data_path.as_ref().expect("written to disk").is_file());
+            assert_eq!(
+                modes_by_local_name(&outcome, update_refs),
+                vec![
+                    ("refs/remotes/origin/fast-forward".into(), Mode::FastForward),
+                    ("refs/remotes/origin/forced".into(), Mode::Forced),
+                    ("refs/remotes/origin/main".into(), Mode::NoChangeNeeded),
+                    ("refs/remotes/origin/new".into(), Mode::New),
+                    ("refs/tags/moved-tag".into(), Mode::RejectedTagUpdate),
+                    ("refs/tags/new-tag".into(), Mode::New),
+                ]
+            );
+            assert_eq!(update_refs.edits.len(), 4, "rejected or unchanged refs aren't edited");
+
+            let base = git::open_opts(tmp.path().join("base"), git::open::Options::isolated())?;
+            for (local, remote) in [
+                ("refs/remotes/origin/fast-forward", "refs/heads/fast-forward"),
+                ("refs/remotes/origin/forced", "refs/heads/forced"),
+                ("refs/remotes/origin/main", "refs/heads/main"),
+                ("refs/remotes/origin/new", "refs/heads/new"),
+                ("refs/tags/new-tag", "refs/tags/new-tag"),
+            ] {
+                assert_eq!(
+                    id_of(&repo, local),
+                    id_of(&base, remote),
+                    "{} matches {}",
+                    local,
+                    remote
+                );
+            }
+            assert_ne!(
+                id_of(&repo, "refs/tags/moved-tag"),
+                id_of(&base, "refs/tags/moved-tag"),
+                "tags aren't moved without force"
+            );
+            let new_tag = repo.find_reference("refs/tags/new-tag")?.id().object()?;
+            assert_eq!(
+                new_tag.kind,
+                git::objs::Kind::Tag,
+                "annotated tags are fetched as tag objects"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn non_fast_forward_updates_are_rejected_without_force() -> crate::Result {
+        let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+        let repo = repo_with_origin(tmp.path(), "clone-without-force", None)?;
+        let forced_before = id_of(&repo, "refs/remotes/origin/forced");
+
+        let remote = repo.find_remote("origin")?;
+        let outcome = repo.fetch(&remote, progress::Discard, &AtomicBool::default())?;
+        let update_refs = match &outcome.status {
+            fetch::Status::Change { update_refs, .. } => update_refs,
+            fetch::Status::NoPackReceived { .. } => unreachable!("objects are missing locally"),
+        };
+        assert_eq!(
+            modes_by_local_name(&outcome, update_refs),
+            vec![
+                ("refs/remotes/origin/fast-forward".into(), Mode::FastForward),
+                ("refs/remotes/origin/forced".into(), Mode::RejectedNonFastForward),
+                ("refs/remotes/origin/main".into(), Mode::NoChangeNeeded),
+                ("refs/remotes/origin/new".into(), Mode::New),
+            ]
+        );
+        assert_eq!(id_of(&repo, "refs/remotes/origin/forced"), forced_before);
+        Ok(())
+    }
+
+    #[test]
+    fn up_to_date_repositories_receive_no_pack() -> crate::Result {
+        let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+        let repo = repo_with_origin(tmp.path(), "up-to-date", None)?;
+        let packs_before = std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count();
+
+        let remote = repo.find_remote("origin")?;
+        let outcome = repo.fetch(&remote, progress::Discard, &AtomicBool::default())?;
+        let update_refs = match &outcome.status {
+            fetch::Status::NoPackReceived { update_refs } => update_refs,
+            fetch::Status::Change { .. } => unreachable!("all objects are present"),
+        };
+        assert!(update_refs.edits.is_empty());
+        assert!(update_refs
+            .updates
+            .iter()
+            .all(|update| update.mode == Mode::NoChangeNeeded));
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count(),
+            packs_before
+        );
+        Ok(())
+    }
+
+    fn num_fetched_objects(repo: &git::Repository) -> crate::Result<u32> {
+        let remote = repo.find_remote("origin")?;
+        let outcome = repo.fetch(&remote, progress::Discard, &AtomicBool::default())?;
+        Ok(match outcome.status {
+            fetch::Status::Change { write_pack_bundle, .. } => write_pack_bundle.index.num_objects,
+            fetch::Status::NoPackReceived { .. } => unreachable!("objects are missing locally"),
+        })
+    }
+
+    #[test]
+    fn local_history_unknown_to_the_remote_is_negotiated_until_common_commits_are_found() -> crate::Result {
+        for version in [
+            git::protocol::transport::Protocol::V2,
+            git::protocol::transport::Protocol::V1,
+        ] {
+            let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+            let expected = num_fetched_objects(&repo_with_origin(tmp.path(), "clone", Some(version))?)?;
+
+            let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+            let repo = repo_with_origin(tmp.path(), "clone", Some(version))?;
+            let mut tip = repo.head_id()?.detach();
+            let tree = repo.find_object(tip)?.into_commit().tree_id()?.detach();
+            for idx in 0..(git::protocol::fetch::negotiate::INITIAL_BATCH_SIZE as u32 * 2) {
+                let signature = git::actor::Signature {
+                    name: "local".into(),
+                    email: "local@example.com".into(),
+                    time: git::date::Time::new(2_000_000_000 + idx * 60, 0),
+                };
+                tip = repo
+                    .commit(
+                        "refs/heads/local-only",
+                        signature.to_ref(),
+                        signature.to_ref(),
+                        format!("local {}", idx),
+                        tree,
+                        Some(tip),
+                    )?
+                    .detach();
+            }
+            assert_eq!(
+                num_fetched_objects(&repo)?,
+                expected,
+                "{:?}: the newest local commits aren't known to the remote, but the common ones are found eventually",
+                version
+            );
+        }
+        Ok(())
+    }
+
+    /// A transport which calls `before_fetch` right before the fetch request, which is the second request after `ls-refs`.
+    struct RunBeforeFetch<F> {
+        inner: Box<dyn git::protocol::transport::client::Transport + Send>,
+        requests: usize,
+        before_fetch: Option<F>,
+    }
+
+    impl<F: FnOnce()> git::protocol::transport::client::TransportWithoutIO for RunBeforeFetch<F> {
+        fn request(
+            &mut self,
+            write_mode: git::protocol::transport::client::WriteMode,
+            on_into_read: git::protocol::transport::client::MessageKind,
+        ) -> Result<git::protocol::transport::client::RequestWriter<'_>, git::protocol::transport::client::Error>
+        {
+            self.requests += 1;
+            if self.requests == 2 {
+                if let Some(f) = self.before_fetch.take() {
+                    f()
+                }
+            }
+            self.inner.request(write_mode, on_into_read)
+        }
+
+        fn to_url(&self) -> String {
+            self.inner.to_url()
+        }
+
+        fn connection_persists_across_multiple_requests(&self) -> bool {
+            self.inner.connection_persists_across_multiple_requests()
+        }
+    }
+
+    impl<F: FnOnce()> git::protocol::transport::client::Transport for RunBeforeFetch<F> {
+        fn handshake<'a>(
+            &mut self,
+            service: git::protocol::transport::Service,
+            extra_parameters: &'a [(&'a str, Option<&'a str>)],
+        ) -> Result<git::protocol::transport::client::SetServiceResponse<'_>, git::protocol::transport::client::Error>
+        {
+            self.inner.handshake(service, extra_parameters)
+        }
+    }
+
+    fn allow_ref_in_want(base: &std::path::Path) -> crate::Result {
+        let status = std::process::Command::new("git")
+            .args(["config", "uploadpack.allowRefInWant", "true"])
+            .current_dir(base)
+            .status()?;
+        assert!(status.success());
+        Ok(())
+    }
+
+    #[test]
+    fn refs_are_wanted_by_name_if_the_server_supports_it() -> crate::Result {
+        let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+        allow_ref_in_want(&tmp.path().join("base"))?;
+        let repo = repo_with_origin(tmp.path(), "clone", Some(git::protocol::transport::Protocol::V2))?;
+
+        let remote = repo.find_remote("origin")?;
+        let outcome = repo.fetch(&remote, progress::Discard, &AtomicBool::default())?;
+        let update_refs = match &outcome.status {
+            fetch::Status::Change { update_refs, .. } => update_refs,
+            fetch::Status::NoPackReceived { .. } => unreachable!("objects are missing locally"),
+        };
+        assert_eq!(
+            modes_by_local_name(&outcome, update_refs),
+            vec![
+                ("refs/remotes/origin/fast-forward".into(), Mode::FastForward),
+                ("refs/remotes/origin/forced".into(), Mode::Forced),
+                ("refs/remotes/origin/main".into(), Mode::NoChangeNeeded),
+                ("refs/remotes/origin/new".into(), Mode::New),
+                ("refs/tags/moved-tag".into(), Mode::RejectedTagUpdate),
+                ("refs/tags/new-tag".into(), Mode::New),
+            ]
+        );
+        let base = git::open_opts(tmp.path().join("base"), git::open::Options::isolated())?;
+        assert_eq!(id_of(&repo, "refs/remotes/origin/new"), id_of(&base, "refs/heads/new"));
+        assert_eq!(
+            repo.find_reference("refs/tags/new-tag")?.id().object()?.kind,
+            git::objs::Kind::Tag
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wanted_refs_sent_by_the_server_are_authoritative() -> crate::Result {
+        let tmp = scripted_fixture_repo_writable("make_fetch_repos.sh")?;
+        let base_dir = tmp.path().join("base");
+        allow_ref_in_want(&base_dir)?;
+        let repo = repo_with_origin(tmp.path(), "clone", Some(git::protocol::transport::Protocol::V2))?;
+
+        let transport = RunBeforeFetch {
+            inner: git::protocol::transport::connect(
+                git::url::parse(base_dir.join(".git").to_str().expect("valid UTF-8").into())?,
+                git::protocol::transport::Protocol::V2,
+            )?,
+            requests: 0,
+            before_fetch: Some(|| {
+                let status = std::process::Command::new("git")
+                    .args(["update-ref", "refs/heads/new", "refs/heads/main"])
+                    .current_dir(&base_dir)
+                    .status()
+                    .expect("git can be run");
+                assert!(status.success());
+            }),
+        };
+        let remote = repo.find_remote("origin")?;
+        let outcome = remote
+            .to_connection_with_transport(transport, progress::Discard)
+            .fetch(&AtomicBool::default())?;
+        assert!(matches!(outcome.status, fetch::Status::Change { .. }));
+
+        let base = git::open_opts(&base_dir, git::open::Options::isolated())?;
+        let new_id = id_of(&base, "refs/heads/new");
+        assert_eq!(new_id, id_of(&base, "refs/heads/main"), "the ref was moved");
+        assert_eq!(
+            id_of(&repo, "refs/remotes/origin/new"),
+            new_id,
+            "the id the server sent with the pack is used, not the one it advertised before"
+        );
+        Ok(())
+    }
+}
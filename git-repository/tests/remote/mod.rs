@@ -17,4 +17,5 @@ pub(crate) fn cow_str(s: &str) -> Cow<str> {
 }
 
 mod connect;
+mod fetch;
 mod list_refs;
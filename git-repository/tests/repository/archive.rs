@@ -0,0 +1,133 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use git_repository as git;
+use git_repository::archive::{Format, Options};
+
+fn repo() -> crate::Result<git::Repository> {
+    crate::named_repo("make_archive_repo.sh")
+}
+
+fn archive(repo: &git::Repository, spec: &str, options: Options) -> crate::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    repo.archive(repo.rev_parse_single(spec)?, &mut out, options)?;
+    Ok(out)
+}
+
+/// Return all paths below `root`, except for those in the `.git` directory, relative to `root` and sorted.
+fn paths_below(root: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<_> = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .map(|entry| {
+            entry
+                .expect("walk works")
+                .path()
+                .strip_prefix(root)
+                .expect("below root")
+                .to_owned()
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn tar_round_trips_to_the_checkout() -> crate::Result {
+    let repo = repo()?;
+    let data = archive(
+        &repo,
+        "HEAD",
+        Options {
+            prefix: "project/".into(),
+            ..Default::default()
+        },
+    )?;
+
+    let commit_time = repo.head_commit()?.time()?.seconds_since_unix_epoch as u64;
+    for entry in tar::Archive::new(data.as_slice()).entries()? {
+        let entry = entry?;
+        assert!(entry.path()?.starts_with("project"), "every entry has the prefix");
+        assert_eq!(entry.header().mtime()?, commit_time, "entries use the commit time");
+    }
+
+    let out = tempfile::tempdir()?;
+    tar::Archive::new(data.as_slice()).unpack(out.path())?;
+    let checkout = repo.work_dir().expect("non-bare");
+    let extracted = out.path().join("project");
+    assert_eq!(paths_below(&extracted), paths_below(checkout));
+
+    for path in paths_below(checkout) {
+        let (expected, actual) = (checkout.join(&path), extracted.join(&path));
+        let (expected_meta, actual_meta) = (
+            std::fs::symlink_metadata(&expected)?,
+            std::fs::symlink_metadata(&actual)?,
+        );
+        assert_eq!(actual_meta.file_type(), expected_meta.file_type(), "{:?}", path);
+        if expected_meta.file_type().is_symlink() {
+            assert_eq!(std::fs::read_link(&actual)?, std::fs::read_link(&expected)?);
+        } else if expected_meta.is_file() {
+            assert_eq!(std::fs::read(&actual)?, std::fs::read(&expected)?, "{:?}", path);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let is_executable = |meta: &std::fs::Metadata| meta.permissions().mode() & 0o100 != 0;
+                assert_eq!(is_executable(&actual_meta), is_executable(&expected_meta), "{:?}", path);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn entries_have_the_modes_of_git_archive() -> crate::Result {
+    let repo = repo()?;
+    let data = archive(&repo, "HEAD", Options::default())?;
+    let mut modes: Vec<_> = tar::Archive::new(data.as_slice())
+        .entries()?
+        .map(|entry| {
+            let entry = entry.expect("valid entry");
+            (
+                entry.path().expect("valid path").to_string_lossy().into_owned(),
+                entry.header().entry_type(),
+                entry.header().mode().expect("valid mode"),
+            )
+        })
+        .collect();
+    modes.sort_by(|a, b| a.0.cmp(&b.0));
+    use tar::EntryType::*;
+    assert_eq!(
+        modes,
+        vec![
+            ("a".into(), Regular, 0o644),
+            ("dir".into(), Directory, 0o755),
+            ("dir/b".into(), Regular, 0o644),
+            ("dir/exe".into(), Regular, 0o755),
+            ("dir/sub".into(), Directory, 0o755),
+            ("dir/sub/c".into(), Regular, 0o644),
+            ("link".into(), Symlink, 0o777),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn tar_gz_is_a_compressed_tar_and_tags_are_peeled() -> crate::Result {
+    let repo = repo()?;
+    let tar = archive(&repo, "HEAD", Options::default())?;
+    let tar_gz = archive(
+        &repo,
+        "v1",
+        Options {
+            format: Format::TarGz,
+            ..Default::default()
+        },
+    )?;
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(tar_gz.as_slice()).read_to_end(&mut decompressed)?;
+    assert_eq!(decompressed, tar, "the annotated tag is peeled to the same commit");
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use git_repository as git;
+use git_repository::bstr::ByteSlice;
+
+fn repo() -> crate::Result<(git::Repository, std::path::PathBuf)> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_blame_repo.sh")?;
+    Ok((git::open_opts(&dir, crate::restricted())?, dir))
+}
+
+/// Parse the output of `git blame --porcelain` into the commit and original line number of each line, in order.
+fn porcelain_baseline(path: &std::path::Path) -> crate::Result<Vec<(git::ObjectId, usize)>> {
+    let mut out = Vec::new();
+    for line in std::fs::read(path)?.lines() {
+        let mut tokens = line.split_str(" ");
+        let id = match tokens.next().and_then(|id| git::ObjectId::from_hex(id).ok()) {
+            Some(id) if !line.starts_with(b"\t") => id,
+            _ => continue,
+        };
+        let original_line: usize = tokens.next().expect("original line").to_str()?.parse()?;
+        out.push((id, original_line - 1));
+    }
+    Ok(out)
+}
+
+fn lines_of(entries: &[git::blame::Entry]) -> Vec<(git::ObjectId, usize)> {
+    entries
+        .iter()
+        .flat_map(|entry| {
+            assert_eq!(entry.lines.len(), entry.original_lines.len());
+            entry.original_lines.clone().map(move |line| (entry.commit_id, line))
+        })
+        .collect()
+}
+
+#[test]
+fn lines_are_attributed_like_git_blame_does() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let head = repo.head_id()?;
+    for (options, baseline) in [
+        (git::blame::Options::default(), "blame.baseline"),
+        (
+            git::blame::Options {
+                first_parent: true,
+                ..Default::default()
+            },
+            "blame-first-parent.baseline",
+        ),
+        (
+            git::blame::Options {
+                ignore_whitespace: true,
+                ..Default::default()
+            },
+            "blame-ignore-whitespace.baseline",
+        ),
+    ] {
+        let entries = repo.blame("renamed", head, options)?;
+        assert_eq!(
+            lines_of(&entries),
+            porcelain_baseline(&dir.join(baseline))?,
+            "{}",
+            baseline
+        );
+        assert_eq!(
+            entries.last().expect("non-empty").lines.end,
+            8,
+            "all lines of the file are covered"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn entries_know_their_author_and_follow_renames() -> crate::Result {
+    let (repo, _dir) = repo()?;
+    let entries = repo.blame("renamed", repo.head_id()?, Default::default())?;
+    let authors = entries
+        .iter()
+        .map(|e| (e.author.name.to_string(), e.lines.clone(), e.original_path.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        authors,
+        [
+            ("erin".into(), 0..1, "renamed".into()),
+            ("bob".into(), 1..2, "file".into()),
+            ("dave".into(), 2..3, "renamed".into()),
+            ("alice".into(), 3..4, "file".into()),
+            ("grace".into(), 4..6, "renamed".into()),
+            ("erin".into(), 6..7, "renamed".into()),
+            ("bob".into(), 7..8, "file".into()),
+        ],
+        "lines from before the rename are attributed to the commits that changed the original file"
+    );
+    Ok(())
+}
+
+#[test]
+fn missing_files_are_an_error() -> crate::Result {
+    let (repo, _dir) = repo()?;
+    assert!(matches!(
+        repo.blame("file", repo.head_id()?, Default::default()),
+        Err(git::blame::Error::FileNotFound { .. })
+    ));
+    Ok(())
+}
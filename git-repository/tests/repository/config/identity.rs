@@ -124,3 +124,63 @@ fn author_and_committer_and_fallback() {
         }
     }
 }
+
+#[test]
+fn missing_identity_lists_what_was_searched() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    git::init(dir.path())?;
+    let repo = git::open_opts(dir.path(), git::open::Options::isolated())?;
+
+    assert_eq!(repo.committer(), None, "isolated repositories don't see any identity");
+    assert_eq!(
+        repo.committer_or_err().unwrap_err().to_string(),
+        "No committer identity is configured, searched committer.name, user.name, committer.email, user.email"
+    );
+    assert_eq!(
+        repo.author_or_err().unwrap_err().to_string(),
+        "No author identity is configured, searched author.name, user.name, author.email, user.email"
+    );
+    Ok(())
+}
+
+#[test]
+fn times_can_be_pinned_in_configuration() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    git::init(dir.path())?;
+    let mut repo: git::Repository = git::open_opts(dir.path(), git::open::Options::isolated())?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("user", None, "name", "user")?;
+        config.set_raw_value("user", None, "email", "user@email")?;
+        config.set_raw_value("gitoxide", Some("commit"), "committerDate", "1660874655 -0230")?;
+        config.set_raw_value(
+            "gitoxide",
+            Some("commit"),
+            "authorDate",
+            "Thu, 18 Aug 2022 12:45:06 +0800",
+        )?;
+    }
+
+    let committer = repo.committer_or_err()?;
+    assert_eq!(committer.name, "user");
+    assert_eq!(committer.email, "user@email");
+    assert_eq!(
+        committer.time,
+        git_date::Time {
+            seconds_since_unix_epoch: 1660874655,
+            offset_in_seconds: -9000,
+            sign: git_date::time::Sign::Minus
+        },
+        "raw git dates are supported"
+    );
+    assert_eq!(
+        repo.author_or_err()?.time,
+        git_date::Time {
+            seconds_since_unix_epoch: 1660797906,
+            offset_in_seconds: 28800,
+            sign: git_date::time::Sign::Plus
+        },
+        "RFC-2822 dates are supported as well"
+    );
+    Ok(())
+}
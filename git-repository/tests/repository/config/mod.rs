@@ -1,3 +1,140 @@
 mod config_snapshot;
 mod identity;
 mod remote;
+
+#[test]
+#[serial_test::serial]
+fn global_configuration_is_read_from_xdg_config_home() -> crate::Result {
+    use git_repository as git;
+    let repo = crate::named_repo("make_config_repo.sh")?;
+    let xdg_config_home = tempfile::tempdir()?;
+    let home = tempfile::tempdir()?;
+    std::fs::create_dir_all(xdg_config_home.path().join("git"))?;
+    std::fs::write(
+        xdg_config_home.path().join("git").join("config"),
+        "[a]\n\txdg = from-xdg",
+    )?;
+    std::fs::create_dir_all(home.path().join(".config").join("git"))?;
+    std::fs::write(
+        home.path().join(".config").join("git").join("config"),
+        "[a]\n\txdg = from-home",
+    )?;
+
+    let open = |xdg_config_home: &str| -> crate::Result<git::Repository> {
+        let _env = git_testtools::Env::new()
+            .set("GIT_CONFIG_NOSYSTEM", "1")
+            .set("XDG_CONFIG_HOME", xdg_config_home)
+            .set("HOME", home.path().display().to_string());
+        assert_eq!(
+            git::path::xdg_config_home().as_deref(),
+            Some(
+                if xdg_config_home.is_empty() {
+                    home.path().join(".config")
+                } else {
+                    xdg_config_home.into()
+                }
+                .join("git")
+                .as_path()
+            ),
+            "an empty XDG_CONFIG_HOME is the same as an unset one"
+        );
+        Ok(git::open_opts(
+            repo.git_dir(),
+            repo.open_options().clone().permissions(git::Permissions {
+                config: git::permissions::Config {
+                    git: true,
+                    ..git::Permissions::isolated().config
+                },
+                env: git::permissions::Environment::all(),
+            }),
+        )?)
+    };
+    let repo_with_xdg = open(&xdg_config_home.path().display().to_string())?;
+    assert_eq!(
+        repo_with_xdg
+            .config_snapshot()
+            .string("a.xdg")
+            .expect("present")
+            .as_ref(),
+        "from-xdg"
+    );
+
+    let repo_without_xdg = open("")?;
+    assert_eq!(
+        repo_without_xdg
+            .config_snapshot()
+            .string("a.xdg")
+            .expect("present")
+            .as_ref(),
+        "from-home",
+        "the fallback is $HOME/.config/git"
+    );
+    Ok(())
+}
+
+#[test]
+fn fs_capabilities_are_overridden_by_configuration() -> crate::Result {
+    let mut repo = crate::named_repo("make_config_repo.sh")?;
+    let dir = tempfile::tempdir()?;
+    let probed = git_repository::worktree::fs::Capabilities::probe(dir.path());
+    assert_eq!(
+        repo.fs_capability_overrides().symlink,
+        None,
+        "git only writes `core.symlinks` if they are not supported"
+    );
+
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("core", None, "symlinks", "false")?;
+        config.set_raw_value("core", None, "fileMode", "false")?;
+    }
+    let overrides = repo.fs_capability_overrides();
+    assert_eq!(overrides.symlink, Some(false));
+    assert_eq!(overrides.executable_bit, Some(false));
+
+    let actual = repo.fs_capabilities(dir.path());
+    assert!(!actual.symlink, "configuration takes precedence over probed values");
+    assert!(!actual.executable_bit);
+    assert_eq!(actual.ignore_case, overrides.ignore_case.unwrap_or(probed.ignore_case));
+    Ok(())
+}
+
+#[test]
+fn checkout_options_are_derived_from_configuration() -> crate::Result {
+    let mut repo = crate::named_repo("make_config_repo.sh")?;
+    let dir = tempfile::tempdir()?;
+    let attributes_file = dir.path().join("attributes");
+    std::fs::write(&attributes_file, b"*.txt text\n*.bin -text\n")?;
+
+    let opts = repo.checkout_options(dir.path())?;
+    assert_eq!(opts.thread_limit, None, "all cores are used by default");
+    assert_eq!(opts.thread_threshold, 100, "the same default as git");
+
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("checkout", None, "workers", "-1")?;
+        config.set_raw_value("checkout", None, "thresholdForParallelism", "5")?;
+        config.set_raw_value("core", None, "symlinks", "false")?;
+        config.set_raw_value("core", None, "trustCTime", "false")?;
+        config.set_raw_value("core", None, "checkStat", "minimal")?;
+        config.set_raw_value(
+            "core",
+            None,
+            "attributesFile",
+            attributes_file.to_str().expect("valid UTF-8"),
+        )?;
+    }
+    let opts = repo.checkout_options(dir.path())?;
+    assert_eq!(opts.thread_limit, Some(0), "values below 1 mean one thread per core");
+    assert_eq!(opts.thread_threshold, 5);
+    assert!(!opts.fs.symlink);
+    assert!(!opts.trust_ctime);
+    assert!(!opts.check_stat);
+    assert_eq!(opts.attribute_globals.patterns.len(), 1);
+    assert_eq!(opts.attribute_globals.patterns[0].patterns.len(), 2);
+
+    repo.config_snapshot_mut()
+        .set_raw_value("checkout", None, "workers", "3")?;
+    assert_eq!(repo.checkout_options(dir.path())?.thread_limit, Some(3));
+    Ok(())
+}
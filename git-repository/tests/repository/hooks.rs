@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use git_repository as git;
+
+use crate::util::repo_rw;
+
+fn shell_available() -> bool {
+    std::process::Command::new("sh")
+        .args(["-c", "true"])
+        .status()
+        .map_or(false, |status| status.success())
+}
+
+fn write_hook(dir: &Path, name: &str, script: &str) -> crate::Result {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{}", script))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_hooks_are_not_run() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let hooks = repo.hooks();
+    assert_eq!(hooks.dir()?, repo.git_dir().join("hooks"));
+    assert!(!hooks.exists("pre-commit"), "only samples exist after `git init`");
+    assert!(hooks.run("pre-commit", None::<&str>, None)?.is_none());
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_which_are_not_executable_are_ignored() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let dir = repo.hooks().dir()?;
+    write_hook(&dir, "pre-commit", "exit 1\n")?;
+    assert!(repo.hooks().exists("pre-commit"));
+
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(dir.join("pre-commit"), std::fs::Permissions::from_mode(0o644))?;
+    assert!(!repo.hooks().exists("pre-commit"));
+    assert!(repo.hooks().run("pre-commit", None::<&str>, None)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn run_with_arguments_input_and_environment() -> crate::Result {
+    if !shell_available() {
+        return Ok(());
+    }
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    write_hook(
+        &repo.hooks().dir()?,
+        "pre-push",
+        "echo \"$1 $2\"\ncat\npwd\necho \"$GIT_DIR\"\necho failure >&2\nexit 3\n",
+    )?;
+
+    let outcome = repo
+        .hooks()
+        .run("pre-push", ["origin", "url"], Some(b"refs/heads/main\n"))?
+        .expect("hook exists");
+    assert!(!outcome.success());
+    assert_eq!(outcome.status.code(), Some(3));
+    assert_eq!(outcome.stderr, b"failure\n");
+
+    let stdout = String::from_utf8(outcome.stdout)?;
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines[..2],
+        ["origin url", "refs/heads/main"],
+        "arguments and input are passed"
+    );
+    let work_dir = repo.work_dir().expect("non-bare");
+    assert_eq!(
+        Path::new(lines[2]).canonicalize()?,
+        work_dir.canonicalize()?,
+        "hooks run in the worktree"
+    );
+    assert_eq!(Path::new(lines[3]).canonicalize()?, repo.git_dir().canonicalize()?);
+    Ok(())
+}
+
+#[test]
+fn core_hooks_path_is_relative_to_the_worktree() -> crate::Result {
+    if !shell_available() {
+        return Ok(());
+    }
+    let (mut repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    repo.config_snapshot_mut()
+        .set_raw_value("core", None, "hooksPath", "custom-hooks")?;
+    let dir = repo.hooks().dir()?;
+    assert_eq!(dir, repo.work_dir().expect("non-bare").join("custom-hooks"));
+
+    write_hook(&dir, "pre-commit", "echo custom\n")?;
+    let outcome = repo.hooks().run("pre-commit", None::<&str>, None)?.expect("exists");
+    assert!(outcome.success());
+    assert_eq!(outcome.stdout, b"custom\n");
+    Ok(())
+}
+
+#[test]
+fn switching_branches_can_run_the_post_checkout_hook() -> crate::Result {
+    if !shell_available() {
+        return Ok(());
+    }
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    write_hook(&repo.hooks().dir()?, "post-checkout", "echo \"$1 $2 $3\"\n")?;
+    let previous = repo.head_id()?.detach();
+
+    let outcome = repo.switch_branch("other".into(), git::switch::Options::default())?;
+    assert!(outcome.post_checkout_hook.is_none(), "hooks are opt-in");
+
+    let outcome = repo.switch_branch(
+        "main".into(),
+        git::switch::Options {
+            run_hooks: true,
+            ..Default::default()
+        },
+    )?;
+    let hook = outcome.post_checkout_hook.expect("hook was run")?;
+    assert!(hook.success());
+    assert_eq!(
+        hook.stdout,
+        format!("{} {} 1\n", outcome.previous_commit.expect("born"), previous).as_bytes()
+    );
+
+    #[cfg(unix)]
+    {
+        let hook = repo.hooks().dir()?.join("post-checkout");
+        std::fs::write(&hook, "#!/does/not/exist\n")?;
+        let outcome = repo.switch_branch(
+            "other".into(),
+            git::switch::Options {
+                run_hooks: true,
+                ..Default::default()
+            },
+        )?;
+        assert!(
+            matches!(
+                outcome.post_checkout_hook,
+                Some(Err(git::hooks::run::Error::Spawn { ref path, .. })) if path.ends_with("post-checkout")
+            ),
+            "hooks that can't be run don't fail the switch, which already happened"
+        );
+        assert_eq!(repo.head_name()?.expect("on a branch").as_bstr(), "refs/heads/other");
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+mod is_inside_work_tree {
+    use git_repository as git;
+
+    #[test]
+    fn bare_repositories_have_no_work_tree() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = git::init_bare(tmp.path())?;
+        assert!(!repo.is_inside_work_tree(tmp.path())?);
+        assert!(!repo.is_inside_work_tree(tmp.path().join("objects"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn paths_within_the_work_tree_but_not_in_the_git_dir() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = git::init(tmp.path())?;
+        let work_dir = repo.work_dir().expect("non-bare");
+
+        assert!(
+            repo.is_inside_work_tree(work_dir)?,
+            "the work tree root itself is inside of the work tree"
+        );
+        assert!(
+            repo.is_inside_work_tree(format!("{}/", work_dir.display()))?,
+            "trailing slashes don't matter"
+        );
+        assert!(
+            repo.is_inside_work_tree(work_dir.join("dir/file"))?,
+            "paths don't have to exist"
+        );
+        assert!(
+            repo.is_inside_work_tree(work_dir.join("dir/../file"))?,
+            "relative components are resolved"
+        );
+        assert!(!repo.is_inside_work_tree(repo.git_dir())?, "the git dir is excluded");
+        assert!(!repo.is_inside_work_tree(repo.git_dir().join("refs/heads"))?);
+        assert!(
+            repo.is_inside_work_tree(work_dir.join(".gitignore"))?,
+            "only the git dir is excluded, not paths that start like it"
+        );
+        assert!(!repo.is_inside_work_tree(work_dir.join(".."))?);
+        assert!(
+            !repo.is_inside_work_tree(format!("{}-sibling", work_dir.display()))?,
+            "paths are compared by component"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlinks_are_resolved_on_both_sides() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo_dir = tmp.path().join("repo");
+        std::fs::create_dir(&repo_dir)?;
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&repo_dir, &link)?;
+
+        let repo = git::init(&link)?;
+        assert!(repo.is_inside_work_tree(repo_dir.join("file"))?);
+        assert!(repo.is_inside_work_tree(link.join("file"))?);
+        assert!(!repo.is_inside_work_tree(repo_dir.join(".git/HEAD"))?);
+        assert!(!repo.is_inside_work_tree(tmp.path())?);
+        Ok(())
+    }
+}
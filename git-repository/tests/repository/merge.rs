@@ -0,0 +1,42 @@
+use git_repository as git;
+
+fn repo(name: &str) -> crate::Result<(git::Repository, std::path::PathBuf)> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_fetch_head_repos.sh")?.join(name);
+    Ok((git::open_opts(&dir, crate::restricted())?, dir))
+}
+
+#[test]
+fn message_from_fetch_head_matches_git_fmt_merge_msg() -> crate::Result {
+    for name in [
+        "two-branches",
+        "with-log",
+        "local-tags-and-remote-tracking-branch",
+        "multiple-tags",
+        "commit-into-feature",
+        "detached",
+        "suppressed-destination",
+    ] {
+        let (repo, dir) = repo(name)?;
+        let expected = std::fs::read(dir.join("baseline"))?;
+        let actual = repo
+            .merge_message_from_fetch_head()?
+            .expect("there is something to merge");
+        assert_eq!(actual, expected, "{}", name);
+    }
+    Ok(())
+}
+
+#[test]
+fn message_from_fetch_head_is_none_if_everything_is_merged_already() -> crate::Result {
+    let (repo, dir) = repo("already-merged")?;
+    assert!(std::fs::read(dir.join("baseline"))?.is_empty());
+    assert_eq!(repo.merge_message_from_fetch_head()?, None);
+    Ok(())
+}
+
+#[test]
+fn message_from_fetch_head_is_none_without_fetch_head() -> crate::Result {
+    let (repo, _dir) = repo("base")?;
+    assert_eq!(repo.merge_message_from_fetch_head()?, None);
+    Ok(())
+}
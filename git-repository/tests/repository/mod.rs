@@ -1,16 +1,25 @@
 use git_repository::Repository;
 
+mod archive;
+mod blame;
 mod config;
+mod hooks;
+mod location;
+mod merge;
 mod object;
 mod open;
 mod reference;
 mod remote;
+mod shallow;
 mod state;
+mod status;
+mod submodule;
+mod switch;
 mod worktree;
 
 #[test]
 fn size_in_memory() {
-    let expected = [760, 800];
+    let expected = [792, 832];
     let actual_size = std::mem::size_of::<Repository>();
     assert!(
         expected.contains(&actual_size),
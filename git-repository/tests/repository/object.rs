@@ -56,6 +56,46 @@ mod write_blob {
     }
 }
 
+mod write_existing {
+    use std::path::PathBuf;
+
+    use crate::repository::object::empty_bare_repo;
+
+    fn loose_path(repo: &git_repository::Repository, id: git_repository::Id<'_>) -> PathBuf {
+        let hex = id.to_hex().to_string();
+        repo.objects.store_ref().path().join(&hex[..2]).join(&hex[2..])
+    }
+
+    #[test]
+    fn objects_are_not_written_again() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        let blob = repo.write_blob(b"hello world")?;
+        let tree_with_blob = || git_repository::objs::Tree {
+            entries: vec![git_repository::objs::tree::Entry {
+                mode: git_repository::objs::tree::EntryMode::Blob,
+                filename: "file".into(),
+                oid: blob.detach(),
+            }],
+        };
+        let tree = repo.write_object(tree_with_blob())?;
+        for id in [blob, tree] {
+            std::fs::write(loose_path(&repo, id), b"marker")?;
+        }
+
+        assert_eq!(repo.write_blob(b"hello world")?, blob);
+        assert_eq!(repo.write_blob_stream(std::io::Cursor::new(b"hello world"))?, blob);
+        assert_eq!(repo.write_object(tree_with_blob())?, tree);
+        for id in [blob, tree] {
+            assert_eq!(
+                std::fs::read(loose_path(&repo, id))?,
+                b"marker",
+                "existing objects are detected by their id and left untouched"
+            );
+        }
+        Ok(())
+    }
+}
+
 mod find {
     use git_pack::Find;
     use git_repository as git;
@@ -93,6 +133,94 @@ mod find {
         Ok(())
     }
 
+    #[test]
+    fn objects_of_any_kind_can_be_decoded_attached_or_detached() -> crate::Result {
+        let repo = basic_repo()?;
+        let commit = repo.head_commit()?;
+        let tree = repo.find_object(commit.tree_id()?)?;
+        let blob_id = match tree.decode()? {
+            git_object::ObjectRef::Tree(tree) => tree.entries[0].oid.to_owned(),
+            other => unreachable!("{:?} must be a tree", other.kind()),
+        };
+        assert!(
+            matches!(repo.find_object(commit.id)?.decode()?, git_object::ObjectRef::Commit(c) if c.message == "c2\n")
+        );
+
+        let blob = repo.find_object(blob_id)?.detach();
+        assert!(
+            matches!(blob.decode()?, git_object::ObjectRef::Blob(b) if b.data == b"hello\n"),
+            "detached objects can be decoded as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn many_objects_can_be_held_at_once_and_missing_ones_are_none() -> crate::Result {
+        let repo = basic_repo()?;
+        let commit_id = repo.head_id()?;
+        let commit = repo.find_object(commit_id)?;
+        let tree = repo.find_object(commit.to_commit_ref().tree())?;
+        let commit_again = repo.try_find_object(commit_id)?.expect("present");
+
+        assert_eq!(commit.kind, git_object::Kind::Commit);
+        assert_eq!(tree.kind, git_object::Kind::Tree);
+        assert_eq!(commit.data, commit_again.data, "each object has its own data");
+        assert!(!tree.data.is_empty());
+
+        let missing = git::hash::ObjectId::null(repo.object_hash());
+        assert!(repo.try_find_object(missing)?.is_none());
+        assert!(repo.find_object(missing).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "max-performance-safe")]
+    fn find_with_and_without_pack_cache() -> crate::Result {
+        let mut repo = basic_repo()?;
+        assert!(repo.objects.has_pack_cache(), "a pack cache is set by default");
+
+        for round in 1..=3 {
+            match round {
+                1 => repo.pack_cache_size(None),
+                2 => repo.pack_cache_size(0),
+                3 => repo.pack_cache_size(1024 * 1024),
+                _ => unreachable!("BUG"),
+            }
+            assert_eq!(repo.objects.has_pack_cache(), round == 3);
+            for commit_id in repo.head()?.peeled()?.id().expect("born").ancestors().all()? {
+                assert_eq!(commit_id?.object()?.kind, git_object::Kind::Commit);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "max-performance-safe")]
+    fn pack_cache_is_configured_by_core_delta_base_cache_limit() -> crate::Result {
+        let (repo, tmp) = crate::util::basic_rw_repo()?;
+        let git_dir = repo.git_dir().to_owned();
+        drop(repo);
+
+        for (value, has_pack_cache) in [("0", false), ("1m", true)] {
+            assert!(git_testtools::run_git(tmp.path(), &["config", "core.deltaBaseCacheLimit", value])?.success());
+            let repo = git::open(&git_dir)?;
+            assert_eq!(repo.objects.has_pack_cache(), has_pack_cache, "{}", value);
+        }
+
+        assert!(git_testtools::run_git(tmp.path(), &["config", "core.deltaBaseCacheLimit", "-1"])?.success());
+        assert!(
+            git::open(&git_dir)?.objects.has_pack_cache(),
+            "invalid values fall back to the default with lenient configuration"
+        );
+        let err = git::open_opts(&git_dir, git::open::Options::isolated().strict_config(true))
+            .expect_err("negative values are rejected with strict configuration");
+        assert!(
+            matches!(err, git::open::Error::Config(git::config::Error::DecodeInteger { ref key, .. }) if key == "core.deltaBaseCacheLimit"),
+            "the error names the whole key"
+        );
+        Ok(())
+    }
+
     #[test]
     fn empty_tree_can_always_be_found() -> crate::Result {
         let repo = basic_repo()?;
@@ -116,7 +244,252 @@ mod find {
     }
 }
 
+mod buffer {
+    use crate::basic_repo;
+
+    #[test]
+    fn buffers_are_exclusive_while_in_use_and_reused_once_dropped() -> crate::Result {
+        let repo = basic_repo()?;
+        let commit = repo.head_commit()?;
+
+        let mut buf = repo.buffer();
+        buf.extend_from_slice(&commit.data);
+        let tree = commit.tree()?;
+        let mut other = repo.buffer();
+        other.extend_from_slice(&tree.data);
+        assert_ne!(buf.as_ptr(), other.as_ptr(), "nested buffers don't alias");
+        assert_eq!(
+            &buf[..],
+            &commit.data[..],
+            "loading the tree doesn't affect buffers in use"
+        );
+
+        let ptr = buf.as_ptr();
+        drop(buf);
+        let buf = repo.buffer();
+        assert!(buf.is_empty(), "buffers are always handed out empty");
+        assert_eq!(buf.as_ptr(), ptr, "the most recently returned buffer is reused");
+        Ok(())
+    }
+}
+
+mod exists {
+    use git_repository as git;
+
+    use crate::basic_repo;
+
+    #[test]
+    fn single_and_batched() -> crate::Result {
+        let repo = basic_repo()?;
+        let head_id = repo.head_id()?;
+        let tree_id = head_id.object()?.into_commit().tree_id()?;
+        let missing = git::hash::ObjectId::null(repo.object_hash());
+        let empty_tree = git::hash::ObjectId::empty_tree(repo.object_hash());
+
+        assert!(repo.object_exists(head_id));
+        assert!(repo.object_exists(tree_id));
+        assert!(!repo.object_exists(missing));
+        assert!(
+            repo.object_exists(empty_tree),
+            "the empty tree always exists, just like it can always be found"
+        );
+
+        assert_eq!(
+            repo.objects_exist([head_id.detach(), missing, tree_id.detach(), missing]),
+            [true, false, true, false],
+            "one flag per id, in order"
+        );
+        assert!(repo.objects_exist(None::<git::ObjectId>).is_empty());
+        Ok(())
+    }
+}
+
+mod find_by_prefix {
+    use git_repository as git;
+
+    use crate::repository::object::empty_bare_repo;
+
+    fn prefix(hex: &str) -> git::hash::Prefix {
+        git::hash::Prefix::from_hex(hex).expect("valid prefix")
+    }
+
+    #[test]
+    fn single_and_full_length_matches() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        let id = repo.write_blob(b"hello world")?.detach();
+
+        let mut buf = Vec::new();
+        for hex in ["95d0", "95d09f2b10", "95d09f2b10159347eece71399a7e2e907ea3df4f"] {
+            assert_eq!(
+                repo.find_object_by_prefix(&prefix(hex), &mut buf)?,
+                Some((id, git_object::Kind::Blob))
+            );
+            assert_eq!(buf, b"hello world", "the object data is decoded into the buffer");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn no_match() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        repo.write_blob(b"hello world")?;
+        assert_eq!(repo.find_object_by_prefix(&prefix("95d1"), &mut Vec::new())?, None);
+        assert_eq!(
+            repo.find_object_by_prefix(&prefix("4b825dc642cb6eb9a060e54bf8d69288fbee4904"), &mut Vec::new())?,
+            None,
+            "the empty tree doesn't exist in the object database and isn't special-cased here"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ambiguous_matches_list_all_candidates() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        // Both start with "dead", see https://github.com/git/git/blob/8168d5e9c23ed44ae3d604f392320d66556453c9/t/t1512-rev-parse-disambiguation.sh#L38
+        let a = repo.write_blob(b"brocdnra\n")?.detach();
+        let b = repo.write_blob(b"brigddsv\n")?.detach();
+
+        match repo.find_object_by_prefix(&prefix("dead"), &mut Vec::new()) {
+            Err(git::object::find::by_prefix::Error::Ambiguous { prefix, candidates }) => {
+                assert_eq!(prefix.to_string(), "dead");
+                let mut expected = vec![a, b];
+                expected.sort();
+                assert_eq!(candidates, expected, "candidates are sorted");
+            }
+            res => unreachable!("expected ambiguity error, got {:?}", res),
+        }
+
+        let mut buf = Vec::new();
+        assert_eq!(
+            repo.find_object_by_prefix(&prefix(&a.to_hex_with_len(10).to_string()), &mut buf)?,
+            Some((a, git_object::Kind::Blob)),
+            "a longer prefix disambiguates"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefixes_shorter_than_the_minimum_are_rejected() {
+        assert!(git::hash::Prefix::from_hex("dea").is_err());
+    }
+}
+
+mod shorten_id_and_disambiguate_prefix {
+    use std::process::Command;
+
+    use git_repository as git;
+
+    use crate::repository::object::empty_bare_repo;
+
+    fn git(repo: &git::Repository, args: &[&str], stdin: Option<&str>) -> crate::Result<String> {
+        let mut child = Command::new("git")
+            .args(args)
+            .current_dir(repo.git_dir())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+        if let Some(stdin) = stdin {
+            use std::io::Write;
+            child.stdin.take().expect("piped").write_all(stdin.as_bytes())?;
+        }
+        let out = child.wait_with_output()?;
+        assert!(out.status.success(), "{:?} failed", args);
+        Ok(String::from_utf8(out.stdout)?.trim().to_owned())
+    }
+
+    #[test]
+    fn a_prefix_shared_by_a_loose_and_a_packed_object() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        // Both start with "51d2738", which is as long as the default abbreviation.
+        let packed = repo.write_blob(b"4827\n")?.detach();
+        git(
+            &repo,
+            &["pack-objects", "-q", "objects/pack/pack"],
+            Some(&format!("{}\n", packed)),
+        )?;
+        git(&repo, &["prune-packed"], None)?;
+        let loose = repo.write_blob(b"11742\n")?.detach();
+        assert!(
+            !repo
+                .git_dir()
+                .join("objects")
+                .join(&packed.to_string()[..2])
+                .join(&packed.to_string()[2..])
+                .exists(),
+            "one object is only packed"
+        );
+
+        match repo.disambiguate_prefix("51d2738") {
+            Err(git::object::find::disambiguate_prefix::Error::Find(
+                git::object::find::by_prefix::Error::Ambiguous { candidates, .. },
+            )) => {
+                let mut expected = vec![packed, loose];
+                expected.sort();
+                assert_eq!(candidates, expected, "objects are found in packs and loose alike");
+            }
+            res => unreachable!("expected ambiguity error, got {:?}", res),
+        }
+
+        for id in [packed, loose] {
+            let short = repo.shorten_id(id)?;
+            assert_eq!(
+                short,
+                git(&repo, &["rev-parse", "--short", &id.to_string()], None)?,
+                "the length is extended until it is unambiguous, just like git does"
+            );
+            assert!(short.len() > 7, "the default length of 7 would be ambiguous");
+            assert_eq!(repo.disambiguate_prefix(&short)?, Some(id));
+            assert_eq!(
+                repo.disambiguate_prefix(&short[..short.len() - 1])
+                    .unwrap_err()
+                    .to_string(),
+                format!(
+                    "Short id {} is ambiguous as 2 objects match it",
+                    &short[..short.len() - 1]
+                ),
+                "one character less is ambiguous"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unambiguous_ids_have_the_default_length() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        let id = repo.write_blob(b"hello world")?.detach();
+        assert_eq!(repo.shorten_id(id)?, "95d09f2");
+        assert_eq!(repo.disambiguate_prefix("95d0")?, Some(id));
+        assert_eq!(repo.disambiguate_prefix(&id.to_string())?, Some(id));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_objects_and_invalid_prefixes() -> crate::Result {
+        let (_tmp, repo) = empty_bare_repo()?;
+        let id = repo.write_blob(b"hello world")?.detach();
+        let mut missing = id;
+        missing.as_mut_slice()[19] ^= 1;
+        assert!(
+            repo.shorten_id(missing).is_err(),
+            "ids of objects that don't exist can't be shortened, even if they share a prefix with an existing one"
+        );
+        assert_eq!(repo.disambiguate_prefix("95d1")?, None);
+        assert!(matches!(
+            repo.disambiguate_prefix("95d"),
+            Err(git::object::find::disambiguate_prefix::Error::Prefix(_))
+        ));
+        assert!(matches!(
+            repo.disambiguate_prefix("95dx"),
+            Err(git::object::find::disambiguate_prefix::Error::Prefix(_))
+        ));
+        Ok(())
+    }
+}
+
 mod tag {
+    use git_ref::transaction::PreviousValue;
+    use git_repository as git;
+
     #[test]
     fn simple() -> crate::Result {
         let (repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
@@ -128,7 +501,7 @@ mod tag {
             git_object::Kind::Commit,
             Some(repo.committer_or_default()),
             message,
-            git_ref::transaction::PreviousValue::MustNotExist,
+            PreviousValue::MustNotExist,
         )?;
         assert_eq!(tag_ref.name().as_bstr(), "refs/tags/v1.0.0");
         assert_ne!(tag_ref.id(), current_head_id, "it points to the tag object");
@@ -144,6 +517,77 @@ mod tag {
         assert_eq!(tag.message, message);
         Ok(())
     }
+
+    #[test]
+    fn existing_tags_are_only_overwritten_if_forced() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+        let empty_tree = git::ObjectId::empty_tree(repo.object_hash());
+        let tag_ref = repo.tag_lightweight("light", head_id, false)?;
+        assert_eq!(tag_ref.name().as_bstr(), "refs/tags/light");
+        assert_eq!(tag_ref.id(), head_id, "lightweight tags point to their target directly");
+
+        assert!(matches!(
+            repo.tag_lightweight("light", empty_tree, false),
+            Err(git::tag::Error::AlreadyExists { name }) if name.as_bstr() == "refs/tags/light"
+        ));
+        assert_eq!(repo.find_reference("light")?.id(), head_id, "nothing was changed");
+        assert_eq!(repo.tag_lightweight("light", empty_tree, true)?.id(), empty_tree);
+        assert_eq!(repo.find_reference("light")?.id(), empty_tree);
+
+        let err = repo
+            .tag(
+                "light",
+                head_id,
+                git_object::Kind::Commit,
+                None,
+                "annotated",
+                PreviousValue::MustNotExist,
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "The tag 'refs/tags/light' already exists");
+        Ok(())
+    }
+
+    #[test]
+    fn signed_tags_can_be_written_from_tag_objects() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+        let tag = git_object::Tag {
+            target: head_id,
+            target_kind: git_object::Kind::Commit,
+            name: "signed".into(),
+            tagger: Some(repo.committer_or_default().to_owned()),
+            message: "message\n".into(),
+            pgp_signature: Some("-----BEGIN PGP SIGNATURE-----\nsignature\n-----END PGP SIGNATURE-----\n".into()),
+        };
+        let tag_ref = repo.tag_object(&tag, PreviousValue::MustNotExist)?;
+        assert_eq!(tag_ref.name().as_bstr(), "refs/tags/signed");
+
+        let object = tag_ref.id().object()?;
+        let mut buf = Vec::new();
+        git_object::WriteTo::write_to(&tag, &mut buf)?;
+        assert_eq!(
+            tag_ref.id(),
+            git::odb::Write::write_buf(&git::odb::sink(repo.object_hash()), git_object::Kind::Tag, &buf)?,
+            "the id is computed from the serialized tag"
+        );
+        assert_eq!(git_object::Tag::from(object.try_to_tag_ref()?), tag);
+        assert_eq!(
+            repo.tag_object(&tag, PreviousValue::MustNotExist)?.id(),
+            tag_ref.id(),
+            "writing the same tag again is fine"
+        );
+        let different_tag = git_object::Tag {
+            message: "different message\n".into(),
+            ..tag
+        };
+        assert!(matches!(
+            repo.tag_object(&different_tag, PreviousValue::MustNotExist),
+            Err(git::tag::Error::AlreadyExists { .. })
+        ));
+        Ok(())
+    }
 }
 
 mod commit {
@@ -291,10 +735,152 @@ fn empty_bare_repo() -> crate::Result<(tempfile::TempDir, git::Repository)> {
         tmp.path(),
         git::create::Options {
             bare: true,
-            fs_capabilities: None,
+            ..Default::default()
         },
         git::open::Options::isolated(),
     )?
     .into();
     Ok((tmp, repo))
 }
+
+mod sha256 {
+    use git_repository as git;
+
+    #[test]
+    fn packed_and_loose_objects_can_be_read_and_written() -> crate::Result {
+        let repo = crate::named_repo("make_sha256_repo.sh")?;
+        assert_eq!(
+            repo.object_hash(),
+            git::hash::Kind::Sha256,
+            "derived from extensions.objectFormat"
+        );
+
+        let baseline = std::fs::read_to_string(repo.git_dir().join("rev-parse.baseline"))?;
+        let expected = baseline
+            .lines()
+            .map(|hex| git::hash::ObjectId::from_hex(hex.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let head = repo.head_commit()?;
+        assert_eq!(head.id, expected[0], "the head commit is a loose object");
+        assert_eq!(head.tree_id()?, expected[1]);
+        let parent_ids: Vec<_> = head.parent_ids().map(|id| id.detach()).collect();
+        assert_eq!(parent_ids, vec![expected[2]]);
+
+        let parent = repo.find_object(expected[2])?.try_into_commit()?;
+        assert_eq!(
+            parent.message()?.summary().as_ref(),
+            "packed commit",
+            "the parent lives in a pack"
+        );
+        let entry = parent
+            .tree()?
+            .lookup_entry_by_path("packed")?
+            .expect("trees with sha256 entries can be decoded");
+        assert_eq!(entry.oid.kind(), git::hash::Kind::Sha256);
+
+        let (repo, _tmp) = crate::repo_rw("make_sha256_repo.sh")?;
+        let id = repo.write_blob(b"hello world")?;
+        assert_eq!(
+            id,
+            git::hash::ObjectId::from_hex(b"fee53a18d32820613c0527aa79be5cb30173c823a9b448fa4817767cc84c6f03")?
+        );
+        assert_eq!(repo.find_object(id)?.data, b"hello world");
+        Ok(())
+    }
+
+    #[test]
+    fn trees_are_decoded_with_sha256_entries_by_the_object_database_and_tree_diffs() -> crate::Result {
+        use git::prelude::FindExt;
+
+        let repo = crate::named_repo("make_sha256_repo.sh")?;
+        let head = repo.head_commit()?;
+        let tree_id = head.tree_id()?.detach();
+        let parent_tree_id = repo
+            .find_object(head.parent_ids().next().expect("one parent"))?
+            .try_into_commit()?
+            .tree_id()?
+            .detach();
+
+        let mut buf = Vec::new();
+        let entries = repo
+            .objects
+            .find_tree_iter(tree_id, &mut buf)?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            entries.iter().map(|e| e.filename.to_string()).collect::<Vec<_>>(),
+            ["loose", "packed"]
+        );
+        assert!(entries.iter().all(|e| e.oid.kind() == git::hash::Kind::Sha256));
+        let blob_id = entries[0].oid.to_owned();
+        assert_eq!(entries[1].oid, blob_id, "both files have the same content");
+
+        let tree = repo.objects.find_tree(tree_id, &mut buf)?;
+        assert_eq!(tree.entries.len(), 2);
+        assert!(tree.entries.iter().all(|e| e.oid.kind() == git::hash::Kind::Sha256));
+
+        let object = repo.find_object(tree_id)?;
+        let tree = object.decode()?.into_tree().expect("a tree");
+        assert_eq!(tree.entries[1].filename, "packed");
+        assert_eq!(tree.entries[1].oid, blob_id);
+
+        let mut buf2 = Vec::new();
+        let mut recorder = git::diff::tree::Recorder::default();
+        git::diff::tree::Changes::from(repo.objects.find_tree_iter(parent_tree_id, &mut buf)?).needed_to_obtain(
+            repo.objects.find_tree_iter(tree_id, &mut buf2)?,
+            git::diff::tree::State::default(),
+            |oid, buf| repo.objects.find_tree_iter(oid, buf),
+            &mut recorder,
+        )?;
+        assert_eq!(
+            recorder.records,
+            vec![git::diff::tree::recorder::Change::Addition {
+                entry_mode: git::objs::tree::EntryMode::Blob,
+                oid: blob_id,
+                path: "loose".into(),
+            }]
+        );
+        Ok(())
+    }
+}
+
+mod refresh_object_database {
+    use git_repository as git;
+
+    fn repack(repo: &git::Repository) -> crate::Result {
+        assert!(git_testtools::run_git(repo.git_dir(), &["repack", "-a", "-d", "-q"])?.success());
+        Ok(())
+    }
+
+    #[test]
+    fn packs_written_by_other_processes_are_found_on_miss_or_after_refreshing() -> crate::Result {
+        let mut fixture = git::fixture::Builder::bare()?;
+        let c1 = fixture.commit("main", "c1")?;
+        let (repo, _tmp) = fixture.finish()?;
+        assert!(repo.object_exists(c1), "the commit is loose");
+
+        let mut no_auto_refresh = repo.clone();
+        no_auto_refresh.objects.refresh_never();
+        repack(&repo)?;
+        assert!(
+            !no_auto_refresh.object_exists(c1),
+            "the loose object is gone and the new pack isn't known yet"
+        );
+        no_auto_refresh.refresh_object_database()?;
+        assert!(no_auto_refresh.object_exists(c1), "an explicit refresh finds the pack");
+
+        let tree = repo.find_object(c1)?.into_commit().tree_id()?;
+        let committer = repo.committer_or_default();
+        let c2 = repo.commit("refs/heads/main", committer, committer, "c2", tree, Some(c1))?;
+        repack(&repo)?;
+        assert!(
+            !no_auto_refresh.object_exists(c2),
+            "the previous pack was replaced by one that isn't known yet"
+        );
+        assert!(
+            repo.object_exists(c1) && repo.object_exists(c2),
+            "by default, a miss triggers a refresh once before giving up"
+        );
+        Ok(())
+    }
+}
@@ -44,3 +44,152 @@ mod submodules {
         .to_thread_local())
     }
 }
+
+mod environment_overrides {
+    use git_repository as git;
+
+    #[test]
+    #[serial_test::serial]
+    fn git_dir_work_tree_and_common_dir_take_precedence() -> crate::Result {
+        let repo_dir = git_testtools::scripted_fixture_repo_read_only("make_basic_repo.sh")?;
+        let tmp = tempfile::tempdir()?;
+        std::fs::create_dir(tmp.path().join("common.git"))?;
+        let common_repo = git::init_bare(tmp.path().join("common.git"))?;
+        let work_tree = tmp.path().join("worktree");
+        std::fs::create_dir(&work_tree)?;
+
+        let _env = git_testtools::Env::new()
+            .set("GIT_DIR", repo_dir.join(".git").display().to_string())
+            .set("GIT_WORK_TREE", work_tree.display().to_string())
+            .set("GIT_COMMON_DIR", common_repo.git_dir().display().to_string());
+        let repo = git::ThreadSafeRepository::open_with_environment_overrides(
+            tmp.path(),
+            git_sec::trust::Mapping {
+                full: crate::restricted(),
+                reduced: crate::restricted(),
+            },
+        )?
+        .to_thread_local();
+
+        assert_eq!(
+            repo.git_dir(),
+            repo_dir.join(".git"),
+            "GIT_DIR overrides the fallback directory"
+        );
+        assert_eq!(
+            repo.work_dir(),
+            Some(work_tree.as_path()),
+            "GIT_WORK_TREE is used as is"
+        );
+        assert_eq!(
+            repo.common_dir(),
+            common_repo.git_dir(),
+            "GIT_COMMON_DIR overrides the one implied by the git directory"
+        );
+        assert!(
+            repo.find_reference("refs/heads/main").is_err(),
+            "references are read from the common directory, which has none"
+        );
+        Ok(())
+    }
+}
+
+mod worktree_config {
+    use git_repository as git;
+
+    #[test]
+    fn is_layered_on_top_of_the_shared_configuration_only_if_enabled() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_worktree_config_repo.sh")?;
+        for name in ["with-extension", "with-extension-wt", "without-extension-wt"] {
+            let baseline = std::fs::read_to_string(dir.join(format!("{}.baseline", name)))?;
+            let mut lines = baseline.lines();
+            let mut expected = || lines.next().expect("one line per value");
+            let repo = git::open_opts(dir.join(name), crate::restricted())?;
+
+            assert_eq!(repo.is_bare(), expected() == "true", "{}: core.bare", name);
+            let config = repo.config_snapshot();
+            assert_eq!(
+                config
+                    .boolean("core.sparseCheckout")
+                    .map_or("unset", |v| if v { "true" } else { "false" }),
+                expected(),
+                "{}: core.sparseCheckout",
+                name
+            );
+            assert_eq!(
+                config.string("wt.value").expect("always set").to_string(),
+                expected(),
+                "{}: wt.value",
+                name
+            );
+            assert_eq!(
+                repo.work_dir(),
+                Some(dir.join(name).as_path()),
+                "{}: core.worktree of the main worktree doesn't apply to linked worktrees",
+                name
+            );
+        }
+        Ok(())
+    }
+}
+
+mod repository_format {
+    use git_repository as git;
+
+    #[test]
+    fn unknown_versions_and_extensions_are_refused_like_git_does() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_repository_format_repo.sh")?;
+        for name in [
+            "v0",
+            "v0-unknown-extension",
+            "v0-worktree-config",
+            "v0-v1-only-extension",
+            "v1",
+            "v1-known-extensions",
+            "v1-unknown-extension",
+            "v2",
+        ] {
+            let expected = std::fs::read_to_string(dir.join(format!("{}.baseline", name)))?;
+            let res = git::open_opts(dir.join(name), crate::restricted());
+            match expected.trim() {
+                "ok" => assert!(res.is_ok(), "{}: {:?}", name, res.err()),
+                "refused" => {
+                    assert!(
+                        matches!(res, Err(git::open::Error::Config(_))),
+                        "{}: expected a configuration error",
+                        name
+                    );
+                    assert!(
+                        git::open_opts(dir.join(name), crate::restricted().ignore_extension_check(true)).is_ok(),
+                        "{}: the check can be disabled",
+                        name
+                    );
+                }
+                other => unreachable!("unexpected baseline value: {}", other),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn errors_name_the_offending_extension() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_repository_format_repo.sh")?;
+        for (name, expected) in [
+            (
+                "v1-unknown-extension",
+                "Unknown repository extension 'unknownExtension'",
+            ),
+            (
+                "v0-v1-only-extension",
+                "Repository extension 'objectFormat' requires repository format version 1, but version 0 was found",
+            ),
+            ("v2", "Expected git repository format version 0 or 1, found 2"),
+        ] {
+            match git::open_opts(dir.join(name), crate::restricted()) {
+                Err(git::open::Error::Config(err)) => assert_eq!(err.to_string(), expected),
+                res => unreachable!("{}: expected a configuration error, got {:?}", name, res.map(|_| ())),
+            }
+        }
+        Ok(())
+    }
+}
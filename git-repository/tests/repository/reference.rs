@@ -80,6 +80,63 @@ mod set_namespace {
     }
 }
 
+mod with_namespace {
+    use git_repository as git;
+    use git_repository::refs::transaction::PreviousValue;
+
+    #[test]
+    fn lookups_are_transparently_prefixed_and_namespaces_nest() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_references_repo.sh")?;
+        let id = git::ObjectId::empty_tree(git::hash::Kind::Sha1);
+        repo.reference(
+            "refs/namespaces/foo/refs/heads/main",
+            id,
+            PreviousValue::MustNotExist,
+            "in namespace",
+        )?;
+        repo.reference(
+            "refs/namespaces/foo/refs/namespaces/bar/refs/heads/main",
+            id,
+            PreviousValue::MustNotExist,
+            "in nested namespace",
+        )?;
+
+        let foo = repo.clone().with_namespace("foo")?;
+        assert_eq!(foo.namespace().expect("set").as_bstr(), "refs/namespaces/foo/");
+        let main = foo.find_reference("refs/heads/main")?;
+        assert_eq!(main.name().as_bstr(), "refs/heads/main", "the namespace is stripped");
+        assert_eq!(
+            main.id(),
+            id,
+            "it's the namespaced reference, not the one of the same name"
+        );
+        assert!(repo.namespace().is_none(), "the original instance is unaffected");
+
+        let nested = foo.with_namespace("bar")?;
+        assert_eq!(
+            nested.namespace().expect("set").as_bstr(),
+            "refs/namespaces/foo/refs/namespaces/bar/",
+            "namespaces compose"
+        );
+        assert_eq!(nested.find_reference("refs/heads/main")?.id(), id);
+        assert_eq!(
+            nested.references()?.all()?.count(),
+            1,
+            "only references within the nested namespace are visible"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_namespaces_are_rejected() -> crate::Result {
+        let repo = crate::repo("make_references_repo.sh")?.to_thread_local();
+        assert!(repo.clone().with_namespace("foo/../bar").is_err());
+        assert!(repo.clone().with_namespace("/foo").is_err());
+        assert!(repo.with_namespace("").is_err());
+        Ok(())
+    }
+}
+
 mod iter_references {
     use git_repository as git;
     use git_testtools::hex_to_id;
@@ -224,3 +281,98 @@ mod head {
         Ok(())
     }
 }
+
+mod remove_reference_log {
+    #[test]
+    fn the_log_is_removed_but_the_reference_and_its_category_directory_remain() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let heads_logs = repo.git_dir().join("logs").join("refs").join("heads");
+        assert!(heads_logs.join("main").is_file());
+
+        assert!(
+            repo.remove_reference_log("refs/heads/main")?,
+            "there was a log to remove"
+        );
+        assert!(!heads_logs.join("main").exists());
+        assert!(heads_logs.is_dir(), "like git, category directories are kept");
+        assert!(repo.find_reference("main").is_ok(), "the reference itself is untouched");
+
+        assert!(
+            !repo.remove_reference_log("refs/heads/main")?,
+            "it's not an error if there is no log"
+        );
+        Ok(())
+    }
+}
+
+mod create_branch {
+    use git_repository as git;
+
+    fn last_log_message(reference: &git::Reference<'_>) -> crate::Result<git::bstr::BString> {
+        let mut log = reference.log_iter();
+        let mut lines = log.rev()?.expect("log present");
+        Ok(lines.next().expect("at least one line")?.message.to_owned())
+    }
+
+    #[test]
+    fn new_branches_are_created_with_a_log_and_listed_by_their_short_name() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let head_id = repo.head_id()?.detach();
+        let branch = repo.create_branch("topic/new", head_id, false)?;
+        assert_eq!(branch.name().as_bstr(), "refs/heads/topic/new");
+        assert_eq!(branch.id(), head_id);
+        assert_eq!(
+            last_log_message(&branch)?,
+            format!("branch: Created from {}", head_id).as_str()
+        );
+
+        let branches: Vec<_> = repo
+            .references()?
+            .local_branches()?
+            .map(|r| r.expect("valid ref").name().shorten().to_owned())
+            .collect();
+        assert!(branches.iter().any(|name| name == "topic/new"));
+        assert!(branches.iter().any(|name| name == "main"));
+        Ok(())
+    }
+
+    #[test]
+    fn existing_branches_are_only_reset_with_force() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let head = repo.head_commit()?;
+        let parent_id = head.parent_ids().next().expect("has parent").detach();
+        repo.create_branch("other", head.id, false)?;
+
+        let err = repo.create_branch("other", parent_id, false).unwrap_err();
+        assert!(
+            matches!(&err, git::reference::create_branch::Error::AlreadyExists { name } if name.as_bstr() == "refs/heads/other"),
+            "{:?}",
+            err
+        );
+        assert_eq!(repo.find_reference("other")?.id(), head.id, "nothing changed");
+
+        let branch = repo.create_branch("other", parent_id, true)?;
+        assert_eq!(repo.find_reference("other")?.id(), parent_id);
+        assert_eq!(
+            last_log_message(&branch)?,
+            format!("branch: Reset to {}", parent_id).as_str()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_checked_out_branch_cannot_be_reset() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let head = repo.head_commit()?;
+        let parent_id = head.parent_ids().next().expect("has parent").detach();
+
+        let err = repo.create_branch("main", parent_id, true).unwrap_err();
+        assert!(
+            matches!(&err, git::reference::create_branch::Error::CheckedOut { name } if name.as_bstr() == "refs/heads/main"),
+            "{:?}",
+            err
+        );
+        assert_eq!(repo.find_reference("main")?.id(), head.id, "nothing changed");
+        Ok(())
+    }
+}
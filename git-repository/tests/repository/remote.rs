@@ -16,7 +16,8 @@ mod remote_at {
         let mut remote = remote.push_url("user@host.xz:./relative")?;
         assert_eq!(
             remote.url(Direction::Push).unwrap().to_bstring(),
-            "ssh://user@host.xz/relative"
+            "user@host.xz:relative",
+            "scp-like urls remain scp-like"
         );
         assert_eq!(remote.url(Direction::Fetch).unwrap().to_bstring(), fetch_url);
 
@@ -160,10 +161,12 @@ mod find_remote {
         assert_eq!(
             remote.refspecs(Direction::Fetch),
             &[
-                fetchspec("HEAD"),
                 fetchspec("+refs/heads/*:refs/remotes/origin/*"),
-                fetchspec("refs/tags/*:refs/tags/*")
-            ]
+                fetchspec("@"),
+                fetchspec("refs/tags/*:refs/tags/*"),
+                fetchspec("HEAD"),
+            ],
+            "all specs are retained in order of occurrence, even if they are equivalent"
         )
     }
 
@@ -247,6 +250,45 @@ mod find_remote {
         Ok(())
     }
 
+    #[test]
+    fn urls_and_refspecs_match_git() -> crate::Result {
+        // `url-rewriting` isn't included as we apply `pushInsteadOf` to explicit push urls as well, which git doesn't do.
+        for name in ["clone", "push-url", "many-fetchspecs"] {
+            let repo = remote::repo(name);
+            let git = |args: &[&str]| -> crate::Result<BString> {
+                let out = std::process::Command::new("git")
+                    .args(args)
+                    .current_dir(remote::repo_path(name))
+                    .output()?;
+                assert!(out.status.success(), "{:?} failed in {}", args, name);
+                Ok(out.stdout.into())
+            };
+
+            let mut remote_v = BString::default();
+            for remote_name in repo.remote_names() {
+                let remote = repo.find_remote(remote_name)?;
+                for (direction, label) in [(Direction::Fetch, "fetch"), (Direction::Push, "push")] {
+                    let url = remote.url(direction).expect("url is set").to_bstring();
+                    remote_v.extend_from_slice(format!("{}\t{} ({})\n", remote_name, url, label).as_bytes());
+                }
+
+                let specs_in_config = git(&["config", "--get-all", &format!("remote.{}.fetch", remote_name)])?;
+                assert_eq!(
+                    remote.refspecs(Direction::Fetch),
+                    specs_in_config
+                        .lines()
+                        .map(|spec| fetchspec(&spec.expect("valid UTF-8")))
+                        .collect::<Vec<_>>(),
+                    "all fetch refspecs are retained in order for {} in {}",
+                    remote_name,
+                    name
+                );
+            }
+            assert_eq!(git(&["remote", "-v"])?, remote_v, "`git remote -v` parity in {}", name);
+        }
+        Ok(())
+    }
+
     fn fetchspec(spec: &str) -> git_refspec::RefSpec {
         git::refspec::parse(spec.into(), git::refspec::parse::Operation::Fetch)
             .unwrap()
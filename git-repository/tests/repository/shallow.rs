@@ -0,0 +1,98 @@
+use git_repository as git;
+
+fn repo(name: &str) -> crate::Result<git::Repository> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_shallow_repo.sh")?;
+    Ok(git::open_opts(dir.join(name), crate::restricted())?)
+}
+
+#[test]
+fn non_shallow_repositories_have_no_shallow_commits() -> crate::Result {
+    let repo = repo("full")?;
+    assert!(!repo.is_shallow());
+    assert!(repo.shallow_commits()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn shallow_commits_are_the_tips_of_a_depth_1_clone() -> crate::Result {
+    let repo = repo("shallow")?;
+    assert!(repo.is_shallow());
+    let mut expected = vec![
+        repo.find_reference("origin/main")?.id().detach(),
+        repo.find_reference("origin/other")?.id().detach(),
+    ];
+    expected.sort();
+    assert_eq!(
+        repo.shallow_commits()?.expect("present").as_slice(),
+        expected,
+        "it's sorted"
+    );
+    Ok(())
+}
+
+#[test]
+fn traversals_stop_at_shallow_boundaries_without_error() -> crate::Result {
+    let repo = repo("shallow")?;
+    let tips = [
+        repo.find_reference("origin/main")?.id().detach(),
+        repo.find_reference("origin/other")?.id().detach(),
+    ];
+    for sorting in [
+        git::traverse::commit::Sorting::BreadthFirst,
+        git::traverse::commit::Sorting::Topological,
+        git::traverse::commit::Sorting::ByCommitTimeNewestFirst,
+    ] {
+        let mut walk = repo.rev_walk(tips).sorting(sorting).all()?.error_on_missing_commit();
+        let mut actual = walk
+            .by_ref()
+            .map(|id| id.map(|id| id.detach()))
+            .collect::<Result<Vec<_>, _>>()?;
+        actual.sort();
+        let mut expected = tips.to_vec();
+        expected.sort();
+        assert_eq!(actual, expected, "only the shallow commits are reachable");
+        assert_eq!(walk.is_shallow, Some(false), "no commit was missing");
+    }
+    Ok(())
+}
+
+#[test]
+fn shallow_commits_are_updated_when_the_file_changes() -> crate::Result {
+    let tmp = git_testtools::scripted_fixture_repo_writable("make_shallow_repo.sh")?;
+    let repo = git::open_opts(tmp.path().join("shallow"), crate::restricted())?;
+    assert_eq!(repo.shallow_commits()?.expect("present").len(), 2);
+
+    std::fs::remove_file(repo.shallow_file())?;
+    assert!(!repo.is_shallow());
+    assert!(
+        repo.shallow_commits()?.is_none(),
+        "the cached value is discarded once the file is gone"
+    );
+    Ok(())
+}
+
+#[test]
+fn shallow_file_can_be_edited_and_written() -> crate::Result {
+    let tmp = git_testtools::scripted_fixture_repo_writable("make_shallow_repo.sh")?;
+    let repo = git::open_opts(tmp.path().join("shallow"), crate::restricted())?;
+    let commits = repo.shallow_commits()?.expect("present");
+    let mut file = git::shallow::File::from(commits.to_vec());
+    assert!(commits.iter().all(|id| file.contains(id)));
+
+    let other = git::hash::ObjectId::empty_tree(repo.object_hash());
+    assert!(!file.contains(other));
+    assert!(file.add(other), "new commits are added");
+    assert!(!file.add(other), "existing ones are ignored");
+    assert!(file.contains(other));
+
+    file.write_to(repo.shallow_file())?;
+    assert_eq!(
+        git::shallow::File::from_bytes(&std::fs::read(repo.shallow_file())?)?,
+        file,
+        "it round-trips"
+    );
+
+    git::shallow::File::default().write_to(repo.shallow_file())?;
+    assert!(!repo.is_shallow(), "writing an empty file removes it");
+    Ok(())
+}
@@ -27,6 +27,9 @@ fn cherry_pick() -> Result {
 
     assert_eq!(repo.head_name()?.unwrap().shorten(), "main");
     assert_eq!(repo.state(), Some(git::state::InProgress::CherryPick));
+    assert_eq!(repo.cherry_pick_head()?, Some(tip_of(&repo, "other-branch")?));
+    assert_eq!(repo.merge_heads()?, None);
+    assert_eq!(repo.revert_head()?, None);
     Ok(())
 }
 
@@ -46,6 +49,8 @@ fn merge() -> Result {
 
     assert_eq!(repo.head_name()?.unwrap().shorten(), "main");
     assert_eq!(repo.state(), Some(git::state::InProgress::Merge));
+    assert_eq!(repo.merge_heads()?, Some(vec![tip_of(&repo, "other-branch")?]));
+    assert_eq!(repo.cherry_pick_head()?, None);
 
     Ok(())
 }
@@ -66,6 +71,21 @@ fn revert() -> Result {
 
     assert_eq!(repo.head_name()?.unwrap().shorten(), "main");
     assert_eq!(repo.state(), Some(git::state::InProgress::Revert));
+    assert_eq!(
+        repo.revert_head()?,
+        Some(
+            repo.head()?
+                .peeled()?
+                .id()
+                .expect("born")
+                .ancestors()
+                .all()?
+                .nth(1)
+                .expect("parent")?
+                .detach()
+        )
+    );
+    assert_eq!(repo.merge_heads()?, None);
 
     Ok(())
 }
@@ -79,3 +99,76 @@ fn revert_sequence() -> Result {
 
     Ok(())
 }
+
+fn tip_of(repo: &git::Repository, branch: &str) -> Result<git::ObjectId> {
+    Ok(repo.find_reference(branch)?.peel_to_id_in_place()?.detach())
+}
+
+mod write {
+    use git_repository as git;
+
+    use crate::util::repo_rw;
+
+    #[test]
+    fn orig_head_is_updated_in_a_transaction() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+        let other = super::tip_of(&repo, "other-branch")?;
+
+        assert_eq!(
+            repo.set_orig_head(other, "")?,
+            Some(head_id),
+            "the merge left ORIG_HEAD, whose previous value is returned"
+        );
+        assert_eq!(repo.find_reference("ORIG_HEAD")?.id(), other);
+        assert!(
+            !repo.refs.reflog_exists("ORIG_HEAD")?,
+            "no reflog is created for pseudo-refs by default"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn orig_head_can_not_be_set_while_it_is_locked() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+        let _lock = git::lock::Marker::acquire_to_hold_resource(
+            repo.path().join("ORIG_HEAD"),
+            git::lock::acquire::Fail::Immediately,
+            None,
+        )?;
+
+        match repo.set_orig_head(super::tip_of(&repo, "other-branch")?, "") {
+            Err(git::reference::edit::Error::FileTransactionPrepare(
+                git::refs::file::transaction::prepare::Error::LockAcquire {
+                    source: git::lock::acquire::Error::PermanentlyLocked { .. },
+                    full_name,
+                },
+            )) => assert_eq!(full_name, "ORIG_HEAD"),
+            res => unreachable!("unexpected result: {:?}", res),
+        }
+        assert_eq!(
+            repo.find_reference("ORIG_HEAD")?.id(),
+            head_id,
+            "the value written by the merge is unchanged"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge_and_cherry_pick_heads_mark_operations_as_in_progress() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_merge_repo.sh")?;
+        std::fs::remove_file(repo.path().join("MERGE_HEAD"))?;
+        assert_eq!(repo.state(), None);
+        let other = super::tip_of(&repo, "other-branch")?;
+
+        repo.set_merge_head(other)?;
+        assert_eq!(repo.merge_heads()?, Some(vec![other]));
+        assert_eq!(repo.state(), Some(git::state::InProgress::Merge));
+
+        repo.set_cherry_pick_head(other)?;
+        assert_eq!(repo.cherry_pick_head()?, Some(other));
+        assert_eq!(repo.state(), Some(git::state::InProgress::CherryPick));
+        Ok(())
+    }
+}
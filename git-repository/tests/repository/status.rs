@@ -0,0 +1,132 @@
+use git_repository as git;
+use git_worktree::status::Change;
+
+fn porcelain(repo: &git::Repository) -> crate::Result<Vec<String>> {
+    let index = repo.index()?;
+    Ok(repo
+        .status(&index)?
+        .map(|item| {
+            let item = item?;
+            let code = match item.status {
+                git::status::Status::Change(Change::Removed) => " D",
+                git::status::Status::Change(Change::Type) => " T",
+                git::status::Status::Change(Change::Modification { .. }) => " M",
+                git::status::Status::Conflict => "UU",
+                git::status::Status::Untracked => "??",
+            };
+            Ok(format!("{} {}", code, item.rela_path))
+        })
+        .collect::<Result<Vec<_>, git::status::Error>>()?)
+}
+
+#[test]
+fn changes_and_untracked_files_match_git() -> crate::Result {
+    let repo = crate::named_repo("make_status_repo.sh")?;
+    let expected: Vec<_> = std::fs::read_to_string(repo.git_dir().join("status.baseline"))?
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect();
+    assert_eq!(
+        porcelain(&repo)?,
+        expected,
+        "tracked paths come first in index order, followed by untracked ones"
+    );
+    Ok(())
+}
+
+#[test]
+fn modifications_tell_content_changes_from_executable_bit_changes() -> crate::Result {
+    let repo = crate::named_repo("make_status_repo.sh")?;
+    let index = repo.index()?;
+    let changes: Vec<_> = repo
+        .status(&index)?
+        .filter_map(|item| {
+            let item = item.expect("no io error");
+            match item.status {
+                git::status::Status::Change(change @ Change::Modification { .. }) => Some((item.rela_path, change)),
+                _ => None,
+            }
+        })
+        .collect();
+    let modification = |executable_bit_changed, content_changed| Change::Modification {
+        executable_bit_changed,
+        content_changed,
+    };
+    assert_eq!(
+        changes,
+        vec![
+            ("executable".into(), modification(true, false)),
+            ("modified".into(), modification(false, true)),
+            ("modified-same-size".into(), modification(false, true)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn executable_bits_are_ignored_if_core_filemode_is_false() -> crate::Result {
+    let mut repo = crate::named_repo("make_status_repo.sh")?;
+    repo.config_snapshot_mut()
+        .set_raw_value("core", None, "fileMode", "false")?;
+    assert!(!porcelain(&repo)?.contains(&" M executable".to_string()));
+    Ok(())
+}
+
+#[test]
+fn stat_comparisons_are_configurable() -> crate::Result {
+    let mut repo = crate::named_repo("make_status_repo.sh")?;
+    assert!(repo.check_stat(), "all stat fields are compared by default");
+    assert!(repo.trust_ctime());
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("core", None, "checkStat", "minimal")?;
+        config.set_raw_value("core", None, "trustCTime", "false")?;
+    }
+    assert!(!repo.check_stat());
+    assert!(!repo.trust_ctime());
+
+    let expected: Vec<_> = std::fs::read_to_string(repo.git_dir().join("status.baseline"))?
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect();
+    assert_eq!(
+        porcelain(&repo)?,
+        expected,
+        "fewer stat fields don't change the outcome"
+    );
+    Ok(())
+}
+
+#[test]
+fn nested_repositories_are_reported_as_single_untracked_directory_unless_disabled() -> crate::Result {
+    let repo = crate::named_repo("make_status_repo.sh")?;
+    let expected = [
+        "?? nested/",
+        "?? nested-with-files/",
+        "?? nested-with-git-file/",
+        "?? nested-with-ignored-files/",
+    ];
+    let untracked = porcelain(&repo)?;
+    for expected in expected {
+        assert!(untracked.iter().any(|line| line == expected), "{} is missing", expected);
+    }
+
+    let index = repo.index()?;
+    let untracked: Vec<_> = repo
+        .status(&index)?
+        .detect_nested_repositories(false)
+        .map(|item| item.map(|item| item.rela_path))
+        .collect::<Result<_, _>>()?;
+    assert!(
+        untracked.iter().any(|path| path == "nested-with-files/"),
+        "directories with untracked files are still reported"
+    );
+    for path in ["nested/", "nested-with-git-file/", "nested-with-ignored-files/"] {
+        assert!(
+            !untracked.iter().any(|untracked| untracked == path),
+            "{} contains nothing but its .git entry and ignored files, and isn't considered a repository",
+            path
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use git_repository as git;
+use git_repository::submodule::{State, Submodule};
+
+fn repo(name: &str) -> crate::Result<git::Repository> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_submodule_list_repo.sh")?;
+    Ok(git::open_opts(dir.join(name), crate::restricted())?)
+}
+
+fn module1_head() -> crate::Result<git::ObjectId> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_submodule_list_repo.sh")?;
+    Ok(git::open_opts(dir.join("module1"), crate::restricted())?
+        .head_id()?
+        .detach())
+}
+
+fn expected(state: [State; 2]) -> crate::Result<Vec<Submodule>> {
+    let head_id = Some(module1_head()?);
+    Ok(vec![
+        Submodule {
+            name: "m1".into(),
+            path: "m1".into(),
+            url: Some("../module1".into()),
+            head_id,
+            state: state[0],
+        },
+        Submodule {
+            name: "other-name".into(),
+            path: "dir/m2".into(),
+            url: Some("../module1".into()),
+            head_id,
+            state: state[1],
+        },
+    ])
+}
+
+const CHECKED_OUT: State = State {
+    is_initialized: true,
+    is_checked_out: true,
+};
+
+const INITIALIZED: State = State {
+    is_initialized: true,
+    is_checked_out: false,
+};
+
+const ABSENT: State = State {
+    is_initialized: false,
+    is_checked_out: false,
+};
+
+#[test]
+fn checked_out_submodules_are_listed_in_order_without_those_lacking_a_path() -> crate::Result {
+    let repo = repo("super")?;
+    assert_eq!(repo.submodules()?, expected([CHECKED_OUT, CHECKED_OUT])?);
+    Ok(())
+}
+
+#[test]
+fn clones_know_submodules_but_they_are_not_initialized_or_checked_out() -> crate::Result {
+    assert_eq!(repo("not-initialized")?.submodules()?, expected([ABSENT, ABSENT])?);
+    assert_eq!(
+        repo("partially-initialized")?.submodules()?,
+        expected([INITIALIZED, ABSENT])?
+    );
+    Ok(())
+}
+
+#[test]
+fn bare_repositories_read_gitmodules_and_gitlinks_from_head() -> crate::Result {
+    let repo = repo("bare.git")?;
+    assert_eq!(repo.submodules()?, expected([ABSENT, ABSENT])?);
+    Ok(())
+}
+
+#[test]
+fn repositories_without_submodules_have_none() -> crate::Result {
+    let repo = repo("module1")?;
+    assert!(repo.submodules()?.is_empty());
+    Ok(())
+}
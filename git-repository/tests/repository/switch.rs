@@ -0,0 +1,273 @@
+use git_repository as git;
+use git_repository::switch::{Change, ChangeKind, Error, Options};
+
+use crate::util::repo_rw;
+
+/// Return the output of `git status --porcelain` in the worktree of `repo`, which shows differences between `HEAD` and the index
+/// as well as between the index and the worktree.
+fn git_status(repo: &git::Repository) -> crate::Result<String> {
+    let out = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(repo.work_dir().expect("non-bare"))
+        .output()?;
+    assert!(out.status.success());
+    Ok(String::from_utf8(out.stdout)?)
+}
+
+fn head_name(repo: &git::Repository) -> crate::Result<String> {
+    Ok(repo
+        .head()?
+        .referent_name()
+        .expect("not detached")
+        .as_bstr()
+        .to_string())
+}
+
+/// Return the previous and new object ids and the message of the last entry in the reflog of `HEAD`.
+fn last_head_reflog_entry(repo: &git::Repository) -> crate::Result<(git::ObjectId, git::ObjectId, String)> {
+    let line = repo
+        .find_reference("HEAD")?
+        .log_iter()
+        .rev()?
+        .expect("reflog exists")
+        .next()
+        .expect("at least one entry")?;
+    Ok((line.previous_oid, line.new_oid, line.message.to_string()))
+}
+
+fn change(rela_path: &str, kind: ChangeKind) -> Change {
+    Change {
+        rela_path: rela_path.into(),
+        kind,
+    }
+}
+
+#[test]
+fn clean_worktree_is_updated_to_match_the_branch_like_git_does() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let previous = repo.head_id()?.detach();
+    let outcome = repo.switch_branch("other".into(), Options::default())?;
+
+    assert_eq!(outcome.previous_commit, Some(previous));
+    assert_eq!(outcome.commit, repo.find_reference("refs/heads/other")?.id());
+    assert_eq!(
+        outcome.changes,
+        vec![
+            change("added-on-other", ChangeKind::Added),
+            change("deleted-on-other", ChangeKind::Deleted),
+            change("dir/deleted-on-other", ChangeKind::Deleted),
+            change("executable-on-other", ChangeKind::Modified),
+            change("modified-on-other", ChangeKind::Modified),
+            change("new-dir/added-on-other", ChangeKind::Added),
+        ]
+    );
+    assert_eq!(head_name(&repo)?, "refs/heads/other");
+    assert_eq!(
+        git_status(&repo)?,
+        "",
+        "index and worktree match the tree of the branch"
+    );
+
+    let work_dir = repo.work_dir().expect("non-bare");
+    assert!(
+        !work_dir.join("dir").exists(),
+        "directories which become empty are removed"
+    );
+    assert_eq!(std::fs::read(work_dir.join("modified-on-other"))?, b"other content\n");
+
+    repo.switch_branch("refs/heads/main".into(), Options::default())?;
+    assert_eq!(head_name(&repo)?, "refs/heads/main");
+    assert_eq!(git_status(&repo)?, "", "switching back restores the original state");
+    assert!(work_dir.join("dir/deleted-on-other").is_file());
+    assert!(!work_dir.join("new-dir").exists());
+    Ok(())
+}
+
+#[test]
+fn unrelated_local_changes_are_kept() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    std::fs::write(work_dir.join("unchanged"), "local change\n")?;
+    std::fs::write(work_dir.join("staged"), "new\n")?;
+    assert!(git_testtools::run_git(work_dir, &["add", "staged"])?.success());
+
+    let outcome = repo.switch_branch("other".into(), Options::default())?;
+    assert!(outcome
+        .changes
+        .iter()
+        .all(|c| c.rela_path != "unchanged" && c.rela_path != "staged"));
+    assert_eq!(head_name(&repo)?, "refs/heads/other");
+    assert_eq!(git_status(&repo)?, "A  staged\n M unchanged\n");
+    Ok(())
+}
+
+#[test]
+fn local_changes_to_paths_that_differ_between_branches_prevent_switching() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    std::fs::write(work_dir.join("modified-on-other"), "local change\n")?;
+    std::fs::write(work_dir.join("deleted-on-other"), "staged change\n")?;
+    assert!(git_testtools::run_git(work_dir, &["add", "deleted-on-other"])?.success());
+    let status_before = git_status(&repo)?;
+
+    match repo.switch_branch("other".into(), Options::default()) {
+        Err(Error::LocalChangesWouldBeOverwritten { paths }) => {
+            assert_eq!(paths, vec!["deleted-on-other", "modified-on-other"])
+        }
+        res => unreachable!("unexpected result: {:?}", res),
+    }
+    assert_eq!(head_name(&repo)?, "refs/heads/main");
+    assert_eq!(git_status(&repo)?, status_before, "nothing was changed");
+
+    let outcome = repo.switch_branch(
+        "other".into(),
+        Options {
+            force: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(head_name(&repo)?, "refs/heads/other");
+    assert_eq!(
+        git_status(&repo)?,
+        "",
+        "forcing throws away all changes to tracked files"
+    );
+    assert_eq!(outcome.changes.len(), 6);
+    Ok(())
+}
+
+#[test]
+fn untracked_files_in_the_way_prevent_switching() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    std::fs::write(work_dir.join("added-on-other"), "untracked\n")?;
+    std::fs::write(work_dir.join("new-dir"), "untracked file where a directory is needed\n")?;
+
+    match repo.switch_branch("other".into(), Options::default()) {
+        Err(Error::UntrackedFilesWouldBeOverwritten { paths }) => {
+            assert_eq!(paths, vec!["added-on-other", "new-dir"])
+        }
+        res => unreachable!("unexpected result: {:?}", res),
+    }
+    assert_eq!(head_name(&repo)?, "refs/heads/main");
+
+    repo.switch_branch(
+        "other".into(),
+        Options {
+            force: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(git_status(&repo)?, "");
+    Ok(())
+}
+
+#[test]
+fn unknown_branches_are_an_error() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    assert!(matches!(
+        repo.switch_branch("does-not-exist".into(), Options::default()),
+        Err(Error::FindBranch { name, .. }) if name == "refs/heads/does-not-exist"
+    ));
+    Ok(())
+}
+
+#[test]
+fn switching_is_recorded_in_the_reflog_of_head() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let main = repo.head_id()?.detach();
+    let outcome = repo.switch_branch("other".into(), Options::default())?;
+    assert_eq!(
+        last_head_reflog_entry(&repo)?,
+        (main, outcome.commit, "checkout: moving from main to other".into())
+    );
+    assert_eq!(
+        repo.head()?.prior_checked_out_branch(1)?,
+        Some(("main".into(), main)),
+        "the entry is understood by the reflog parser"
+    );
+    Ok(())
+}
+
+#[test]
+fn checkout_ref_detaches_head_if_the_reference_is_no_branch() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    assert!(git_testtools::run_git(work_dir, &["tag", "v1", "other"])?.success());
+    let main = repo.head_id()?.detach();
+
+    let outcome = repo.checkout_ref("v1".into(), Options::default())?;
+    let other = repo.find_reference("refs/heads/other")?.id().detach();
+    assert_eq!(outcome.commit, other);
+    assert!(repo.head()?.is_detached());
+    assert_eq!(repo.head_id()?, other);
+    assert_eq!(
+        git_status(&repo)?,
+        "",
+        "index and worktree match the tree of the commit"
+    );
+    assert_eq!(
+        last_head_reflog_entry(&repo)?,
+        (main, other, "checkout: moving from main to v1".into())
+    );
+
+    repo.checkout_ref("main".into(), Options::default())?;
+    assert_eq!(head_name(&repo)?, "refs/heads/main");
+    assert_eq!(git_status(&repo)?, "");
+    assert_eq!(
+        last_head_reflog_entry(&repo)?,
+        (other, main, format!("checkout: moving from {} to main", other))
+    );
+    Ok(())
+}
+
+#[test]
+fn checkout_ref_prefers_branches_over_other_references_of_the_same_name() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    assert!(git_testtools::run_git(work_dir, &["tag", "other", "main"])?.success());
+
+    let outcome = repo.checkout_ref("other".into(), Options::default())?;
+    assert_eq!(head_name(&repo)?, "refs/heads/other");
+    assert_eq!(outcome.commit, repo.find_reference("refs/heads/other")?.id());
+
+    repo.checkout_ref("refs/tags/other".into(), Options::default())?;
+    assert!(repo.head()?.is_detached(), "full names are used as is");
+    assert_eq!(repo.head_id()?, repo.find_reference("refs/heads/main")?.id());
+    Ok(())
+}
+
+#[test]
+fn checkout_ref_refuses_to_overwrite_local_changes_unless_forced() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    let work_dir = repo.work_dir().expect("non-bare");
+    assert!(git_testtools::run_git(work_dir, &["tag", "v1", "other"])?.success());
+    std::fs::write(work_dir.join("modified-on-other"), "local change\n")?;
+
+    assert!(matches!(
+        repo.checkout_ref("v1".into(), Options::default()),
+        Err(Error::LocalChangesWouldBeOverwritten { .. })
+    ));
+    assert_eq!(head_name(&repo)?, "refs/heads/main");
+
+    repo.checkout_ref(
+        "v1".into(),
+        Options {
+            force: true,
+            ..Default::default()
+        },
+    )?;
+    assert!(repo.head()?.is_detached());
+    assert_eq!(git_status(&repo)?, "");
+    Ok(())
+}
+
+#[test]
+fn checkout_ref_of_unknown_references_is_an_error() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_switch_repo.sh")?;
+    assert!(matches!(
+        repo.checkout_ref("does-not-exist".into(), Options::default()),
+        Err(Error::FindReference { name, .. }) if name == "does-not-exist"
+    ));
+    Ok(())
+}
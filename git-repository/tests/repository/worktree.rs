@@ -268,6 +268,14 @@ fn run_assertions(main_repo: git::Repository, should_be_bare: bool) {
         assert_eq!(proxy_is_locked, proxy_lock_reason.is_some());
         // TODO: check id of expected worktree, but need access to .gitdir from worktree base
         let proxy_id = actual.id().to_owned();
+        let head = actual.head().unwrap();
+        assert_eq!(head.id().expect("born").detach(), expected.peeled);
+        assert_eq!(
+            head.referent_name().map(|name| name.as_bstr().to_owned()),
+            expected.branch,
+            "detached worktrees have no branch"
+        );
+        assert_eq!(actual.prunable_reason(), expected.prunable);
         assert_eq!(
             base.is_dir(),
             expected.prunable.is_none(),
@@ -277,10 +285,25 @@ fn run_assertions(main_repo: git::Repository, should_be_bare: bool) {
         let repo = if base.is_dir() {
             let repo = actual.into_repo().unwrap();
             assert_eq!(
-                &git::open(base).unwrap(),
+                &git::open(&base).unwrap(),
                 &repo,
                 "repos are considered the same no matter if opened from worktree or from git dir"
             );
+            let discovered = git::discover(base.join("dir")).unwrap();
+            assert_eq!(
+                discovered, repo,
+                "the `.git` file of linked worktrees is followed when discovering from a sub-directory"
+            );
+            assert_eq!(
+                discovered.work_dir().expect("non-bare").canonicalize().unwrap(),
+                base.canonicalize().unwrap()
+            );
+            assert_eq!(
+                discovered.common_dir().canonicalize().unwrap(),
+                main_repo.common_dir().canonicalize().unwrap(),
+                "objects and references are shared with the main repository"
+            );
+            assert_eq!(discovered.head_id().unwrap(), expected.peeled);
             repo
         } else {
             assert!(
@@ -304,3 +327,130 @@ fn run_assertions(main_repo: git::Repository, should_be_bare: bool) {
         );
     }
 }
+
+#[test]
+fn lock_unlock_and_prune() -> crate::Result {
+    if git_testtools::should_skip_as_git_version_is_smaller_than(2, 31, 0) {
+        return Ok(());
+    }
+    let dir = git_testtools::scripted_fixture_repo_writable("make_worktree_repo.sh")?;
+    let repo = git::open(dir.path().join("repo"))?;
+    let worktree = |name: &str| {
+        repo.worktrees()
+            .unwrap()
+            .into_iter()
+            .find(|wt| wt.id() == name)
+            .expect("worktree exists")
+    };
+
+    let wt = worktree("wt-a");
+    assert!(!wt.is_locked());
+    assert!(matches!(wt.unlock(), Err(git::worktree::proxy::lock::Error::NotLocked)));
+    wt.lock("on removable media".into())?;
+    assert_eq!(wt.lock_reason().expect("locked"), "on removable media");
+    assert!(matches!(
+        wt.lock("again".into()),
+        Err(git::worktree::proxy::lock::Error::AlreadyLocked { reason }) if reason == "on removable media"
+    ));
+    assert!(
+        matches!(
+            wt.clone().prune(),
+            Err(git::worktree::proxy::prune::Error::Locked { .. })
+        ),
+        "locked worktrees are never pruned"
+    );
+    wt.unlock()?;
+    assert!(!wt.is_locked());
+    assert!(
+        matches!(wt.prune(), Err(git::worktree::proxy::prune::Error::NotPrunable)),
+        "worktrees with a checkout can't be pruned"
+    );
+
+    let wt = worktree("wt-c-locked");
+    assert!(wt.is_locked());
+    assert!(matches!(
+        wt.prune(),
+        Err(git::worktree::proxy::prune::Error::Locked { .. })
+    ));
+
+    let count_before = repo.worktrees()?.len();
+    let wt = worktree("wt-deleted");
+    let git_dir = wt.git_dir().to_owned();
+    assert_eq!(
+        wt.prunable_reason().expect("prunable"),
+        "gitdir file points to non-existent location"
+    );
+    wt.prune()?;
+    assert!(!git_dir.exists(), "administrative files are removed");
+    assert_eq!(repo.worktrees()?.len(), count_before - 1);
+    Ok(())
+}
+
+mod index {
+    use git_repository as git;
+
+    #[test]
+    fn missing_index_files_are_distinguished_from_corrupt_ones() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = git::init(tmp.path())?;
+
+        assert!(matches!(
+            repo.index().unwrap_err(),
+            git::worktree::open_index::Error::NotFound { path } if path == repo.index_path()
+        ));
+        assert!(repo.try_index()?.is_none());
+        let index = repo.index_or_empty()?;
+        assert!(matches!(index, git::worktree::IndexPersistedOrInMemory::InMemory(_)));
+        assert_eq!(index.entries().len(), 0, "an empty index is created in memory");
+        assert_eq!(index.path, repo.index_path());
+
+        std::fs::write(repo.index_path(), b"DIRC but not really")?;
+        assert!(matches!(
+            repo.index().unwrap_err(),
+            git::worktree::open_index::Error::IndexFile(git::index::file::init::Error::Decode(_))
+        ));
+        assert!(repo.index_or_empty().is_err(), "corrupt indices are never replaced");
+        Ok(())
+    }
+
+    #[test]
+    fn split_indices_are_rejected_as_their_entries_are_incomplete() -> crate::Result {
+        let (repo, _tmp) = crate::util::repo_rw("make_status_repo.sh")?;
+        assert!(!repo.open_index()?.entries().is_empty());
+
+        let work_dir = repo.work_dir().expect("non-bare");
+        assert!(git_testtools::run_git(work_dir, &["update-index", "--split-index"])?.success());
+        assert!(matches!(
+            repo.open_index().unwrap_err(),
+            git::worktree::open_index::Error::UnsupportedExtension { path, signature }
+                if path == repo.index_path() && &signature == b"link"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn the_index_is_cached_until_the_file_changes() -> crate::Result {
+        let (repo, _tmp) = crate::util::repo_rw("make_status_repo.sh")?;
+        let index = repo.index()?;
+        assert!(!index.entries().is_empty());
+        assert!(
+            git::threading::OwnShared::ptr_eq(&index, &repo.index()?),
+            "the same snapshot is returned while the file is unchanged"
+        );
+        assert!(matches!(
+            repo.index_or_empty()?,
+            git::worktree::IndexPersistedOrInMemory::Persisted(_)
+        ));
+
+        let work_dir = repo.work_dir().expect("non-bare");
+        std::fs::write(work_dir.join("new-file"), b"content")?;
+        assert!(git_testtools::run_git(work_dir, &["add", "new-file"])?.success());
+        let reloaded = repo.index()?;
+        assert!(
+            !git::threading::OwnShared::ptr_eq(&index, &reloaded),
+            "a changed index file is reloaded"
+        );
+        assert_eq!(reloaded.entries().len(), index.entries().len() + 1);
+        Ok(())
+    }
+}
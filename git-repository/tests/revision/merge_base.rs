@@ -0,0 +1,38 @@
+use git_repository as git;
+
+use crate::util::ForkAndDates;
+
+#[test]
+fn of_branches_and_their_merge() -> crate::Result {
+    let (repo, _tmp, ForkAndDates { c1, b1c1, c2, m1b1 }) = crate::util::repo_with_fork_and_dates()?;
+
+    assert_eq!(repo.merge_base(b1c1, c2)?, vec![c1]);
+    assert_eq!(
+        repo.merge_base(m1b1, b1c1)?,
+        vec![b1c1],
+        "ancestors are their own merge base"
+    );
+    assert_eq!(repo.merge_base_octopus([m1b1, b1c1, c2])?, vec![c1]);
+    Ok(())
+}
+
+#[test]
+fn ahead_behind_with_and_without_commit_graph() -> crate::Result {
+    let (repo, _tmp, ForkAndDates { c1, b1c1, c2, m1b1 }) = crate::util::repo_with_fork_and_dates()?;
+
+    for with_commit_graph in [false, true] {
+        if with_commit_graph {
+            assert!(git_testtools::run_git(repo.git_dir(), &["commit-graph", "write", "--reachable"])?.success());
+        }
+        assert_eq!(repo.ahead_behind(b1c1, c2)?, (1, 1));
+        assert_eq!(repo.ahead_behind(m1b1, c2)?, (2, 0));
+
+        let mut graph = repo.graph();
+        assert_eq!(graph.commit_graph().is_some(), with_commit_graph);
+        assert_eq!(git::revision::plumbing::merge_base(&b1c1, &[c2], &mut graph)?, vec![c1]);
+        assert_eq!(git::revision::plumbing::ahead_behind(&c2, &m1b1, &mut graph)?, (0, 2));
+        assert_eq!(graph.len(), 4, "all commits are cached");
+        assert_eq!(graph.lookup(&m1b1)?.parents, &[c2, b1c1]);
+    }
+    Ok(())
+}
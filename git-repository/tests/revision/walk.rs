@@ -0,0 +1,91 @@
+use git_hash::ObjectId;
+use git_repository as git;
+
+fn baseline(repo: &git::Repository, name: &str) -> crate::Result<Vec<ObjectId>> {
+    let content = std::fs::read_to_string(repo.work_dir().expect("non-bare").join(name))?;
+    Ok(content
+        .lines()
+        .map(|hex| ObjectId::from_hex(hex.as_bytes()))
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn paths_simplify_history_like_git_log() -> crate::Result {
+    let repo = crate::named_repo("make_log_paths_repo.sh")?;
+    let head = repo.head_id()?;
+    for spec in ["a", "dir", "dir/b", "dir/", "a dir/c", "other", "missing"] {
+        let name = spec.replace('/', "_").replace(' ', "+");
+        for first_parent in [false, true] {
+            let mut platform = head.ancestors().paths(spec.split(' '));
+            if first_parent {
+                platform = platform.first_parent_only();
+            }
+            let actual = platform
+                .all()?
+                .map(|id| id.map(|id| id.detach()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let expected = baseline(
+                &repo,
+                &format!(
+                    "log-{}{}.baseline",
+                    if first_parent { "first-parent-" } else { "" },
+                    name
+                ),
+            )?;
+            assert_eq!(
+                actual, expected,
+                "{:?} (first-parent: {}) matches `git log -- <paths>`",
+                spec, first_parent
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn paths_fail_on_trees_of_the_wrong_kind_or_with_missing_subtrees() -> crate::Result {
+    let mut fixture = git::fixture::Builder::bare()?;
+    fixture.write_file("a", "a")?;
+    let c1 = fixture.commit("main", "c1")?;
+    let (repo, _tmp) = fixture.finish()?;
+
+    let commit_with_tree = |tree: ObjectId| -> crate::Result<ObjectId> {
+        let mut commit: git::objs::Commit = repo.find_object(c1)?.try_into_commit()?.decode()?.into();
+        commit.tree = tree;
+        commit.parents.clear();
+        Ok(repo.write_object(&commit)?.detach())
+    };
+    let first_error = |tip: ObjectId, path: &str| -> crate::Result<git::traverse::commit::ancestors::Error> {
+        Ok(repo
+            .rev_walk(Some(tip))
+            .paths(Some(path))
+            .all()?
+            .find_map(Result::err)
+            .expect("an error, not a silent stop of the iteration"))
+    };
+
+    let blob = repo.write_blob(b"not a tree")?.detach();
+    let err = first_error(commit_with_tree(blob)?, "a")?;
+    assert!(
+        matches!(err, git::traverse::commit::ancestors::Error::ObjectKind { oid, expected: git::objs::Kind::Tree, actual: git::objs::Kind::Blob } if oid == blob),
+        "{:?}",
+        err
+    );
+
+    let tree_with_missing_subtree = repo
+        .write_object(&git::objs::Tree {
+            entries: vec![git::objs::tree::Entry {
+                mode: git::objs::tree::EntryMode::Tree,
+                filename: "dir".into(),
+                oid: ObjectId::from_hex(b"ffffffffffffffffffffffffffffffffffffffff")?,
+            }],
+        })?
+        .detach();
+    let err = first_error(commit_with_tree(tree_with_missing_subtree)?, "dir/b")?;
+    assert!(
+        matches!(err, git::traverse::commit::ancestors::Error::TreeEntryLookup { oid, .. } if oid == tree_with_missing_subtree),
+        "{:?}",
+        err
+    );
+    Ok(())
+}
@@ -39,3 +39,23 @@ pub fn basic_repo() -> Result<Repository> {
 pub fn basic_rw_repo() -> Result<(Repository, tempfile::TempDir)> {
     repo_rw("make_basic_repo.sh")
 }
+
+/// The ids of the commits created by [`repo_with_fork_and_dates()`].
+pub struct ForkAndDates {
+    pub c1: git_repository::ObjectId,
+    pub b1c1: git_repository::ObjectId,
+    pub c2: git_repository::ObjectId,
+    pub m1b1: git_repository::ObjectId,
+}
+
+/// A repository with `branch1` forking off `main` at `c1` and being merged back, with commits made in different years.
+pub fn repo_with_fork_and_dates() -> Result<(Repository, tempfile::TempDir, ForkAndDates)> {
+    let mut fixture = git_repository::fixture::Builder::new()?;
+    let c1 = fixture.time(946771200 /* 2000-01-02 */).commit("main", "c1")?;
+    fixture.reference("refs/heads/branch1", c1)?;
+    let b1c1 = fixture.time(978393600 /* 2001-01-02 */).commit("branch1", "b1c1")?;
+    let c2 = fixture.time(946771200 /* 2000-01-02 */).commit("main", "c2")?;
+    let m1b1 = fixture.time(1009929600 /* 2002-01-02 */).merge("main", "m1b1", b1c1)?;
+    let (repo, tmp) = fixture.finish()?;
+    Ok((repo, tmp, ForkAndDates { c1, b1c1, c2, m1b1 }))
+}
@@ -0,0 +1,199 @@
+use std::ops::Range;
+
+use git_hash::{oid, ObjectId};
+use git_object::CommitRefIter;
+
+/// The generation number of commits which aren't stored in a commit-graph file, as their position in history isn't known.
+///
+/// This matches the behaviour of `git`, and such commits compare as being newer than all commits with known generation.
+pub const GENERATION_NUMBER_INFINITY: u32 = u32::MAX;
+
+/// The error returned when looking up commits in a [`Graph`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    #[error("Commit {} could not be found during graph traversal", .oid.to_hex())]
+    Find {
+        #[source]
+        err: Option<E>,
+        oid: ObjectId,
+    },
+    #[error("A commit could not be decoded during traversal")]
+    Decode(#[from] git_object::decode::Error),
+    #[error("A commit could not be read from the commit-graph file")]
+    CommitGraph(#[from] git_commitgraph::file::commit::Error),
+}
+
+/// A commit as known to the [`Graph`], with all information needed by graph algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commit<'graph> {
+    /// The id of the commit.
+    pub id: &'graph oid,
+    /// The ids of all parents of the commit, in order.
+    pub parents: &'graph [ObjectId],
+    /// The time at which the commit was committed, in seconds since the unix epoch.
+    pub commit_time: u32,
+    /// The generation number as stored in the commit-graph file, or [`GENERATION_NUMBER_INFINITY`] if it is unknown.
+    pub generation: u32,
+}
+
+/// A commit in the arena of the graph, with its parents stored in a shared list.
+struct Entry {
+    id: ObjectId,
+    parents: Range<u32>,
+    commit_time: u32,
+    generation: u32,
+}
+
+type FindFn<'find, E> = dyn for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<Option<CommitRefIter<'a>>, E> + 'find;
+
+/// A cache of commits and their parents to provide fast random access to the commit graph, which is populated on the fly.
+///
+/// Each commit is parsed at most once, or read from a commit-graph file if one is available and contains it. Keep the instance
+/// alive to share it among multiple graph algorithms, like [`merge_base()`][crate::merge_base()] and
+/// [`ahead_behind()`][crate::ahead_behind()], for as long as the commits of interest don't change.
+pub struct Graph<'find, E> {
+    find: Box<FindFn<'find, E>>,
+    commit_graph: Option<git_commitgraph::Graph>,
+    index_by_id: hash_hasher::HashedMap<ObjectId, u32>,
+    entries: Vec<Entry>,
+    parent_ids: Vec<ObjectId>,
+    buf: Vec<u8>,
+}
+
+/// Initialization
+impl<'find, E> Graph<'find, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Create a new instance which uses `find` to obtain commits which aren't available in the optional `commit_graph`.
+    ///
+    /// `find` is expected to return `Ok(None)` for commits that don't exist.
+    pub fn new(
+        find: impl for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<Option<CommitRefIter<'a>>, E> + 'find,
+        commit_graph: impl Into<Option<git_commitgraph::Graph>>,
+    ) -> Self {
+        Graph {
+            find: Box::new(find),
+            commit_graph: commit_graph.into(),
+            index_by_id: Default::default(),
+            entries: Vec::new(),
+            parent_ids: Vec::new(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// Access
+impl<'find, E> Graph<'find, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Return the commit with `id`, or `None` if it doesn't exist.
+    pub fn try_lookup(&mut self, id: &oid) -> Result<Option<Commit<'_>>, Error<E>> {
+        let idx = match self.index_by_id.get(id) {
+            Some(idx) => *idx,
+            None => match self.insert(id)? {
+                Some(idx) => idx,
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(self.commit_at(idx)))
+    }
+
+    /// Return the commit with `id`, or fail if it doesn't exist.
+    pub fn lookup(&mut self, id: &oid) -> Result<Commit<'_>, Error<E>> {
+        self.try_lookup(id)?.ok_or_else(|| Error::Find {
+            err: None,
+            oid: id.to_owned(),
+        })
+    }
+
+    /// Return the amount of commits held in memory.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if no commit was looked up yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the commit-graph file used to look up commits, if there is one.
+    pub fn commit_graph(&self) -> Option<&git_commitgraph::Graph> {
+        self.commit_graph.as_ref()
+    }
+
+    fn commit_at(&self, idx: u32) -> Commit<'_> {
+        let entry = &self.entries[idx as usize];
+        Commit {
+            id: &entry.id,
+            parents: &self.parent_ids[entry.parents.start as usize..entry.parents.end as usize],
+            commit_time: entry.commit_time,
+            generation: entry.generation,
+        }
+    }
+
+    /// Read the commit with `id` from the commit-graph or the object database and store it, returning its index.
+    fn insert(&mut self, id: &oid) -> Result<Option<u32>, Error<E>> {
+        let parents_start = self.parent_ids.len();
+        let from_commit_graph = self
+            .commit_graph
+            .as_ref()
+            .and_then(|graph| graph.commit_by_id(id).map(|commit| (graph, commit)));
+        let (commit_time, generation) = match from_commit_graph {
+            Some((graph, commit)) => {
+                for parent in commit.iter_parents() {
+                    match parent {
+                        Ok(pos) => self.parent_ids.push(graph.id_at(pos).to_owned()),
+                        Err(err) => {
+                            self.parent_ids.truncate(parents_start);
+                            return Err(err.into());
+                        }
+                    }
+                }
+                (commit.committer_timestamp() as u32, commit.generation())
+            }
+            None => {
+                let commit = match (self.find)(id, &mut self.buf).map_err(|err| Error::Find {
+                    err: Some(err),
+                    oid: id.to_owned(),
+                })? {
+                    Some(commit) => commit,
+                    None => return Ok(None),
+                };
+                let mut commit_time = 0;
+                for token in commit {
+                    match token {
+                        Ok(git_object::commit::ref_iter::Token::Tree { .. })
+                        | Ok(git_object::commit::ref_iter::Token::Author { .. }) => continue,
+                        Ok(git_object::commit::ref_iter::Token::Parent { id }) => self.parent_ids.push(id),
+                        Ok(git_object::commit::ref_iter::Token::Committer { signature }) => {
+                            commit_time = signature.time.seconds_since_unix_epoch;
+                            break;
+                        }
+                        Ok(_unused_token) => break,
+                        Err(err) => {
+                            self.parent_ids.truncate(parents_start);
+                            return Err(err.into());
+                        }
+                    }
+                }
+                (commit_time, GENERATION_NUMBER_INFINITY)
+            }
+        };
+
+        let idx = self.entries.len() as u32;
+        self.entries.push(Entry {
+            id: id.to_owned(),
+            parents: parents_start as u32..self.parent_ids.len() as u32,
+            commit_time,
+            generation,
+        });
+        self.index_by_id.insert(id.to_owned(), idx);
+        Ok(Some(idx))
+    }
+}
@@ -1,4 +1,5 @@
-//! Interact with git revisions by parsing them from rev-specs and describing them in terms of reference names.
+//! Interact with git revisions by parsing them from rev-specs, describing them in terms of reference names and
+//! finding their common ancestors in a cached commit graph.
 //!
 //! ## Feature Flags
 #![cfg_attr(
@@ -15,6 +16,14 @@ pub use hash_hasher;
 pub mod describe;
 pub use describe::function::describe;
 
+///
+pub mod graph;
+pub use graph::Graph;
+
+///
+pub mod merge_base;
+pub use merge_base::function::{ahead_behind, commits_not_in, independent, merge_base, merge_base_octopus};
+
 ///
 pub mod spec;
 
@@ -0,0 +1,283 @@
+type Flags = u8;
+
+/// The commit was reached from the first commit.
+const PARENT1: Flags = 1 << 0;
+/// The commit was reached from one of the other commits.
+const PARENT2: Flags = 1 << 1;
+/// The commit is an ancestor of a commit known to be common, and thus can't be a best common ancestor itself.
+const STALE: Flags = 1 << 2;
+/// The commit was found to be a common ancestor.
+const RESULT: Flags = 1 << 3;
+
+/// The error returned by the [`merge_base()`][function::merge_base()], [`ahead_behind()`][function::ahead_behind()]
+/// and all other functions in this module.
+pub type Error<E> = crate::graph::Error<E>;
+
+pub(crate) mod function {
+    use std::collections::{hash_map, VecDeque};
+
+    use git_hash::{oid, ObjectId};
+
+    use super::{Error, Flags, PARENT1, PARENT2, RESULT, STALE};
+    use crate::Graph;
+
+    /// Given a `first` commit and `others`, traverse the commit `graph` to find all best common ancestors of `first` and
+    /// any of `others`, similar to `git merge-base --all first others…`.
+    ///
+    /// The returned ids are sorted by commit time, most recent first.
+    /// There may be more than one result in case of criss-cross merges, or none at all if the histories are unrelated.
+    pub fn merge_base<E>(first: &oid, others: &[ObjectId], graph: &mut Graph<'_, E>) -> Result<Vec<ObjectId>, Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if others.iter().any(|other| other.as_ref() == first) {
+            return Ok(vec![first.to_owned()]);
+        }
+
+        let (paint, bases) = paint_down_to_common(first, others, graph)?;
+        let mut bases: Vec<_> = bases
+            .into_iter()
+            .filter(|id| paint[id].flags & STALE == 0)
+            .map(|id| (id, paint[&id].time))
+            .collect();
+        drop(paint);
+
+        if bases.len() > 1 {
+            bases = remove_redundant(bases, graph)?;
+        }
+        bases.sort_by(|a, b| a.1.cmp(&b.1).reverse());
+        Ok(bases.into_iter().map(|(id, _time)| id).collect())
+    }
+
+    /// Find the best common ancestors of all `tips`, similar to `git merge-base --octopus`, by repeatedly
+    /// computing the [merge bases][merge_base()] of the bases found so far and the next tip.
+    pub fn merge_base_octopus<E>(tips: &[ObjectId], graph: &mut Graph<'_, E>) -> Result<Vec<ObjectId>, Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut tips = tips.iter();
+        let mut bases: Vec<_> = tips.next().cloned().into_iter().collect();
+        for tip in tips {
+            let mut next_bases = Vec::new();
+            for base in &bases {
+                for id in merge_base(base, std::slice::from_ref(tip), graph)? {
+                    if !next_bases.contains(&id) {
+                        next_bases.push(id);
+                    }
+                }
+            }
+            bases = next_bases;
+        }
+        Ok(bases)
+    }
+
+    /// Count the commits reachable from `one` but not from `two`, and the commits reachable from `two` but not from `one`,
+    /// returning `(ahead, behind)` similar to `git rev-list --left-right --count one...two`.
+    ///
+    /// Like `git`, the traversal stops once all remaining commits are known to be common to both, which relies on commit times
+    /// not decreasing from parents to children.
+    pub fn ahead_behind<E>(one: &oid, two: &oid, graph: &mut Graph<'_, E>) -> Result<(usize, usize), Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if one == two {
+            return Ok((0, 0));
+        }
+        let (paint, _bases) = paint_down_to_common(one, std::slice::from_ref(&two.to_owned()), graph)?;
+        Ok(paint
+            .values()
+            .fold((0, 0), |(ahead, behind), info| match info.flags & (PARENT1 | PARENT2) {
+                PARENT1 => (ahead + 1, behind),
+                PARENT2 => (ahead, behind + 1),
+                _ => (ahead, behind),
+            }))
+    }
+
+    /// Return all unique `commits` which aren't reachable from any of the other `commits`, similar to `git merge-base --independent`.
+    ///
+    /// The returned ids are in the order in which they were first seen in `commits`.
+    pub fn independent<E>(commits: &[ObjectId], graph: &mut Graph<'_, E>) -> Result<Vec<ObjectId>, Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut unique = Vec::with_capacity(commits.len());
+        for id in commits {
+            if !unique.iter().any(|(unique_id, _)| unique_id == id) {
+                unique.push((*id, graph.lookup(id)?.commit_time));
+            }
+        }
+        if unique.len() > 1 {
+            unique = remove_redundant(unique, graph)?;
+        }
+        Ok(unique.into_iter().map(|(id, _time)| id).collect())
+    }
+
+    /// Return all commits reachable from `tip` but not from any of `hidden`, similar to `git rev-list tip ^hidden…`.
+    ///
+    /// The commits are returned in the order in which they were encountered while walking by commit time, newest first.
+    pub fn commits_not_in<E>(
+        tip: &oid,
+        hidden: &[ObjectId],
+        graph: &mut Graph<'_, E>,
+    ) -> Result<Vec<ObjectId>, Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut visited = Vec::new();
+        let (paint, _bases) = paint_down_to_common_inner(tip, hidden, graph, |id| visited.push(id))?;
+        visited.retain(|id| paint[id].flags & (PARENT2 | STALE) == 0);
+        Ok(visited)
+    }
+
+    struct Info {
+        flags: Flags,
+        time: u32,
+    }
+
+    type Paint = hash_hasher::HashedMap<ObjectId, Info>;
+
+    /// Mark all commits reachable from `first` with `PARENT1` and all commits reachable from `others` with `PARENT2`,
+    /// in order of commit time, until only commits known to be in the past of common ancestors are left.
+    fn paint_down_to_common<E>(
+        first: &oid,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, E>,
+    ) -> Result<(Paint, Vec<ObjectId>), Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        paint_down_to_common_inner(first, others, graph, |_| {})
+    }
+
+    /// Like [`paint_down_to_common()`], but call `visit` with each commit in the order in which it is taken off the queue.
+    fn paint_down_to_common_inner<E>(
+        first: &oid,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, E>,
+        mut visit: impl FnMut(ObjectId),
+    ) -> Result<(Paint, Vec<ObjectId>), Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut paint = Paint::default();
+        let mut queue = VecDeque::new();
+        let mut results = Vec::new();
+
+        for (id, flags) in std::iter::once((first, PARENT1)).chain(others.iter().map(|id| (id.as_ref(), PARENT2))) {
+            match paint.entry(id.to_owned()) {
+                hash_map::Entry::Occupied(mut entry) => entry.get_mut().flags |= flags,
+                hash_map::Entry::Vacant(entry) => {
+                    let time = graph.lookup(id)?.commit_time;
+                    entry.insert(Info { flags, time });
+                    insert_by_date(&mut queue, id.to_owned(), time);
+                }
+            }
+        }
+
+        let mut parents = Vec::new();
+        let mut oldest_unsettled_time = None;
+        while let Some(&(_, newest_time)) = queue.front() {
+            if queue.iter().all(|(id, _)| paint[id].flags & STALE != 0) {
+                // Commits with the same time may have been painted before their stale children. Keep marking them stale until
+                // no queued commit can be a descendant of a commit that is still reachable from only one side.
+                let oldest_unsettled_time = *oldest_unsettled_time.get_or_insert_with(|| {
+                    paint
+                        .values()
+                        .filter(|info| {
+                            info.flags & STALE == 0 && info.flags & (PARENT1 | PARENT2) != (PARENT1 | PARENT2)
+                        })
+                        .map(|info| info.time)
+                        .min()
+                });
+                if oldest_unsettled_time.map_or(true, |oldest| newest_time < oldest) {
+                    break;
+                }
+            }
+            let (commit, _commit_time) = queue.pop_front().expect("non-empty queue");
+            visit(commit);
+            let info = paint.get_mut(&commit).expect("queued commits are known");
+            let mut flags = info.flags & (PARENT1 | PARENT2 | STALE);
+            if flags & (PARENT1 | PARENT2) == (PARENT1 | PARENT2) {
+                if info.flags & RESULT == 0 {
+                    info.flags |= RESULT;
+                    results.push(commit);
+                }
+                flags |= STALE;
+            }
+
+            parents.clear();
+            parents.extend_from_slice(graph.lookup(&commit)?.parents);
+            for parent_id in parents.drain(..) {
+                match paint.entry(parent_id) {
+                    hash_map::Entry::Occupied(mut entry) => {
+                        let info = entry.get_mut();
+                        if info.flags & flags == flags {
+                            continue;
+                        }
+                        info.flags |= flags;
+                        let time = info.time;
+                        if !queue.iter().any(|(id, _)| *id == parent_id) {
+                            insert_by_date(&mut queue, parent_id, time);
+                        }
+                    }
+                    hash_map::Entry::Vacant(entry) => {
+                        let time = match graph.try_lookup(&parent_id)? {
+                            Some(parent) => parent.commit_time,
+                            None => continue, // skip missing objects, they don't exist.
+                        };
+                        entry.insert(Info { flags, time });
+                        insert_by_date(&mut queue, parent_id, time);
+                    }
+                }
+            }
+        }
+        Ok((paint, results))
+    }
+
+    /// Remove all `bases` which are reachable from any of the other `bases`.
+    fn remove_redundant<E>(
+        bases: Vec<(ObjectId, u32)>,
+        graph: &mut Graph<'_, E>,
+    ) -> Result<Vec<(ObjectId, u32)>, Error<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut redundant = vec![false; bases.len()];
+        let mut others = Vec::with_capacity(bases.len());
+        for (idx, (base, _time)) in bases.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            others.clear();
+            others.extend(
+                bases
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_idx, _)| *other_idx != idx && !redundant[*other_idx])
+                    .map(|(_, (id, _))| *id),
+            );
+            let (paint, _) = paint_down_to_common(base, &others, graph)?;
+            if paint[base].flags & PARENT2 != 0 {
+                redundant[idx] = true;
+            }
+            for (other_idx, (other, _)) in bases.iter().enumerate().filter(|(other_idx, _)| *other_idx != idx) {
+                if paint.get(other).map_or(false, |info| info.flags & PARENT1 != 0) {
+                    redundant[other_idx] = true;
+                }
+            }
+        }
+        Ok(bases
+            .into_iter()
+            .zip(redundant)
+            .filter(|(_, redundant)| !redundant)
+            .map(|(base, _)| base)
+            .collect())
+    }
+
+    /// Insert `id` behind all commits at least as recent as `time`, keeping the queue sorted newest first and
+    /// commits of the same time in order of insertion, just like `git` does.
+    fn insert_by_date(queue: &mut VecDeque<(ObjectId, u32)>, id: ObjectId, time: u32) {
+        let pos = queue.partition_point(|(_, queued_time)| *queued_time >= time);
+        queue.insert(pos, (id, time));
+    }
+}
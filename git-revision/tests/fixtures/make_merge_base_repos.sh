@@ -0,0 +1,120 @@
+#!/bin/bash
+set -eu -o pipefail
+
+tick=1112911993
+function commit () {
+  local message=${1:?first argument is the commit message}
+  tick=$((tick + 60))
+  GIT_COMMITTER_DATE="$tick -0700" GIT_AUTHOR_DATE="$tick -0700" git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge () {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick=$((tick + 60))
+  GIT_COMMITTER_DATE="$tick -0700" GIT_AUTHOR_DATE="$tick -0700" git merge -q -m "$message" "$@"
+  git tag "$message"
+}
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+commit c1
+commit c2
+
+# criss-cross: a2 and b2 both merge a1 and b1
+git checkout -q -b a
+commit a1
+git checkout -q -b b c2
+commit b1
+git checkout -q a
+merge a2 b1
+git checkout -q b
+merge b2 a1
+commit b3
+git checkout -q a
+commit a3
+
+# three branches off of c1 and c2 for octopus merge-bases
+git checkout -q -b branch-o1 c2
+commit o1
+git checkout -q -b branch-o2 c2
+commit o2
+git checkout -q -b branch-o3 c1
+commit o3
+
+git checkout -q --orphan unrelated
+commit u1
+
+git merge-base --all a3 b3 > criss-cross.baseline
+git merge-base --all a2 b2 > criss-cross-merges.baseline
+git merge-base --all a3 c1 > ancestor.baseline
+git merge-base --all o1 o2 > two-branches.baseline
+git merge-base --octopus o1 o2 o3 > octopus.baseline
+git merge-base --all a3 u1 > unrelated.baseline || true
+
+for range in a3...b3 a3...c1 c1...a3 o1...o3 a3...u1; do
+  git rev-list --left-right --count "$range" > "ahead-behind-${range//.../-}.baseline"
+done
+
+git merge-base --independent a3 b3 a2 c1 o1 o2 o3 u1 a3 > independent.baseline
+for range in a3..b3 b3..a3 c1..a3 o3..o1 u1..a3; do
+  git rev-list "$range" > "rev-list-${range//../-}.baseline"
+done
+
+git commit-graph write --no-progress --reachable
+
+# commits which often share their commit time, like it's common for scripted repositories
+git init -q same-time
+(cd same-time
+  git config merge.ff false
+  function commit () {
+    local time=$((1112911993 + ${2:-0}))
+    GIT_COMMITTER_DATE="$time -0700" GIT_AUTHOR_DATE="$time -0700" git commit -q --allow-empty -m "$1"
+    git tag "$1"
+  }
+  function merge () {
+    local message=${1:?first argument is the commit message}
+    local time=$((1112911993 + ${3:-0}))
+    GIT_COMMITTER_DATE="$time -0700" GIT_AUTHOR_DATE="$time -0700" git merge -q -m "$message" "$2"
+    git tag "$message"
+  }
+
+  # all commits have the same time
+  git checkout -q -b main
+  commit c1
+  commit c2
+  commit c3
+  git checkout -q -b a
+  commit a1
+  commit a2
+  git checkout -q -b b c1
+  commit b1
+  merge b2 a1
+  git checkout -q a
+  merge a3 b1
+  commit a4
+  git checkout -q b
+  commit b3
+  commit b4
+
+  # parents with the same time as their children, and older ones
+  git checkout -q -b m c1
+  commit m1 60
+  commit m2 60
+  git checkout -q -b n c1
+  merge m3 m2 60
+  git checkout -q -b o m1
+  merge m4 m3 120
+
+  tags=$(git tag)
+  for one in $tags; do
+    for two in $tags; do
+      echo "$one $two $(git merge-base --all "$one" "$two" | tr '\n' ' ')" >> merge-base.baseline
+      echo "$one $two $(git rev-list --left-right --count "$one...$two")" >> ahead-behind.baseline
+      echo "$one $two $(git rev-list "$one..$two" | tr '\n' ' ')" >> rev-list.baseline
+    done
+  done
+)
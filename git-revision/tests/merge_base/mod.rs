@@ -0,0 +1,273 @@
+use std::{cell::RefCell, collections::HashMap, path::PathBuf};
+
+use git_hash::ObjectId;
+use git_repository::{odb::FindExt, Repository};
+use git_revision::graph::GENERATION_NUMBER_INFINITY;
+
+#[test]
+fn criss_cross_merges_yield_multiple_best_common_ancestors() -> crate::Result {
+    let (repo, dir) = repo()?;
+    for (one, two, baseline) in [
+        ("a3", "b3", "criss-cross"),
+        ("a2", "b2", "criss-cross-merges"),
+        ("b3", "a3", "criss-cross"),
+    ] {
+        let actual = merge_base(&repo, one, two)?;
+        assert_eq!(actual, expected(&dir, baseline)?, "{}..{}", one, two);
+        assert_eq!(actual.len(), 2);
+    }
+    Ok(())
+}
+
+#[test]
+fn ancestors_are_their_own_merge_base() -> crate::Result {
+    let (repo, dir) = repo()?;
+    assert_eq!(merge_base(&repo, "a3", "c1")?, expected(&dir, "ancestor")?);
+    assert_eq!(merge_base(&repo, "c1", "a3")?, expected(&dir, "ancestor")?);
+    let a3 = id(&repo, "a3")?;
+    assert_eq!(
+        merge_base(&repo, "a3", "a3")?,
+        vec![a3],
+        "a commit is its own merge base"
+    );
+    Ok(())
+}
+
+#[test]
+fn diverging_branches_have_a_single_merge_base() -> crate::Result {
+    let (repo, dir) = repo()?;
+    assert_eq!(merge_base(&repo, "o1", "o2")?, expected(&dir, "two-branches")?);
+    Ok(())
+}
+
+#[test]
+fn unrelated_histories_have_no_merge_base() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let actual = merge_base(&repo, "a3", "u1")?;
+    assert!(actual.is_empty());
+    assert_eq!(actual, expected(&dir, "unrelated")?);
+    Ok(())
+}
+
+#[test]
+fn octopus() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let tips = ["o1", "o2", "o3"]
+        .iter()
+        .map(|name| id(&repo, name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let actual = git_revision::merge_base_octopus(&tips, &mut graph(&repo))?;
+    assert_eq!(actual, expected(&dir, "octopus")?);
+    Ok(())
+}
+
+#[test]
+fn ahead_behind_counts_commits_only_reachable_from_one_side() -> crate::Result {
+    let (repo, dir) = repo()?;
+    for (one, two) in [("a3", "b3"), ("a3", "c1"), ("c1", "a3"), ("o1", "o3"), ("a3", "u1")] {
+        let actual = git_revision::ahead_behind(&id(&repo, one)?, &id(&repo, two)?, &mut graph(&repo))?;
+        assert_eq!(
+            actual,
+            expected_ahead_behind(&dir, &format!("ahead-behind-{}-{}", one, two))?,
+            "{}...{}",
+            one,
+            two
+        );
+    }
+    let a3 = id(&repo, "a3")?;
+    assert_eq!(git_revision::ahead_behind(&a3, &a3, &mut graph(&repo))?, (0, 0));
+    Ok(())
+}
+
+#[test]
+fn independent_commits_are_not_reachable_from_each_other() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let commits = ["a3", "b3", "a2", "c1", "o1", "o2", "o3", "u1", "a3"]
+        .iter()
+        .map(|name| id(&repo, name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut actual = git_revision::independent(&commits, &mut graph(&repo))?;
+    let mut expected = expected(&dir, "independent")?;
+    actual.sort();
+    expected.sort();
+    assert_eq!(actual, expected);
+    assert_eq!(
+        git_revision::independent(&commits[..1], &mut graph(&repo))?,
+        &commits[..1],
+        "a single commit is independent"
+    );
+    Ok(())
+}
+
+#[test]
+fn commits_not_in_hidden_ones_are_listed_newest_first() -> crate::Result {
+    let (repo, dir) = repo()?;
+    for (hidden, tip) in [("a3", "b3"), ("b3", "a3"), ("c1", "a3"), ("o3", "o1"), ("u1", "a3")] {
+        let actual = git_revision::commits_not_in(&id(&repo, tip)?, &[id(&repo, hidden)?], &mut graph(&repo))?;
+        assert_eq!(
+            actual,
+            expected(&dir, &format!("rev-list-{}-{}", hidden, tip))?,
+            "{}..{}",
+            hidden,
+            tip
+        );
+    }
+    let a3 = id(&repo, "a3")?;
+    assert!(git_revision::commits_not_in(&a3, &[a3], &mut graph(&repo))?.is_empty());
+    assert_eq!(
+        git_revision::commits_not_in(&id(&repo, "c2")?, &[], &mut graph(&repo))?,
+        [id(&repo, "c2")?, id(&repo, "c1")?],
+        "without hidden commits, all ancestors are listed"
+    );
+    Ok(())
+}
+
+#[test]
+fn commits_with_the_same_commit_time_are_handled_like_git() -> crate::Result {
+    let (_repo, dir) = repo()?;
+    let dir = dir.join("same-time");
+    let repo = git_repository::open(&dir)?;
+
+    let merge_bases = std::fs::read_to_string(dir.join("merge-base.baseline"))?;
+    for line in merge_bases.lines() {
+        let mut tokens = line.split_whitespace();
+        let (one, two) = (tokens.next().expect("one"), tokens.next().expect("two"));
+        let mut expected = tokens
+            .map(|hex| ObjectId::from_hex(hex.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut actual = merge_base(&repo, one, two)?;
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected, "merge-base {} {}", one, two);
+    }
+
+    let counts = std::fs::read_to_string(dir.join("ahead-behind.baseline"))?;
+    for line in counts.lines() {
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        let (one, two) = (tokens[0], tokens[1]);
+        let expected: (usize, usize) = (tokens[2].parse()?, tokens[3].parse()?);
+        let actual = git_revision::ahead_behind(&id(&repo, one)?, &id(&repo, two)?, &mut graph(&repo))?;
+        assert_eq!(actual, expected, "rev-list --left-right --count {}...{}", one, two);
+    }
+
+    let rev_lists = std::fs::read_to_string(dir.join("rev-list.baseline"))?;
+    for line in rev_lists.lines() {
+        let mut tokens = line.split_whitespace();
+        let (hidden, tip) = (tokens.next().expect("hidden"), tokens.next().expect("tip"));
+        let expected = tokens
+            .map(|hex| ObjectId::from_hex(hex.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let actual = git_revision::commits_not_in(&id(&repo, tip)?, &[id(&repo, hidden)?], &mut graph(&repo))?;
+        assert_eq!(actual, expected, "rev-list {}..{}", hidden, tip);
+    }
+    Ok(())
+}
+
+#[test]
+fn graph_parses_each_commit_at_most_once_across_algorithms() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let parse_count = RefCell::new(HashMap::<ObjectId, usize>::new());
+    let mut graph = git_revision::Graph::new(
+        |id, buf| {
+            *parse_count.borrow_mut().entry(id.to_owned()).or_default() += 1;
+            repo.objects.find_commit_iter(id, buf).map(Some)
+        },
+        None,
+    );
+
+    let (a3, b3, c1) = (id(&repo, "a3")?, id(&repo, "b3")?, id(&repo, "c1")?);
+    assert_eq!(
+        git_revision::merge_base(&a3, &[b3], &mut graph)?,
+        expected(&dir, "criss-cross")?
+    );
+    assert_eq!(
+        git_revision::ahead_behind(&a3, &b3, &mut graph)?,
+        expected_ahead_behind(&dir, "ahead-behind-a3-b3")?
+    );
+    assert_eq!(
+        git_revision::merge_base(&a3, &[c1], &mut graph)?,
+        expected(&dir, "ancestor")?
+    );
+    assert_eq!(
+        git_revision::ahead_behind(&c1, &a3, &mut graph)?,
+        expected_ahead_behind(&dir, "ahead-behind-c1-a3")?
+    );
+    assert_eq!(graph.lookup(&a3)?.generation, GENERATION_NUMBER_INFINITY);
+
+    let cached_commits = graph.len();
+    drop(graph);
+    let parse_count = parse_count.into_inner();
+    assert_eq!(
+        parse_count.len(),
+        cached_commits,
+        "every commit that was parsed is cached"
+    );
+    assert!(
+        parse_count.values().all(|count| *count == 1),
+        "no commit is parsed more than once: {:?}",
+        parse_count
+    );
+    Ok(())
+}
+
+#[test]
+fn graph_uses_the_commit_graph_file_instead_of_parsing_commits() -> crate::Result {
+    let (repo, dir) = repo()?;
+    let commit_graph = git_commitgraph::Graph::from_info_dir(dir.join(".git/objects/info"))?;
+    let mut graph = git_revision::Graph::new(
+        |id, _buf| -> Result<Option<git_object::CommitRefIter<'_>>, std::convert::Infallible> {
+            unreachable!("all commits are in the commit-graph, but {} was looked up", id)
+        },
+        commit_graph,
+    );
+
+    let (a3, b3) = (id(&repo, "a3")?, id(&repo, "b3")?);
+    assert_eq!(
+        git_revision::merge_base(&a3, &[b3], &mut graph)?,
+        expected(&dir, "criss-cross")?
+    );
+    assert_eq!(
+        git_revision::ahead_behind(&a3, &b3, &mut graph)?,
+        expected_ahead_behind(&dir, "ahead-behind-a3-b3")?
+    );
+    let commit = graph.lookup(&a3)?;
+    assert_eq!(commit.parents.len(), 1);
+    assert_ne!(commit.generation, GENERATION_NUMBER_INFINITY);
+    Ok(())
+}
+
+fn merge_base(repo: &Repository, one: &str, two: &str) -> crate::Result<Vec<ObjectId>> {
+    Ok(git_revision::merge_base(
+        &id(repo, one)?,
+        &[id(repo, two)?],
+        &mut graph(repo),
+    )?)
+}
+
+fn graph(
+    repo: &Repository,
+) -> git_revision::Graph<'_, git_repository::odb::find::existing_iter::Error<git_repository::odb::store::find::Error>> {
+    git_revision::Graph::new(move |id, buf| repo.objects.find_commit_iter(id, buf).map(Some), None)
+}
+
+fn id(repo: &Repository, name: &str) -> crate::Result<ObjectId> {
+    Ok(repo.rev_parse_single(name)?.detach())
+}
+
+fn expected(dir: &std::path::Path, name: &str) -> crate::Result<Vec<ObjectId>> {
+    Ok(std::fs::read_to_string(dir.join(format!("{}.baseline", name)))?
+        .lines()
+        .map(|line| ObjectId::from_hex(line.as_bytes()))
+        .collect::<Result<_, _>>()?)
+}
+
+fn expected_ahead_behind(dir: &std::path::Path, name: &str) -> crate::Result<(usize, usize)> {
+    let counts = std::fs::read_to_string(dir.join(format!("{}.baseline", name)))?;
+    let (ahead, behind) = counts.trim().split_once('\t').expect("tab-separated counts");
+    Ok((ahead.parse()?, behind.parse()?))
+}
+
+fn repo() -> crate::Result<(Repository, PathBuf)> {
+    let dir = git_testtools::scripted_fixture_repo_read_only("make_merge_base_repos.sh")?;
+    Ok((git_repository::open(&dir)?, dir))
+}
@@ -35,7 +35,7 @@ pub struct SpawnProcessOnDemand {
     pub(crate) path: BString,
     ssh_program: Option<String>,
     ssh_args: Vec<String>,
-    ssh_env: Vec<(&'static str, String)>,
+    env: Vec<(&'static str, String)>,
     connection: Option<git::Connection<process::ChildStdout, process::ChildStdin>>,
     child: Option<process::Child>,
 }
@@ -62,7 +62,7 @@ impl SpawnProcessOnDemand {
             path,
             ssh_program: Some(program),
             ssh_args: args.into_iter().map(|s| s.into()).collect(),
-            ssh_env: env.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            env: env.into_iter().map(|(k, v)| (k, v.into())).collect(),
             child: None,
             connection: None,
             desired_version: version,
@@ -74,7 +74,11 @@ impl SpawnProcessOnDemand {
             path,
             ssh_program: None,
             ssh_args: Vec::new(),
-            ssh_env: Vec::new(),
+            // Like `git`, tell the local process which protocol version we desire as there is no other way to pass it.
+            env: match version {
+                Protocol::V1 => Vec::new(),
+                Protocol::V2 => vec![("GIT_PROTOCOL", format!("version={}", version as usize))],
+            },
             child: None,
             connection: None,
             desired_version: version,
@@ -120,7 +124,7 @@ impl client::Transport for SpawnProcessOnDemand {
         for env_to_remove in ENV_VARS_TO_REMOVE {
             cmd.env_remove(env_to_remove);
         }
-        cmd.envs(std::mem::take(&mut self.ssh_env));
+        cmd.envs(std::mem::take(&mut self.env));
         cmd.args(&mut self.ssh_args);
         cmd.stdin(Stdio::piped()).stdout(Stdio::piped());
         if self.ssh_program.is_some() {
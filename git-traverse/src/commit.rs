@@ -1,10 +1,16 @@
 /// An iterator over the ancestors one or more starting commits
-pub struct Ancestors<Find, Predicate, StateMut> {
+pub struct Ancestors<Find, Predicate, StateMut, Generation = fn(&git_hash::oid) -> Option<u32>> {
     find: Find,
     predicate: Predicate,
     state: StateMut,
     parents: Parents,
     sorting: Sorting,
+    generation: Generation,
+    generation_cutoff: Option<u32>,
+    shallow: hash_hasher::HashedSet<git_hash::ObjectId>,
+    cache: Option<git_commitgraph::Graph>,
+    /// `true` if the amount of children of each commit to be returned was counted for topological sorting.
+    children_counted: bool,
 }
 
 /// Specify how to handle commit parents during traversal.
@@ -25,7 +31,19 @@ impl Default for Parents {
 /// Specify how to sort commits during traversal.
 #[derive(Copy, Clone)]
 pub enum Sorting {
-    /// Commits are sorted as they are mentioned in the commit graph.
+    /// Commits are sorted as they are mentioned in the commit graph, breadth first.
+    ///
+    /// Note that this may return a parent before all of its children are returned, as is typical for histories with merges.
+    BreadthFirst,
+    /// Commits are returned only after all of their children were returned, similar to `git log --topo-order`.
+    ///
+    /// Lines of history aren't intermixed, instead each is followed to its end before the next one is started,
+    /// beginning with the one reachable through the first parent.
+    ///
+    /// # Performance
+    ///
+    /// All commits to be returned are looked up once before the first one is returned, and once when they are returned,
+    /// so this mode benefits greatly from having an object_cache in `find()`.
     Topological,
     /// Commits are sorted by their commit time in descending order, that is newest first.
     ///
@@ -40,14 +58,18 @@ pub enum Sorting {
 
 impl Default for Sorting {
     fn default() -> Self {
-        Sorting::Topological
+        Sorting::BreadthFirst
     }
 }
 
 ///
 pub mod ancestors {
     use std::borrow::Borrow;
-    use std::{borrow::BorrowMut, collections::VecDeque, iter::FromIterator};
+    use std::{
+        borrow::BorrowMut,
+        collections::{BinaryHeap, VecDeque},
+        iter::FromIterator,
+    };
 
     use git_hash::{oid, ObjectId};
     use git_object::CommitRefIter;
@@ -65,6 +87,19 @@ pub mod ancestors {
         },
         #[error(transparent)]
         ObjectDecode(#[from] git_object::decode::Error),
+        #[error(transparent)]
+        CommitGraphParent(#[from] git_commitgraph::file::commit::Error),
+        #[error("Object {oid} was expected to be a {expected}, but was a {actual}")]
+        ObjectKind {
+            oid: ObjectId,
+            expected: git_object::Kind,
+            actual: git_object::Kind,
+        },
+        #[error("Could not look up an entry in tree {oid}")]
+        TreeEntryLookup {
+            oid: ObjectId,
+            source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        },
     }
 
     type TimeInSeconds = u32;
@@ -76,6 +111,9 @@ pub mod ancestors {
         buf: Vec<u8>,
         seen: hash_hasher::HashedSet<ObjectId>,
         parents_buf: Vec<u8>,
+        /// The amount of children yet to be returned for each commit, for topological sorting.
+        children: hash_hasher::HashedMap<ObjectId, u32>,
+        parent_ids: Vec<ObjectId>,
     }
 
     impl State {
@@ -83,36 +121,86 @@ pub mod ancestors {
             self.next.clear();
             self.buf.clear();
             self.seen.clear();
+            self.children.clear();
         }
     }
 
     /// Builder
-    impl<Find, Predicate, StateMut> Ancestors<Find, Predicate, StateMut> {
+    impl<Find, Predicate, StateMut, Generation> Ancestors<Find, Predicate, StateMut, Generation> {
         /// Change our commit parent handling mode to the given one.
         pub fn parents(mut self, mode: Parents) -> Self {
             self.parents = mode;
             self
         }
+
+        /// Use `generation` to obtain the generation number of a commit, typically from a commit-graph,
+        /// returning `None` if it is unknown.
+        ///
+        /// Generation numbers are used by [`with_generation_cutoff()`][Self::with_generation_cutoff()]
+        /// and [`with_hidden()`][Self::with_hidden()].
+        pub fn generations<G>(self, generation: G) -> Ancestors<Find, Predicate, StateMut, G>
+        where
+            G: FnMut(&oid) -> Option<u32>,
+        {
+            Ancestors {
+                find: self.find,
+                predicate: self.predicate,
+                state: self.state,
+                parents: self.parents,
+                sorting: self.sorting,
+                generation,
+                generation_cutoff: self.generation_cutoff,
+                shallow: self.shallow,
+                cache: self.cache,
+                children_counted: self.children_counted,
+            }
+        }
+
+        /// Don't traverse into parents whose [generation number][Self::generations()] is known and is at or below
+        /// `min_generation`, without looking up the parent commit itself.
+        ///
+        /// Note that this also prunes commits that can't be reached from any commit at `min_generation`, so it's
+        /// up to the caller to only use it if it's known that these aren't of interest.
+        pub fn with_generation_cutoff(mut self, min_generation: u32) -> Self {
+            self.generation_cutoff = Some(min_generation);
+            self
+        }
+
+        /// Treat the `shallow` commits as if they had no parents, so the traversal ends there without trying to look up parents
+        /// which aren't available in shallow repositories.
+        ///
+        /// Call this before [`with_hidden()`][Self::with_hidden()] for it to be respected there as well.
+        pub fn with_shallow_commits(mut self, shallow: impl IntoIterator<Item = impl Into<ObjectId>>) -> Self {
+            self.shallow = shallow.into_iter().map(Into::into).collect();
+            self
+        }
+
+        /// Read parents, commit times and generation numbers of the commits contained in the commit-graph `cache`
+        /// from it instead of decoding commits obtained with `find()`, which speeds up traversals considerably.
+        ///
+        /// Generation numbers in `cache` take precedence over the ones provided by [`generations()`][Self::generations()].
+        /// Note that [`commit_iter()`][Self::commit_iter()] has no data for commits read from the commit-graph.
+        pub fn commit_graph(mut self, cache: Option<git_commitgraph::Graph>) -> Self {
+            self.cache = cache;
+            self
+        }
     }
 
     /// Builder
-    impl<Find, Predicate, StateMut, E> Ancestors<Find, Predicate, StateMut>
+    impl<Find, Predicate, StateMut, Generation, E> Ancestors<Find, Predicate, StateMut, Generation>
     where
         Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
         StateMut: BorrowMut<State>,
+        Generation: FnMut(&oid) -> Option<u32>,
         E: std::error::Error + Send + Sync + 'static,
     {
-        /// Set the sorting method, either topological or by author date
+        /// Set the sorting method, either breadth first, topological or by commit date
         pub fn sorting(mut self, sorting: Sorting) -> Result<Self, Error> {
             self.sorting = sorting;
-            if !matches!(self.sorting, Sorting::Topological) {
+            if matches!(self.sorting, Sorting::ByCommitTimeNewestFirst) {
                 let state = self.state.borrow_mut();
                 for (commit_id, commit_time) in state.next.iter_mut() {
-                    let commit_iter = (self.find)(commit_id, &mut state.buf).map_err(|err| Error::FindExisting {
-                        oid: *commit_id,
-                        source: err.into(),
-                    })?;
-                    *commit_time = commit_iter.committer()?.time.seconds_since_unix_epoch;
+                    *commit_time = commit_time_of(self.cache.as_ref(), &mut self.find, commit_id, &mut state.buf)?;
                 }
                 let mut v = Vec::from_iter(std::mem::take(&mut state.next).into_iter());
                 v.sort_by(|a, b| a.1.cmp(&b.1).reverse());
@@ -120,6 +208,136 @@ pub mod ancestors {
             }
             Ok(self)
         }
+
+        /// Don't return any commit that is reachable from the `hidden` commits, including the `hidden` commits themselves,
+        /// similar to `git log ^hidden tips`.
+        ///
+        /// The ancestry of `hidden` and of the tips is walked together, newest first as determined by their
+        /// [generation numbers][Self::generations()], until only hidden commits are left to look at. With generation numbers
+        /// available for all commits this is exact, and the walk ends as soon as possible. For commits without generation number
+        /// the commit time is used instead, which may cause hidden commits to be returned if clocks were skewed.
+        pub fn with_hidden(mut self, hidden: impl IntoIterator<Item = impl Into<ObjectId>>) -> Result<Self, Error> {
+            #[derive(Copy, Clone)]
+            struct Flags {
+                hidden: bool,
+                queued: bool,
+            }
+            let state = self.state.borrow_mut();
+            let mut flags = hash_hasher::HashedMap::<ObjectId, Flags>::default();
+            let mut queue = BinaryHeap::<(u32, TimeInSeconds, ObjectId)>::new();
+            let mut interesting_in_queue = 0_usize;
+
+            let tips: Vec<_> = state.next.iter().map(|(id, _)| (*id, false)).collect();
+            let hidden = hidden.into_iter().map(|id| (id.into(), true));
+            for (id, is_hidden) in tips.into_iter().chain(hidden) {
+                let flags = flags.entry(id).or_insert(Flags {
+                    hidden: is_hidden,
+                    queued: false,
+                });
+                if is_hidden && !flags.hidden {
+                    flags.hidden = true;
+                    if flags.queued {
+                        interesting_in_queue -= 1;
+                    }
+                }
+                if !flags.queued {
+                    flags.queued = true;
+                    if !flags.hidden {
+                        interesting_in_queue += 1;
+                    }
+                    queue.push(queue_key(
+                        self.cache.as_ref(),
+                        &mut self.find,
+                        &mut self.generation,
+                        &mut state.buf,
+                        id,
+                    )?);
+                }
+            }
+
+            let mut parent_ids = Vec::new();
+            while interesting_in_queue != 0 {
+                let (_, _, id) = match queue.pop() {
+                    Some(item) => item,
+                    None => break,
+                };
+                let is_hidden = {
+                    let flags = flags.get_mut(&id).expect("queued items have flags");
+                    flags.queued = false;
+                    flags.hidden
+                };
+                if !is_hidden {
+                    interesting_in_queue -= 1;
+                }
+                collect_parents(
+                    self.cache.as_ref(),
+                    &mut self.find,
+                    &self.shallow,
+                    Parents::All,
+                    &id,
+                    &mut state.buf,
+                    &mut parent_ids,
+                )?;
+                for parent_id in parent_ids.drain(..) {
+                    match flags.get_mut(&parent_id) {
+                        Some(flags) => {
+                            if is_hidden && !flags.hidden {
+                                flags.hidden = true;
+                                if flags.queued {
+                                    interesting_in_queue -= 1;
+                                }
+                            }
+                        }
+                        None => {
+                            flags.insert(
+                                parent_id,
+                                Flags {
+                                    hidden: is_hidden,
+                                    queued: true,
+                                },
+                            );
+                            if !is_hidden {
+                                interesting_in_queue += 1;
+                            }
+                            queue.push(queue_key(
+                                self.cache.as_ref(),
+                                &mut self.find,
+                                &mut self.generation,
+                                &mut state.parents_buf,
+                                parent_id,
+                            )?);
+                        }
+                    }
+                }
+            }
+
+            let hidden: hash_hasher::HashedSet<_> = flags
+                .into_iter()
+                .filter(|(_, flags)| flags.hidden)
+                .map(|(id, _)| id)
+                .collect();
+            state.next.retain(|(id, _)| !hidden.contains(id));
+            state.seen.extend(hidden);
+            Ok(self)
+        }
+    }
+
+    fn queue_key<Find, Generation, E>(
+        cache: Option<&git_commitgraph::Graph>,
+        find: &mut Find,
+        generation: &mut Generation,
+        buf: &mut Vec<u8>,
+        id: ObjectId,
+    ) -> Result<(u32, TimeInSeconds, ObjectId), Error>
+    where
+        Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
+        Generation: FnMut(&oid) -> Option<u32>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Ok(match generation_of(cache, generation, &id) {
+            Some(generation) => (generation, 0, id),
+            None => (u32::MAX, commit_time_of(cache, find, &id, buf)?, id),
+        })
     }
 
     /// Initialization
@@ -188,11 +406,20 @@ pub mod ancestors {
                 state,
                 parents: Default::default(),
                 sorting: Default::default(),
+                generation: unknown_generation,
+                generation_cutoff: None,
+                shallow: Default::default(),
+                cache: None,
+                children_counted: false,
             }
         }
     }
+
+    fn unknown_generation(_id: &oid) -> Option<u32> {
+        None
+    }
     /// Access
-    impl<Find, Predicate, StateMut> Ancestors<Find, Predicate, StateMut>
+    impl<Find, Predicate, StateMut, Generation> Ancestors<Find, Predicate, StateMut, Generation>
     where
         StateMut: Borrow<State>,
     {
@@ -202,130 +429,296 @@ pub mod ancestors {
         }
     }
 
-    impl<Find, Predicate, StateMut, E> Iterator for Ancestors<Find, Predicate, StateMut>
+    impl<Find, Predicate, StateMut, Generation, E> Iterator for Ancestors<Find, Predicate, StateMut, Generation>
     where
         Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
         Predicate: FnMut(&oid) -> bool,
         StateMut: BorrowMut<State>,
+        Generation: FnMut(&oid) -> Option<u32>,
         E: std::error::Error + Send + Sync + 'static,
     {
         type Item = Result<ObjectId, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            if matches!(self.parents, Parents::First) {
-                self.next_by_topology()
-            } else {
-                match self.sorting {
-                    Sorting::Topological => self.next_by_topology(),
-                    Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(),
-                }
+            match self.sorting {
+                Sorting::Topological => self.next_by_topological_order(),
+                _ if matches!(self.parents, Parents::First) => self.next_by_topology(),
+                Sorting::BreadthFirst => self.next_by_topology(),
+                Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(),
             }
         }
     }
 
     /// Utilities
-    impl<Find, Predicate, StateMut, E> Ancestors<Find, Predicate, StateMut>
+    impl<Find, Predicate, StateMut, Generation, E> Ancestors<Find, Predicate, StateMut, Generation>
     where
         Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
         Predicate: FnMut(&oid) -> bool,
         StateMut: BorrowMut<State>,
+        Generation: FnMut(&oid) -> Option<u32>,
         E: std::error::Error + Send + Sync + 'static,
     {
         fn next_by_commit_date(&mut self) -> Option<Result<ObjectId, Error>> {
             let state = self.state.borrow_mut();
 
             let (oid, _commit_time) = state.next.pop_front()?;
-            match (self.find)(&oid, &mut state.buf) {
-                Ok(commit_iter) => {
-                    let mut count = 0;
-                    for token in commit_iter {
-                        count += 1;
-                        let is_first = count == 1;
-                        match token {
-                            Ok(git_object::commit::ref_iter::Token::Tree { .. }) => continue,
-                            Ok(git_object::commit::ref_iter::Token::Parent { id }) => {
-                                let was_inserted = state.seen.insert(id);
-                                if !(was_inserted && (self.predicate)(&id)) {
-                                    if is_first && matches!(self.parents, Parents::First) {
-                                        break;
-                                    } else {
-                                        continue;
-                                    }
-                                }
-
-                                let parent = (self.find)(id.as_ref(), &mut state.parents_buf).ok();
-                                let parent_commit_time = parent
-                                    .and_then(|parent| {
-                                        parent
-                                            .committer()
-                                            .ok()
-                                            .map(|committer| committer.time.seconds_since_unix_epoch)
-                                    })
-                                    .unwrap_or_default();
-
-                                match state.next.binary_search_by(|c| c.1.cmp(&parent_commit_time).reverse()) {
-                                    Ok(_) => state.next.push_back((id, parent_commit_time)), // collision => topo-sort
-                                    Err(pos) => state.next.insert(pos, (id, parent_commit_time)), // otherwise insert by commit-time
-                                }
-
-                                if is_first && matches!(self.parents, Parents::First) {
-                                    break;
-                                }
-                            }
-                            Ok(_unused_token) => break,
-                            Err(err) => return Some(Err(err.into())),
-                        }
-                    }
+            let mut parent_ids = std::mem::take(&mut state.parent_ids);
+            if let Err(err) = collect_parents(
+                self.cache.as_ref(),
+                &mut self.find,
+                &self.shallow,
+                self.parents,
+                &oid,
+                &mut state.buf,
+                &mut parent_ids,
+            ) {
+                return Some(Err(err));
+            }
+            for id in parent_ids.drain(..) {
+                let was_inserted = state.seen.insert(id);
+                if !(was_inserted
+                    && !is_cut_off(self.cache.as_ref(), &mut self.generation, self.generation_cutoff, &id)
+                    && (self.predicate)(&id))
+                {
+                    continue;
                 }
-                Err(err) => {
-                    return Some(Err(Error::FindExisting {
-                        oid,
-                        source: err.into(),
-                    }))
+
+                let parent_commit_time =
+                    commit_time_of(self.cache.as_ref(), &mut self.find, &id, &mut state.parents_buf)
+                        .unwrap_or_default();
+                match state.next.binary_search_by(|c| c.1.cmp(&parent_commit_time).reverse()) {
+                    Ok(_) => state.next.push_back((id, parent_commit_time)), // collision => topo-sort
+                    Err(pos) => state.next.insert(pos, (id, parent_commit_time)), // otherwise insert by commit-time
                 }
             }
+            state.parent_ids = parent_ids;
             Some(Ok(oid))
         }
     }
 
     /// Utilities
-    impl<Find, Predicate, StateMut, E> Ancestors<Find, Predicate, StateMut>
+    impl<Find, Predicate, StateMut, Generation, E> Ancestors<Find, Predicate, StateMut, Generation>
     where
         Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
         Predicate: FnMut(&oid) -> bool,
         StateMut: BorrowMut<State>,
+        Generation: FnMut(&oid) -> Option<u32>,
         E: std::error::Error + Send + Sync + 'static,
     {
         fn next_by_topology(&mut self) -> Option<Result<ObjectId, Error>> {
             let state = self.state.borrow_mut();
             let (oid, _commit_time) = state.next.pop_front()?;
-            match (self.find)(&oid, &mut state.buf) {
-                Ok(commit_iter) => {
-                    for token in commit_iter {
-                        match token {
-                            Ok(git_object::commit::ref_iter::Token::Tree { .. }) => continue,
-                            Ok(git_object::commit::ref_iter::Token::Parent { id }) => {
-                                let was_inserted = state.seen.insert(id);
-                                if was_inserted && (self.predicate)(&id) {
-                                    state.next.push_back((id, 0));
-                                }
-                                if matches!(self.parents, Parents::First) {
-                                    break;
-                                }
+            let mut parent_ids = std::mem::take(&mut state.parent_ids);
+            if let Err(err) = collect_parents(
+                self.cache.as_ref(),
+                &mut self.find,
+                &self.shallow,
+                self.parents,
+                &oid,
+                &mut state.buf,
+                &mut parent_ids,
+            ) {
+                return Some(Err(err));
+            }
+            for id in parent_ids.drain(..) {
+                let was_inserted = state.seen.insert(id);
+                if was_inserted
+                    && !is_cut_off(self.cache.as_ref(), &mut self.generation, self.generation_cutoff, &id)
+                    && (self.predicate)(&id)
+                {
+                    state.next.push_back((id, 0));
+                }
+            }
+            state.parent_ids = parent_ids;
+            Some(Ok(oid))
+        }
+    }
+
+    /// Utilities
+    impl<Find, Predicate, StateMut, Generation, E> Ancestors<Find, Predicate, StateMut, Generation>
+    where
+        Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
+        Predicate: FnMut(&oid) -> bool,
+        StateMut: BorrowMut<State>,
+        Generation: FnMut(&oid) -> Option<u32>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        fn next_by_topological_order(&mut self) -> Option<Result<ObjectId, Error>> {
+            if !self.children_counted {
+                self.children_counted = true;
+                if let Err(err) = self.count_children() {
+                    self.state.borrow_mut().next.clear();
+                    return Some(Err(err));
+                }
+            }
+            let state = self.state.borrow_mut();
+            let (oid, _) = state.next.pop_back()?;
+            let mut parent_ids = std::mem::take(&mut state.parent_ids);
+            if let Err(err) = collect_parents(
+                self.cache.as_ref(),
+                &mut self.find,
+                &self.shallow,
+                self.parents,
+                &oid,
+                &mut state.buf,
+                &mut parent_ids,
+            ) {
+                return Some(Err(err));
+            }
+            // The stack is popped from the back, so the first parent must be pushed last to be visited next.
+            for id in parent_ids.drain(..).rev() {
+                if let Some(children) = state.children.get_mut(&id) {
+                    *children -= 1;
+                    if *children == 0 {
+                        state.next.push_back((id, 0));
+                    }
+                }
+            }
+            state.parent_ids = parent_ids;
+            Some(Ok(oid))
+        }
+
+        /// Walk all commits to return and count their children among them, and keep only the tips without children
+        /// as the commits to return first.
+        fn count_children(&mut self) -> Result<(), Error> {
+            let state = self.state.borrow_mut();
+            let mut queue: VecDeque<_> = state.next.iter().map(|(id, _)| *id).collect();
+            state.children.extend(queue.iter().map(|id| (*id, 0)));
+            let mut parent_ids = std::mem::take(&mut state.parent_ids);
+            while let Some(id) = queue.pop_front() {
+                collect_parents(
+                    self.cache.as_ref(),
+                    &mut self.find,
+                    &self.shallow,
+                    self.parents,
+                    &id,
+                    &mut state.buf,
+                    &mut parent_ids,
+                )?;
+                for parent_id in parent_ids.drain(..) {
+                    match state.children.get_mut(&parent_id) {
+                        Some(children) => *children += 1,
+                        None => {
+                            if state.seen.insert(parent_id)
+                                && !is_cut_off(
+                                    self.cache.as_ref(),
+                                    &mut self.generation,
+                                    self.generation_cutoff,
+                                    &parent_id,
+                                )
+                                && (self.predicate)(&parent_id)
+                            {
+                                state.children.insert(parent_id, 1);
+                                queue.push_back(parent_id);
                             }
-                            Ok(_a_token_past_the_parents) => break,
-                            Err(err) => return Some(Err(err.into())),
                         }
                     }
                 }
-                Err(err) => {
-                    return Some(Err(Error::FindExisting {
-                        oid,
-                        source: err.into(),
-                    }))
+            }
+            state.parent_ids = parent_ids;
+            let children = &state.children;
+            state.next.retain(|(id, _)| children.get(id) == Some(&0));
+            state.next.make_contiguous().reverse();
+            Ok(())
+        }
+    }
+
+    /// Write the parents of the commit `id` into `out`, or only its first parent if `mode` is [`Parents::First`],
+    /// or none at all if it's a `shallow` commit.
+    ///
+    /// The parents are read from the commit-graph `cache` if it contains the commit, which leaves `buf` empty,
+    /// or by decoding the commit obtained with `find()` into `buf` otherwise.
+    fn collect_parents<Find, E>(
+        cache: Option<&git_commitgraph::Graph>,
+        find: &mut Find,
+        shallow: &hash_hasher::HashedSet<ObjectId>,
+        mode: Parents,
+        id: &oid,
+        buf: &mut Vec<u8>,
+        out: &mut Vec<ObjectId>,
+    ) -> Result<(), Error>
+    where
+        Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        out.clear();
+        if let Some((graph, commit)) = cache.and_then(|graph| graph.commit_by_id(id).map(|commit| (graph, commit))) {
+            buf.clear();
+            if shallow.contains(id) {
+                return Ok(());
+            }
+            for pos in commit.iter_parents() {
+                out.push(graph.id_at(pos?).to_owned());
+                if matches!(mode, Parents::First) {
+                    break;
                 }
             }
-            Some(Ok(oid))
+            return Ok(());
+        }
+
+        let commit_iter = find(id, buf).map_err(|err| Error::FindExisting {
+            oid: id.to_owned(),
+            source: err.into(),
+        })?;
+        if shallow.contains(id) {
+            return Ok(());
         }
+        for token in commit_iter {
+            match token? {
+                git_object::commit::ref_iter::Token::Tree { .. } => continue,
+                git_object::commit::ref_iter::Token::Parent { id } => {
+                    out.push(id);
+                    if matches!(mode, Parents::First) {
+                        break;
+                    }
+                }
+                _a_token_past_the_parents => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the commit time of `id`, preferably from the commit-graph `cache`.
+    fn commit_time_of<Find, E>(
+        cache: Option<&git_commitgraph::Graph>,
+        find: &mut Find,
+        id: &oid,
+        buf: &mut Vec<u8>,
+    ) -> Result<TimeInSeconds, Error>
+    where
+        Find: for<'a> FnMut(&oid, &'a mut Vec<u8>) -> Result<CommitRefIter<'a>, E>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if let Some(commit) = cache.and_then(|graph| graph.commit_by_id(id)) {
+            return Ok(commit.committer_timestamp() as TimeInSeconds);
+        }
+        let commit_iter = find(id, buf).map_err(|err| Error::FindExisting {
+            oid: id.to_owned(),
+            source: err.into(),
+        })?;
+        Ok(commit_iter.committer()?.time.seconds_since_unix_epoch)
+    }
+
+    /// Return the generation number of `id`, preferably from the commit-graph `cache`.
+    fn generation_of(
+        cache: Option<&git_commitgraph::Graph>,
+        generation: &mut impl FnMut(&oid) -> Option<u32>,
+        id: &oid,
+    ) -> Option<u32> {
+        cache
+            .and_then(|graph| graph.commit_by_id(id))
+            .map(|commit| commit.generation())
+            .or_else(|| generation(id))
+    }
+
+    fn is_cut_off(
+        cache: Option<&git_commitgraph::Graph>,
+        generation: &mut impl FnMut(&oid) -> Option<u32>,
+        cutoff: Option<u32>,
+        id: &oid,
+    ) -> bool {
+        cutoff.map_or(false, |cutoff| {
+            generation_of(cache, generation, id).map_or(false, |generation| generation <= cutoff)
+        })
     }
 }
@@ -66,14 +66,18 @@ mod ancestor {
 
         fn check(&self) -> crate::Result {
             let (store, tips, expected) = self.setup()?;
-            let oids: Result<Vec<_>, _> =
-                commit::Ancestors::new(tips, commit::ancestors::State::default(), move |oid, buf| {
-                    store.find_commit_iter(oid, buf).map(|t| t.0)
-                })
-                .sorting(self.sorting)?
-                .parents(self.mode)
-                .collect();
-            assert_eq!(oids?, expected);
+            let commit_graph = git_commitgraph::Graph::from_info_dir(store.store_ref().path().join("info")).ok();
+            for cache in [None, commit_graph] {
+                let oids: Result<Vec<_>, _> =
+                    commit::Ancestors::new(tips.clone(), commit::ancestors::State::default(), |oid, buf| {
+                        store.find_commit_iter(oid, buf).map(|t| t.0)
+                    })
+                    .commit_graph(cache)
+                    .sorting(self.sorting)?
+                    .parents(self.mode)
+                    .collect();
+                assert_eq!(oids?, expected);
+            }
             Ok(())
         }
     }
@@ -207,6 +211,54 @@ mod ancestor {
         .check()
     }
 
+    #[test]
+    fn topologically_sorted_commits_follow_each_line_of_history_to_its_end() -> crate::Result {
+        TraversalAssertion::new(
+            "make_traversal_repo_for_commits.sh",
+            &["01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"],
+            &[
+                "efd9a841189668f1bab5b8ebade9cd0a1b139a37",
+                "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353",
+                "9152eeee2328073cf23dcf8e90c949170b711659",
+                "9556057aee5abb06912922e9f26c46386a816822",
+                "17d78c64cef6c33a10a604573fd2c429e477fd63",
+                "9902e3c3e8f0c569b4ab295ddf473e6de763e1e7",
+                "134385f6d781b7e97062102c6a483440bfda2a03",
+            ],
+        )
+        .with_sorting(commit::Sorting::Topological)
+        .check()
+    }
+
+    #[test]
+    fn topologically_sorted_commits_come_after_all_their_descendants() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_traversal_repo_for_commits.sh")?;
+        let store = git_odb::at(dir.join(".git").join("objects"))?;
+        let tips = [
+            hex_to_id("9556057aee5abb06912922e9f26c46386a816822"),
+            hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"),
+        ];
+        let actual: Vec<_> = commit::Ancestors::new(tips, commit::ancestors::State::default(), |oid, buf| {
+            store.find_commit_iter(oid, buf).map(|t| t.0)
+        })
+        .sorting(commit::Sorting::Topological)?
+        .collect::<Result<_, _>>()?;
+        assert_eq!(actual.len(), 8, "each commit is returned once");
+        assert_eq!(actual[0], tips[1], "tips that are ancestors of other tips are deferred");
+
+        let mut buf = Vec::new();
+        for (pos, id) in actual.iter().enumerate() {
+            for parent in store.find_commit_iter(id, &mut buf)?.0.parent_ids() {
+                let parent_pos = actual
+                    .iter()
+                    .position(|id| *id == parent)
+                    .expect("all parents are returned");
+                assert!(parent_pos > pos, "{} is returned before its child {}", parent, id);
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn committer_date_sorted_commits() -> crate::Result {
         TraversalAssertion::new(
@@ -237,3 +289,230 @@ mod ancestor {
         .check()
     }
 }
+
+mod with_commit_graph {
+    use git_hash::ObjectId;
+    use git_odb::pack::FindExt;
+    use git_traverse::commit;
+
+    use crate::hex_to_id;
+
+    const MERGE: &str = "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b";
+    const C5: &str = "efd9a841189668f1bab5b8ebade9cd0a1b139a37";
+    const B1C2: &str = "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353";
+    const B1C1: &str = "9152eeee2328073cf23dcf8e90c949170b711659";
+    const C4: &str = "9556057aee5abb06912922e9f26c46386a816822";
+
+    fn setup() -> crate::Result<(git_odb::Handle, git_commitgraph::Graph)> {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_traversal_repo_for_commits.sh")?;
+        let store = git_odb::at(dir.join(".git").join("objects"))?;
+        let graph = git_commitgraph::Graph::from_info_dir(dir.join(".git").join("objects").join("info"))?;
+        Ok((store, graph))
+    }
+
+    fn ids(hex: &[&str]) -> Vec<ObjectId> {
+        hex.iter().copied().map(hex_to_id).collect()
+    }
+
+    #[test]
+    fn commits_in_the_commit_graph_are_never_decoded() -> crate::Result {
+        let (store, _graph) = setup()?;
+        for sorting in [
+            commit::Sorting::BreadthFirst,
+            commit::Sorting::Topological,
+            commit::Sorting::ByCommitTimeNewestFirst,
+        ] {
+            let expected: Vec<_> = commit::Ancestors::new(
+                Some(hex_to_id(MERGE)),
+                commit::ancestors::State::default(),
+                |oid, buf| store.find_commit_iter(oid, buf).map(|t| t.0),
+            )
+            .sorting(sorting)?
+            .collect::<Result<_, _>>()?;
+
+            let (_store, graph) = setup()?;
+            let mut lookups = 0;
+            let actual: Vec<_> = commit::Ancestors::new(
+                Some(hex_to_id(MERGE)),
+                commit::ancestors::State::default(),
+                |oid, buf| {
+                    lookups += 1;
+                    store.find_commit_iter(oid, buf).map(|t| t.0)
+                },
+            )
+            .commit_graph(Some(graph))
+            .sorting(sorting)?
+            .with_hidden(Some(hex_to_id(C4)))?
+            .collect::<Result<_, _>>()?;
+            assert_eq!(lookups, 0, "everything is read from the commit-graph");
+            assert_eq!(
+                actual,
+                expected
+                    .into_iter()
+                    .filter(|id| ids(&[MERGE, C5, B1C2, B1C1]).contains(id))
+                    .collect::<Vec<_>>(),
+                "the order is the same as without the commit-graph"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generation_cutoff_prunes_parents_at_or_below_the_given_generation() -> crate::Result {
+        let (store, graph) = setup()?;
+        let c4_generation = graph.commit_by_id(hex_to_id(C4)).expect("present").generation();
+        assert_eq!(c4_generation, 4);
+
+        let mut lookups = Vec::new();
+        let actual: Vec<_> = commit::Ancestors::new(
+            Some(hex_to_id(MERGE)),
+            commit::ancestors::State::default(),
+            |oid, buf| {
+                lookups.push(oid.to_owned());
+                store.find_commit_iter(oid, buf).map(|t| t.0)
+            },
+        )
+        .generations(|id| graph.commit_by_id(id).map(|c| c.generation()))
+        .with_generation_cutoff(c4_generation)
+        .collect::<Result<_, _>>()?;
+        assert_eq!(actual, ids(&[MERGE, C5, B1C2, B1C1]));
+        assert_eq!(
+            lookups, actual,
+            "commits that are cut off are never looked up, only the ones that are returned"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generation_cutoff_without_generations_has_no_effect() -> crate::Result {
+        let (store, _graph) = setup()?;
+        let actual = commit::Ancestors::new(
+            Some(hex_to_id(MERGE)),
+            commit::ancestors::State::default(),
+            |oid, buf| store.find_commit_iter(oid, buf).map(|t| t.0),
+        )
+        .with_generation_cutoff(4)
+        .count();
+        assert_eq!(actual, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_commits_and_their_ancestors_are_not_returned() -> crate::Result {
+        let (store, graph) = setup()?;
+        for sorting in [
+            commit::Sorting::BreadthFirst,
+            commit::Sorting::Topological,
+            commit::Sorting::ByCommitTimeNewestFirst,
+        ] {
+            let mut lookups = 0;
+            let actual: Vec<_> = commit::Ancestors::new(
+                Some(hex_to_id(MERGE)),
+                commit::ancestors::State::default(),
+                |oid, buf| {
+                    lookups += 1;
+                    store.find_commit_iter(oid, buf).map(|t| t.0)
+                },
+            )
+            .generations(|id| graph.commit_by_id(id).map(|c| c.generation()))
+            .sorting(sorting)?
+            .with_hidden(Some(hex_to_id(B1C1)))?
+            .collect::<Result<_, _>>()?;
+            assert_eq!(actual, ids(&[MERGE, C5, B1C2]));
+            assert!(
+                lookups < 8 * 2,
+                "the walk stops once only hidden commits are left, without visiting the root commits ({} lookups)",
+                lookups
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_tips_are_not_returned() -> crate::Result {
+        let (store, graph) = setup()?;
+        let actual = commit::Ancestors::new(ids(&[B1C1, C4]), commit::ancestors::State::default(), |oid, buf| {
+            store.find_commit_iter(oid, buf).map(|t| t.0)
+        })
+        .generations(|id| graph.commit_by_id(id).map(|c| c.generation()))
+        .with_hidden(Some(hex_to_id(MERGE)))?
+        .count();
+        assert_eq!(actual, 0, "all tips are reachable from the hidden commit");
+        Ok(())
+    }
+}
+
+mod shallow {
+    use git_hash::ObjectId;
+    use git_odb::pack::FindExt;
+    use git_traverse::commit;
+
+    use crate::hex_to_id;
+
+    const MERGE: &str = "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b";
+    const C5: &str = "efd9a841189668f1bab5b8ebade9cd0a1b139a37";
+    const B1C2: &str = "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353";
+    const B1C1: &str = "9152eeee2328073cf23dcf8e90c949170b711659";
+    const C4: &str = "9556057aee5abb06912922e9f26c46386a816822";
+
+    fn ids(hex: &[&str]) -> Vec<ObjectId> {
+        hex.iter().copied().map(hex_to_id).collect()
+    }
+
+    #[test]
+    fn parents_of_shallow_commits_are_never_looked_up() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_traversal_repo_for_commits.sh")?;
+        let store = git_odb::at(dir.join(".git").join("objects"))?;
+        let available = ids(&[MERGE, C5, B1C2, B1C1, C4]);
+        for sorting in [
+            commit::Sorting::BreadthFirst,
+            commit::Sorting::Topological,
+            commit::Sorting::ByCommitTimeNewestFirst,
+        ] {
+            let mut actual: Vec<_> = commit::Ancestors::new(
+                Some(hex_to_id(MERGE)),
+                commit::ancestors::State::default(),
+                |oid, buf| {
+                    assert!(
+                        available.iter().any(|id| id.as_ref() == oid),
+                        "{} is behind the shallow boundary and must not be looked up",
+                        oid
+                    );
+                    store.find_commit_iter(oid, buf).map(|t| t.0)
+                },
+            )
+            .with_shallow_commits(ids(&[C4, B1C1]))
+            .sorting(sorting)?
+            .collect::<Result<_, _>>()?;
+            actual.sort();
+            let mut expected = available.clone();
+            expected.sort();
+            assert_eq!(actual, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_commits_respect_the_shallow_boundary() -> crate::Result {
+        let dir = git_testtools::scripted_fixture_repo_read_only("make_traversal_repo_for_commits.sh")?;
+        let store = git_odb::at(dir.join(".git").join("objects"))?;
+        let available = ids(&[MERGE, C5, B1C2, B1C1, C4]);
+        let actual: Vec<_> = commit::Ancestors::new(
+            Some(hex_to_id(MERGE)),
+            commit::ancestors::State::default(),
+            |oid, buf| {
+                assert!(
+                    available.iter().any(|id| id.as_ref() == oid),
+                    "{} must not be looked up",
+                    oid
+                );
+                store.find_commit_iter(oid, buf).map(|t| t.0)
+            },
+        )
+        .with_shallow_commits(ids(&[C4, B1C1]))
+        .with_hidden(Some(hex_to_id(B1C2)))?
+        .collect::<Result<_, _>>()?;
+        assert_eq!(actual, ids(&[MERGE, C5, C4]));
+        Ok(())
+    }
+}
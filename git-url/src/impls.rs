@@ -1,4 +1,9 @@
-use std::{convert::TryFrom, path::PathBuf};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
 
 use bstr::BStr;
 
@@ -12,10 +17,44 @@ impl Default for Url {
             host: None,
             port: None,
             path: bstr::BString::default(),
+            serialize_alternative_form: false,
         }
     }
 }
 
+impl Url {
+    /// All fields which identify the location the url points to, leaving out how it is serialized.
+    fn location(&self) -> (&Scheme, &Option<String>, &Option<String>, &Option<u16>, &bstr::BString) {
+        (&self.scheme, &self.user, &self.host, &self.port, &self.path)
+    }
+}
+
+impl PartialEq for Url {
+    fn eq(&self, other: &Self) -> bool {
+        self.location() == other.location()
+    }
+}
+
+impl Eq for Url {}
+
+impl Hash for Url {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.location().hash(state)
+    }
+}
+
+impl PartialOrd for Url {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Url {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.location().cmp(&other.location())
+    }
+}
+
 impl TryFrom<&str> for Url {
     type Error = parse::Error;
 
@@ -29,7 +29,10 @@ pub use scheme::Scheme;
 /// (_see the `Display::fmt()` implementation_).
 ///
 /// Note that we do not support passing the password using the URL as it's likely leading to accidents.
-#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+///
+/// Comparisons and hashing ignore [`serialize_alternative_form`][Url::serialize_alternative_form], so urls that only differ
+/// in the way they are written are equal.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
 pub struct Url {
     /// The URL scheme.
@@ -42,6 +45,11 @@ pub struct Url {
     pub port: Option<u16>,
     /// The path portion of the URL, usually the location of the git repository.
     pub path: bstr::BString,
+    /// If true, the URL is serialized the way it was written if it isn't a URL, that is as plain path for [`Scheme::File`]
+    /// or as scp-like `[user@]host:path` for [`Scheme::Ssh`], instead of using the URL form with `://`.
+    ///
+    /// It's set by [`parse()`] if the input had such an alternative form, to allow serializing it losslessly.
+    pub serialize_alternative_form: bool,
 }
 
 /// Instantiation
@@ -61,6 +69,7 @@ impl Url {
                 host,
                 port,
                 path,
+                serialize_alternative_form: false,
             }
             .to_bstring()
             .as_ref(),
@@ -111,7 +120,25 @@ impl Url {
 /// Serialization
 impl Url {
     /// Write this URL losslessly to `out`, ready to be parsed again.
+    ///
+    /// If [`serialize_alternative_form`][Url::serialize_alternative_form] is set, file urls are written as path and
+    /// ssh urls without port are written in the scp-like form.
     pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        if self.serialize_alternative_form {
+            match (&self.scheme, &self.host, self.port) {
+                (Scheme::File, None, None) => return out.write_all(&self.path),
+                (Scheme::Ssh, Some(host), None) => {
+                    if let Some(user) = &self.user {
+                        out.write_all(user.as_bytes())?;
+                        out.write_all(b"@")?;
+                    }
+                    out.write_all(host.as_bytes())?;
+                    out.write_all(b":")?;
+                    return out.write_all(self.path.strip_prefix(b"/").unwrap_or(&self.path));
+                }
+                _ => {}
+            }
+        }
         out.write_all(self.scheme.as_str().as_bytes())?;
         out.write_all(b"://")?;
         match (&self.user, &self.host) {
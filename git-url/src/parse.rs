@@ -79,6 +79,7 @@ fn to_owned_url(url: url::Url) -> Result<crate::Url, Error> {
         host: url.host_str().map(Into::into),
         port: url.port(),
         path: url.path().into(),
+        serialize_alternative_form: false,
     })
 }
 
@@ -91,19 +92,23 @@ fn to_owned_url(url: url::Url) -> Result<crate::Url, Error> {
 pub fn parse(input: &BStr) -> Result<crate::Url, Error> {
     let guessed_protocol = guess_protocol(input);
     if possibly_strip_file_protocol(input) != input || (has_no_explicit_protocol(input) && guessed_protocol == "file") {
+        let path = possibly_strip_file_protocol(input);
         return Ok(crate::Url {
             scheme: Scheme::File,
-            path: possibly_strip_file_protocol(input).into(),
+            serialize_alternative_form: path == input,
+            path: path.into(),
             ..Default::default()
         });
     }
 
     let url_str = std::str::from_utf8(input)?;
+    let mut is_scp_like = false;
     let mut url = match url::Url::parse(url_str) {
         Ok(url) => url,
         Err(::url::ParseError::RelativeUrlWithoutBase) => {
             // happens with bare paths as well as scp like paths. The latter contain a ':' past the host portion,
             // which we are trying to detect.
+            is_scp_like = true;
             url::Url::parse(&format!(
                 "{}://{}",
                 guessed_protocol,
@@ -115,6 +120,7 @@ pub fn parse(input: &BStr) -> Result<crate::Url, Error> {
     // SCP like URLs without user parse as 'something' with the scheme being the 'host'. Hosts always have dots.
     if url.scheme().find('.').is_some() {
         // try again with prefixed protocol
+        is_scp_like = true;
         url = url::Url::parse(&format!("ssh://{}", sanitize_for_protocol("ssh", url_str)))?;
     }
     if url.scheme() != "rad" && url.path().is_empty() {
@@ -124,5 +130,7 @@ pub fn parse(input: &BStr) -> Result<crate::Url, Error> {
         return Err(Error::RelativeUrl { url: url.into() });
     }
 
-    to_owned_url(url)
+    let mut url = to_owned_url(url)?;
+    url.serialize_alternative_form = is_scp_like;
+    Ok(url)
 }
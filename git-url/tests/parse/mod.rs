@@ -4,6 +4,10 @@ use git_url::Scheme;
 fn assert_url_and(url: &str, expected: git_url::Url) -> Result<git_url::Url, crate::Error> {
     let actual = git_url::parse(url.into())?;
     assert_eq!(actual, expected);
+    assert_eq!(
+        actual.serialize_alternative_form, expected.serialize_alternative_form,
+        "equality doesn't consider the serialization form"
+    );
     if actual.scheme.as_str().starts_with("http") {
         assert!(
             actual.path.starts_with_str("/"),
@@ -43,6 +47,37 @@ fn url(
     .expect("valid")
 }
 
+fn url_alternate(
+    protocol: Scheme,
+    user: impl Into<Option<&'static str>>,
+    host: impl Into<Option<&'static str>>,
+    port: impl Into<Option<u16>>,
+    path: &'static [u8],
+) -> git_url::Url {
+    let mut url = url(protocol, user, host, port, path);
+    url.serialize_alternative_form = true;
+    url
+}
+
+#[test]
+fn urls_only_differing_in_their_serialization_form_are_equal() -> crate::Result {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let scp_like = git_url::parse("user@host.xz:path/to/repo.git".into())?;
+    let url_form = git_url::parse("ssh://user@host.xz/path/to/repo.git".into())?;
+    assert_ne!(scp_like.to_bstring(), url_form.to_bstring());
+    assert_eq!(scp_like, url_form);
+    assert_eq!(scp_like.cmp(&url_form), std::cmp::Ordering::Equal);
+    let hash = |url: &git_url::Url| {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&scp_like), hash(&url_form));
+    Ok(())
+}
+
 mod file;
 mod invalid;
 mod ssh;
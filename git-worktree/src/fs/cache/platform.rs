@@ -152,16 +152,34 @@ fn create_leading_directory(
             if meta.is_dir() {
                 Ok(())
             } else if unlink_on_collision {
-                if meta.file_type().is_symlink() {
-                    crate::os::remove_symlink(stack.current())?;
+                // Other threads may be creating the same leading directories concurrently, so they may have removed what
+                // was in the way or even replaced it with a directory already.
+                let removed = if meta.file_type().is_symlink() {
+                    crate::os::remove_symlink(stack.current())
                 } else {
-                    std::fs::remove_file(stack.current())?;
+                    std::fs::remove_file(stack.current())
+                };
+                match removed {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => {
+                        return if is_existing_dir(stack.current()) {
+                            Ok(())
+                        } else {
+                            Err(err)
+                        }
+                    }
                 }
                 #[cfg(debug_assertions)]
                 {
                     *mkdir_calls += 1;
                 }
-                std::fs::create_dir(stack.current())
+                match std::fs::create_dir(stack.current()) {
+                    Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists && is_existing_dir(stack.current()) => {
+                        Ok(())
+                    }
+                    res => res,
+                }
             } else {
                 Err(err)
             }
@@ -169,3 +187,7 @@ fn create_leading_directory(
         Err(err) => Err(err),
     }
 }
+
+fn is_existing_dir(path: &std::path::Path) -> bool {
+    path.symlink_metadata().map_or(false, |meta| meta.is_dir())
+}
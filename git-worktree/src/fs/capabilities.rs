@@ -1,4 +1,7 @@
-use std::path::Path;
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use crate::fs::Capabilities;
 
@@ -38,37 +41,101 @@ impl Default for Capabilities {
     }
 }
 
+/// Values which take precedence over [probed][Capabilities::probe()] capabilities if set, typically obtained from
+/// configuration like `core.symlinks` or `core.fileMode`.
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub struct Overrides {
+    /// Overrides [`Capabilities::precompose_unicode`], like `core.precomposeUnicode`.
+    pub precompose_unicode: Option<bool>,
+    /// Overrides [`Capabilities::ignore_case`], like `core.ignoreCase`.
+    pub ignore_case: Option<bool>,
+    /// Overrides [`Capabilities::executable_bit`], like `core.fileMode`.
+    pub executable_bit: Option<bool>,
+    /// Overrides [`Capabilities::symlink`], like `core.symlinks`.
+    pub symlink: Option<bool>,
+}
+
+impl Overrides {
+    /// Return the names of all fields in `capabilities` whose value is changed by these overrides, for diagnostics.
+    pub fn changed_fields(&self, capabilities: &Capabilities) -> Vec<&'static str> {
+        [
+            (
+                "precompose_unicode",
+                self.precompose_unicode,
+                capabilities.precompose_unicode,
+            ),
+            ("ignore_case", self.ignore_case, capabilities.ignore_case),
+            ("executable_bit", self.executable_bit, capabilities.executable_bit),
+            ("symlink", self.symlink, capabilities.symlink),
+        ]
+        .iter()
+        .filter_map(|(name, over, value)| over.filter(|over| over != value).map(|_| *name))
+        .collect()
+    }
+}
+
 impl Capabilities {
+    /// Return a copy of these capabilities with all values set in `overrides` taking precedence.
+    pub fn with_config_overrides(self, overrides: Overrides) -> Self {
+        Capabilities {
+            precompose_unicode: overrides.precompose_unicode.unwrap_or(self.precompose_unicode),
+            ignore_case: overrides.ignore_case.unwrap_or(self.ignore_case),
+            executable_bit: overrides.executable_bit.unwrap_or(self.executable_bit),
+            symlink: overrides.symlink.unwrap_or(self.symlink),
+        }
+    }
+
     /// try to determine all values in this context by probing them in the given `git_dir`, which
     /// should be on the file system the git repository is located on.
     /// `git_dir` is a typical git repository, expected to be populated with the typical files like `config`.
     ///
     /// All errors are ignored and interpreted on top of the default for the platform the binary is compiled for.
+    /// Use [`probe_with_report()`][Self::probe_with_report()] to learn which probes failed.
     pub fn probe(git_dir: impl AsRef<Path>) -> Self {
+        Self::probe_with_report(git_dir).0
+    }
+
+    /// Like [`probe()`][Self::probe()], but also return all probes that failed and thus fell back to the default of the platform.
+    ///
+    /// Files created while probing are registered as tempfiles with a name unique to the process and probe,
+    /// so they are removed even if the process is interrupted by a signal, and concurrent probes don't interfere.
+    pub fn probe_with_report(git_dir: impl AsRef<Path>) -> (Self, Vec<ProbeFailure>) {
         let root = git_dir.as_ref();
         let ctx = Capabilities::default();
-        Capabilities {
-            symlink: Self::probe_symlink(root).unwrap_or(ctx.symlink),
-            ignore_case: Self::probe_ignore_case(root).unwrap_or(ctx.ignore_case),
-            precompose_unicode: Self::probe_precompose_unicode(root).unwrap_or(ctx.precompose_unicode),
-            executable_bit: Self::probe_file_mode(root).unwrap_or(ctx.executable_bit),
-        }
+        let mut failures = Vec::new();
+        let mut handle = |capability: &'static str, res: std::io::Result<bool>, default: bool| {
+            res.unwrap_or_else(|error| {
+                failures.push(ProbeFailure { capability, error });
+                default
+            })
+        };
+        let capabilities = Capabilities {
+            symlink: handle("symlink", Self::probe_symlink(root), ctx.symlink),
+            ignore_case: handle("ignore_case", Self::probe_ignore_case(root), ctx.ignore_case),
+            precompose_unicode: handle(
+                "precompose_unicode",
+                Self::probe_precompose_unicode(root),
+                ctx.precompose_unicode,
+            ),
+            executable_bit: handle("executable_bit", Self::probe_file_mode(root), ctx.executable_bit),
+        };
+        (capabilities, failures)
     }
 
     #[cfg(unix)]
     fn probe_file_mode(root: &Path) -> std::io::Result<bool> {
-        use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
-
-        // test it exactly as we typically create executable files, not using chmod.
-        let test_path = root.join("_test_executable_bit");
-        let res = std::fs::OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .mode(0o777)
-            .open(&test_path)
-            .and_then(|f| f.metadata().map(|m| m.mode() & 0o100 == 0o100));
-        std::fs::remove_file(test_path)?;
-        res
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let test_path = root.join(format!("_test_executable_bit{}", unique_suffix()));
+        let _tempfile = create_tempfile(&test_path)?;
+        let survives = |mode: u32| -> std::io::Result<bool> {
+            std::fs::set_permissions(&test_path, std::fs::Permissions::from_mode(mode))?;
+            Ok(std::fs::metadata(&test_path)?.mode() & 0o111 == mode & 0o111)
+        };
+        // Some filesystems, like FAT mounted on Linux, report the same executable bits for all files,
+        // which is why both directions are checked.
+        Ok(survives(0o755)? && survives(0o644)?)
     }
 
     #[cfg(not(unix))]
@@ -87,36 +154,65 @@ impl Capabilities {
     }
 
     fn probe_precompose_unicode(root: &Path) -> std::io::Result<bool> {
-        let precomposed = "ä";
-        let decomposed = "a\u{308}";
-
-        let precomposed = root.join(precomposed);
-        std::fs::OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .open(&precomposed)?;
-        let res = root.join(decomposed).symlink_metadata().map(|_| true);
-        std::fs::remove_file(precomposed)?;
-        res
+        let suffix = unique_suffix();
+        let precomposed = format!("ä{}", suffix);
+        let decomposed = format!("a\u{308}{}", suffix);
+
+        let _tempfile = create_tempfile(&root.join(precomposed))?;
+        root.join(decomposed).symlink_metadata().map(|_| true).or_else(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                Ok(false)
+            } else {
+                Err(err)
+            }
+        })
     }
 
     fn probe_symlink(root: &Path) -> std::io::Result<bool> {
-        let src_path = root.join("__link_src_file");
-        std::fs::OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .open(&src_path)?;
-        let link_path = root.join("__file_link");
+        let suffix = unique_suffix();
+        let src_path = root.join(format!("__link_src_file{}", suffix));
+        let _src_tempfile = create_tempfile(&src_path)?;
+        let (_link_tempfile, link_path) = reserve_unique_path(root, "__file_link")?;
         if crate::os::create_symlink(&src_path, &link_path).is_err() {
-            std::fs::remove_file(&src_path)?;
             return Ok(false);
         }
 
-        let res = std::fs::symlink_metadata(&link_path).map(|m| m.file_type().is_symlink());
+        std::fs::symlink_metadata(&link_path).map(|m| m.file_type().is_symlink())
+    }
+}
 
-        let cleanup = crate::os::remove_symlink(&link_path).or_else(|_| std::fs::remove_file(&link_path));
-        std::fs::remove_file(&src_path).and(cleanup)?;
+/// A probe that failed as part of [`Capabilities::probe_with_report()`].
+#[derive(Debug)]
+pub struct ProbeFailure {
+    /// The name of the field in [`Capabilities`] that couldn't be probed and is set to its default.
+    pub capability: &'static str,
+    /// The error that caused the probe to fail.
+    pub error: std::io::Error,
+}
 
-        res
-    }
+/// Return a suffix for file names that is unique to this process and invocation.
+fn unique_suffix() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    format!("-{}-{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Create an empty file at `path` which is removed when the returned handle is dropped, or on signal.
+fn create_tempfile(path: &Path) -> std::io::Result<git_tempfile::Handle<git_tempfile::handle::Closed>> {
+    git_tempfile::mark_at(
+        path,
+        git_tempfile::ContainingDirectory::Exists,
+        git_tempfile::AutoRemove::Tempfile,
+    )
+}
+
+/// Return a unique path in `root` starting with `prefix`, which doesn't exist yet, along with a handle that removes
+/// whatever is created at this path when dropped, or on signal.
+fn reserve_unique_path(
+    root: &Path,
+    prefix: &str,
+) -> std::io::Result<(git_tempfile::Handle<git_tempfile::handle::Closed>, PathBuf)> {
+    let path = root.join(format!("{}{}", prefix, unique_suffix()));
+    let tempfile = create_tempfile(&path)?;
+    std::fs::remove_file(&path)?;
+    Ok((tempfile, path))
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use bstr::BStr;
 
@@ -73,9 +73,18 @@ pub struct Cache<'paths> {
 
 pub(crate) type PathOidMapping<'paths> = (&'paths BStr, git_hash::ObjectId);
 
+/// Return true if the directory at `dir` contains a `.git` directory or file, making it the root of a nested repository
+/// which is to be treated as a unit instead of being descended into or overwritten.
+pub fn is_nested_repository(dir: &Path) -> bool {
+    dir.join(".git")
+        .symlink_metadata()
+        .map_or(false, |meta| meta.is_dir() || meta.is_file())
+}
+
 ///
 pub mod cache;
 ///
 pub mod stack;
 
-mod capabilities;
+///
+pub mod capabilities;
@@ -22,18 +22,28 @@ pub struct Outcome {
     pub files_updated: usize,
     /// The amount of bytes written to disk,
     pub bytes_written: u64,
+    /// Paths that collided with entries already present on disk, sorted by path.
     pub collisions: Vec<Collision>,
+    /// Errors encountered when `keep_going` is set, sorted by path.
     pub errors: Vec<ErrorRecord>,
 }
 
 #[derive(Clone)]
 pub struct Options {
     /// capabilities of the file system
+    ///
+    /// These are typically [probed][crate::fs::Capabilities::probe()] in the checkout directory, with values set explicitly
+    /// in configuration, like `core.symlinks` or `core.fileMode`, [taking precedence][crate::fs::Capabilities::with_config_overrides()].
+    /// This way, symlinks are checked out as files if `core.symlinks` is `false`, even if the filesystem supports them.
     pub fs: crate::fs::Capabilities,
     /// If set, don't use more than this amount of threads.
     /// Otherwise, usually use as many threads as there are logical cores.
     /// A value of 0 is interpreted as no-limit
     pub thread_limit: Option<usize>,
+    /// Use only a single thread if there are fewer than this amount of entries to checkout, as starting threads isn't worth it then.
+    ///
+    /// Default 0, so that `thread_limit` alone decides. `git` uses 100, as configured by `checkout.thresholdForParallelism`.
+    pub thread_threshold: usize,
     /// If true, we assume no file to exist in the target directory, and want exclusive access to it.
     /// This should be enabled when cloning to avoid checks for freshness of files. This also enables
     /// detection of collisions based on whether or not exclusive file creation succeeds or fails.
@@ -43,6 +53,10 @@ pub struct Options {
     /// these will try to delete the existing entry.
     /// This is similar in behaviour as `git checkout --force`.
     pub overwrite_existing: bool,
+    /// If true, default false, directories containing a nested repository are removed like any other directory
+    /// when they are in the way of an entry and `overwrite_existing` is set.
+    /// Otherwise checking out such an entry fails with [`Error::NestedRepository`] to protect the nested repository.
+    pub overwrite_nested_repositories: bool,
     /// If true, default false, try to checkout as much as possible and don't abort on first error which isn't
     /// due to a conflict.
     /// The checkout operation will never fail, but count the encountered errors instead along with their paths.
@@ -67,11 +81,13 @@ impl Default for Options {
         Options {
             fs: Default::default(),
             thread_limit: None,
+            thread_threshold: 0,
             destination_is_initially_empty: false,
             keep_going: false,
             trust_ctime: true,
             check_stat: true,
             overwrite_existing: false,
+            overwrite_nested_repositories: false,
             attribute_globals: Default::default(),
         }
     }
@@ -80,6 +96,13 @@ impl Default for Options {
 pub enum Error<E: std::error::Error + Send + Sync + 'static> {
     #[error("Could not convert path to UTF8: {}", .path)]
     IllformedUtf8 { path: BString },
+    #[error("Entry at '{}' has mode {:?} which cannot be checked out", .path, .mode)]
+    UnsupportedMode {
+        mode: git_index::entry::Mode,
+        path: BString,
+    },
+    #[error("Refusing to replace the nested repository at '{}' with an entry of the index", .path)]
+    NestedRepository { path: BString },
     #[error("The clock was off when reading file related metadata after updating a file on disk")]
     Time(#[from] std::time::SystemTimeError),
     #[error("IO error while writing blob or reading file metadata or changing filetype")]
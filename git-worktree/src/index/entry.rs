@@ -26,6 +26,7 @@ pub fn checkout<Find, E>(
         },
         destination_is_initially_empty,
         overwrite_existing,
+        overwrite_nested_repositories,
         ..
     }: index::checkout::Options,
 ) -> Result<usize, index::checkout::Error<E>>
@@ -36,8 +37,24 @@ where
     let dest_relative = git_path::try_from_bstr(entry_path).map_err(|_| index::checkout::Error::IllformedUtf8 {
         path: entry_path.to_owned(),
     })?;
-    let is_dir = Some(entry.mode == git_index::entry::Mode::COMMIT || entry.mode == git_index::entry::Mode::DIR);
-    let dest = path_cache.at_path(dest_relative, is_dir, &mut *find)?.path();
+    let is_dir = match entry.mode {
+        git_index::entry::Mode::FILE | git_index::entry::Mode::FILE_EXECUTABLE | git_index::entry::Mode::SYMLINK => {
+            false
+        }
+        // Submodules are represented by an empty directory, just like git does it before they are initialized.
+        git_index::entry::Mode::COMMIT => true,
+        mode => {
+            return Err(index::checkout::Error::UnsupportedMode {
+                mode,
+                path: entry_path.to_owned(),
+            })
+        }
+    };
+    let dest = path_cache.at_path(dest_relative, Some(is_dir), &mut *find)?.path();
+    let overwrite = Overwrite {
+        existing: overwrite_existing,
+        nested_repositories: overwrite_nested_repositories,
+    };
 
     let object_size = match entry.mode {
         git_index::entry::Mode::FILE | git_index::entry::Mode::FILE_EXECUTABLE => {
@@ -58,7 +75,7 @@ where
                 options.mode(0o777);
             }
 
-            let mut file = try_write_or_unlink(dest, overwrite_existing, |p| options.open(p))?;
+            let mut file = try_write_or_unlink(dest, entry_path, overwrite, |p| options.open(p))?;
             file.write_all(obj.data)?;
 
             // For possibly existing, overwritten files, we must change the file mode explicitly.
@@ -85,11 +102,11 @@ where
                 .map_err(|_| index::checkout::Error::IllformedUtf8 { path: obj.data.into() })?;
 
             if symlink {
-                try_write_or_unlink(dest, overwrite_existing, |p| {
+                try_write_or_unlink(dest, entry_path, overwrite, |p| {
                     crate::os::create_symlink(symlink_destination, p)
                 })?;
             } else {
-                let mut file = try_write_or_unlink(dest, overwrite_existing, |p| {
+                let mut file = try_write_or_unlink(dest, entry_path, overwrite, |p| {
                     open_options(p, destination_is_initially_empty, overwrite_existing).open(&dest)
                 })?;
                 file.write_all(obj.data)?;
@@ -99,32 +116,50 @@ where
             update_fstat(entry, std::fs::symlink_metadata(&dest)?)?;
             obj.data.len()
         }
-        git_index::entry::Mode::DIR => todo!(),
-        git_index::entry::Mode::COMMIT => todo!(),
-        _ => unreachable!(),
+        git_index::entry::Mode::COMMIT => {
+            // The directory was already created by the path cache.
+            0
+        }
+        _ => unreachable!("BUG: unsupported modes are rejected earlier"),
     };
     Ok(object_size)
 }
 
+#[derive(Clone, Copy)]
+struct Overwrite {
+    existing: bool,
+    nested_repositories: bool,
+}
+
 /// Note that this works only because we assume to not race ourselves when symlinks are involved, and we do this by
 /// delaying symlink creation to the end and will always do that sequentially.
 /// It's still possible to fall for a race if other actors create symlinks in our path, but that's nothing to defend against.
-fn try_write_or_unlink<T>(
+fn try_write_or_unlink<T, E>(
     path: &Path,
-    overwrite_existing: bool,
+    entry_path: &BStr,
+    overwrite: Overwrite,
     op: impl Fn(&Path) -> std::io::Result<T>,
-) -> std::io::Result<T> {
-    if overwrite_existing {
+) -> Result<T, index::checkout::Error<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if overwrite.existing {
         match op(path) {
             Ok(res) => Ok(res),
             Err(err) if os::indicates_collision(&err) => {
-                try_unlink_path_recursively(path, &std::fs::symlink_metadata(path)?)?;
-                op(path)
+                let meta = std::fs::symlink_metadata(path)?;
+                if meta.is_dir() && !overwrite.nested_repositories && fs::is_nested_repository(path) {
+                    return Err(index::checkout::Error::NestedRepository {
+                        path: entry_path.to_owned(),
+                    });
+                }
+                try_unlink_path_recursively(path, &meta)?;
+                Ok(op(path)?)
             }
-            Err(err) => Err(err),
+            Err(err) => Err(err.into()),
         }
     } else {
-        op(path)
+        Ok(op(path)?)
     }
 }
 
@@ -169,6 +204,12 @@ fn update_fstat<E>(entry: &mut Entry, meta: std::fs::Metadata) -> Result<(), ind
 where
     E: std::error::Error + Send + Sync + 'static,
 {
+    #[cfg(unix)]
+    let ctime = {
+        use std::os::unix::fs::MetadataExt;
+        Duration::new(meta.ctime().try_into().unwrap_or_default(), meta.ctime_nsec() as u32)
+    };
+    #[cfg(not(unix))]
     let ctime = meta
         .created()
         .map_or(Ok(Duration::default()), |x| x.duration_since(std::time::UNIX_EPOCH))?;
@@ -187,5 +228,23 @@ where
         .try_into()
         .expect("by 2038 we found a solution for this");
     stat.ctime.nsecs = ctime.subsec_nanos();
+
+    // Like git, truncate values that don't fit into the index, and leave those unknown to the platform at zero.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        stat.dev = meta.dev() as u32;
+        stat.ino = meta.ino() as u32;
+        stat.uid = meta.uid();
+        stat.gid = meta.gid();
+    }
+    #[cfg(not(unix))]
+    {
+        stat.dev = 0;
+        stat.ino = 0;
+        stat.uid = 0;
+        stat.gid = 0;
+    }
+    stat.size = meta.len() as u32;
     Ok(())
 }
@@ -11,6 +11,9 @@ pub(crate) mod entry;
 /// Note that interruption still produce an `Ok(…)` value, so the caller should look at `should_interrupt` to communicate the outcome.
 /// `dir` is the directory into which to checkout the `index`.
 /// `git_dir` is the `.git` directory for reading additional per-repository configuration files.
+///
+/// `files` is advanced once per processed entry, while `bytes` is advanced by the amount of bytes written to disk, allowing to
+/// display the throughput of the operation. Both are expected to be initialized by the caller.
 #[allow(clippy::too_many_arguments)]
 pub fn checkout<Find, E>(
     index: &mut git_index::State,
@@ -55,7 +58,11 @@ where
     let (chunk_size, thread_limit, num_threads) = git_features::parallel::optimize_chunk_size_and_thread_limit(
         100,
         index.entries().len().into(),
-        options.thread_limit,
+        if index.entries().len() < options.thread_threshold {
+            Some(1)
+        } else {
+            options.thread_limit
+        },
         None,
     );
 
@@ -101,6 +108,9 @@ where
     };
 
     for (entry, entry_path) in delayed {
+        if should_interrupt.load(Ordering::Relaxed) {
+            break;
+        }
         bytes_written += chunk::checkout_entry_handle_result(
             entry,
             entry_path,
@@ -112,6 +122,10 @@ where
         )? as u64;
     }
 
+    // Threads finish their chunks in any order, and delayed entries come last, so sort to make results deterministic.
+    collisions.sort_by(|a, b| a.path.cmp(&b.path));
+    errors.sort_by(|a, b| a.path.cmp(&b.path));
+
     Ok(checkout::Outcome {
         files_updated: num_files.load(Ordering::Relaxed),
         collisions,
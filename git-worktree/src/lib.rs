@@ -12,4 +12,7 @@ pub mod fs;
 ///
 pub mod index;
 
+///
+pub mod status;
+
 pub(crate) mod os;
@@ -0,0 +1,171 @@
+use std::{io, path::Path};
+
+use git_features::hash;
+use git_hash::ObjectId;
+use git_index::entry::Mode;
+
+use crate::fs;
+
+/// The way an index entry differs from its counterpart in the worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// The entry doesn't exist in the worktree anymore, or was replaced by a directory.
+    Removed,
+    /// The kind of the entry changed, for instance a file turned into a symbolic link.
+    Type,
+    /// The entry is still of the same kind, but its content or its executable bit changed.
+    Modification {
+        /// If true, the executable bit of a file was flipped.
+        executable_bit_changed: bool,
+        /// If true, the content of the file or the target of the symbolic link differs from what's stored in the index.
+        content_changed: bool,
+    },
+}
+
+/// Options for use in [`compare_to_worktree()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Capabilities of the file system which contains the worktree.
+    ///
+    /// Executable bits are only compared if `executable_bit` is set, and symbolic links are expected to be checked out as
+    /// plain files containing the link target if `symlink` is unset.
+    pub fs: fs::Capabilities,
+    /// The kind of hash to use when hashing worktree files to compare them with the id stored in the index.
+    pub object_hash: git_hash::Kind,
+    /// The [timestamp][git_index::State::timestamp()] of the index the entries to compare belong to.
+    ///
+    /// Entries modified at or after this time are racily clean and have their content hashed even if their stat
+    /// information matches.
+    pub index_timestamp: filetime::FileTime,
+    /// If true, the creation time of files is compared as well, which is what `core.trustCTime` controls.
+    /// Can be set to false in case other tools alter the creation time in ways that interfere with our operation.
+    pub trust_ctime: bool,
+    /// If true, all stat fields are compared, otherwise only the whole-second part of the modification time and the file size
+    /// are, which is what `core.checkStat=minimal` controls.
+    pub check_stat: bool,
+}
+
+/// Compare `entry` to the file at `worktree_path` and return how it changed, or `None` if it is unchanged.
+///
+/// Stat information is compared first as configured in `options`, and only if it differs or if the entry is
+/// [racy][git_index::Entry::is_racy()] the file is read to compare its hash to the one in the index, using `buf` as storage
+/// for its content. Note that worktree filters like the conversion of line endings are not applied before hashing.
+/// Submodule entries are considered unchanged as long as their directory exists, and sparse directory entries
+/// always are unchanged.
+pub fn compare_to_worktree(
+    entry: &git_index::Entry,
+    worktree_path: &Path,
+    options: &Options,
+    buf: &mut Vec<u8>,
+) -> io::Result<Option<Change>> {
+    if entry.mode.is_sparse() {
+        return Ok(None);
+    }
+    let meta = match std::fs::symlink_metadata(worktree_path) {
+        Ok(meta) => meta,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Some(Change::Removed)),
+        Err(_err) if !worktree_path.parent().map_or(false, Path::is_dir) => return Ok(Some(Change::Removed)),
+        Err(err) => return Err(err),
+    };
+
+    let file_type = meta.file_type();
+    let is_symlink_entry = entry.mode == Mode::SYMLINK;
+    match entry.mode {
+        Mode::COMMIT if file_type.is_dir() => return Ok(None),
+        Mode::COMMIT => return Ok(Some(Change::Type)),
+        _ if file_type.is_dir() => return Ok(Some(Change::Removed)),
+        _ if file_type.is_symlink() && !is_symlink_entry => return Ok(Some(Change::Type)),
+        Mode::SYMLINK if options.fs.symlink && !file_type.is_symlink() => return Ok(Some(Change::Type)),
+        _ => {}
+    }
+
+    let executable_bit_changed =
+        !is_symlink_entry && options.fs.executable_bit && (entry.mode == Mode::FILE_EXECUTABLE) != is_executable(&meta);
+    let content_changed = if entry.stat.size != 0 && entry.stat.size != meta.len() as u32 {
+        true
+    } else if stat_matches(&entry.stat, &meta, options) && !entry.is_racy(options.index_timestamp) {
+        false
+    } else {
+        buf.clear();
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(worktree_path)?;
+            buf.extend_from_slice(&git_path::into_bstr(target));
+        } else {
+            use std::io::Read;
+            std::fs::File::open(worktree_path)?.read_to_end(buf)?;
+        }
+        hash_blob(buf, options.object_hash) != entry.id
+    };
+
+    Ok(
+        (executable_bit_changed || content_changed).then(|| Change::Modification {
+            executable_bit_changed,
+            content_changed,
+        }),
+    )
+}
+
+fn hash_blob(data: &[u8], object_hash: git_hash::Kind) -> ObjectId {
+    let mut hasher = hash::hasher(object_hash);
+    hasher.update(&git_object::encode::loose_header(git_object::Kind::Blob, data.len()));
+    hasher.update(data);
+    hasher.digest()
+}
+
+/// Compare `stat` to `meta` like `git` does, which is why fields that don't fit into the index are truncated.
+/// Note that a size of 0 never matches non-empty files, as that is how racily clean entries are smudged.
+fn stat_matches(stat: &git_index::entry::Stat, meta: &std::fs::Metadata, options: &Options) -> bool {
+    let mtime = filetime::FileTime::from_last_modification_time(meta);
+    if stat.size != meta.len() as u32 || stat.mtime.secs as i64 != mtime.unix_seconds() {
+        return false;
+    }
+    if !options.check_stat {
+        return true;
+    }
+    if stat.mtime.nsecs != mtime.nanoseconds() {
+        return false;
+    }
+    if options.trust_ctime {
+        match ctime(meta) {
+            Some(ctime)
+                if (stat.ctime.secs as i64, stat.ctime.nsecs) != (ctime.unix_seconds(), ctime.nanoseconds()) =>
+            {
+                return false
+            }
+            _ => {}
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if stat.ino != meta.ino() as u32 || stat.uid != meta.uid() || stat.gid != meta.gid() {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(unix)]
+fn ctime(meta: &std::fs::Metadata) -> Option<filetime::FileTime> {
+    use std::os::unix::fs::MetadataExt;
+    Some(filetime::FileTime::from_unix_time(
+        meta.ctime(),
+        meta.ctime_nsec() as u32,
+    ))
+}
+
+#[cfg(not(unix))]
+fn ctime(meta: &std::fs::Metadata) -> Option<filetime::FileTime> {
+    filetime::FileTime::from_creation_time(meta)
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    meta.mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &std::fs::Metadata) -> bool {
+    false
+}
@@ -0,0 +1,13 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+echo -n "content" > file
+mkdir dir
+echo -n "other content" > dir/content
+
+git add -A
+# A gitlink as it is created by `git submodule add`, the commit it points to doesn't have to exist.
+git update-index --add --cacheinfo 160000,1111111111111111111111111111111111111111,dir/submodule
+git commit -m "Commit"
@@ -46,6 +46,31 @@ fn directory_paths_are_created_in_full() {
     assert_eq!(cache.num_mkdir_calls(), 3);
 }
 
+#[test]
+fn sorted_paths_only_create_the_directories_that_changed_since_the_previous_path() -> crate::Result {
+    let (mut cache, _tmp) = new_cache();
+
+    for idx in 0..100 {
+        cache.at_path(format!("a/b/c/file{}", idx), Some(false), panic_on_find)?;
+    }
+    assert_eq!(
+        cache.num_mkdir_calls(),
+        3,
+        "each leading directory is created once, no matter how many files it contains"
+    );
+
+    for path in ["a/b/d/file", "a/b/d/other", "a/e/file", "a/file", "file"] {
+        let path = cache.at_path(path, Some(false), panic_on_find)?.path();
+        assert!(path.parent().unwrap().is_dir());
+    }
+    assert_eq!(
+        cache.num_mkdir_calls(),
+        5,
+        "only the trailing directories that differ from the previous path are created"
+    );
+    Ok(())
+}
+
 #[test]
 fn existing_directories_are_fine() -> crate::Result {
     let (mut cache, tmp) = new_cache();
@@ -98,6 +123,46 @@ fn symlinks_or_files_in_path_are_forbidden_or_unlinked_when_forced() -> crate::R
     Ok(())
 }
 
+#[test]
+fn concurrent_caches_unlinking_and_creating_the_same_directories_all_succeed() -> crate::Result {
+    const NUM_THREADS: usize = 8;
+    for _round in 0..1000 {
+        let tmp = tempdir()?;
+        std::fs::write(tmp.path().join("a"), b"")?;
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(NUM_THREADS));
+        let threads: Vec<_> = (0..NUM_THREADS)
+            .map(|idx| {
+                let root = tmp.path().to_owned();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || -> std::io::Result<()> {
+                    let mut cache = fs::Cache::new(
+                        root,
+                        fs::cache::State::for_checkout(true, Default::default()),
+                        Default::default(),
+                        Vec::new(),
+                        Default::default(),
+                    );
+                    cache.unlink_on_collision(true);
+                    barrier.wait();
+                    let path = cache
+                        .at_path(format!("a/b/c/file{}", idx), Some(false), panic_on_find)?
+                        .path();
+                    assert!(path.parent().expect("has parent").is_dir());
+                    Ok(())
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().expect("no panic")?;
+        }
+        assert!(
+            tmp.path().join("a/b/c").is_dir(),
+            "the file in the way was replaced once"
+        );
+    }
+    Ok(())
+}
+
 fn new_cache() -> (fs::Cache<'static>, TempDir) {
     let dir = tempdir().unwrap();
     let cache = fs::Cache::new(
@@ -18,5 +18,99 @@ fn from_probing_cwd() {
     );
 }
 
+#[test]
+fn probing_concurrently_does_not_interfere_or_leave_files_behind() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::File::create(dir.path().join("config")).unwrap();
+    let expected = git_worktree::fs::Capabilities::probe(dir.path());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let path = dir.path().to_owned();
+            std::thread::spawn(move || git_worktree::fs::Capabilities::probe_with_report(path))
+        })
+        .collect();
+    for handle in handles {
+        let (actual, failures) = handle.join().unwrap();
+        assert_eq!(actual, expected);
+        assert!(failures.is_empty(), "{:?}", failures);
+    }
+    assert_eq!(
+        std::fs::read_dir(dir.path()).unwrap().count(),
+        1,
+        "only the config file is left"
+    );
+}
+
+#[test]
+fn failed_probes_are_reported_and_fall_back_to_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("does-not-exist");
+    let (actual, failures) = git_worktree::fs::Capabilities::probe_with_report(&missing);
+    assert_eq!(actual, Default::default());
+    let failed: Vec<_> = failures.iter().map(|failure| failure.capability).collect();
+    assert_eq!(
+        failed,
+        if cfg!(unix) {
+            vec!["symlink", "precompose_unicode", "executable_bit"]
+        } else {
+            vec!["symlink", "precompose_unicode"]
+        },
+        "the case-sensitivity probe only checks for the existence of a file"
+    );
+    assert!(failures
+        .iter()
+        .all(|failure| failure.error.kind() == std::io::ErrorKind::NotFound));
+}
+
+#[test]
+fn config_overrides_take_precedence_over_probed_values() {
+    use git_worktree::fs::{capabilities::Overrides, Capabilities};
+    let probed = Capabilities {
+        precompose_unicode: false,
+        ignore_case: false,
+        executable_bit: true,
+        symlink: true,
+    };
+    assert_eq!(
+        probed.with_config_overrides(Overrides::default()),
+        probed,
+        "nothing changes without overrides"
+    );
+
+    let overrides = Overrides {
+        executable_bit: Some(false),
+        symlink: Some(false),
+        ignore_case: Some(false),
+        precompose_unicode: None,
+    };
+    assert_eq!(
+        probed.with_config_overrides(overrides),
+        Capabilities {
+            precompose_unicode: false,
+            ignore_case: false,
+            executable_bit: false,
+            symlink: false,
+        }
+    );
+    assert_eq!(
+        overrides.changed_fields(&probed),
+        vec!["executable_bit", "symlink"],
+        "overrides which match the probed value don't count as change"
+    );
+}
+
 mod cache;
 mod stack;
+
+#[test]
+fn executable_bit_is_probed_by_changing_the_mode_of_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::File::create(dir.path().join("config")).unwrap();
+    let (actual, failures) = git_worktree::fs::Capabilities::probe_with_report(dir.path());
+    assert!(failures.is_empty(), "{:?}", failures);
+    assert_eq!(
+        actual.executable_bit,
+        cfg!(unix),
+        "temporary directories on unix honor the executable bit, while it's never used on windows"
+    );
+}
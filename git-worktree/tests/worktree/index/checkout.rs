@@ -10,7 +10,11 @@ use std::{
 use git_features::progress;
 use git_object::bstr::ByteSlice;
 use git_odb::FindExt;
-use git_worktree::{fs::Capabilities, index, index::checkout::Collision};
+use git_worktree::{
+    fs::{capabilities::Overrides, Capabilities},
+    index,
+    index::checkout::Collision,
+};
 use tempfile::TempDir;
 
 use crate::fixture_path;
@@ -146,6 +150,91 @@ fn overwriting_files_and_lone_directories_works() {
     assert_eq!(std::fs::read(symlink).unwrap(), b"other content");
 }
 
+#[test]
+fn nested_repositories_are_not_overwritten_unless_forced() -> crate::Result {
+    for dot_git_is_file in [false, true] {
+        let mut opts = opts_from_probe();
+        opts.overwrite_existing = true;
+        opts.destination_is_initially_empty = false;
+        opts.keep_going = true;
+        let prep_dest = |d: &Path| {
+            let nested = d.join("dir").join("content"); // 'content' is a nested repository now
+            std::fs::create_dir_all(&nested)?;
+            if dot_git_is_file {
+                std::fs::write(nested.join(".git"), b"gitdir: ../somewhere-else")?;
+            } else {
+                std::fs::create_dir(nested.join(".git"))?;
+            }
+            std::fs::write(nested.join("file"), b"precious")
+        };
+
+        let (_source_tree, destination, _index, outcome) =
+            checkout_index_in_tmp_dir_opts(opts.clone(), "make_mixed_without_submodules", |_| true, prep_dest)?;
+        assert!(outcome.collisions.is_empty());
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(outcome.errors[0].path, "dir/content");
+        assert!(
+            matches!(
+                outcome.errors[0]
+                    .error
+                    .downcast_ref::<index::checkout::Error<git_odb::find::existing_object::Error<git_odb::store::find::Error>>>(),
+                Some(index::checkout::Error::NestedRepository { path }) if path == "dir/content"
+            ),
+            "the refusal is typed"
+        );
+        assert_eq!(
+            std::fs::read(destination.path().join("dir/content/file"))?,
+            b"precious",
+            "the nested repository is left untouched"
+        );
+
+        opts.overwrite_nested_repositories = true;
+        let (_source_tree, destination, _index, outcome) =
+            checkout_index_in_tmp_dir_opts(opts, "make_mixed_without_submodules", |_| true, prep_dest)?;
+        assert!(outcome.collisions.is_empty());
+        assert!(outcome.errors.is_empty());
+        assert_eq!(
+            std::fs::read(destination.path().join("dir/content"))?,
+            b"other content",
+            "if forced, nested repositories are replaced like any other directory"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn stat_of_checked_out_entries_matches_the_files_on_disk() -> crate::Result {
+    let (_source_tree, destination, index, outcome) =
+        checkout_index_in_tmp_dir(opts_from_probe(), "make_mixed_without_submodules")?;
+    assert!(outcome.collisions.is_empty());
+
+    for entry in index.entries() {
+        let path = destination.path().join(git_path::from_bstr(entry.path(&index)));
+        let meta = std::fs::symlink_metadata(&path)?;
+        let stat = &entry.stat;
+        let mtime = filetime::FileTime::from_last_modification_time(&meta);
+        assert_eq!(
+            (stat.mtime.secs as i64, stat.mtime.nsecs),
+            (mtime.unix_seconds(), mtime.nanoseconds())
+        );
+        assert_eq!(stat.size as u64, meta.len(), "the size is the one of the blob on disk");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            assert_eq!(
+                (stat.ctime.secs as i64, stat.ctime.nsecs as i64),
+                (meta.ctime(), meta.ctime_nsec())
+            );
+            assert_eq!(
+                (stat.dev, stat.ino, stat.uid, stat.gid),
+                (meta.dev() as u32, meta.ino() as u32, meta.uid(), meta.gid()),
+                "all fields are set, truncated to their size in the index"
+            );
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn symlinks_become_files_if_disabled() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -158,6 +247,29 @@ fn symlinks_become_files_if_disabled() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn symlinks_become_files_if_disabled_by_configuration_on_capable_filesystems() -> crate::Result {
+    let mut opts = opts_from_probe();
+    let overrides = Overrides {
+        symlink: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(
+        overrides.changed_fields(&opts.fs),
+        if opts.fs.symlink { vec!["symlink"] } else { vec![] }
+    );
+    opts.fs = opts.fs.with_config_overrides(overrides);
+    assert!(!opts.fs.symlink, "configuration takes precedence over what was probed");
+
+    let (source_tree, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts.clone(), "make_mixed_without_submodules")?;
+
+    assert_equality(&source_tree, &destination, opts.fs.symlink)?;
+    assert!(!std::fs::symlink_metadata(destination.path().join("dir/sub-dir/symlink"))?.is_symlink());
+    assert!(outcome.collisions.is_empty());
+    Ok(())
+}
+
 #[test]
 fn allow_or_disallow_symlinks() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -172,6 +284,52 @@ fn allow_or_disallow_symlinks() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn submodules_are_checked_out_as_empty_directories() -> crate::Result {
+    let opts = opts_from_probe();
+    let (_source_tree, destination, _index, outcome) = checkout_index_in_tmp_dir(opts, "make_mixed_with_submodule")?;
+
+    assert!(outcome.collisions.is_empty());
+    assert!(outcome.errors.is_empty());
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["dir/content", "file"]),
+    );
+    let submodule = destination.path().join("dir/submodule");
+    assert!(
+        submodule.is_dir(),
+        "a placeholder directory is created for the submodule"
+    );
+    assert_eq!(std::fs::read_dir(submodule)?.count(), 0, "and it is empty");
+    Ok(())
+}
+
+#[test]
+fn directory_entries_of_sparse_indices_are_rejected() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.thread_limit = Some(1);
+    let git_dir = fixture_path("make_mixed_without_submodules").join(".git");
+    let mut index = git_index::File::at(git_dir.join("index"), Default::default())?;
+    let odb = git_odb::at(git_dir.join("objects"))?.into_inner().into_arc()?;
+    index.entries_mut()[0].mode = git_index::entry::Mode::DIR;
+    let destination = tempfile::tempdir_in(std::env::current_dir()?)?;
+
+    let res = index::checkout(
+        &mut index,
+        destination.path(),
+        move |oid, buf| odb.find_blob(oid, buf),
+        &mut progress::Discard,
+        &mut progress::Discard,
+        &AtomicBool::default(),
+        opts,
+    );
+    assert!(matches!(
+        res,
+        Err(index::checkout::Error::UnsupportedMode { mode, path }) if mode == git_index::entry::Mode::DIR && path == "dir/content"
+    ));
+    Ok(())
+}
+
 #[test]
 fn keep_going_collects_results() {
     let mut opts = opts_from_probe();
@@ -198,7 +356,11 @@ fn keep_going_collects_results() {
             outcome.errors.len(),
             2,
             "content changes due to non-deterministic nature of racy threads"
-        )
+        );
+        assert!(
+            outcome.errors.windows(2).all(|w| w[0].path <= w[1].path),
+            "errors are sorted by path even though threads finish in any order"
+        );
     } else {
         assert_eq!(
             outcome
@@ -271,6 +433,10 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem() {
             5,
             "can only assert on number as it's racily creating files so unclear which one clashes"
         );
+        assert!(
+            outcome.collisions.windows(2).all(|w| w[0].path <= w[1].path),
+            "collisions are sorted by path"
+        );
     } else {
         assert_eq!(
             stripped_prefix(&destination, &dest_files),
@@ -291,6 +457,10 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem() {
             5,
             "can only assert on number as it's racily creating files so unclear which one clashes"
         );
+        assert!(
+            outcome.collisions.windows(2).all(|w| w[0].path <= w[1].path),
+            "collisions are sorted by path"
+        );
     } else {
         assert_eq!(
             outcome.collisions,
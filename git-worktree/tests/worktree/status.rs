@@ -0,0 +1,190 @@
+use git_worktree::{
+    fs::Capabilities,
+    status::{compare_to_worktree, Change, Options},
+};
+
+fn options(index: &git_index::State, executable_bit: bool, symlink: bool) -> Options {
+    Options {
+        fs: Capabilities {
+            executable_bit,
+            symlink,
+            ..Capabilities::default()
+        },
+        object_hash: git_hash::Kind::Sha1,
+        index_timestamp: index.timestamp(),
+        trust_ctime: true,
+        check_stat: true,
+    }
+}
+
+fn changes(worktree: &std::path::Path, index: &git_index::State, options: &Options) -> Vec<(String, Change)> {
+    let mut buf = Vec::new();
+    index
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path(index);
+            compare_to_worktree(entry, &worktree.join(path.to_string()), options, &mut buf)
+                .expect("no io error")
+                .map(|change| (path.to_string(), change))
+        })
+        .collect()
+}
+
+#[test]
+fn a_fresh_checkout_is_unchanged() -> crate::Result {
+    let worktree = crate::fixture_path("make_mixed_without_submodules");
+    let index = git_index::File::at(worktree.join(".git").join("index"), Default::default())?;
+    for (executable_bit, symlink) in [(true, true), (false, false)] {
+        assert_eq!(
+            changes(&worktree, &index, &options(&index, executable_bit, symlink)),
+            vec![]
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn executable_bits_and_symlinks_respect_filesystem_capabilities() -> crate::Result {
+    let tmp = git_testtools::scripted_fixture_repo_writable("make_mixed_without_submodules.sh")?;
+    let worktree = tmp.path();
+    let index = git_index::File::at(worktree.join(".git").join("index"), Default::default())?;
+
+    let symlink = worktree.join("dir").join("sub-dir").join("symlink");
+    std::fs::remove_file(&symlink)?;
+    std::fs::write(&symlink, "../content")?;
+    let executable = worktree.join("executable");
+    std::fs::remove_file(&executable)?;
+    std::fs::write(&executable, "content")?;
+
+    assert_eq!(
+        changes(worktree, &index, &options(&index, false, false)),
+        vec![],
+        "symlinks are files with the link target as content if symlinks aren't supported, and executable bits are ignored"
+    );
+    assert_eq!(
+        changes(worktree, &index, &options(&index, true, true)),
+        vec![
+            ("dir/sub-dir/symlink".into(), Change::Type),
+            (
+                "executable".into(),
+                Change::Modification {
+                    executable_bit_changed: true,
+                    content_changed: false
+                }
+            ),
+        ]
+    );
+
+    std::fs::remove_file(worktree.join("empty"))?;
+    std::fs::write(worktree.join("dir").join("content"), "changed")?;
+    assert_eq!(
+        changes(worktree, &index, &options(&index, false, false)),
+        vec![
+            (
+                "dir/content".into(),
+                Change::Modification {
+                    executable_bit_changed: false,
+                    content_changed: true
+                }
+            ),
+            ("empty".into(), Change::Removed),
+        ]
+    );
+    Ok(())
+}
+
+fn mtime_of(entry: &git_index::Entry) -> filetime::FileTime {
+    filetime::FileTime::from_unix_time(entry.stat.mtime.secs as i64, entry.stat.mtime.nsecs)
+}
+
+#[test]
+fn racily_clean_entries_are_hashed_even_if_their_stat_matches() -> crate::Result {
+    let tmp = git_testtools::scripted_fixture_repo_writable("make_mixed_without_submodules.sh")?;
+    let worktree = tmp.path();
+    let index = git_index::File::at(worktree.join(".git").join("index"), Default::default())?;
+    let entry = index.entry_by_path_and_stage("dir/content".into(), 0).expect("present");
+    let path = worktree.join("dir").join("content");
+    std::fs::write(&path, "other CONTENT")?;
+    filetime::set_file_mtime(&path, mtime_of(entry))?;
+
+    let mut options = Options {
+        check_stat: false,
+        index_timestamp: filetime::FileTime::from_unix_time(mtime_of(entry).unix_seconds() + 1, 0),
+        ..options(&index, true, true)
+    };
+    let mut buf = Vec::new();
+    assert_eq!(
+        compare_to_worktree(entry, &path, &options, &mut buf)?,
+        None,
+        "a same-size change that doesn't show in the stat information goes unnoticed if the entry isn't racy"
+    );
+    assert!(buf.is_empty(), "the file wasn't read");
+
+    options.index_timestamp = mtime_of(entry);
+    assert_eq!(
+        compare_to_worktree(entry, &path, &options, &mut buf)?,
+        Some(Change::Modification {
+            executable_bit_changed: false,
+            content_changed: true
+        }),
+        "entries written within the same instant as the index are hashed"
+    );
+    assert_eq!(buf, b"other CONTENT", "the file was read");
+    Ok(())
+}
+
+#[test]
+fn minimal_stat_checks_only_compare_the_modification_time_in_seconds_and_the_size() -> crate::Result {
+    let tmp = git_testtools::scripted_fixture_repo_writable("make_mixed_without_submodules.sh")?;
+    let worktree = tmp.path();
+    let index = git_index::File::at(worktree.join(".git").join("index"), Default::default())?;
+    let entry = index.entry_by_path_and_stage("executable".into(), 0).expect("present");
+    let path = worktree.join("executable");
+    let mtime = mtime_of(entry);
+    std::fs::write(&path, "content")?;
+    filetime::set_file_mtime(
+        &path,
+        filetime::FileTime::from_unix_time(mtime.unix_seconds(), (mtime.nanoseconds() + 1) % 1_000_000_000),
+    )?;
+
+    let index_timestamp = filetime::FileTime::from_unix_time(mtime.unix_seconds() + 1, 0);
+    let mut buf = Vec::new();
+    for check_stat in [true, false] {
+        let options = Options {
+            check_stat,
+            index_timestamp,
+            ..options(&index, true, true)
+        };
+        buf.clear();
+        assert_eq!(compare_to_worktree(entry, &path, &options, &mut buf)?, None);
+        assert_eq!(
+            !buf.is_empty(),
+            check_stat,
+            "the file is only hashed if nanoseconds and other stat fields are compared"
+        );
+
+        let mut smudged = entry.clone();
+        smudged.stat.size = 0;
+        buf.clear();
+        assert_eq!(compare_to_worktree(&smudged, &path, &options, &mut buf)?, None);
+        assert!(!buf.is_empty(), "smudged entries of non-empty files are always hashed");
+    }
+
+    std::fs::write(&path, "contents")?;
+    filetime::set_file_mtime(&path, mtime)?;
+    let options = Options {
+        check_stat: false,
+        index_timestamp,
+        ..options(&index, true, true)
+    };
+    assert_eq!(
+        compare_to_worktree(entry, &path, &options, &mut buf)?,
+        Some(Change::Modification {
+            executable_bit_changed: false,
+            content_changed: true
+        }),
+        "the size is always compared"
+    );
+    Ok(())
+}
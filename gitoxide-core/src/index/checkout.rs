@@ -58,8 +58,21 @@ pub fn checkout_exclusive(
         progress.info(format!("Skipping {} DIR/SYMLINK/COMMIT entries", num_skipped));
     }
 
+    let mut fs = git::worktree::fs::Capabilities::probe(dest_directory);
+    if let Some(repo) = &repo {
+        let overrides = repo.fs_capability_overrides();
+        let changed = overrides.changed_fields(&fs);
+        if !changed.is_empty() {
+            progress.info(format!(
+                "Configuration overrides probed filesystem capabilities: {}",
+                changed.join(", ")
+            ));
+        }
+        fs = fs.with_config_overrides(overrides);
+    }
+
     let opts = git::worktree::index::checkout::Options {
-        fs: git::worktree::fs::Capabilities::probe(dest_directory),
+        fs,
 
         destination_is_initially_empty: true,
         overwrite_existing: false,
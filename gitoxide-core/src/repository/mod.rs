@@ -8,7 +8,7 @@ pub fn init(directory: Option<PathBuf>) -> Result<git::discover::repository::Pat
         directory.unwrap_or_default(),
         git::create::Options {
             bare: false,
-            fs_capabilities: None,
+            ..Default::default()
         },
     )
     .with_context(|| "Repository initialization failed")